@@ -2,11 +2,13 @@
 //
 // SPDX-License-Identifier: MIT
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use clap::{Parser, Subcommand, ValueEnum};
 use thiserror::Error as TeError;
+use crate::config::ProjectConfig;
 
-#[derive(Debug, Clone, ValueEnum)]
+#[derive(Debug, Clone, ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum TxConfigFormat {
     /// Generate .tx/transifex.yaml file
     Yaml,
@@ -14,8 +16,34 @@ pub enum TxConfigFormat {
     Txconfig,
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum AddLocation {
+    /// Write `#:` references with file names and line numbers
+    Full,
+    /// Write `#:` references with file names only
+    File,
+    /// Do not write `#:` references
+    Never,
+}
+
+impl From<AddLocation> for crate::i18n_file::gettext::PoLocationStyle {
+    fn from(value: AddLocation) -> Self {
+        match value {
+            AddLocation::Full => Self::Full,
+            AddLocation::File => Self::File,
+            AddLocation::Never => Self::Never,
+        }
+    }
+}
+
 #[derive(Debug, Parser)]
 pub struct Cli {
+    /// Maximum number of parallel jobs for batch operations, defaults to the number of CPUs
+    #[arg(short, long, global = true)]
+    pub jobs: Option<usize>,
+    /// Run with the given nice level (niceness), e.g. 10, to be friendly to other processes on shared machines
+    #[arg(long, global = true, allow_negative_numbers = true)]
+    pub nice: Option<i32>,
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -30,48 +58,241 @@ pub enum Commands {
     #[command(
         about = "Converts Chinese texts in Qt Linguist or GNU Gettext file among regional variants",
         long_about = "Converts given Qt Linguist (.ts) file or GNU Gettext (.po) file among traditional/simplified scripts or regional variants.\n\n\
-            Converted files are written to the same directory as the original file with the same name but with different language code suffix to the file name.",
+            Converted files are written to the same directory as the original file with the same name but with different language code suffix to the file name. \
+            Both directions are supported, e.g. use `-s zh_TW -t zh_CN` for projects that maintain traditional Chinese as the canonical Chinese resource. \
+            With `--project`, all resources listed in the transifex.yaml or .tx/config file of the project are converted instead.\n\n\
+            Defaults of the options, extra conversion rules and protected terms can be set in the `[zhconv]` table of a `.deepin-translation-utils.toml` file \
+            at the project root, options given on the command line take precedence.",
     )]
     ZhConv {
-        #[arg(short, long, default_value = "zh_CN")]
-        source_language: String,
-        #[arg(short, long, default_value = "zh_HK,zh_TW", value_delimiter = ',')]
-        target_languages: Vec<String>,
-        linguist_ts_file: PathBuf,
+        /// Source language, defaults to zh_CN
+        #[arg(short, long)]
+        source_language: Option<String>,
+        /// Target languages, defaults to zh_HK,zh_TW
+        #[arg(short, long, value_delimiter = ',')]
+        target_languages: Option<Vec<String>>,
+        /// Value of the `Report-Msgid-Bugs-To` header for newly created PO files. Copied from the source file if not set.
+        #[arg(long)]
+        report_msgid_bugs_to: Option<String>,
+        /// Do not write `<location>` elements into converted TS files, or `#:` references into converted PO files
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        strip_locations: bool,
+        /// Maximum line width of converted PO files
+        #[arg(long, default_value_t = crate::i18n_file::gettext::DEFAULT_WRAP_WIDTH, conflicts_with = "no_wrap")]
+        width: usize,
+        /// Do not wrap long lines of converted PO files, only break strings after `\n`
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        no_wrap: bool,
+        /// File of extra conversion rules in MediaWiki syntax, one rule per line, e.g. `zh-cn:鼠标;zh-tw:滑鼠;zh-hk:滑鼠;`
+        #[arg(long = "rules-file")]
+        rules_files: Vec<PathBuf>,
+        /// Term that should never be converted, e.g. a product name
+        #[arg(long = "protect")]
+        protected_terms: Vec<String>,
+        /// Copy the translation of messages with exactly the given source text (msgid for PO files) verbatim instead of converting it
+        #[arg(long = "skip-source")]
+        skip_sources: Vec<String>,
+        /// File of source texts to copy verbatim like `--skip-source`, one source text per line
+        #[arg(long = "skip-list")]
+        skip_list_files: Vec<PathBuf>,
+        /// Also convert finished messages of target files, e.g. after conversion tables are improved.
+        /// Manually reviewed messages are never converted.
+        #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "all")]
+        force: Option<crate::conversion::ForceMode>,
+        /// Only convert messages inside contexts (msgctxt for PO files) matching the glob, e.g. `dcc::*`
+        #[arg(long = "context")]
+        contexts: Vec<String>,
+        /// Do not convert messages inside contexts (msgctxt for PO files) matching the glob, e.g. `LegalNotice`
+        #[arg(long = "exclude-context")]
+        exclude_contexts: Vec<String>,
+        /// Only convert messages whose source text contains the given text
+        #[arg(long)]
+        source_contains: Option<String>,
+        /// Also fill obsolete and vanished messages, which are kept obsolete or vanished (Qt Linguist TS files only)
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        include_obsolete: bool,
+        /// Also convert translator comments written in Chinese (`<translatorcomment>` of TS files and `#` comments of PO files).
+        /// Disambiguations and developer comments are never converted.
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        convert_comments: bool,
+        /// Do not write any file, print changes that would be made to each target file instead
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        dry_run: bool,
+        /// Convert all resources of the project at the given root according to its Transifex config
+        #[arg(long, conflicts_with = "linguist_ts_file")]
+        project: Option<PathBuf>,
+        /// Transifex project file (transifex.yaml or .tx/config) to use with `--project`, instead of looking it up under the project root
+        #[arg(long = "config", value_name = "FILE", requires = "project")]
+        tx_project_file: Option<PathBuf>,
+        #[arg(required_unless_present = "project")]
+        linguist_ts_file: Option<PathBuf>,
     },
 
     #[command(name = "zhconv-plain")]
     #[command(
         about = "Converts given Chinese texts among regional variants",
         long_about = "Converts given text among traditional/simplified scripts or regional variants.\n\n\
-            Converted texts are printed to stdout, splitted by new line.",
+            The text is read from the command line, the given file, or stdin if neither is provided. \
+            Converted texts are printed to stdout one target language after another, with the line structure kept as-is.\n\n\
+            The `variant-mapping` of the `[zhconv]` table in `.deepin-translation-utils.toml` of the current directory or its ancestors is respected.",
     )]
     ZhConvPlain {
         #[arg(short, long, default_value = "zh_HK,zh_TW", value_delimiter = ',')]
         target_languages: Vec<String>,
-        content: String,
+        /// Read the text to convert from the given file
+        #[arg(short, long, conflicts_with = "content")]
+        file: Option<PathBuf>,
+        content: Option<String>,
+    },
+
+    #[command(name = "mark-manual")]
+    #[command(
+        about = "Marks translations in a converted file as manually reviewed",
+        long_about = "Marks finished translations in the given Qt Linguist (.ts) file or GNU Gettext (.po) file as manually reviewed.\n\n\
+            Marked messages will never be overwritten by zhconv. The marker is stored as a `zhconv-manual` extracomment in TS files, and as a `zhconv-manual` flag in PO files.",
+    )]
+    MarkManual {
+        /// Only process messages inside the given context (Qt Linguist context or Gettext msgctxt)
+        #[arg(short, long)]
+        context: Option<String>,
+        /// Only process messages whose source text is one of the given texts. By default all messages are processed.
+        #[arg(short, long)]
+        source: Vec<String>,
+        /// Remove the marker instead of adding it
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        unmark: bool,
+        translation_file: PathBuf,
     },
 
     #[command(name = "statistics", visible_alias = "stat", visible_alias = "stats")]
     #[command(
         about = "Prints translation statistics of the provided project",
         long_about = "Prints translation statistics of the provided project according to transifex.yaml or .tx/config file.\n\n\
-            Only Qt Linguist-based and PO-based resources are processed, other resources are ignored.",
+            Only Qt Linguist-based and PO-based resources are processed, other resources are ignored. \
+            For projects without Transifex config, translation files are grouped into inferred resources like `--discover` does.\n\n\
+            Defaults of the options can be set in the `[statistics]` table of a `.deepin-translation-utils.toml` file at the project root.",
     )]
     Statistics {
         project_root: PathBuf,
-        #[clap(short, long, default_value_t, value_enum)]
-        format: crate::subcmd::statistics::StatsFormat,
-        #[clap(short, long, default_value_t, value_enum)]
-        sort_by: crate::subcmd::statistics::StatsSortBy,
+        /// Output format, defaults to plain-table
+        #[clap(short, long, value_enum)]
+        format: Option<crate::subcmd::statistics::StatsFormat>,
+        /// Sort order of the resources, defaults to completeness
+        #[clap(short, long, value_enum)]
+        sort_by: Option<crate::subcmd::statistics::StatsSortBy>,
         #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
         standalone_percentage: bool,
+        /// How completeness is measured, defaults to strings
+        #[clap(long, value_enum)]
+        metric: Option<crate::subcmd::statistics::CompletenessMetric>,
         /// languages that needs to be included in the statistics, by default (empty), all languages will be included
-        #[arg(short = 'l', long, value_delimiter = ',')]
-        accept_languages: Vec<String>,
-        /// languages that needs to be excluded from the statistics
-        #[arg(short, long, default_value = "en,en_US", value_delimiter = ',')]
-        ignore_languages: Vec<String>,
+        #[arg(short = 'l', long, visible_alias = "only-languages", value_delimiter = ',', conflicts_with = "ignore_languages")]
+        accept_languages: Option<Vec<String>>,
+        /// languages that needs to be excluded from the statistics, defaults to en,en_US unless `--only-languages` is given
+        #[arg(short, long, value_delimiter = ',')]
+        ignore_languages: Option<Vec<String>>,
+        /// Discover resources by scanning translation files like `gentxcfg`, instead of reading the Transifex config.
+        /// Files that fail to load are reported and skipped. This is always the case for projects without Transifex config.
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        discover: bool,
+        /// Write the report into the given file instead of stdout, e.g. `--format html --output index.html`.
+        /// Progress messages are still printed to stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Generate a shields.io endpoint badge JSON showing the completeness of the given language, instead of the report
+        #[arg(long, value_name = "LANG", conflicts_with = "format")]
+        badge: Option<String>,
+        /// Completeness percentage from which languages are shown in green in SVG charts
+        #[arg(long, default_value_t = 90.0)]
+        good_threshold: f64,
+        /// Completeness percentage from which languages are shown in yellow instead of red in SVG charts
+        #[arg(long, default_value_t = 50.0)]
+        fair_threshold: f64,
+        /// Exit with a non-zero status if any checked language is less complete than the given percentage, e.g. for CI gating
+        #[arg(long, value_name = "PERCENT")]
+        fail_under: Option<f64>,
+        /// Languages to check with `--fail-under`, by default (empty), all target languages are checked
+        #[arg(long, value_delimiter = ',', requires = "fail_under")]
+        languages: Vec<String>,
+        /// Compare completeness of each resource with Transifex, which requires resource slugs in .tx/config and a token in ~/.transifexrc (or `TX_TOKEN`).
+        /// Also warns about local languages not enabled on Transifex and vice versa
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        with_remote: bool,
+        /// Append a timestamped snapshot of the completeness of each language to the given history file,
+        /// which is in CSV format if the file name ends with `.csv`, JSON otherwise. See `statistics-trend`.
+        #[arg(long, value_name = "FILE")]
+        record: Option<PathBuf>,
+        /// Only count resources whose source file path (relative to the project root) matches one of the globs, e.g. `dcc-network/**`.
+        /// `**` matches any number of directories, `*`, `?` and classes like `[a-z]` match within a path component,
+        /// and globs starting with `!` exclude resources, e.g. `dcc-*/**,!dcc-bluetooth/**`
+        #[arg(long, value_name = "GLOB", value_delimiter = ',')]
+        resources: Vec<String>,
+        /// Also show sub-totals of each repository (the leading `owner/repo` directories of source files),
+        /// for configs covering many repositories like the one generated by `monotxconfig`, other configs are rejected
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        group_by_project: bool,
+        /// Transifex project file (transifex.yaml or .tx/config) to read resources from, instead of looking it up under the project root.
+        /// Paths in the file are still relative to the project root
+        #[arg(long = "config", value_name = "FILE", conflicts_with = "discover")]
+        tx_project_file: Option<PathBuf>,
+        /// List unfinished and fuzzy strings (with file and context) of the given language after the table, or of all languages if no language is given.
+        /// Implies the plain-table format
+        #[arg(long, value_name = "LANG", num_args = 0..=1, conflicts_with_all = ["format", "badge"])]
+        list_untranslated: Option<Option<String>>,
+        /// Break the completeness of each resource down by Linguist context (msgctxt for PO files) after the table,
+        /// to find out which UI modules are missing translations. Implies the plain-table format
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false, conflicts_with_all = ["format", "badge"])]
+        by_context: bool,
+    },
+    #[command(name = "remote-stats")]
+    #[command(
+        about = "Prints translation statistics of the provided project as they are on Transifex",
+        long_about = "Prints translation statistics of the resources in .tx/config as they are on Transifex, in the same formats as `statistics`, \
+            without pulling the translation files. The API token is read from ~/.transifexrc, or the `TX_TOKEN` environment variable if the file does not exist.\n\n\
+            Defaults of the options can be set in the `[statistics]` table of a `.deepin-translation-utils.toml` file at the project root.",
+    )]
+    RemoteStats {
+        project_root: PathBuf,
+        /// Output format, defaults to plain-table
+        #[clap(short, long, value_enum)]
+        format: Option<crate::subcmd::statistics::StatsFormat>,
+        /// Sort order of the resources, defaults to completeness
+        #[clap(short, long, value_enum)]
+        sort_by: Option<crate::subcmd::statistics::StatsSortBy>,
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        standalone_percentage: bool,
+        /// How completeness is measured, defaults to strings
+        #[clap(long, value_enum)]
+        metric: Option<crate::subcmd::statistics::CompletenessMetric>,
+        /// languages that needs to be included in the statistics, by default (empty), all languages will be included
+        #[arg(short = 'l', long, visible_alias = "only-languages", value_delimiter = ',', conflicts_with = "ignore_languages")]
+        accept_languages: Option<Vec<String>>,
+        /// languages that needs to be excluded from the statistics, defaults to en,en_US unless `--only-languages` is given
+        #[arg(short, long, value_delimiter = ',')]
+        ignore_languages: Option<Vec<String>>,
+        /// Write the report into the given file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Only count resources whose source file path (relative to the project root) matches one of the globs, see `statistics --resources`
+        #[arg(long, value_name = "GLOB", value_delimiter = ',')]
+        resources: Vec<String>,
+        /// Also show sub-totals of each repository, see `statistics --group-by-project`
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        group_by_project: bool,
+        /// .tx/config file to read resources from, instead of looking it up under the project root
+        #[arg(long = "config", value_name = "FILE")]
+        tx_project_file: Option<PathBuf>,
+    },
+    #[command(name = "statistics-trend", visible_alias = "trend")]
+    #[command(
+        about = "Prints how translation completeness changed over time",
+        long_about = "Prints how translation completeness of each language changed over the snapshots recorded by `statistics --record`.",
+    )]
+    StatisticsTrend {
+        history_file: PathBuf,
+        /// Number of latest snapshots to show
+        #[arg(short = 'n', long, default_value_t = 5)]
+        last: usize,
     },
     #[command(name = "yaml2txconfig")]
     #[command(
@@ -84,16 +305,28 @@ pub enum Commands {
         /// Force to fetch the resource slugs via Transifex REST API, and update local cache.
         #[clap(short, long, action = clap::ArgAction::SetTrue, default_value_t = false)]
         force_online: bool,
-        /// GitHub repository name in owner/repo format. e.g. linuxdeepin/dde-control-center
+        /// GitHub repository name in owner/repo format. e.g. linuxdeepin/dde-control-center.
+        /// If not provided, it is detected from the `origin` remote of the git repository, or asked interactively
         #[arg(short, long)]
         github_repository: Option<String>,
-        /// organization slug of the project on Transifex platform
+        /// Git branch to resolve linked resources for, when the repository has per-branch resources on Transifex.
+        /// If not provided, the current branch is used, falling back to the default branch
+        #[arg(short, long)]
+        branch: Option<String>,
+        /// organization slug of the project on Transifex platform, see `orgs`
         #[arg(short, long, default_value = "linuxdeepin")]
         organization_slug: String,
-        /// project slug of the project on Transifex platform.
+        /// project slug of the project on Transifex platform, see `projects`.
         /// If not provided, it will lookup all projects under the organization slug.
         #[arg(short, long, default_value = None)]
         project_slug: Option<String>,
+        /// Value of `minimum_perc` in the main section of the generated .tx/config, translations less complete than this are not pulled
+        #[arg(long, value_parser = clap::value_parser!(i64).range(0..=100))]
+        minimum_perc: Option<i64>,
+        /// Update an existing .tx/config instead of leaving it untouched: add sections for new resources and resolve
+        /// `unknown-org` placeholder slugs, other sections are kept as they are
+        #[clap(short, long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        merge: bool,
     },
     #[command(name = "txconfig2yaml")]
     #[command(
@@ -101,6 +334,12 @@ pub enum Commands {
     )]
     TxConfig2Yaml {
         project_root: PathBuf,
+        /// Overwrite transifex.yaml if it already exists
+        #[clap(short, long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        force: bool,
+        /// Print only the generated YAML to stdout instead of writing the file, handy for scripts
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false, conflicts_with = "force")]
+        stdout: bool,
     },
     #[command(name = "monotxconfig")]
     #[command(
@@ -113,24 +352,429 @@ pub enum Commands {
         /// Force to fetch the resource slugs via Transifex REST API, and update local cache.
         #[clap(short, long, action = clap::ArgAction::SetTrue, default_value_t = false)]
         force_online: bool,
-        /// organization slug of the project on Transifex platform
+        /// organization slug of the project on Transifex platform, see `orgs`
+        #[arg(short, long, default_value = "linuxdeepin")]
+        organization_slug: String,
+        /// Only include projects whose slug matches any of these glob patterns, e.g. `dde-*`. All projects are included if omitted
+        #[arg(long, value_delimiter = ',')]
+        include_projects: Vec<String>,
+        /// Exclude projects whose slug matches any of these glob patterns
+        #[arg(long, value_delimiter = ',')]
+        exclude_projects: Vec<String>,
+        /// Value of `minimum_perc` in the main section of the generated .tx/config, translations less complete than this are not pulled
+        #[arg(long, value_parser = clap::value_parser!(i64).range(0..=100))]
+        minimum_perc: Option<i64>,
+        /// Write the config into the given file (overwriting it) instead of .tx/config of the project root, or `-` for stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    #[command(name = "orgs")]
+    #[command(
+        about = "List Transifex organizations accessible with the API token",
+        long_about = "List slugs and names of the Transifex organizations accessible with the API token, e.g. for `--organization-slug`.\n\n\
+            The API token is read from ~/.transifexrc, or the `TX_TOKEN` environment variable if the file does not exist.",
+    )]
+    Orgs,
+    #[command(name = "projects")]
+    #[command(
+        about = "List projects of a Transifex organization",
+        long_about = "List slugs and names of the projects of a Transifex organization, e.g. for `--project-slug`.\n\n\
+            The API token is read from ~/.transifexrc, or the `TX_TOKEN` environment variable if the file does not exist.",
+    )]
+    Projects {
+        /// organization slug of the projects on Transifex platform, see `orgs`
         #[arg(short, long, default_value = "linuxdeepin")]
         organization_slug: String,
     },
+    #[command(name = "pull")]
+    #[command(
+        about = "Download translations of all resources in .tx/config from Transifex",
+        long_about = "Download translations of all resources in .tx/config from Transifex via REST API, and write them to the paths given by `file_filter`.\n\n\
+            Languages less complete than `minimum_perc` are skipped. By default only existing translation files are updated, like `tx pull` does. \
+            The API token is read from ~/.transifexrc, or the `TX_TOKEN` environment variable if the file does not exist.",
+    )]
+    Pull {
+        project_root: PathBuf,
+        /// Transifex config file to use, instead of .tx/config under the project root
+        #[arg(long = "config", value_name = "FILE")]
+        tx_config_file: Option<PathBuf>,
+        /// Only pull the given languages, by default (empty), all languages on Transifex are pulled
+        #[arg(short, long, value_delimiter = ',')]
+        languages: Vec<String>,
+        /// Also pull languages without a local translation file
+        #[clap(short, long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        all: bool,
+        /// Download mode like `reviewed` or `onlytranslated`, defaults to the `mode` in .tx/config or `default`
+        #[arg(long)]
+        mode: Option<String>,
+    },
+    #[command(name = "push")]
+    #[command(
+        about = "Upload source files and translations of resources in .tx/config to Transifex",
+        long_about = "Upload source files, and optionally translation files, of resources in .tx/config to Transifex via REST API.\n\n\
+            Only source files are pushed by default, like `tx push` does. The API token is read from ~/.transifexrc, or the `TX_TOKEN` environment variable if the file does not exist.",
+    )]
+    Push {
+        project_root: PathBuf,
+        /// Transifex config file to use, instead of .tx/config under the project root
+        #[arg(long = "config", value_name = "FILE")]
+        tx_config_file: Option<PathBuf>,
+        /// Push source files, this is the default if neither `--source` nor `--translations` is given
+        #[clap(short, long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        source: bool,
+        /// Push translation files found by `file_filter`
+        #[clap(short, long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        translations: bool,
+        /// Only push resources whose source file path matches one of the globs (see `statistics --resources`), or whose resource slug is one of them
+        #[arg(short, long, value_name = "GLOB", value_delimiter = ',')]
+        resources: Vec<String>,
+        /// Only push translations of the given languages, by default (empty), all local translations are pushed
+        #[arg(short, long, value_delimiter = ',')]
+        languages: Vec<String>,
+    },
+    #[command(name = "status")]
+    #[command(
+        about = "Show whether resources need to be pushed to or pulled from Transifex",
+        long_about = "For each resource in .tx/config, show the modification time and content hash of the local files, when they were last updated on Transifex, \
+            and the completeness of each translation, telling whether a `push` or `pull` is needed, similar to `tx status`.\n\n\
+            The decision is based on the content hash and Transifex update time recorded in .tx/.sync-state.json by the `push` and `pull` subcommands, \
+            files never pushed or pulled with this tool are reported as unknown. \
+            Languages translated locally but not enabled on Transifex, or the other way around, are printed as warnings.\n\n\
+            The API token is read from ~/.transifexrc, or the `TX_TOKEN` environment variable if the file does not exist.",
+    )]
+    Status {
+        project_root: PathBuf,
+        /// Transifex config file to use, instead of .tx/config under the project root
+        #[arg(long = "config", value_name = "FILE")]
+        tx_config_file: Option<PathBuf>,
+    },
+    #[command(name = "verify-sources")]
+    #[command(
+        about = "Check whether source strings on Transifex match the local source files",
+        long_about = "Download the source strings of each Qt Linguist-based and PO-based resource in .tx/config, and compare them with the local source file. \
+            Exits with a non-zero status if any resource on Transifex is stale, e.g. because the latest source file was not pushed.\n\n\
+            The API token is read from ~/.transifexrc, or the `TX_TOKEN` environment variable if the file does not exist.",
+    )]
+    VerifySources {
+        project_root: PathBuf,
+        /// Transifex config file to use, instead of .tx/config under the project root
+        #[arg(long = "config", value_name = "FILE")]
+        tx_config_file: Option<PathBuf>,
+        /// Also list the source strings that differ
+        #[clap(short, long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        details: bool,
+    },
+    #[command(name = "lint")]
+    #[command(
+        about = "Check the Transifex configuration of a project for problems",
+        long_about = "Check .tx/config of a project for problems, like resources whose slug was never resolved. \
+            Exits with a non-zero status if any problem is found.\n\n\
+            With `--remote`, resources are also checked against Transifex, using the cached linked resource list when possible, \
+            and resources Transifex has linked to the GitHub repository of the project but are not configured locally are reported. \
+            Languages translated locally but not enabled on Transifex, or the other way around, are printed as warnings. \
+            The API token is read from ~/.transifexrc, or the `TX_TOKEN` environment variable if the file does not exist.",
+    )]
+    Lint {
+        project_root: PathBuf,
+        /// Transifex config file to use, instead of .tx/config under the project root
+        #[arg(long = "config", value_name = "FILE")]
+        tx_config_file: Option<PathBuf>,
+        /// Also check that each resource in .tx/config exists on Transifex, and that linked resources on Transifex are configured locally
+        #[clap(short, long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        remote: bool,
+        /// Force to fetch the resource slugs via Transifex REST API, and update local cache.
+        #[clap(short, long, action = clap::ArgAction::SetTrue, default_value_t = false, requires = "remote")]
+        force_online: bool,
+    },
+    #[command(name = "lint-config")]
+    #[command(
+        about = "Check transifex.yaml and .tx/config of a project for structural problems",
+        long_about = "Check transifex.yaml (or .tx/transifex.yaml) of a project for structural problems that Transifex would only \
+            report after the file is pushed, like unknown or missing keys, `filter_type` values other than `file` and `dir`, \
+            unknown `file_format` values, and `translation_files_expression` without `<lang>`. \
+            .tx/config is checked as well: resource section names must be in the form of `o:<organization>:p:<project>:r:<resource>`, \
+            mandatory keys must be present, `minimum_perc` must be from 0 to 100, and `source_lang` should be the same for all resources. \
+            Source files of both files must exist (paths that only differ in case are reported as such), and translation file patterns \
+            must match existing files, with language codes that look like language codes. \
+            Source languages, `trans.<lang>` keys and the Transifex side of language mappings must be known ISO 639/3166 language codes. \
+            Exits with a non-zero status if any error is found. Inconsistent source languages, translation file patterns that match \
+            no file and matched language codes that don't look like language codes are warnings, `--fail-on` sets which findings make the \
+            command fail, e.g. `--fail-on warning`.\n\n\
+            Findings are printed as `<file>:<line>: <message> [<rule>]`, with a `warning:` prefix for warnings, \
+            or as a JSON array with `--format json`.",
+    )]
+    LintConfig {
+        #[clap(short, long, default_value_t, value_enum)]
+        format: crate::subcmd::validate::ValidateFormat,
+        /// Which findings make the command exit with a non-zero status, defaults to errors only
+        #[arg(long, value_enum, default_value_t)]
+        fail_on: crate::subcmd::validate::FailOn,
+        project_root: PathBuf,
+    },
+    #[command(name = "validate")]
+    #[command(
+        about = "Check translation files for structural problems",
+        long_about = "Check Qt Linguist (.ts) and GNU Gettext (.po/.pot) files for structural problems, like malformed XML or PO syntax, \
+            missing language attribute or header, invalid translation types, and broken plural blocks. \
+            The `nplurals` of the `Plural-Forms` header of PO files must match the language and the number of `msgstr[N]` of each \
+            plural entry, and finished numerus messages of .ts files must have no empty `<numerusform>`. \
+            Translations are also checked against their source text: placeholders like `%1`, `%n`, `{0}` and `${var}` must match, \
+            and so must rich text tags like `<b>` and `<br/>`, and `&` accelerators (as a `(&X)` suffix for Chinese, Japanese and Korean). \
+            Leading and trailing whitespace, terminal punctuation (full-width for Chinese and Japanese) and double spaces are checked as well, \
+            and so are simplified Chinese characters in traditional Chinese translations (e.g. `zh_TW` and `zh_HK`), and vice versa. \
+            The charset in the `Content-Type` header of PO files must match the file encoding, and text that looks like mojibake is reported. \
+            Messages with the same context and source within a file are reported as duplicates, and so are translations of a source \
+            that differ from the most common one among the checked files of the same language. \
+            The `language` and `sourcelanguage` attributes of .ts files are checked against the languages implied by the nearest .tx/config \
+            or the file name, and their `version` must be one that can be saved without changing the structure of the file. \
+            Directories are scanned recursively. Exits with a non-zero status if any error is found. \
+            Findings of mojibake, mismatched languages, unsupported versions, terminal punctuation, double spaces and inconsistent translations are warnings by default.\n\n\
+            With `--glossary`, translations of messages using a source term of the glossary must use its target term for the language \
+            of the file (terms of `zh_CN` take precedence over terms of `zh`, which take precedence over terms without a language). \
+            Terms with the `warning` severity are reported as warnings.\n\n\
+            The severity of the findings of a rule can be changed with e.g. `--severity double-space=error`, \
+            and `--fail-on` sets which findings make the command fail, e.g. `--fail-on warning` or `--fail-on never`. \
+            Both can also be set in the `[validate]` table of a `.deepin-translation-utils.toml` file at the project root. \
+            With `--fix`, mismatched leading and trailing whitespace of translations and mismatched `language` and `sourcelanguage` attributes \
+            of .ts files are fixed in place before checking, without touching the rest of the files.\n\n\
+            Findings of a message that are known to be intentional can be suppressed with a `dtu:disable <rule>...` line in its \
+            `<translatorcomment>` or `<extracomment>` (.ts files) or its translator or extracted comments (`#` and `#.` in PO files), \
+            e.g. `dtu:disable placeholder-mismatch, double-space`. A plain `dtu:disable` suppresses all rules for the message.\n\n\
+            Findings are printed as `<file>:<line>: <message> [<rule>]`, with a `warning:` or `info:` prefix for those severities, \
+            or as a JSON array with `--format json`.",
+    )]
+    Validate {
+        #[clap(short, long, default_value_t, value_enum)]
+        format: crate::subcmd::validate::ValidateFormat,
+        /// Glossary (TBX, or CSV with `source`, `target`, `language` and `severity` columns) whose terms translations must use
+        #[arg(short, long)]
+        glossary: Option<PathBuf>,
+        /// Severity of the findings of a rule as `<rule>=<error|warning|info>`, e.g. `double-space=warning`
+        #[arg(long = "severity", value_parser = crate::subcmd::validate::parse_rule_severity)]
+        severities: Vec<(String, crate::subcmd::validate::Severity)>,
+        /// Which findings make the command exit with a non-zero status, defaults to errors only
+        #[arg(long, value_enum)]
+        fail_on: Option<crate::subcmd::validate::FailOn>,
+        /// Fix mismatched surrounding whitespace of translations and language attributes of Qt Linguist files in place before checking
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        fix: bool,
+        /// Translation files or directories to check
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
+    #[command(name = "orphans")]
+    #[command(
+        about = "List resources on Transifex that no longer exist locally",
+        long_about = "List resources in the Transifex projects of .tx/config that have no section in .tx/config, or whose source file no longer exists, \
+            e.g. after the source file was deleted or renamed. Since Transifex projects are shared, only resources linked to the GitHub repository \
+            and branch of the project are considered.\n\n\
+            With `--delete`, they are deleted from Transifex along with their translations after confirmation. The API token is read from ~/.transifexrc, or the `TX_TOKEN` environment variable if the file does not exist.",
+    )]
+    Orphans {
+        project_root: PathBuf,
+        /// Transifex config file to use, instead of .tx/config under the project root
+        #[arg(long = "config", value_name = "FILE")]
+        tx_config_file: Option<PathBuf>,
+        /// GitHub repository name in owner/repo format, detected from the `origin` remote of the git repository if not provided
+        #[arg(short, long)]
+        github_repository: Option<String>,
+        /// Git branch of the linked resources, the current branch is used if not provided
+        #[arg(short, long)]
+        branch: Option<String>,
+        /// Delete the orphaned resources from Transifex
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        delete: bool,
+        /// Delete without asking for confirmation
+        #[clap(short, long, action = clap::ArgAction::SetTrue, default_value_t = false, requires = "delete")]
+        yes: bool,
+    },
     #[command(name = "gentxcfg")]
     #[command(
         about = "Generate Transifex configuration by scanning translation files in the repository",
-        long_about = "Scan the repository for translation files (.ts and .po) and generate a corresponding Transifex configuration file.\n\n\
-            This is useful for new projects that don't have any existing configuration files. The configuration will be saved to the .tx/ directory.",
+        long_about = "Scan the repository for translation files (.ts and .po) and generate a corresponding Transifex configuration file. \
+            Other translatable assets are also recognized: .desktop files are configured in the DESKTOP format, whose translations are kept in the source file, \
+            while dconfig meta JSON files and AppStream metainfo files are reported and skipped, as Transifex can't translate them in place. \
+            If CMakeLists.txt (`qt_add_translations()` and friends) or qmake project files (`TRANSLATIONS`) declare TS files, \
+            only the declared ones are used instead of all .ts files found.\n\n\
+            This is useful for new projects that don't have any existing configuration files. The configuration will be saved to the .tx/ directory, \
+            or elsewhere with `--output`.\n\n\
+            Defaults of the options can be set in the `[gentxcfg]` table of a `.deepin-translation-utils.toml` file at the project root.",
     )]
     GenTxCfg {
         project_root: PathBuf,
-        /// Output format for the generated configuration file
-        #[arg(short, long, default_value = "yaml", value_enum)]
-        format: TxConfigFormat,
-        /// Paths to ignore during scanning (relative to project root)
-        #[arg(short, long, default_value = "build", value_delimiter = ',')]
-        ignore_paths: Vec<String>,
+        /// Output format for the generated configuration file, defaults to yaml
+        #[arg(short, long, value_enum)]
+        format: Option<TxConfigFormat>,
+        /// Glob patterns of paths to ignore during scanning (relative to project root), e.g. `third_party/**` or `**/tests/**`.
+        /// Patterns without a slash match at any depth, like in .gitignore. Defaults to build,node_modules. .git directories are always skipped
+        #[arg(short, long, visible_alias = "ignore", value_delimiter = ',')]
+        ignore_paths: Option<Vec<String>>,
+        /// Also scan directories behind symlinks. Symlink loops are skipped with a warning
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        follow_symlinks: bool,
+        /// Value of `minimum_perc` in the main section of the generated .tx/config (the .tx/config format only), translations less complete than this are not pulled
+        #[arg(long, value_parser = clap::value_parser!(i64).range(0..=100))]
+        minimum_perc: Option<i64>,
+        /// Source language of the resources, e.g. `en`. Inferred from each source file name (e.g. `app_en.ts`) if not provided,
+        /// falling back to en_US
+        #[arg(short, long)]
+        source_language: Option<String>,
+        /// Add newly found resources to an existing configuration file instead of leaving it untouched, and report configured ones not found anymore
+        #[clap(short, long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        merge: bool,
+        /// Review each detected source file and its target pattern before writing, to accept, edit the pattern, or skip it
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        interactive: bool,
+        /// Only print the detected resources and the configuration that would be written, without creating or writing any file
+        #[clap(short = 'n', long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        dry_run: bool,
+        /// Write the configuration into the given file (overwriting it unless --merge is set) instead of the .tx/ directory of the project root,
+        /// or `-` for stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    #[command(name = "update-po")]
+    #[command(
+        about = "Update a GNU Gettext PO file against a POT template",
+        long_about = "Update the translations of the given GNU Gettext (.po) file against the given template (.pot) file, like what `msgmerge` does.\n\n\
+            Exactly matched translations are kept, slightly changed messages are filled with the most similar translation and marked as fuzzy, \
+            and translations that no longer exist in the template are kept as obsolete entries.\n\n\
+            The file is written in the same layout as `msgmerge`, lines are wrapped at 79 columns by default. \
+            Use `--width`, `--no-wrap`, `--no-obsolete` and `--add-location` to match the options passed to `msgmerge` elsewhere \
+            in the project or the files pulled from Transifex, so that diffs stay clean.",
+    )]
+    UpdatePo {
+        /// The existing translation file, it will be updated in-place unless --output is provided
+        po_file: PathBuf,
+        /// The template file that contains the latest source strings
+        pot_file: PathBuf,
+        /// Do not use fuzzy matching when an exact match is not found
+        #[clap(short = 'N', long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        no_fuzzy_matching: bool,
+        /// Write the updated file to the given path instead of updating the PO file in-place
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Maximum line width of the written file
+        #[arg(short, long, default_value_t = crate::i18n_file::gettext::DEFAULT_WRAP_WIDTH, conflicts_with = "no_wrap")]
+        width: usize,
+        /// Do not wrap long lines, only break strings after `\n`
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        no_wrap: bool,
+        /// Drop obsolete (`#~`) entries instead of keeping them at the end of the file
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        no_obsolete: bool,
+        /// How to write `#:` reference comments
+        #[arg(long, value_enum, default_value_t = AddLocation::Full)]
+        add_location: AddLocation,
+    },
+    #[command(name = "extract")]
+    #[command(
+        about = "Extract translatable strings from source code into a translation file",
+        long_about = "Scan C++ source files for Qt translation functions (`tr()`, `translate()`, `QT_TR_NOOP()` and `QT_TRANSLATE_NOOP()` families) \
+            and QML/JavaScript source files for `qsTr()`, `qsTranslate()`, `QT_TR_NOOP()` and `QT_TRANSLATE_NOOP()`, \
+            and create or update the given Qt Linguist (.ts) file with contexts and locations, like what `lupdate` does.\n\n\
+            If the output is a Gettext (.pot/.po) file, C/C++ sources are scanned for gettext keywords instead, like what `xgettext` does. \
+            A .pot output is overwritten with the extracted template, while a .po output is merged against it like what `msgmerge` does.\n\n\
+            Directories are scanned recursively, hidden files and directories are skipped. \
+            Existing translations are kept, and translated messages that no longer exist are marked as vanished or obsolete.",
+    )]
+    Extract {
+        /// The translation file to create or update
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Language code to set on the translation file
+        #[arg(short, long)]
+        language: Option<String>,
+        /// Remove messages that no longer exist in the source code instead of marking them as vanished
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        no_obsolete: bool,
+        /// Additional gettext keyword in xgettext syntax, e.g. `tr_n:1,2` or `tr_p:1c,2` (Gettext output only)
+        #[arg(short, long = "keyword")]
+        keywords: Vec<String>,
+        /// Do not use the default gettext keywords like `gettext`, `_` and `N_` (Gettext output only)
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        no_default_keywords: bool,
+        /// Keep comments starting with the given tag as comments for translators (Gettext output only)
+        #[arg(long, default_value = "TRANSLATORS:")]
+        add_comments: String,
+        /// Do not write `<location>` elements into the TS file (Qt Linguist output only)
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        strip_locations: bool,
+        /// Source files or directories to scan
+        #[arg(required = true)]
+        source_paths: Vec<PathBuf>,
+    },
+    #[command(name = "diff")]
+    #[command(
+        about = "Compare messages of two translation files",
+        long_about = "Compare messages of two Qt Linguist (.ts) or GNU Gettext (.po) files, e.g. the same resource before and after a Transifex pull.\n\n\
+            Messages are matched by context, source text and disambiguation, and reported as added, removed, changed (translation changed) \
+            or state changed (e.g. unfinished to finished).",
+    )]
+    Diff {
+        old_file: PathBuf,
+        new_file: PathBuf,
+        #[clap(short, long, default_value_t, value_enum)]
+        format: crate::subcmd::diff::DiffFormat,
+    },
+    #[command(name = "normalize")]
+    #[command(
+        about = "Rewrite translation files into a canonical form",
+        long_about = "Rewrite Qt Linguist (.ts) and GNU Gettext (.po/.pot) files into a canonical form, so files edited by different tools stop producing conflicting diffs.\n\n\
+            TS files get contexts sorted by name, messages sorted by source text, and elements and attributes in the order written by Qt Linguist. \
+            PO files get messages sorted by msgctxt and msgid. Directories are scanned recursively.",
+    )]
+    Normalize {
+        /// Do not write files, fail if any file is not normalized
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        check: bool,
+        /// Translation files or directories to normalize
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
+    #[command(name = "dedupe")]
+    #[command(
+        about = "Merge duplicated messages in Qt Linguist TS files",
+        long_about = "Merge messages with the same context, source text and disambiguation in Qt Linguist (.ts) files, which may accumulate after repeated lupdate runs.\n\n\
+            The best translation among the duplicates is kept: non-empty translations win over empty ones, then manually reviewed ones, then finished ones. \
+            Locations of all duplicates are consolidated into the kept message. Directories are scanned recursively.",
+    )]
+    Dedupe {
+        /// Do not write files, fail if any file contains duplicated messages
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        check: bool,
+        /// TS files or directories to deduplicate
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
+    #[command(name = "split")]
+    #[command(
+        about = "Split a Qt Linguist TS file into one file per context",
+        long_about = "Split the given Qt Linguist (.ts) file into one file per context, which is easier to review. Use `join` to recombine them.\n\n\
+            Each split file is named after its context and keeps the language and version of the original file.",
+    )]
+    Split {
+        /// Directory to write split files into, defaults to a directory next to the file with the same name
+        #[arg(short, long)]
+        output_dir: Option<PathBuf>,
+        ts_file: PathBuf,
+    },
+    #[command(name = "join")]
+    #[command(
+        about = "Join Qt Linguist TS files into one file",
+        long_about = "Join the given Qt Linguist (.ts) files, e.g. files created by `split`, into one file. Directories are scanned recursively.\n\n\
+            Contexts with the same name are merged. All files must have the same language unless `--language` is set.",
+    )]
+    Join {
+        /// The TS file to write
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Language code to set on the joined file, instead of requiring the same language for all files
+        #[arg(short, long)]
+        language: Option<String>,
+        /// TS files or directories to join
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
     },
 }
 
@@ -138,37 +782,239 @@ pub enum Commands {
 #[error("{0}")]
 pub enum CliError {
     ZhConv(#[from] crate::subcmd::zhconv::CmdError),
+    Conversion(#[from] crate::conversion::ConversionError),
     Statistics(#[from] crate::subcmd::statistics::CmdError),
+    StatisticsTrend(#[from] crate::subcmd::stats_history::CmdError),
     Yaml2TxConfig(#[from] crate::subcmd::yaml2txconfig::CmdError),
     TxConfig2Yaml(#[from] crate::subcmd::txconfig2yaml::CmdError),
+    MonoTxConfig(#[from] crate::subcmd::monotxconfig::CmdError),
     GenTxCfg(#[from] crate::subcmd::gentxcfg::CmdError),
+    Orgs(#[from] crate::subcmd::orgs::CmdError),
+    Orphans(#[from] crate::subcmd::orphans::CmdError),
+    VerifySources(#[from] crate::subcmd::verify_sources::CmdError),
+    Lint(#[from] crate::subcmd::lint::CmdError),
+    LintConfig(#[from] crate::subcmd::lint_config::CmdError),
+    Validate(#[from] crate::subcmd::validate::CmdError),
+    Pull(#[from] crate::subcmd::pull::CmdError),
+    Push(#[from] crate::subcmd::push::CmdError),
+    Status(#[from] crate::subcmd::status::CmdError),
+    UpdatePo(#[from] crate::subcmd::updatepo::CmdError),
+    Extract(#[from] crate::subcmd::extract::CmdError),
+    Diff(#[from] crate::subcmd::diff::CmdError),
+    Normalize(#[from] crate::subcmd::normalize::CmdError),
+    Dedupe(#[from] crate::subcmd::dedupe::CmdError),
+    Split(#[from] crate::subcmd::split::CmdError),
+    Config(#[from] crate::config::ConfigLoadError),
+    SetNice(#[from] crate::parallel::SetNiceError),
 }
 
 pub fn execute() -> Result<(), CliError> {
     let args = Cli::parse();
+    if let Some(nice) = args.nice {
+        crate::parallel::set_nice(nice)?;
+    }
+    if let Some(jobs) = args.jobs {
+        crate::parallel::set_jobs(jobs);
+    }
 
     use crate::subcmd;
     match args.command {
-        Commands::ZhConv { source_language, target_languages, linguist_ts_file } => {
-            subcmd::subcmd_zhconv(&source_language, &target_languages, &linguist_ts_file)?;
+        Commands::ZhConv { source_language, target_languages, report_msgid_bugs_to, strip_locations, width, no_wrap, rules_files, protected_terms, skip_sources, skip_list_files, force, contexts, exclude_contexts, source_contains, include_obsolete, convert_comments, dry_run, project, tx_project_file, linguist_ts_file } => {
+            use crate::conversion::VariantMapping;
+            use crate::subcmd::zhconv::{load_rules_file, load_skip_list};
+            let config_dir = match (&project, &linguist_ts_file) {
+                (Some(project_root), _) => project_root.clone(),
+                (None, Some(linguist_ts_file)) => linguist_ts_file.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new(".")).to_path_buf(),
+                (None, None) => unreachable!("clap ensures either the file or the project is provided"),
+            };
+            let config = ProjectConfig::discover(&config_dir)?.zhconv;
+            let source_language = source_language.or(config.source_language).unwrap_or_else(|| "zh_CN".to_string());
+            let target_languages = target_languages.or(config.target_languages).unwrap_or_else(|| vec!["zh_HK".to_string(), "zh_TW".to_string()]);
+            let mut rules = Vec::new();
+            for rules_file in config.rules_files.iter().chain(&rules_files) {
+                rules.extend(load_rules_file(rules_file)?);
+            }
+            let mut skip_sources: std::collections::HashSet<String> = config.skip_sources.into_iter().chain(skip_sources).collect();
+            for skip_list_file in &skip_list_files {
+                skip_sources.extend(load_skip_list(skip_list_file)?);
+            }
+            let options = crate::conversion::ZhConvOptions {
+                force,
+                contexts: crate::glob::PathGlobs::new(&contexts.into_iter().chain(exclude_contexts.into_iter().map(|glob| format!("!{glob}"))).collect::<Vec<_>>())
+                    .map_err(crate::subcmd::zhconv::CmdError::InvalidContextGlob)?,
+                source_contains,
+                rules,
+                protected_terms: config.protected_terms.into_iter().chain(protected_terms).collect(),
+                skip_sources,
+                variant_mapping: VariantMapping::with_overrides(&config.variant_mapping)?,
+                include_obsolete,
+                convert_comments,
+            };
+            let po_header_options = crate::i18n_file::gettext::PoHeaderOptions { report_msgid_bugs_to: report_msgid_bugs_to.or(config.report_msgid_bugs_to) };
+            let strip_locations = strip_locations || config.strip_locations.unwrap_or_default();
+            let ts_save_options = crate::i18n_file::linguist::TsSaveOptions { strip_locations };
+            let po_save_options = crate::i18n_file::gettext::PoSaveOptions {
+                wrap_width: Some(width).filter(|_| !no_wrap),
+                locations: match strip_locations {
+                    true => crate::i18n_file::gettext::PoLocationStyle::Never,
+                    false => crate::i18n_file::gettext::PoLocationStyle::Full,
+                },
+                ..Default::default()
+            };
+            match (project, linguist_ts_file) {
+                (Some(project_root), _) => subcmd::subcmd_zhconv_project(&project_root, tx_project_file.as_deref(), &source_language, &target_languages, &options, &po_header_options, &ts_save_options, &po_save_options, dry_run)?,
+                (None, Some(linguist_ts_file)) => subcmd::subcmd_zhconv(&source_language, &target_languages, &linguist_ts_file, &options, &po_header_options, &ts_save_options, &po_save_options, dry_run)?,
+                (None, None) => unreachable!("clap ensures either the file or the project is provided"),
+            }
+        },
+        Commands::ZhConvPlain { target_languages, file, content } => {
+            let config = ProjectConfig::discover(Path::new("."))?.zhconv;
+            let variant_mapping = crate::conversion::VariantMapping::with_overrides(&config.variant_mapping)?;
+            subcmd::subcmd_zhconv_plain(&target_languages, content.as_deref(), file.as_deref(), &variant_mapping)?;
+        },
+        Commands::MarkManual { context, source, unmark, translation_file } => {
+            subcmd::subcmd_mark_manual(&translation_file, context.as_deref(), &source, unmark)?;
+        },
+        Commands::Statistics { project_root, format, sort_by, standalone_percentage, metric, accept_languages, ignore_languages, discover, output, badge, good_threshold, fair_threshold, fail_under, languages, with_remote, record, resources, group_by_project, tx_project_file, list_untranslated, by_context } => {
+            let config = ProjectConfig::discover(&project_root)?.statistics;
+            let report_options = crate::subcmd::statistics::StatsReportOptions {
+                format: match list_untranslated.is_some() || by_context {
+                    true => crate::subcmd::statistics::StatsFormat::PlainTable,
+                    false => format.or(config.format).unwrap_or_default(),
+                },
+                sort_by: sort_by.or(config.sort_by).unwrap_or_default(),
+                standalone_percentage: standalone_percentage || config.standalone_percentage.unwrap_or_default(),
+                badge,
+                chart_thresholds: crate::subcmd::statistics::ChartThresholds { good: good_threshold, fair: fair_threshold },
+                metric: metric.or(config.metric).unwrap_or_default(),
+                with_remote,
+                output,
+                record,
+                resources: crate::glob::PathGlobs::new(&resources).map_err(crate::subcmd::statistics::CmdError::InvalidGlob)?,
+                group_by_project,
+                list_untranslated: list_untranslated.map(|lang| lang.into_iter().collect()),
+                by_context,
+            };
+            let accept_languages = accept_languages.or(config.accept_languages).unwrap_or_default();
+            // The default ignore list would silently drop explicitly accepted languages like `en`.
+            let ignore_languages = match accept_languages.is_empty() {
+                true => ignore_languages.or(config.ignore_languages).unwrap_or_else(|| vec!["en".to_string(), "en_US".to_string()]),
+                false => ignore_languages.unwrap_or_default(),
+            };
+            subcmd::subcmd_statistics(&project_root,
+                tx_project_file.as_deref(),
+                accept_languages,
+                ignore_languages,
+                discover,
+                &report_options,
+                fail_under,
+                &languages)?;
+        },
+        Commands::RemoteStats { project_root, format, sort_by, standalone_percentage, metric, accept_languages, ignore_languages, output, resources, group_by_project, tx_project_file } => {
+            let config = ProjectConfig::discover(&project_root)?.statistics;
+            let report_options = crate::subcmd::statistics::StatsReportOptions {
+                format: format.or(config.format).unwrap_or_default(),
+                sort_by: sort_by.or(config.sort_by).unwrap_or_default(),
+                standalone_percentage: standalone_percentage || config.standalone_percentage.unwrap_or_default(),
+                metric: metric.or(config.metric).unwrap_or_default(),
+                output,
+                resources: crate::glob::PathGlobs::new(&resources).map_err(crate::subcmd::statistics::CmdError::InvalidGlob)?,
+                group_by_project,
+                ..Default::default()
+            };
+            let accept_languages = accept_languages.or(config.accept_languages).unwrap_or_default();
+            let ignore_languages = match accept_languages.is_empty() {
+                true => ignore_languages.or(config.ignore_languages).unwrap_or_else(|| vec!["en".to_string(), "en_US".to_string()]),
+                false => ignore_languages.unwrap_or_default(),
+            };
+            subcmd::subcmd_remote_statistics(&project_root, tx_project_file.as_deref(), accept_languages, ignore_languages, &report_options)?;
+        },
+        Commands::StatisticsTrend { history_file, last } => {
+            subcmd::subcmd_statistics_trend(&history_file, last)?;
+        },
+        Commands::Yaml2TxConfig { project_root, force_online, github_repository, branch, organization_slug, project_slug, minimum_perc, merge } => {
+            subcmd::subcmd_yaml2txconfig(&project_root, force_online, github_repository, branch, organization_slug, project_slug, minimum_perc, merge)?;
+        },
+        Commands::TxConfig2Yaml { project_root, force, stdout } => {
+            subcmd::subcmd_txconfig2yaml(&project_root, force, stdout)?;
+        },
+        Commands::MonoTxConfig { project_root, force_online, organization_slug, include_projects, exclude_projects, minimum_perc, output } => {
+            subcmd::subcmd_monotxconfig(&project_root, force_online, organization_slug, &include_projects, &exclude_projects, minimum_perc, output.as_deref())?;
+        },
+        Commands::Orgs => {
+            subcmd::subcmd_orgs()?;
+        },
+        Commands::Projects { organization_slug } => {
+            subcmd::subcmd_projects(&organization_slug)?;
+        },
+        Commands::Pull { project_root, tx_config_file, languages, all, mode } => {
+            subcmd::subcmd_pull(&project_root, tx_config_file.as_deref(), &languages, all, mode.as_deref())?;
+        },
+        Commands::Push { project_root, tx_config_file, source, translations, resources, languages } => {
+            subcmd::subcmd_push(&project_root, tx_config_file.as_deref(), source, translations, &resources, &languages)?;
+        },
+        Commands::Status { project_root, tx_config_file } => {
+            subcmd::subcmd_status(&project_root, tx_config_file.as_deref())?;
+        },
+        Commands::VerifySources { project_root, tx_config_file, details } => {
+            subcmd::subcmd_verify_sources(&project_root, tx_config_file.as_deref(), details)?;
+        },
+        Commands::Lint { project_root, tx_config_file, remote, force_online } => {
+            subcmd::subcmd_lint(&project_root, tx_config_file.as_deref(), remote, force_online)?;
+        },
+        Commands::LintConfig { format, fail_on, project_root } => {
+            subcmd::subcmd_lint_config(&project_root, format, fail_on)?;
+        },
+        Commands::Validate { format, glossary, severities, fail_on, fix, paths } => {
+            let config = ProjectConfig::discover(&paths[0])?.validate;
+            let options = crate::subcmd::validate::ValidateOptions {
+                glossary,
+                severities: config.severity.into_iter().chain(severities).collect(),
+                fail_on: fail_on.or(config.fail_on).unwrap_or_default(),
+                fix,
+            };
+            subcmd::subcmd_validate(&paths, format, &options)?;
+        },
+        Commands::Orphans { project_root, tx_config_file, github_repository, branch, delete, yes } => {
+            subcmd::subcmd_orphans(&project_root, tx_config_file.as_deref(), github_repository, branch, delete, yes)?;
+        },
+        Commands::GenTxCfg { project_root, format, ignore_paths, follow_symlinks, source_language, minimum_perc, merge, interactive, dry_run, output } => {
+            let config = ProjectConfig::discover(&project_root)?.gentxcfg;
+            subcmd::subcmd_gentxcfg(&project_root,
+                format.or(config.format).unwrap_or(TxConfigFormat::Yaml),
+                ignore_paths.or(config.ignore_paths)
+                    .unwrap_or_else(|| subcmd::gentxcfg::DEFAULT_IGNORE_PATHS.iter().map(|path| path.to_string()).collect()),
+                follow_symlinks || config.follow_symlinks.unwrap_or_default(),
+                source_language.or(config.source_language).as_deref(),
+                minimum_perc.or(config.minimum_perc), merge, interactive, dry_run, output.as_deref())?;
+        },
+        Commands::UpdatePo { po_file, pot_file, no_fuzzy_matching, output, width, no_wrap, no_obsolete, add_location } => {
+            let save_options = crate::i18n_file::gettext::PoSaveOptions {
+                wrap_width: Some(width).filter(|_| !no_wrap),
+                no_obsolete,
+                locations: add_location.into(),
+            };
+            subcmd::subcmd_updatepo(&po_file, &pot_file, no_fuzzy_matching, output.as_deref(), &save_options)?;
         },
-        Commands::ZhConvPlain { target_languages, content } => {
-            subcmd::subcmd_zhconv_plain(&target_languages, &content)?;
+        Commands::Extract { output, language, no_obsolete, keywords, no_default_keywords, add_comments, strip_locations, source_paths } => {
+            let ts_save_options = crate::i18n_file::linguist::TsSaveOptions { strip_locations };
+            subcmd::subcmd_extract(&source_paths, &output, language.as_deref(), no_obsolete,
+                &keywords, no_default_keywords, Some(add_comments.as_str()).filter(|tag| !tag.is_empty()), &ts_save_options)?;
         },
-        Commands::Statistics { project_root, format, sort_by, standalone_percentage, accept_languages, ignore_languages } => {
-            subcmd::subcmd_statistics(&project_root, format, sort_by, standalone_percentage, accept_languages, ignore_languages)?;
+        Commands::Diff { old_file, new_file, format } => {
+            subcmd::subcmd_diff(&old_file, &new_file, format)?;
         },
-        Commands::Yaml2TxConfig { project_root, force_online, github_repository, organization_slug, project_slug } => {
-            subcmd::subcmd_yaml2txconfig(&project_root, force_online, github_repository, organization_slug, project_slug)?;
+        Commands::Normalize { check, paths } => {
+            subcmd::subcmd_normalize(&paths, check)?;
         },
-        Commands::TxConfig2Yaml { project_root } => {
-            subcmd::subcmd_txconfig2yaml(&project_root)?;
+        Commands::Dedupe { check, paths } => {
+            subcmd::subcmd_dedupe(&paths, check)?;
         },
-        Commands::MonoTxConfig { project_root, force_online, organization_slug } => {
-            subcmd::subcmd_monotxconfig(&project_root, force_online, organization_slug);
+        Commands::Split { output_dir, ts_file } => {
+            subcmd::subcmd_split(&ts_file, output_dir.as_deref())?;
         },
-        Commands::GenTxCfg { project_root, format, ignore_paths } => {
-            subcmd::subcmd_gentxcfg(&project_root, format, ignore_paths)?;
+        Commands::Join { output, language, paths } => {
+            subcmd::subcmd_join(&paths, &output, language.as_deref())?;
         },
     }
 
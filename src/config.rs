@@ -0,0 +1,224 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+//! Project-level configuration file, i.e. `.deepin-translation-utils.toml` at the project root.
+//!
+//! Every value is optional, options given on the command line always take precedence.
+//!
+//! ```toml
+//! [zhconv]
+//! source-language = "zh_CN"
+//! target-languages = ["zh_HK", "zh_TW"]
+//! # Extra conversion rules in MediaWiki syntax, one rule per line, e.g. `zh-cn:鼠标;zh-tw:滑鼠;zh-hk:滑鼠;`
+//! rules-files = ["translations/zhconv-rules.txt"]
+//! # Terms that are never converted
+//! protected-terms = ["deepin"]
+//! # Source texts (msgid for PO files) whose translation is copied verbatim
+//! skip-sources = ["deepin", "UOS"]
+//! strip-locations = true
+//!
+//! # Conversion targets of language codes, in addition to the built-in mapping
+//! [zhconv.variant-mapping]
+//! zh_SG = "zh-CN"
+//!
+//! [statistics]
+//! format = "json"
+//! ignore-languages = ["en", "en_US"]
+//!
+//! [gentxcfg]
+//! format = "txconfig"
+//! ignore-paths = ["build", "3rdparty"]
+//! source-language = "en_US"
+//! minimum-perc = 30
+//! follow-symlinks = false
+//!
+//! [validate]
+//! # Exit with a non-zero status on warnings too
+//! fail-on = "warning"
+//!
+//! # Severity of the findings of rules, one of error, warning and info
+//! [validate.severity]
+//! double-space = "warning"
+//! terminal-punctuation = "info"
+//! ```
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+use thiserror::Error as TeError;
+use crate::cli::TxConfigFormat;
+use crate::subcmd::statistics::{CompletenessMetric, StatsFormat, StatsSortBy};
+use crate::subcmd::validate::{check_rule_name, FailOn, Severity};
+
+pub const PROJECT_CONFIG_FILE_NAME: &str = ".deepin-translation-utils.toml";
+
+#[derive(TeError, Debug)]
+pub enum ConfigLoadError {
+    #[error("Fail to read config file {0:?} because: {1}")]
+    ReadFile(PathBuf, #[source] std::io::Error),
+    #[error("Fail to parse config file {0:?} because: {1}")]
+    Parse(PathBuf, #[source] toml::de::Error),
+    #[error("Invalid value in config file {0:?}: {1}")]
+    InvalidValue(PathBuf, String),
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ProjectConfig {
+    pub zhconv: ZhConvConfig,
+    pub statistics: StatisticsConfig,
+    pub gentxcfg: GenTxCfgConfig,
+    pub validate: ValidateConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ZhConvConfig {
+    pub source_language: Option<String>,
+    pub target_languages: Option<Vec<String>>,
+    /// Files of extra conversion rules in MediaWiki syntax, relative to the config file.
+    pub rules_files: Vec<PathBuf>,
+    pub protected_terms: Vec<String>,
+    pub skip_sources: Vec<String>,
+    pub strip_locations: Option<bool>,
+    pub report_msgid_bugs_to: Option<String>,
+    /// Conversion variants of language codes like `zh_SG = "zh-CN"`, overriding the built-in mapping.
+    pub variant_mapping: HashMap<String, String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct StatisticsConfig {
+    pub format: Option<StatsFormat>,
+    pub sort_by: Option<StatsSortBy>,
+    pub standalone_percentage: Option<bool>,
+    pub metric: Option<CompletenessMetric>,
+    pub accept_languages: Option<Vec<String>>,
+    pub ignore_languages: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct GenTxCfgConfig {
+    pub format: Option<TxConfigFormat>,
+    pub ignore_paths: Option<Vec<String>>,
+    pub source_language: Option<String>,
+    /// Value of `minimum_perc` in the main section of generated .tx/config files, in range `0..=100`.
+    pub minimum_perc: Option<i64>,
+    pub follow_symlinks: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ValidateConfig {
+    /// Severity of the findings of each rule, e.g. `double-space = "warning"`.
+    pub severity: HashMap<String, Severity>,
+    pub fail_on: Option<FailOn>,
+}
+
+impl ProjectConfig {
+    pub fn load_from_str(content: &str, config_file: &Path) -> Result<Self, ConfigLoadError> {
+        let mut config: ProjectConfig = toml::from_str(content)
+            .map_err(|e| ConfigLoadError::Parse(config_file.to_path_buf(), e))?;
+        let base_dir = config_file.parent().unwrap_or(Path::new(""));
+        for rules_file in &mut config.zhconv.rules_files {
+            *rules_file = base_dir.join(&*rules_file);
+        }
+        for rule in config.validate.severity.keys() {
+            check_rule_name(rule).map_err(|e| ConfigLoadError::InvalidValue(config_file.to_path_buf(), format!("validate.severity: {e}")))?;
+        }
+        if let Some(minimum_perc) = config.gentxcfg.minimum_perc.filter(|minimum_perc| !(0..=100).contains(minimum_perc)) {
+            return Err(ConfigLoadError::InvalidValue(config_file.to_path_buf(), format!("gentxcfg.minimum-perc {minimum_perc} is not in range 0..=100")));
+        }
+        Ok(config)
+    }
+
+    pub fn load_from_file(config_file: &Path) -> Result<Self, ConfigLoadError> {
+        let content = fs::read_to_string(config_file)
+            .map_err(|e| ConfigLoadError::ReadFile(config_file.to_path_buf(), e))?;
+        Self::load_from_str(&content, config_file)
+    }
+
+    /// Find the config file in `start_dir` or its ancestors, and load it if found.
+    ///
+    /// Returns the default (empty) config if no config file is found.
+    pub fn discover(start_dir: &Path) -> Result<Self, ConfigLoadError> {
+        let start_dir = std::path::absolute(start_dir).unwrap_or_else(|_| start_dir.to_path_buf());
+        for dir in start_dir.ancestors() {
+            let config_file = dir.join(PROJECT_CONFIG_FILE_NAME);
+            if config_file.is_file() {
+                return Self::load_from_file(&config_file);
+            }
+        }
+        Ok(Self::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_parse_project_config() {
+        let config = ProjectConfig::load_from_str(r#"
+[zhconv]
+source-language = "zh_TW"
+target-languages = ["zh_CN"]
+rules-files = ["translations/rules.txt"]
+protected-terms = ["deepin"]
+
+[zhconv.variant-mapping]
+zh_SG = "zh-CN"
+
+[statistics]
+format = "plain-table"
+sort-by = "language-code"
+metric = "words"
+
+[gentxcfg]
+format = "txconfig"
+minimum-perc = 30
+
+[validate]
+fail-on = "never"
+
+[validate.severity]
+double-space = "info"
+"#, Path::new("/project/.deepin-translation-utils.toml")).unwrap();
+        assert_eq!(config.zhconv.source_language.as_deref(), Some("zh_TW"));
+        assert_eq!(config.zhconv.target_languages, Some(vec!["zh_CN".to_string()]));
+        assert_eq!(config.zhconv.rules_files, vec![PathBuf::from("/project/translations/rules.txt")]);
+        assert_eq!(config.zhconv.strip_locations, None);
+        assert_eq!(config.zhconv.variant_mapping.get("zh_SG").map(String::as_str), Some("zh-CN"));
+        assert!(matches!(config.statistics.format, Some(StatsFormat::PlainTable)));
+        assert!(matches!(config.statistics.sort_by, Some(StatsSortBy::LanguageCode)));
+        assert!(matches!(config.statistics.metric, Some(CompletenessMetric::Words)));
+        assert!(matches!(config.gentxcfg.format, Some(TxConfigFormat::Txconfig)));
+        assert_eq!(config.gentxcfg.ignore_paths, None);
+        assert_eq!(config.gentxcfg.minimum_perc, Some(30));
+        assert_eq!(config.gentxcfg.follow_symlinks, None);
+        assert!(matches!(config.validate.fail_on, Some(FailOn::Never)));
+        assert_eq!(config.validate.severity.get("double-space"), Some(&Severity::Info));
+
+        assert!(ProjectConfig::load_from_str("[zhconv]\nunknown = 1\n", Path::new("config.toml")).is_err());
+        assert!(ProjectConfig::load_from_str("[gentxcfg]\nminimum-perc = 101\n", Path::new("config.toml")).is_err());
+        assert!(ProjectConfig::load_from_str("[validate.severity]\ndouble-spaces = \"info\"\n", Path::new("config.toml")).is_err());
+    }
+    #[test]
+    fn tst_parse_doc_example() {
+        // The example in the module documentation, without the `//! ` prefixes.
+        let example = include_str!("config.rs").lines()
+            .filter_map(|line| line.strip_prefix("//!"))
+            .map(|line| line.strip_prefix(' ').unwrap_or(line))
+            .skip_while(|line| *line != "```toml").skip(1)
+            .take_while(|line| *line != "```")
+            .collect::<Vec<_>>().join("\n");
+        let config = ProjectConfig::load_from_str(&example, Path::new("/project/.deepin-translation-utils.toml")).unwrap();
+        assert_eq!(config.zhconv.strip_locations, Some(true));
+        assert_eq!(config.zhconv.variant_mapping.get("zh_SG").map(String::as_str), Some("zh-CN"));
+        assert_eq!(config.gentxcfg.minimum_perc, Some(30));
+        assert_eq!(config.validate.severity.get("terminal-punctuation"), Some(&Severity::Info));
+    }
+}
@@ -0,0 +1,603 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+//! Conversion between Chinese variants of translation files, e.g. filling `zh_TW` translations from `zh_CN` ones.
+//!
+//! Target messages are converted from the source messages at the same position, so both files are expected to be
+//! updated from the same template beforehand.
+
+use thiserror::Error as TeError;
+use std::collections::{HashMap, HashSet};
+use zhconv::{zhconv, get_builtin_converter, get_builtin_tables, Variant, ZhConverter, ZhConverterBuilder};
+use crate::glob::PathGlobs;
+use crate::i18n_file::{self, linguist::Ts, gettext::Po};
+
+#[derive(TeError, Debug)]
+pub enum ConversionError {
+    #[error("Target file {0:?} has different number of contexts")]
+    DifferentContexts(String),
+    #[error("Target file for language {0:?} has different number of messages (Source {1:?} != Target {2:?})")]
+    DifferentMessages(String, usize, usize),
+    #[error("Target language ({0:?})'s source string doesn't match (Source: {1:?} != Target: {2:?}), did you forget to run `update_translations` beforehand?")]
+    DifferentMessage(String, String, String),
+    #[error("Fail to parse language code {0:?} as a Chinese variant")]
+    ParseLanguageCode(String),
+    #[error("Language code {0:?} is mapped to invalid Chinese variant {1:?}")]
+    InvalidVariantMapping(String, String),
+    #[error("Missing language code in Linguist TS file")]
+    MissingLanguageCode,
+}
+
+/// Which finished messages of target files should be converted again.
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ForceMode {
+    /// Reconvert all finished messages
+    All,
+    /// Only reconvert finished messages whose translation is the same as the source translation
+    SameAsSource,
+}
+
+/// Options of how messages are converted.
+#[derive(Debug, Default, Clone)]
+pub struct ZhConvOptions {
+    /// Also convert finished messages, manually reviewed messages are still skipped.
+    pub force: Option<ForceMode>,
+    /// Only convert messages inside contexts matching the globs, contexts matching `!`-prefixed globs are never converted.
+    /// For GNU Gettext PO files, the context is the msgctxt (empty if not set).
+    pub contexts: PathGlobs,
+    /// Only convert messages whose source text contains the given text.
+    pub source_contains: Option<String>,
+    /// Extra conversion rules in MediaWiki syntax, e.g. `zh-cn:鼠标;zh-tw:滑鼠;zh-hk:滑鼠;`.
+    pub rules: Vec<String>,
+    /// Terms that are kept as-is instead of being converted, e.g. product names.
+    pub protected_terms: Vec<String>,
+    /// Source texts (msgid for PO files) of messages whose translation is copied verbatim instead of being converted.
+    pub skip_sources: HashSet<String>,
+    /// How language codes of target files are mapped to conversion variants.
+    pub variant_mapping: VariantMapping,
+    /// Also fill obsolete and vanished messages of TS files, their type is kept as-is.
+    pub include_obsolete: bool,
+    /// Also convert translator comments written in Chinese, i.e. `<translatorcomment>` of TS files and `#` comments of PO files.
+    pub convert_comments: bool,
+}
+
+impl ZhConvOptions {
+    fn accepts_context(&self, context: &str) -> bool {
+        self.contexts.is_match(context)
+    }
+
+    fn accepts_source(&self, source: &str) -> bool {
+        self.source_contains.as_ref().is_none_or(|text| source.contains(text.as_str()))
+    }
+
+    /// Convert the translation of the message with the given source text, unless it's in the skip list.
+    fn convert(&self, converter: &TextConverter, source: &str, translation: &str) -> String {
+        match self.skip_sources.contains(source) {
+            true => translation.to_string(),
+            false => converter.convert(translation),
+        }
+    }
+}
+
+// ===== Utils Functions =====
+
+/// Language codes that can't be mapped to a variant by their script and region subtags, in normalized form.
+const BUILTIN_VARIANT_MAPPING: &[(&str, Variant)] = &[
+    // Legacy culture names used by .NET and some older Qt projects
+    ("zh-chs", Variant::ZhHans),
+    ("zh-cht", Variant::ZhHant),
+    // Classical Chinese is written in traditional Chinese
+    ("lzh", Variant::ZhHant),
+];
+
+/// Strip encoding and modifier suffixes like `.UTF-8`, and use lowercase with `-` as the separator, e.g. `zh_TW.UTF-8` becomes `zh-tw`.
+fn normalize_language_code(language_code: &str) -> String {
+    language_code.split(['.', '@']).next().unwrap_or_default().replace('_', "-").to_ascii_lowercase()
+}
+
+/// The script variant of a regional variant, e.g. `zh-Hant` for `zh-TW`.
+pub(crate) fn script_of(variant: Variant) -> Variant {
+    match variant {
+        Variant::ZhTW | Variant::ZhHK | Variant::ZhMO => Variant::ZhHant,
+        Variant::ZhCN | Variant::ZhSG | Variant::ZhMY => Variant::ZhHans,
+        variant => variant,
+    }
+}
+
+/// Map a normalized `zh[-script][-region]` language code to a variant.
+///
+/// The region decides the variant when it's written in the given script, e.g. `zh-hant-hk`. Otherwise the script decides,
+/// e.g. `zh-hans-hk` (simplified Chinese used in Hong Kong) is converted as `zh-Hans` instead of `zh-HK`.
+fn variant_of_subtags(code: &str) -> Option<Variant> {
+    let parse = |subtag: &str| format!("zh-{subtag}").parse::<Variant>().ok();
+    let (script, region) = match code.split('-').collect::<Vec<_>>().as_slice() {
+        ["zh"] => return Some(Variant::Zh),
+        ["zh", script] if script.len() == 4 => (Some(parse(script)?), None),
+        ["zh", region] => (None, Some(parse(region)?)),
+        ["zh", script, region] => (Some(parse(script)?), parse(region)),
+        _ => return None,
+    };
+    match (script, region) {
+        (Some(script), Some(region)) if script_of(region) == script => Some(region),
+        (Some(script), _) => Some(script),
+        (None, region) => region,
+    }
+}
+
+/// Maps language codes of translation files to conversion variants, e.g. `zh_TW`, `zh-Hans` or `zh_Hant_HK`.
+///
+/// Overrides are looked up first, then the built-in mapping, then the script and region subtags of the code.
+#[derive(Debug, Default, Clone)]
+pub struct VariantMapping {
+    overrides: HashMap<String, Variant>,
+}
+
+impl VariantMapping {
+    /// Create a mapping with extra `(language code, variant)` entries like `("zh_SG", "zh-CN")`, which take precedence over the built-in ones.
+    pub fn with_overrides<'a>(overrides: impl IntoIterator<Item = (&'a String, &'a String)>) -> Result<Self, ConversionError> {
+        let overrides = overrides.into_iter()
+            .map(|(language_code, variant)| match variant.parse::<Variant>() {
+                Ok(parsed) => Ok((normalize_language_code(language_code), parsed)),
+                Err(_) => Err(ConversionError::InvalidVariantMapping(language_code.clone(), variant.clone())),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { overrides })
+    }
+
+    pub fn variant(&self, language_code: &str) -> Result<Variant, ConversionError> {
+        let code = normalize_language_code(language_code);
+        self.overrides.get(&code).copied()
+            .or_else(|| BUILTIN_VARIANT_MAPPING.iter().find(|(builtin, _)| *builtin == code).map(|(_, variant)| *variant))
+            .or_else(|| variant_of_subtags(&code))
+            .ok_or_else(|| ConversionError::ParseLanguageCode(language_code.to_string()))
+    }
+}
+
+/// Whether the text contains any CJK ideograph, comments without them are left untouched.
+fn contains_chinese(text: &str) -> bool {
+    text.chars().any(i18n_file::common::is_cjk_ideograph)
+}
+
+/// Whether a finished target translation should be converted again.
+fn should_reconvert(options: &ZhConvOptions, same_as_source: bool) -> bool {
+    match options.force {
+        None => false,
+        Some(ForceMode::All) => true,
+        Some(ForceMode::SameAsSource) => same_as_source,
+    }
+}
+
+/// Converter of a target language, the built-in one is used if there are no extra rules or protected terms.
+enum TextConverter {
+    Builtin(&'static ZhConverter),
+    Custom(ZhConverter),
+}
+
+impl TextConverter {
+    fn new(language_code: &str, options: &ZhConvOptions) -> Result<Self, ConversionError> {
+        let variant = options.variant_mapping.variant(language_code)?;
+        if options.rules.is_empty() && options.protected_terms.is_empty() {
+            return Ok(Self::Builtin(get_builtin_converter(variant)));
+        }
+        let converter = ZhConverterBuilder::targeted(variant)
+            .tables(get_builtin_tables(variant))
+            .conv_lines(&options.rules)
+            // Mapping a term to itself takes precedence over the built-in tables.
+            .conv_pairs(options.protected_terms.iter().filter(|term| !term.is_empty()).map(|term| (term.clone(), term.clone())))
+            .build();
+        Ok(Self::Custom(converter))
+    }
+
+    fn convert(&self, text: &str) -> String {
+        match self {
+            Self::Builtin(converter) => converter.convert(text),
+            Self::Custom(converter) => converter.convert(text),
+        }
+    }
+}
+
+/// Convert a plain text into the variant of the given language code.
+pub fn convert_text(text: &str, target: &str, variant_mapping: &VariantMapping) -> Result<String, ConversionError> {
+    Ok(zhconv(text, variant_mapping.variant(target)?))
+}
+
+/// Fill the target Linguist TS file with translations converted from the source file.
+///
+/// Both files must have the same contexts and messages in the same order, only unfinished messages (or finished ones
+/// selected by [`ZhConvOptions::force`]) which are not manually reviewed are filled.
+pub fn translate_ts_content(source_content: &Ts, target_content: &mut Ts, options: &ZhConvOptions) -> Result<(), ConversionError> {
+    use i18n_file::linguist::TranslationType;
+
+    let language_code = target_content.get_language().ok_or(ConversionError::MissingLanguageCode)?;
+    let converter = TextConverter::new(&language_code, options)?;
+    if target_content.contexts.len() != source_content.contexts.len() {
+        return Err(ConversionError::DifferentContexts(language_code.clone()));
+    }
+    for (index, context) in target_content.contexts.iter_mut().enumerate() {
+        let source_context = &source_content.contexts[index];
+        if context.messages.len() != source_context.messages.len() {
+            return Err(ConversionError::DifferentMessages(language_code.clone(), source_context.messages.len(), context.messages.len()));
+        }
+        if !options.accepts_context(&context.name) {
+            continue;
+        }
+        // for loop with index so we could access the source context and message at the same index
+        for (index, message) in context.messages.iter_mut().enumerate() {
+            let source_message = &source_context.messages[index];
+            // Skip the message if it's finished (unless forced), manually reviewed or filtered out
+            if message.is_manually_reviewed() || !options.accepts_source(&message.source) {
+                continue;
+            }
+            let source_text = source_message.translation.value.as_deref().unwrap_or_default();
+            let target_text = message.translation.value.as_deref().unwrap_or_default();
+            let keep_type = match message.translation.type_attr {
+                Some(TranslationType::Unfinished) => false,
+                None if should_reconvert(options, target_text == source_text) => false,
+                Some(TranslationType::Obsolete | TranslationType::Vanished)
+                    if options.include_obsolete && (target_text.is_empty() || should_reconvert(options, target_text == source_text)) => true,
+                _ => continue,
+            };
+            if matches!(source_message.translation.type_attr, Some(TranslationType::Unfinished)) {
+                continue;
+            }
+            if source_message.source != message.source {
+                return Err(ConversionError::DifferentMessage(language_code.clone(), source_message.source.clone(), message.source.clone()));
+            }
+            // `<comment>` is the disambiguation, which is part of the message identity and must not be changed.
+            if options.convert_comments {
+                if let Some(comment) = source_message.translatorcomment.as_deref().filter(|comment| contains_chinese(comment)) {
+                    message.translatorcomment = Some(converter.convert(comment));
+                }
+            }
+            if let Some(value) = &source_message.translation.value {
+                let converted = options.convert(&converter, &message.source, value);
+                match keep_type {
+                    true => message.translation.value = Some(converted),
+                    false => message.fill_translation(&converted),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Translations of the message, with one item per plural form for plural messages.
+fn message_forms(message: &(impl polib::message::MessageView + ?Sized)) -> Vec<String> {
+    match message.is_plural() {
+        true => message.msgstr_plural().cloned().unwrap_or_default(),
+        false => vec![message.msgstr().unwrap_or_default().to_string()],
+    }
+}
+
+/// Fill the target GNU Gettext PO file with translations converted from the source file, see [`translate_ts_content`].
+pub fn translate_po_content(source_content: &Po, target_content: &mut Po, options: &ZhConvOptions) -> Result<(), ConversionError> {
+    use polib::message::{MessageMutView, MessageView};
+    use i18n_file::gettext::is_message_manually_reviewed;
+
+    let language_code = target_content.get_language();
+    let converter = TextConverter::new(&language_code, options)?;
+    let source_catalog = &source_content.inner;
+    let target_catalog = &mut target_content.inner;
+    let nplurals = target_catalog.metadata.plural_rules.nplurals.max(1);
+
+    let target_msg_count = target_catalog.count();
+    let source_msg_count = source_catalog.count();
+    if target_msg_count != source_msg_count {
+        return Err(ConversionError::DifferentMessages(language_code, source_msg_count, target_msg_count));
+    };
+    for (mut message, reference_message) in target_catalog.messages_mut().zip(source_catalog.messages()) {
+        // polib treats plural messages without any `msgstr[N]` as translated, there is nothing to convert from though.
+        let reference_translated = reference_message.is_translated() && !message_forms(reference_message).is_empty();
+        if is_message_manually_reviewed(&message) || !reference_translated || message.is_plural() != reference_message.is_plural() {
+            continue;
+        };
+        if !options.accepts_context(message.msgctxt().unwrap_or_default()) || !options.accepts_source(message.msgid()) {
+            continue;
+        }
+        if message.is_translated() && !should_reconvert(options, message_forms(&message) == message_forms(reference_message)) {
+            continue;
+        }
+        // Extracted comments (`#.`) are written by developers and kept as-is.
+        if options.convert_comments && message.msgid() == reference_message.msgid() && contains_chinese(reference_message.translator_comments()) {
+            *message.translator_comments_mut() = converter.convert(reference_message.translator_comments());
+        }
+        if message.is_plural() {
+            // The target file may have a different number of plural forms, missing ones reuse the last reference form.
+            let reference_forms = reference_message.msgstr_plural().unwrap();
+            let converted = (0..nplurals)
+                .map(|index| options.convert(&converter, reference_message.msgid(), &reference_forms[index.min(reference_forms.len() - 1)]))
+                .collect();
+            *message.msgstr_plural_mut().unwrap() = converted;
+        } else {
+            let translated_msg = options.convert(&converter, reference_message.msgid(), reference_message.msgstr().unwrap());
+            message.set_msgstr(translated_msg).unwrap();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_message_filters() {
+        use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+
+        let source_ts = Ts::load_from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
+        let mut target_ts = source_ts.clone();
+        target_ts.set_language("zh_TW");
+        target_ts.clear_finished_messages();
+        let options = ZhConvOptions { contexts: PathGlobs::new(&["!ts::*"]).unwrap(), ..ZhConvOptions::default() };
+        translate_ts_content(&source_ts, &mut target_ts, &options).unwrap();
+        assert_eq!(target_ts.contexts[0].messages[0].translation.value, None);
+
+        let options = ZhConvOptions { source_contains: Some("friend".to_string()), ..ZhConvOptions::default() };
+        translate_ts_content(&source_ts, &mut target_ts, &options).unwrap();
+        assert_eq!(target_ts.contexts[0].messages[0].translation.value, Some(String::from("海內存知己")));
+        assert_eq!(target_ts.contexts[0].messages[1].translation.value, None);
+    }
+
+    #[test]
+    fn tst_include_obsolete() {
+        use crate::i18n_file::linguist::TranslationType;
+        use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+
+        let source_ts = Ts::load_from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
+        let mut target_ts = source_ts.clone();
+        target_ts.set_language("zh_TW");
+        target_ts.contexts[0].messages[2].translation.value = None;
+        translate_ts_content(&source_ts, &mut target_ts, &ZhConvOptions::default()).unwrap();
+        assert_eq!(target_ts.contexts[0].messages[2].translation.value, None);
+
+        let options = ZhConvOptions { include_obsolete: true, ..ZhConvOptions::default() };
+        translate_ts_content(&source_ts, &mut target_ts, &options).unwrap();
+        assert_eq!(target_ts.contexts[0].messages[2].translation.value, Some(String::from("電影片段")));
+        assert!(matches!(target_ts.contexts[0].messages[2].translation.type_attr, Some(TranslationType::Obsolete)));
+    }
+
+    #[test]
+    fn tst_convert_comments() {
+        use crate::i18n_file::linguist::TranslationType;
+        let source_ts = Ts::load_from_str(r#"<?xml version="1.0" encoding="utf-8"?>
+<!DOCTYPE TS>
+<TS version="2.1" language="zh_CN">
+<context>
+    <name>Sample</name>
+    <message>
+        <source>Mouse</source>
+        <comment>鼠标</comment>
+        <extracomment>鼠标设置</extracomment>
+        <translatorcomment>指鼠标设备</translatorcomment>
+        <translation>鼠标</translation>
+    </message>
+    <message>
+        <source>Screen</source>
+        <translatorcomment>Review later</translatorcomment>
+        <translation>屏幕</translation>
+    </message>
+</context>
+</TS>"#).unwrap();
+        let mut initial_ts = source_ts.clone();
+        initial_ts.set_language("zh_TW");
+        initial_ts.contexts[0].messages[0].translation.type_attr = Some(TranslationType::Unfinished);
+        initial_ts.contexts[0].messages[1].translatorcomment = Some("稍後校對".to_string());
+        let mut target_ts = initial_ts.clone();
+        translate_ts_content(&source_ts, &mut target_ts, &ZhConvOptions::default()).unwrap();
+        assert_eq!(target_ts.contexts[0].messages[0].translatorcomment.as_deref(), Some("指鼠标设备"));
+
+        let options = ZhConvOptions { convert_comments: true, ..ZhConvOptions::default() };
+        let mut target_ts = initial_ts.clone();
+        translate_ts_content(&source_ts, &mut target_ts, &options).unwrap();
+        let message = &target_ts.contexts[0].messages[0];
+        assert_eq!(message.translatorcomment.as_deref(), Some("指滑鼠裝置"));
+        assert_eq!(message.comment.as_deref(), Some("鼠标"));
+        assert_eq!(message.extracomment.as_deref(), Some("鼠标设置"));
+        assert_eq!(target_ts.contexts[0].messages[1].translatorcomment.as_deref(), Some("稍後校對"));
+
+        // Finished messages that are not reconverted keep their comments untouched.
+        let mut target_ts = initial_ts.clone();
+        target_ts.contexts[0].messages[0].translation.type_attr = None;
+        target_ts.contexts[0].messages[0].translation.value = Some("滑鼠".to_string());
+        translate_ts_content(&source_ts, &mut target_ts, &options).unwrap();
+        assert_eq!(target_ts.contexts[0].messages[0].translatorcomment.as_deref(), Some("指鼠标设备"));
+    }
+
+    #[test]
+    fn tst_skip_sources() {
+        use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+
+        let source_ts = Ts::load_from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
+        let mut target_ts = source_ts.clone();
+        target_ts.set_language("zh_TW");
+        target_ts.clear_finished_messages();
+        let options = ZhConvOptions {
+            skip_sources: HashSet::from(["A friend in need is a friend indeed".to_string()]),
+            ..ZhConvOptions::default()
+        };
+        translate_ts_content(&source_ts, &mut target_ts, &options).unwrap();
+        assert_eq!(target_ts.contexts[0].messages[0].translation.value, Some(String::from("海内存知己")));
+        assert!(target_ts.contexts[0].messages[0].translation.type_attr.is_none());
+        assert_eq!(target_ts.contexts[0].messages[1].translation.value, Some(String::from("軟體開發工程師在使用滑鼠操作螢幕上的游標")));
+    }
+
+    #[test]
+    fn tst_variant_mapping() {
+        let mapping = VariantMapping::default();
+        assert_eq!(mapping.variant("zh_CN").unwrap(), Variant::ZhCN);
+        assert_eq!(mapping.variant("zh_TW.UTF-8").unwrap(), Variant::ZhTW);
+        assert_eq!(mapping.variant("zh-Hans").unwrap(), Variant::ZhHans);
+        assert_eq!(mapping.variant("zh_Hant_HK").unwrap(), Variant::ZhHK);
+        assert_eq!(mapping.variant("zh-Hant-TW").unwrap(), Variant::ZhTW);
+        assert_eq!(mapping.variant("zh_Hans_SG").unwrap(), Variant::ZhSG);
+        assert_eq!(mapping.variant("zh_Hans_HK").unwrap(), Variant::ZhHans);
+        assert_eq!(mapping.variant("zh_Hant_US").unwrap(), Variant::ZhHant);
+        assert_eq!(mapping.variant("zh_CHT").unwrap(), Variant::ZhHant);
+        assert!(mapping.variant("en_US").is_err());
+        assert!(mapping.variant("zh_Latn").is_err());
+        assert_eq!(convert_text("軟體開發工程師", "zh_CN", &mapping).unwrap(), "软件开发工程师");
+
+        let overrides = HashMap::from([("zh_SG".to_string(), "zh-CN".to_string()), ("zh_Hans_HK".to_string(), "zh-HK".to_string())]);
+        let mapping = VariantMapping::with_overrides(&overrides).unwrap();
+        assert_eq!(mapping.variant("zh-sg").unwrap(), Variant::ZhCN);
+        assert_eq!(mapping.variant("zh_Hans_HK").unwrap(), Variant::ZhHK);
+        assert_eq!(mapping.variant("zh_TW").unwrap(), Variant::ZhTW);
+        let overrides = HashMap::from([("zh_SG".to_string(), "simplified".to_string())]);
+        assert!(VariantMapping::with_overrides(&overrides).is_err());
+    }
+
+    #[test]
+    fn tst_custom_rules_and_protected_terms() {
+        let options = ZhConvOptions {
+            rules: vec!["zh-cn:深度;zh-tw:深度科技;zh-hk:深度科技;".to_string()],
+            protected_terms: vec!["软件".to_string()],
+            ..ZhConvOptions::default()
+        };
+        let converter = TextConverter::new("zh_TW", &options).unwrap();
+        assert_eq!(converter.convert("深度的软件"), "深度科技的软件");
+        assert_eq!(TextConverter::new("zh_TW", &ZhConvOptions::default()).unwrap().convert("深度的软件"), "深度的軟體");
+    }
+
+    #[test]
+    fn tst_translate_ts_content() {
+        use crate::i18n_file::linguist::Ts;
+        use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+
+        let source_ts: Ts = Ts::load_from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
+        let mut target_ts: Ts = source_ts.clone();
+        target_ts.set_language("zh_TW");
+        target_ts.clear_finished_messages();
+        assert!(translate_ts_content(&source_ts, &mut target_ts, &ZhConvOptions::default()).is_ok());
+        assert_eq!(target_ts.get_language(), Some("zh_TW".to_string()));
+        assert_eq!(target_ts.contexts.len(), 1);
+        assert_eq!(target_ts.contexts[0].messages.len(), 5);
+        assert_eq!(target_ts.contexts[0].messages[0].translation.value, Some(String::from("海內存知己")));
+        assert_eq!(target_ts.contexts[0].messages[1].translation.value, Some(String::from("軟體開發工程師在使用滑鼠操作螢幕上的游標")));
+        assert_eq!(target_ts.contexts[0].messages[2].translation.value, Some(String::from("电视频段"))); // marked as obsolete, should not be translated.
+        assert_eq!(target_ts.contexts[0].messages[3].translation.value, None); // source is also untranslated
+    }
+
+    #[test]
+    fn tst_force_reconvert() {
+        use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+
+        let source_ts = Ts::load_from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
+        let mut target_ts = source_ts.clone();
+        target_ts.set_language("zh_TW");
+        target_ts.contexts[0].messages[1].fill_translation("軟件開發工程師");
+        translate_ts_content(&source_ts, &mut target_ts, &ZhConvOptions::default()).unwrap();
+        assert_eq!(target_ts.contexts[0].messages[0].translation.value, Some(String::from("海内存知己")));
+
+        let options = ZhConvOptions { force: Some(ForceMode::SameAsSource), ..ZhConvOptions::default() };
+        translate_ts_content(&source_ts, &mut target_ts, &options).unwrap();
+        assert_eq!(target_ts.contexts[0].messages[0].translation.value, Some(String::from("海內存知己")));
+        assert_eq!(target_ts.contexts[0].messages[1].translation.value, Some(String::from("軟件開發工程師")));
+
+        target_ts.contexts[0].messages[0].set_manually_reviewed(true);
+        target_ts.contexts[0].messages[0].fill_translation("海內存知己！");
+        let options = ZhConvOptions { force: Some(ForceMode::All), ..ZhConvOptions::default() };
+        translate_ts_content(&source_ts, &mut target_ts, &options).unwrap();
+        assert_eq!(target_ts.contexts[0].messages[0].translation.value, Some(String::from("海內存知己！")));
+        assert_eq!(target_ts.contexts[0].messages[1].translation.value, Some(String::from("軟體開發工程師在使用滑鼠操作螢幕上的游標")));
+        assert_eq!(target_ts.contexts[0].messages[2].translation.value, Some(String::from("电视频段"))); // obsolete messages are never converted
+    }
+
+    #[test]
+    fn tst_translate_po_content() {
+        use polib::message::MessageView;
+        use crate::i18n_file::gettext::Po;
+        use crate::i18n_file::gettext::tests::TEST_ZH_CN_PO_CONTENT;
+
+        let source_po = Po::load_from_str(TEST_ZH_CN_PO_CONTENT).unwrap();
+        let mut target_po = source_po.clone();
+        target_po.set_language("zh_TW");
+        target_po.clear_finished_messages();
+        assert!(translate_po_content(&source_po, &mut target_po, &ZhConvOptions::default()).is_ok());
+        assert_eq!(target_po.get_language(), "zh_TW".to_string());
+        assert_eq!(target_po.inner.count(), 3);
+        let mut msgs = target_po.inner.messages();
+        assert_eq!(msgs.next().unwrap().msgstr().unwrap(), "海內存知己");
+        assert_eq!(msgs.next().unwrap().msgstr().unwrap(), "軟體開發工程師在使用滑鼠操作螢幕上的游標");
+        assert_eq!(msgs.next().unwrap().msgstr().unwrap(), ""); // source is also untranslated
+        assert_eq!(target_po.obsolete[0].msgstr().unwrap(), "电视频段"); // obsolete messages are never converted
+    }
+
+    #[test]
+    fn tst_translate_po_plural_forms() {
+        use crate::i18n_file::gettext::Po;
+
+        let source_po = Po::load_from_str(r#"msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+"Plural-Forms: nplurals=1; plural=0;\n"
+"Language: zh_CN\n"
+
+msgid "%d file"
+msgid_plural "%d files"
+msgstr[0] "%d 个文件"
+
+msgid "%d folder"
+msgid_plural "%d folders"
+msgstr[0] ""
+
+msgid "%d disk"
+msgid_plural "%d disks"
+"#).unwrap();
+        let translate_into = |plural_forms: &str| {
+            let mut target_po = Po::load_from_str(&format!(r#"msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+"Plural-Forms: {plural_forms}\n"
+"Language: zh_TW\n"
+
+msgid "%d file"
+msgid_plural "%d files"
+msgstr[0] ""
+
+msgid "%d folder"
+msgid_plural "%d folders"
+msgstr[0] ""
+
+msgid "%d disk"
+msgid_plural "%d disks"
+msgstr[0] ""
+"#)).unwrap();
+            translate_po_content(&source_po, &mut target_po, &ZhConvOptions::default()).unwrap();
+            let forms: Vec<Vec<String>> = target_po.inner.messages().map(|message| message.msgstr_plural().unwrap().clone()).collect();
+            forms
+        };
+        // The reference message without any plural form is left untouched.
+        assert_eq!(translate_into("nplurals=1; plural=0;"), vec![vec!["%d 個檔案".to_string()], vec!["".to_string()], vec!["".to_string()]]);
+        // Some files use the English plural rules for Chinese, fill all plural forms.
+        assert_eq!(translate_into("nplurals=2; plural=(n != 1);"),
+            vec![vec!["%d 個檔案".to_string(), "%d 個檔案".to_string()], vec!["".to_string()], vec!["".to_string()]]);
+    }
+
+    #[test]
+    fn tst_skip_manually_reviewed_messages() {
+        use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+        use crate::i18n_file::gettext::tests::TEST_ZH_CN_PO_CONTENT;
+
+        let source_ts = Ts::load_from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
+        let mut target_ts = source_ts.clone();
+        target_ts.set_language("zh_TW");
+        target_ts.clear_finished_messages();
+        target_ts.contexts[0].messages[0].set_manually_reviewed(true);
+        translate_ts_content(&source_ts, &mut target_ts, &ZhConvOptions::default()).unwrap();
+        assert_eq!(target_ts.contexts[0].messages[0].translation.value, None);
+        assert_eq!(target_ts.contexts[0].messages[1].translation.value, Some(String::from("軟體開發工程師在使用滑鼠操作螢幕上的游標")));
+
+        let source_po = Po::load_from_str(TEST_ZH_CN_PO_CONTENT).unwrap();
+        let mut target_po = source_po.clone();
+        target_po.set_language("zh_TW");
+        target_po.clear_finished_messages();
+        i18n_file::gettext::set_message_manually_reviewed(&mut target_po.inner.messages_mut().next().unwrap(), true);
+        translate_po_content(&source_po, &mut target_po, &ZhConvOptions::default()).unwrap();
+        let mut msgs = target_po.inner.messages();
+        assert_eq!(msgs.next().unwrap().msgstr().unwrap(), "");
+        assert_eq!(msgs.next().unwrap().msgstr().unwrap(), "軟體開發工程師在使用滑鼠操作螢幕上的游標");
+    }
+
+}
@@ -0,0 +1,258 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+//! Extract translatable strings from source code, like what `lupdate` and `xgettext` do.
+
+pub mod cpp;
+pub mod gettext;
+mod lexer;
+pub mod qml;
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use crate::i18n_file::linguist::{Context, Location, Message, Translation, TranslationType, Ts};
+
+/// A translatable string found in the source code.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedMessage {
+    pub context: String,
+    pub source: String,
+    /// Disambiguation text, i.e. the `<comment>` element of TS files.
+    pub comment: Option<String>,
+    /// Comment for translators written as `//:` in the source code.
+    pub extracomment: Option<String>,
+    pub numerus: bool,
+    /// Plural form of the source text, only used by gettext resources.
+    pub plural_source: Option<String>,
+    pub filename: String,
+    pub line: usize,
+}
+
+/// A source file to extract strings from.
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    /// File name used in locations, usually relative to the translation file.
+    pub filename: String,
+    pub content: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ExtractResult {
+    pub messages: Vec<ExtractedMessage>,
+    pub warnings: Vec<String>,
+}
+
+/// Statistics of a [`merge_into_ts`] run.
+#[derive(Debug, Default, PartialEq)]
+pub struct TsMergeStats {
+    pub new: u64,
+    pub existing: u64,
+    pub vanished: u64,
+    pub removed: u64,
+}
+
+type MessageKey<'a> = (&'a str, &'a str, Option<&'a str>);
+
+/// Update the given TS file with extracted messages.
+///
+/// Contexts are sorted by name and messages are ordered by their first occurrence. Translations of
+/// existing messages are kept. Translated messages that no longer exist in the source code are
+/// marked as vanished unless `no_obsolete` is set, untranslated ones are removed.
+pub fn merge_into_ts(ts: &Ts, extracted: &[ExtractedMessage], no_obsolete: bool) -> (Ts, TsMergeStats) {
+    let mut stats = TsMergeStats::default();
+
+    let mut existing: HashMap<MessageKey, &Message> = HashMap::new();
+    for context in &ts.contexts {
+        for message in &context.messages {
+            existing.insert((context.name.as_str(), message.source.as_str(), message.comment.as_deref()), message);
+        }
+    }
+
+    let mut contexts: Vec<Context> = vec![];
+    let mut indexes: HashMap<MessageKey, (usize, usize)> = HashMap::new();
+    for item in extracted {
+        let location = Location {
+            filename: Some(item.filename.clone()),
+            line: item.line.to_string(),
+        };
+        let key = (item.context.as_str(), item.source.as_str(), item.comment.as_deref());
+        if let Some(&(context_index, message_index)) = indexes.get(&key) {
+            let message = &mut contexts[context_index].messages[message_index];
+            message.location.push(location);
+            if item.extracomment.is_some() && message.extracomment.is_none() {
+                message.extracomment = item.extracomment.clone();
+            }
+            continue;
+        }
+
+        let message = match existing.get(&key) {
+            Some(old_message) => {
+                stats.existing += 1;
+                let mut message = (*old_message).clone();
+                if matches!(message.translation.type_attr, Some(TranslationType::Vanished | TranslationType::Obsolete)) {
+                    let has_translation = message.translation.value.as_ref().is_some_and(|value| !value.is_empty())
+                        || message.translation.numerus_forms.iter().any(|form| !form.is_empty());
+                    message.translation.type_attr = (!has_translation).then_some(TranslationType::Unfinished);
+                }
+                let manually_reviewed = message.is_manually_reviewed();
+                message.extracomment = item.extracomment.clone();
+                message.set_manually_reviewed(manually_reviewed);
+                message.location = vec![location];
+                message.numerus = item.numerus.then(|| "yes".to_string());
+                message
+            },
+            None => {
+                stats.new += 1;
+                Message {
+                    location: vec![location],
+                    source: item.source.clone(),
+                    translation: Translation {
+                        type_attr: Some(TranslationType::Unfinished),
+                        value: None,
+                        numerus_forms: vec![],
+                    },
+                    comment: item.comment.clone(),
+                    extracomment: item.extracomment.clone(),
+                    translatorcomment: None,
+                    numerus: item.numerus.then(|| "yes".to_string()),
+                }
+            },
+        };
+
+        let context_index = match contexts.iter().position(|context| context.name == item.context) {
+            Some(index) => index,
+            None => {
+                contexts.push(Context { name: item.context.clone(), messages: vec![] });
+                contexts.len() - 1
+            },
+        };
+        contexts[context_index].messages.push(message);
+        indexes.insert(key, (context_index, contexts[context_index].messages.len() - 1));
+    }
+
+    for context in &ts.contexts {
+        for message in &context.messages {
+            let key = (context.name.as_str(), message.source.as_str(), message.comment.as_deref());
+            if indexes.contains_key(&key) {
+                continue;
+            }
+            let has_translation = !matches!(message.translation.type_attr, Some(TranslationType::Unfinished))
+                || message.translation.value.as_ref().is_some_and(|value| !value.is_empty());
+            if no_obsolete || !has_translation {
+                stats.removed += 1;
+                continue;
+            }
+            stats.vanished += 1;
+            let mut message = message.clone();
+            message.location.clear();
+            if !matches!(message.translation.type_attr, Some(TranslationType::Obsolete)) {
+                message.translation.type_attr = Some(TranslationType::Vanished);
+            }
+            match contexts.iter_mut().find(|new_context| new_context.name == context.name) {
+                Some(new_context) => new_context.messages.push(message),
+                None => contexts.push(Context { name: context.name.clone(), messages: vec![message] }),
+            }
+        }
+    }
+
+    contexts.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+    (Ts { language: ts.language.clone(), source_language: ts.source_language.clone(), version: ts.version.clone(), contexts, layout: ts.layout }, stats)
+}
+
+/// Get the path of `path` relative to `base_dir`, used as the `filename` of locations.
+///
+/// Both paths are expected to be absolute. Path separators are always `/`.
+pub fn relative_location_path(base_dir: &Path, path: &Path) -> String {
+    let base_components: Vec<Component> = base_dir.components().collect();
+    let path_components: Vec<Component> = path.components().collect();
+    let common_len = base_components.iter().zip(&path_components)
+        .take_while(|(lhs, rhs)| lhs == rhs)
+        .count();
+    let mut parts: Vec<String> = vec!["..".to_string(); base_components.len() - common_len];
+    parts.extend(path_components[common_len..].iter().map(|component| component.as_os_str().to_string_lossy().to_string()));
+    parts.join("/")
+}
+
+/// Collect source files with the given extensions from files and directories, sorted by path.
+///
+/// Hidden files and directories are skipped.
+pub fn collect_source_files(paths: &[PathBuf], extensions: &[&str]) -> Vec<PathBuf> {
+    let has_extension = |path: &Path| path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.contains(&ext));
+    let mut files = vec![];
+    for path in paths {
+        if path.is_file() {
+            files.push(path.clone());
+            continue;
+        }
+        let entries = walkdir::WalkDir::new(path)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.'))
+            .filter_map(|entry| entry.ok());
+        for entry in entries {
+            if entry.file_type().is_file() && has_extension(entry.path()) {
+                files.push(entry.into_path());
+            }
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+
+    fn extracted(context: &str, source: &str, line: usize) -> ExtractedMessage {
+        ExtractedMessage {
+            context: context.to_string(),
+            source: source.to_string(),
+            comment: None,
+            extracomment: None,
+            numerus: false,
+            plural_source: None,
+            filename: "../src/main.cpp".to_string(),
+            line,
+        }
+    }
+
+    #[test]
+    fn tst_merge_into_ts() {
+        let ts = Ts::load_from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
+        let items = vec![
+            extracted("ts::SampleContext", "A friend in need is a friend indeed", 10),
+            extracted("MainWindow", "Open", 20),
+            extracted("ts::SampleContext", "A friend in need is a friend indeed", 30),
+            extracted("ts::SampleContext", "TV band", 40),
+        ];
+        let (merged, stats) = merge_into_ts(&ts, &items, false);
+        assert_eq!(stats, TsMergeStats { new: 1, existing: 2, vanished: 2, removed: 1 });
+        assert_eq!(merged.language, Some("zh_CN".to_string()));
+        assert_eq!(merged.contexts[0].name, "MainWindow");
+        assert!(matches!(merged.contexts[0].messages[0].translation.type_attr, Some(TranslationType::Unfinished)));
+
+        let messages = &merged.contexts[1].messages;
+        assert_eq!(messages[0].translation.value, Some("海内存知己".to_string()));
+        assert_eq!(messages[0].location.len(), 2);
+        assert_eq!(messages[0].location[1].line, "30");
+        // Revived obsolete message with translation
+        assert_eq!(messages[1].source, "TV band");
+        assert!(messages[1].translation.type_attr.is_none());
+        assert!(matches!(messages[2].translation.type_attr, Some(TranslationType::Vanished)));
+        assert!(messages[2].location.is_empty());
+        assert_eq!(messages.len(), 4);
+
+        let (_, stats) = merge_into_ts(&ts, &items, true);
+        assert_eq!(stats, TsMergeStats { new: 1, existing: 2, vanished: 0, removed: 3 });
+    }
+
+    #[test]
+    fn tst_relative_location_path() {
+        assert_eq!(relative_location_path(Path::new("/proj/translations"), Path::new("/proj/src/main.cpp")), "../src/main.cpp");
+        assert_eq!(relative_location_path(Path::new("/proj"), Path::new("/proj/main.cpp")), "main.cpp");
+    }
+}
@@ -0,0 +1,547 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+//! A lightweight C++ scanner for Qt translation functions.
+//!
+//! This is not a real C++ parser. It only tracks namespaces, class bodies and function bodies to
+//! find out the context of `tr()` calls, which is good enough for typical Qt code.
+
+use std::collections::HashSet;
+
+use super::{ExtractResult, ExtractedMessage, SourceFile};
+use super::lexer::{call_arguments, string_literal, tokenize, SpannedToken, Syntax, Token};
+
+/// File extensions that are treated as C++ sources.
+pub const CPP_EXTENSIONS: &[&str] = &["cpp", "cc", "cxx", "c++", "h", "hh", "hpp", "hxx", "h++"];
+
+/// Context of a message before it's resolved against all known classes.
+#[derive(Debug, Clone, PartialEq)]
+enum ContextRef {
+    Resolved(String),
+    /// A class name written as `Qualifier::` inside the given namespace.
+    Qualified {
+        namespace: String,
+        qualifier: String,
+        /// Whether to prefix the namespace if the class is unknown. This is true for function
+        /// definitions (`Foo::bar() {}` inside a namespace), but false for `Foo::tr()` calls.
+        namespaced_fallback: bool,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum Scope {
+    Namespace(String),
+    Class(String),
+    /// Function body, with the context used by `tr()` calls inside it.
+    Function(Option<ContextRef>),
+    Block,
+}
+
+struct RawMessage {
+    context: ContextRef,
+    message: ExtractedMessage,
+}
+
+/// Identifiers that look like a function call but never start a function definition.
+const NON_FUNCTION_KEYWORDS: &[&str] = &[
+    "if", "for", "while", "switch", "return", "sizeof", "alignof", "alignas", "decltype", "noexcept",
+    "throw", "static_assert", "__attribute__", "__declspec", "catch",
+];
+
+const ACCESS_SPECIFIERS: &[&str] = &["public", "protected", "private", "signals", "slots", "Q_SIGNALS", "Q_SLOTS"];
+
+struct Parser<'a> {
+    filename: &'a str,
+    tokens: &'a [SpannedToken],
+    /// Scope stack, with the paren depth of the outer scope.
+    scopes: Vec<(Scope, usize)>,
+    paren_depth: usize,
+    pending_namespace: Option<String>,
+    pending_class: Option<String>,
+    class_base_seen: bool,
+    pending_function: Option<Option<ContextRef>>,
+    init_list_seen: bool,
+    pending_comments: Vec<String>,
+    classes: Vec<String>,
+    messages: Vec<RawMessage>,
+    warnings: Vec<String>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(filename: &'a str, tokens: &'a [SpannedToken]) -> Self {
+        Parser {
+            filename,
+            tokens,
+            scopes: vec![],
+            paren_depth: 0,
+            pending_namespace: None,
+            pending_class: None,
+            class_base_seen: false,
+            pending_function: None,
+            init_list_seen: false,
+            pending_comments: vec![],
+            classes: vec![],
+            messages: vec![],
+            warnings: vec![],
+        }
+    }
+
+    fn token(&self, index: usize) -> Option<&Token> {
+        self.tokens.get(index).map(|spanned| &spanned.token)
+    }
+
+    fn reset_pending(&mut self) {
+        self.pending_namespace = None;
+        self.pending_class = None;
+        self.class_base_seen = false;
+        self.pending_function = None;
+        self.init_list_seen = false;
+    }
+
+    fn parse(&mut self) {
+        let tokens = self.tokens;
+        for (index, spanned) in tokens.iter().enumerate() {
+            match &spanned.token {
+                Token::TranslatorComment(comment) => self.pending_comments.push(comment.clone()),
+                Token::Ident(ident) => self.handle_ident(index, ident),
+                Token::Punct('(') => {
+                    if self.paren_depth == 0 {
+                        self.handle_definition_paren(index);
+                    }
+                    self.paren_depth += 1;
+                },
+                Token::Punct(')') => self.paren_depth = self.paren_depth.saturating_sub(1),
+                Token::Punct(':') if self.paren_depth == 0 => {
+                    if self.pending_class.is_some() {
+                        self.class_base_seen = true;
+                    } else if self.pending_function.is_some() {
+                        self.init_list_seen = true;
+                    }
+                },
+                Token::Punct('>' | ',' | '=') if self.paren_depth == 0 => {
+                    // `template <class T>` and `template <class T = int>` are not class definitions.
+                    if !self.class_base_seen {
+                        self.pending_class = None;
+                    }
+                    if matches!(spanned.token, Token::Punct('=')) {
+                        self.pending_namespace = None;
+                    }
+                },
+                Token::Punct(';') => {
+                    if self.paren_depth == 0 {
+                        self.reset_pending();
+                    }
+                    self.pending_comments.clear();
+                },
+                Token::Punct('{') => {
+                    let scope = if let Some(namespace) = self.pending_namespace.take() {
+                        Scope::Namespace(namespace)
+                    } else if let Some(class) = self.pending_class.take().filter(|class| !class.is_empty()) {
+                        let class = self.qualified_name(&class);
+                        self.classes.push(class.clone());
+                        Scope::Class(class)
+                    } else if let Some(context) = self.pending_function.take() {
+                        Scope::Function(context)
+                    } else {
+                        Scope::Block
+                    };
+                    self.scopes.push((scope, self.paren_depth));
+                    self.paren_depth = 0;
+                    self.reset_pending();
+                },
+                Token::Punct('}') => {
+                    if let Some((_, paren_depth)) = self.scopes.pop() {
+                        self.paren_depth = paren_depth;
+                    }
+                    self.reset_pending();
+                    self.pending_comments.clear();
+                },
+                _ => {},
+            }
+        }
+    }
+
+    fn handle_ident(&mut self, index: usize, ident: &str) {
+        let previous = index.checked_sub(1).and_then(|index| self.token(index));
+        match ident {
+            "namespace" if previous != Some(&Token::Ident("using".to_string())) => {
+                self.pending_namespace = Some(String::new());
+                return;
+            },
+            "class" | "struct" | "union" if self.paren_depth == 0 && previous != Some(&Token::Ident("enum".to_string())) => {
+                self.pending_class = Some(String::new());
+                self.class_base_seen = false;
+                return;
+            },
+            _ => {},
+        }
+        if let Some(namespace) = &mut self.pending_namespace {
+            if !namespace.is_empty() {
+                namespace.push_str("::");
+            }
+            namespace.push_str(ident);
+        } else if self.pending_class.is_some() && !self.class_base_seen {
+            if ident != "final" {
+                self.pending_class = Some(ident.to_string());
+            }
+        } else if ACCESS_SPECIFIERS.contains(&ident) && self.pending_class.is_none() {
+            self.pending_function = None;
+            self.init_list_seen = false;
+        }
+
+        if self.token(index + 1) == Some(&Token::Punct('(')) {
+            self.handle_translation_call(index, ident);
+        }
+    }
+
+    /// Check whether a `(` at paren depth 0 starts the parameter list of a function definition.
+    fn handle_definition_paren(&mut self, paren_index: usize) {
+        if self.scopes.iter().any(|(scope, _)| matches!(scope, Scope::Function(_))) {
+            return;
+        }
+        if !self.class_base_seen {
+            // e.g. `class Foo *create()`
+            self.pending_class = None;
+        }
+        if self.init_list_seen {
+            return;
+        }
+        let Some(mut name_index) = paren_index.checked_sub(1) else {
+            return;
+        };
+        // `operator==(` and `operator()(`
+        if let Some(operator_index) = (name_index.saturating_sub(3)..=name_index).rev()
+            .find(|&index| self.token(index) == Some(&Token::Ident("operator".to_string())))
+        {
+            name_index = operator_index;
+        }
+        let Some(Token::Ident(name)) = self.token(name_index) else {
+            return;
+        };
+        if NON_FUNCTION_KEYWORDS.contains(&name.as_str()) {
+            return;
+        }
+        if name_index > 0 && self.token(name_index - 1) == Some(&Token::Punct('~')) {
+            name_index -= 1;
+        }
+        let qualifier = name_index.checked_sub(1)
+            .filter(|&index| self.token(index) == Some(&Token::DoubleColon))
+            .map(|index| self.qualifier_before(index))
+            .unwrap_or_default();
+
+        let context = if let Some(class) = self.enclosing_class() {
+            Some(ContextRef::Resolved(class))
+        } else if !qualifier.is_empty() {
+            Some(ContextRef::Qualified {
+                namespace: self.namespace_path(),
+                qualifier,
+                namespaced_fallback: true,
+            })
+        } else {
+            None
+        };
+        self.pending_function = Some(context);
+    }
+
+    /// Collect the qualifier like `A::B` before the `::` token at the given index.
+    fn qualifier_before(&self, double_colon_index: usize) -> String {
+        let mut parts: Vec<&str> = vec![];
+        let mut index = double_colon_index;
+        while self.token(index) == Some(&Token::DoubleColon) {
+            let Some(mut name_index) = index.checked_sub(1) else {
+                break;
+            };
+            // Skip template arguments, e.g. `Foo<T>::bar`
+            if self.token(name_index) == Some(&Token::Punct('>')) {
+                let mut depth = 0;
+                loop {
+                    match self.token(name_index) {
+                        Some(Token::Punct('>')) => depth += 1,
+                        Some(Token::Punct('<')) => depth -= 1,
+                        _ => {},
+                    }
+                    if depth == 0 || name_index == 0 {
+                        break;
+                    }
+                    name_index -= 1;
+                }
+                let Some(index) = name_index.checked_sub(1) else {
+                    break;
+                };
+                name_index = index;
+            }
+            let Some(Token::Ident(name)) = self.token(name_index) else {
+                break;
+            };
+            parts.insert(0, name);
+            let Some(previous_index) = name_index.checked_sub(1) else {
+                break;
+            };
+            index = previous_index;
+        }
+        parts.join("::")
+    }
+
+    fn namespace_path(&self) -> String {
+        self.scopes.iter()
+            .filter_map(|(scope, _)| match scope {
+                Scope::Namespace(name) if !name.is_empty() => Some(name.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+
+    fn qualified_name(&self, name: &str) -> String {
+        let mut parent = match self.enclosing_class() {
+            Some(class) => class,
+            None => self.namespace_path(),
+        };
+        if !parent.is_empty() {
+            parent.push_str("::");
+        }
+        parent + name
+    }
+
+    /// The innermost class whose body is the current scope, without any function in between.
+    fn enclosing_class(&self) -> Option<String> {
+        for (scope, _) in self.scopes.iter().rev() {
+            match scope {
+                Scope::Class(name) => return Some(name.clone()),
+                Scope::Block => continue,
+                _ => return None,
+            }
+        }
+        None
+    }
+
+    fn current_context(&self) -> Option<ContextRef> {
+        if self.init_list_seen {
+            if let Some(context) = &self.pending_function {
+                return context.clone();
+            }
+        }
+        for (scope, _) in self.scopes.iter().rev() {
+            match scope {
+                Scope::Function(context) => return context.clone(),
+                Scope::Class(name) => return Some(ContextRef::Resolved(name.clone())),
+                Scope::Namespace(_) => return None,
+                Scope::Block => continue,
+            }
+        }
+        None
+    }
+
+    fn handle_translation_call(&mut self, index: usize, function: &str) {
+        let line = self.tokens[index].line;
+        let previous = index.checked_sub(1).and_then(|index| self.token(index));
+        let arguments = call_arguments(self.tokens, index + 1);
+        let text = |index: usize| arguments.get(index).and_then(|argument| string_literal(argument));
+
+        let (context, source, comment, numerus) = match function {
+            "tr" | "trUtf8" => {
+                let Some(source) = text(0) else {
+                    return;
+                };
+                let context = match previous {
+                    Some(Token::DoubleColon) => Some(ContextRef::Qualified {
+                        namespace: self.namespace_path(),
+                        qualifier: self.qualifier_before(index - 1),
+                        namespaced_fallback: false,
+                    }),
+                    Some(Token::Punct('.') | Token::Arrow) => None,
+                    _ => self.current_context(),
+                };
+                let Some(context) = context else {
+                    self.warnings.push(format!("{}:{line}: Can not determine the context of tr() call, ignored.", self.filename));
+                    return;
+                };
+                (context, source, text(1), arguments.len() >= 3)
+            },
+            "translate" => {
+                let (Some(context), Some(source)) = (text(0), text(1)) else {
+                    return;
+                };
+                (ContextRef::Resolved(context), source, text(2), arguments.len() >= 4)
+            },
+            "QT_TR_NOOP" | "QT_TR_NOOP_UTF8" | "QT_TR_N_NOOP" => {
+                let Some(source) = text(0) else {
+                    return;
+                };
+                let Some(context) = self.current_context() else {
+                    self.warnings.push(format!("{}:{line}: Can not determine the context of {function}(), ignored.", self.filename));
+                    return;
+                };
+                (context, source, None, function == "QT_TR_N_NOOP")
+            },
+            "QT_TRANSLATE_NOOP" | "QT_TRANSLATE_NOOP_UTF8" | "QT_TRANSLATE_NOOP3" | "QT_TRANSLATE_N_NOOP" | "QT_TRANSLATE_N_NOOP3" => {
+                let (Some(context), Some(source)) = (text(0), text(1)) else {
+                    return;
+                };
+                (ContextRef::Resolved(context), source, text(2), function.starts_with("QT_TRANSLATE_N_"))
+            },
+            _ => return,
+        };
+
+        let extracomment = (!self.pending_comments.is_empty()).then(|| self.pending_comments.join(" "));
+        self.pending_comments.clear();
+        self.messages.push(RawMessage {
+            context,
+            message: ExtractedMessage {
+                context: String::new(),
+                source,
+                comment: comment.filter(|comment| !comment.is_empty()),
+                extracomment,
+                numerus,
+                plural_source: None,
+                filename: self.filename.to_string(),
+                line,
+            },
+        });
+    }
+}
+
+/// `//: ...` and `/*: ... */` comments are comments for translators.
+pub(super) fn translator_comment(comment: &str) -> Option<String> {
+    let comment = comment.strip_prefix(':')?;
+    Some(comment.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn resolve_context(context: ContextRef, known_classes: &HashSet<String>) -> String {
+    let (namespace, qualifier, namespaced_fallback) = match context {
+        ContextRef::Resolved(context) => return context,
+        ContextRef::Qualified { namespace, qualifier, namespaced_fallback } => (namespace, qualifier, namespaced_fallback),
+    };
+    let mut scope = namespace.as_str();
+    loop {
+        let candidate = if scope.is_empty() { qualifier.clone() } else { format!("{scope}::{qualifier}") };
+        if known_classes.contains(&candidate) {
+            return candidate;
+        }
+        if scope.is_empty() {
+            break;
+        }
+        scope = scope.rsplit_once("::").map_or("", |(parent, _)| parent);
+    }
+    // Classes brought in by `using namespace`.
+    let suffix = format!("::{qualifier}");
+    let mut matched = known_classes.iter().filter(|class| class.ends_with(&suffix));
+    if let (Some(class), None) = (matched.next(), matched.next()) {
+        return class.clone();
+    }
+    if namespaced_fallback && !namespace.is_empty() {
+        format!("{namespace}::{qualifier}")
+    } else {
+        qualifier
+    }
+}
+
+/// Extract translatable strings from the given C++ source files.
+///
+/// Class declarations from all files are collected before resolving contexts, so headers should
+/// be passed together with the sources.
+pub fn extract_from_cpp_sources(sources: &[SourceFile]) -> ExtractResult {
+    let mut known_classes = HashSet::new();
+    let mut raw_messages = vec![];
+    let mut result = ExtractResult::default();
+    for source in sources {
+        let tokens = tokenize(&source.content, Syntax::C, translator_comment);
+        let mut parser = Parser::new(&source.filename, &tokens);
+        parser.parse();
+        known_classes.extend(parser.classes);
+        raw_messages.extend(parser.messages);
+        result.warnings.extend(parser.warnings);
+    }
+    result.messages = raw_messages.into_iter()
+        .map(|raw| ExtractedMessage {
+            context: resolve_context(raw.context, &known_classes),
+            ..raw.message
+        })
+        .collect();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_HEADER_CONTENT: &str = r#"
+#pragma once
+#include <QWidget>
+
+namespace dcc {
+namespace network {
+class NETWORK_EXPORT WirelessPage : public QWidget, public Base<int>
+{
+    Q_OBJECT
+    Q_PROPERTY(QString title READ title)
+public:
+    explicit WirelessPage(QWidget *parent = nullptr);
+    QString title() const { return tr("Wireless Network"); }
+
+private:
+    QString m_hint = tr("Scanning...");
+};
+}
+}
+"#;
+
+    const TEST_SOURCE_CONTENT: &str = r#"
+#include "wirelesspage.h"
+#define MY_TR(x) tr(x)
+
+using namespace dcc::network;
+
+static const char *STATES[] = {
+    QT_TRANSLATE_NOOP("NetworkState", "Connected"),
+    QT_TRANSLATE_NOOP3("NetworkState", "Connecting", "in progress"),
+};
+
+WirelessPage::WirelessPage(QWidget *parent)
+    : QWidget(parent)
+    , m_label(new QLabel(tr("Hidden network"), this))
+{
+    //: The text on the refresh button
+    m_button->setText(tr("Refresh"));
+    connect(m_button, &QPushButton::clicked, this, [this] {
+        m_label->setText(tr("%n network(s) found", "", count()));
+    });
+    auto text = qApp->translate("dcc::network::WirelessPage", "Refresh");
+    auto escaped = QObject::tr("Line 1\n" "Line \"2\"" R"(C:\path)");
+}
+
+template <class T>
+void helper()
+{
+    qDebug() << tr("No context");
+}
+"#;
+
+    #[test]
+    fn tst_extract_from_cpp_sources() {
+        let result = extract_from_cpp_sources(&[
+            SourceFile { filename: "wirelesspage.h".to_string(), content: TEST_HEADER_CONTENT.to_string() },
+            SourceFile { filename: "wirelesspage.cpp".to_string(), content: TEST_SOURCE_CONTENT.to_string() },
+        ]);
+        let found: Vec<(&str, &str, usize)> = result.messages.iter()
+            .map(|message| (message.context.as_str(), message.source.as_str(), message.line))
+            .collect();
+        assert_eq!(found, vec![
+            ("dcc::network::WirelessPage", "Wireless Network", 13),
+            ("dcc::network::WirelessPage", "Scanning...", 16),
+            ("NetworkState", "Connected", 8),
+            ("NetworkState", "Connecting", 9),
+            ("dcc::network::WirelessPage", "Hidden network", 14),
+            ("dcc::network::WirelessPage", "Refresh", 17),
+            ("dcc::network::WirelessPage", "%n network(s) found", 19),
+            ("dcc::network::WirelessPage", "Refresh", 21),
+            ("QObject", "Line 1\nLine \"2\"C:\\path", 22),
+        ]);
+        assert_eq!(result.messages[3].comment.as_deref(), Some("in progress"));
+        assert_eq!(result.messages[5].extracomment.as_deref(), Some("The text on the refresh button"));
+        assert!(result.messages[6].numerus);
+        assert!(!result.messages[5].numerus);
+        assert_eq!(result.warnings, vec!["wirelesspage.cpp:28: Can not determine the context of tr() call, ignored."]);
+    }
+}
@@ -0,0 +1,305 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+//! Extract strings marked by gettext keywords from C/C++ sources, like what `xgettext` does.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::LazyLock;
+use polib::catalog::Catalog;
+use polib::message::{Message, MessageMutView, MessageView};
+use polib::metadata::CatalogMetadata;
+use regex::Regex;
+use thiserror::Error as TeError;
+
+use super::{ExtractResult, ExtractedMessage, SourceFile};
+use super::lexer::{call_arguments, string_literal, tokenize, Syntax, Token};
+use crate::i18n_file::gettext::Po;
+
+/// File extensions that are scanned for gettext keywords.
+pub const GETTEXT_EXTENSIONS: &[&str] = &["c", "h", "cpp", "cc", "cxx", "c++", "hh", "hpp", "hxx", "h++"];
+
+/// Keywords recognized by default, same as `xgettext` for C plus the GLib macros.
+pub const DEFAULT_KEYWORDS: &[&str] = &[
+    "gettext", "dgettext:2", "dcgettext:2", "ngettext:1,2", "dngettext:2,3", "dcngettext:2,3", "gettext_noop",
+    "pgettext:1c,2", "dpgettext:2c,3", "dcpgettext:2c,3", "npgettext:1c,2,3", "dnpgettext:2c,3,4", "dcnpgettext:2c,3,4",
+    "_", "N_", "C_:1c,2", "NC_:1c,2",
+];
+
+/// A keyword specification in `xgettext` syntax, e.g. `_`, `ngettext:1,2` or `pgettext:1c,2`.
+///
+/// Argument positions are 1-based.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyword {
+    pub name: String,
+    pub singular: usize,
+    pub plural: Option<usize>,
+    pub context: Option<usize>,
+}
+
+#[derive(TeError, Debug)]
+#[error("Invalid keyword specification {0:?}")]
+pub struct KeywordParseError(String);
+
+impl FromStr for Keyword {
+    type Err = KeywordParseError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let error = || KeywordParseError(spec.to_string());
+        let (name, arguments) = spec.split_once(':').unwrap_or((spec, ""));
+        if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return Err(error());
+        }
+        let mut positions = vec![];
+        let mut context = None;
+        for argument in arguments.split(',').filter(|argument| !argument.is_empty()) {
+            let (number, is_context) = match argument.strip_suffix('c') {
+                Some(number) => (number, true),
+                None => (argument, false),
+            };
+            let number: usize = number.parse().ok().filter(|number| *number > 0).ok_or_else(error)?;
+            if is_context {
+                if context.replace(number).is_some() {
+                    return Err(error());
+                }
+            } else {
+                positions.push(number);
+            }
+        }
+        let (singular, plural) = match positions.as_slice() {
+            [] => (1, None),
+            [singular] => (*singular, None),
+            [singular, plural] => (*singular, Some(*plural)),
+            _ => return Err(error()),
+        };
+        Ok(Keyword { name: name.to_string(), singular, plural, context })
+    }
+}
+
+pub fn default_keywords() -> Vec<Keyword> {
+    DEFAULT_KEYWORDS.iter().map(|spec| spec.parse().unwrap()).collect()
+}
+
+pub struct GettextExtractOptions {
+    pub keywords: Vec<Keyword>,
+    /// Comments starting with this tag are kept as extracted comments, like `xgettext --add-comments`.
+    pub comment_tag: Option<String>,
+}
+
+impl Default for GettextExtractOptions {
+    fn default() -> Self {
+        GettextExtractOptions {
+            keywords: default_keywords(),
+            comment_tag: Some("TRANSLATORS:".to_string()),
+        }
+    }
+}
+
+/// Extract strings marked by the given keywords from C/C++ sources.
+///
+/// The msgctxt is stored as the message context, which is empty if there is no msgctxt.
+pub fn extract_from_gettext_sources(sources: &[SourceFile], options: &GettextExtractOptions) -> ExtractResult {
+    let mut result = ExtractResult::default();
+    for source in sources {
+        let tokens = tokenize(&source.content, Syntax::C, |comment| {
+            let comment = comment.trim();
+            let tag = options.comment_tag.as_deref()?;
+            comment.starts_with(tag).then(|| comment.split_whitespace().collect::<Vec<_>>().join(" "))
+        });
+        let mut pending_comments: Vec<&str> = vec![];
+        for (index, spanned) in tokens.iter().enumerate() {
+            let name = match &spanned.token {
+                Token::TranslatorComment(comment) => {
+                    pending_comments.push(comment);
+                    continue;
+                },
+                Token::Punct(';' | '}') => {
+                    pending_comments.clear();
+                    continue;
+                },
+                Token::Ident(name) if tokens.get(index + 1).is_some_and(|next| next.token == Token::Punct('(')) => name,
+                _ => continue,
+            };
+            let Some(keyword) = options.keywords.iter().find(|keyword| &keyword.name == name) else {
+                continue;
+            };
+            let arguments = call_arguments(&tokens, index + 1);
+            let text = |position: usize| arguments.get(position - 1).and_then(|argument| string_literal(argument));
+            // Arguments that are not string literals are not extracted, e.g. `_(variable)`.
+            let Some(msgid) = text(keyword.singular) else {
+                continue;
+            };
+            let (plural, context) = match (keyword.plural.map(text), keyword.context.map(text)) {
+                (Some(None), _) | (_, Some(None)) => continue,
+                (plural, context) => (plural.flatten(), context.flatten()),
+            };
+            if msgid.is_empty() {
+                result.warnings.push(format!("{}:{}: Empty msgid is reserved for the header entry, ignored.", source.filename, spanned.line));
+                continue;
+            }
+            let extracomment = (!pending_comments.is_empty()).then(|| pending_comments.join("\n"));
+            pending_comments.clear();
+            result.messages.push(ExtractedMessage {
+                context: context.unwrap_or_default(),
+                source: msgid,
+                comment: None,
+                extracomment,
+                numerus: plural.is_some(),
+                plural_source: plural,
+                filename: source.filename.clone(),
+                line: spanned.line,
+            });
+        }
+    }
+    result
+}
+
+static C_FORMAT_DIRECTIVE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"%(\d+\$)?[-+ #0']*(\d+|\*)?(\.(\d+|\*))?(hh|h|ll|l|L|q|j|z|Z|t)?[diouxXeEfFgGaAcspn]").unwrap()
+});
+
+/// Whether the text contains printf-like format directives.
+fn is_c_format(text: &str) -> bool {
+    C_FORMAT_DIRECTIVE.is_match(&text.replace("%%", ""))
+}
+
+/// Build a gettext template (POT) from extracted messages, ordered by their first occurrence.
+///
+/// Header fields and leading comments of the existing template are kept, only `POT-Creation-Date`
+/// is updated.
+pub fn build_pot(extracted: &[ExtractedMessage], existing: Option<&Po>, creation_date: &str) -> Po {
+    let (mut metadata, preheader, extra_headers) = match existing {
+        Some(po) => (po.inner.metadata.clone(), po.inner.preheader.clone(), po.extra_headers.clone()),
+        None => {
+            let metadata = CatalogMetadata {
+                mime_version: "1.0".to_string(),
+                content_type: "text/plain; charset=UTF-8".to_string(),
+                content_transfer_encoding: "8bit".to_string(),
+                ..CatalogMetadata::new()
+            };
+            (metadata, vec![], vec![])
+        },
+    };
+    metadata.pot_creation_date = creation_date.to_string();
+
+    let mut messages: Vec<Message> = vec![];
+    let mut indexes: HashMap<(&str, &str), usize> = HashMap::new();
+    for item in extracted {
+        let location = format!("{}:{}", item.filename, item.line);
+        if let Some(&index) = indexes.get(&(item.context.as_str(), item.source.as_str())) {
+            let message = &mut messages[index];
+            message.source_mut().push('\n');
+            message.source_mut().push_str(&location);
+            if let Some(extracomment) = &item.extracomment {
+                if !message.extracted_comments().split('\n').any(|line| line == extracomment) {
+                    let comments = message.extracted_comments_mut();
+                    if !comments.is_empty() {
+                        comments.push('\n');
+                    }
+                    comments.push_str(extracomment);
+                }
+            }
+            continue;
+        }
+
+        let mut builder = match &item.plural_source {
+            Some(plural_source) => {
+                let mut builder = Message::build_plural();
+                builder.with_msgid_plural(plural_source.clone()).with_msgstr_plural(vec![String::new(); 2]);
+                builder
+            },
+            None => {
+                let mut builder = Message::build_singular();
+                builder.with_msgstr(String::new());
+                builder
+            },
+        };
+        let mut message = builder
+            .with_msgctxt(item.context.clone())
+            .with_msgid(item.source.clone())
+            .with_source(location)
+            .with_extracted_comments(item.extracomment.clone().unwrap_or_default())
+            .done();
+        if is_c_format(&item.source) || item.plural_source.as_deref().is_some_and(is_c_format) {
+            message.flags_mut().add_flag("c-format");
+        }
+        indexes.insert((item.context.as_str(), item.source.as_str()), messages.len());
+        messages.push(message);
+    }
+
+    let mut catalog = Catalog::new(metadata);
+    catalog.preheader = preheader;
+    for message in messages {
+        catalog.append_or_update(message);
+    }
+    Po { inner: catalog, obsolete: vec![], extra_headers }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_C_SOURCE_CONTENT: &str = r#"
+#include <glib/gi18n.h>
+
+static const char *names[] = { N_("Wired"), N_("Wireless") };
+
+void show(int count)
+{
+    /* TRANSLATORS: %d is the number of devices */
+    printf(ngettext("%d device", "%d devices", count), count);
+    // Not a translator comment
+    puts(C_("menu", "Open"));
+    puts(_(names[0]));
+    puts(_("100%% done"));
+    puts(my_tr("Custom"));
+    puts(_("Wired"));
+}
+"#;
+
+    #[test]
+    fn tst_parse_keyword() {
+        assert_eq!("_".parse::<Keyword>().unwrap(), Keyword { name: "_".to_string(), singular: 1, plural: None, context: None });
+        assert_eq!("dcnpgettext:2c,3,4".parse::<Keyword>().unwrap(), Keyword {
+            name: "dcnpgettext".to_string(), singular: 3, plural: Some(4), context: Some(2),
+        });
+        assert!("ngettext:1,2,3".parse::<Keyword>().is_err());
+        assert!("bad name".parse::<Keyword>().is_err());
+        assert!("tr:0".parse::<Keyword>().is_err());
+        assert_eq!(default_keywords().len(), DEFAULT_KEYWORDS.len());
+    }
+
+    #[test]
+    fn tst_extract_and_build_pot() {
+        let mut options = GettextExtractOptions::default();
+        options.keywords.push("my_tr".parse().unwrap());
+        let sources = [SourceFile { filename: "../src/show.c".to_string(), content: TEST_C_SOURCE_CONTENT.to_string() }];
+        let result = extract_from_gettext_sources(&sources, &options);
+        let found: Vec<(&str, &str, usize)> = result.messages.iter()
+            .map(|message| (message.context.as_str(), message.source.as_str(), message.line))
+            .collect();
+        assert_eq!(found, vec![
+            ("", "Wired", 4),
+            ("", "Wireless", 4),
+            ("", "%d device", 9),
+            ("menu", "Open", 11),
+            ("", "100%% done", 13),
+            ("", "Custom", 14),
+            ("", "Wired", 15),
+        ]);
+        assert_eq!(result.messages[2].plural_source.as_deref(), Some("%d devices"));
+        assert_eq!(result.messages[2].extracomment.as_deref(), Some("TRANSLATORS: %d is the number of devices"));
+        assert_eq!(result.messages[3].extracomment, None);
+
+        let pot = build_pot(&result.messages, None, "2025-01-01 00:00+0000");
+        assert_eq!(pot.inner.metadata.pot_creation_date, "2025-01-01 00:00+0000");
+        let messages: Vec<_> = pot.inner.messages().collect();
+        assert_eq!(messages.len(), 6);
+        assert_eq!(messages[0].source(), "../src/show.c:4\n../src/show.c:15");
+        assert!(messages[2].is_plural());
+        assert!(messages[2].flags().contains("c-format"));
+        assert_eq!(messages[3].msgctxt(), Some("menu"));
+        assert!(!messages[4].flags().contains("c-format"));
+    }
+}
@@ -0,0 +1,348 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+//! A tokenizer for C-like languages, shared by source code extractors.
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Token {
+    Ident(String),
+    Str(String),
+    /// Number or character literal
+    Literal,
+    Punct(char),
+    DoubleColon,
+    Arrow,
+    /// Comment for translators, see [`tokenize`]
+    TranslatorComment(String),
+}
+
+/// Syntax variants of C-like languages that affect tokenizing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Syntax {
+    /// C and C++, with preprocessor directives, character literals and raw string literals.
+    C,
+    /// JavaScript and QML, with single-quoted strings and template literals.
+    JavaScript,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct SpannedToken {
+    pub(super) token: Token,
+    pub(super) line: usize,
+}
+
+/// Split C-like source code into tokens. Whitespaces and preprocessor directives are skipped.
+///
+/// Comments are passed to `translator_comment` without the comment markers, and the returned text
+/// is kept as a [`Token::TranslatorComment`]. Other comments are dropped.
+pub(super) fn tokenize(content: &str, syntax: Syntax, translator_comment: impl Fn(&str) -> Option<String>) -> Vec<SpannedToken> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut tokens = vec![];
+    let mut pos = 0;
+    let mut line = 1;
+    let mut line_start = true;
+    while pos < chars.len() {
+        let c = chars[pos];
+        let next = chars.get(pos + 1).copied();
+        if c == '\n' {
+            line += 1;
+            line_start = true;
+            pos += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            pos += 1;
+            continue;
+        }
+        if c == '#' && line_start && syntax == Syntax::C {
+            // Preprocessor directive, skip it including continued lines.
+            while pos < chars.len() && chars[pos] != '\n' {
+                if chars[pos] == '\\' && chars.get(pos + 1) == Some(&'\n') {
+                    line += 1;
+                    pos += 1;
+                }
+                pos += 1;
+            }
+            continue;
+        }
+        line_start = false;
+        let start_line = line;
+        let mut push = |token: Token| tokens.push(SpannedToken { token, line: start_line });
+
+        if c == '/' && next == Some('/') {
+            let end = chars[pos..].iter().position(|&c| c == '\n').map_or(chars.len(), |offset| pos + offset);
+            let text: String = chars[pos + 2..end].iter().collect();
+            if let Some(comment) = translator_comment(&text) {
+                push(Token::TranslatorComment(comment));
+            }
+            pos = end;
+        } else if c == '/' && next == Some('*') {
+            let mut end = pos + 2;
+            while end < chars.len() && !(chars[end] == '*' && chars.get(end + 1) == Some(&'/')) {
+                if chars[end] == '\n' {
+                    line += 1;
+                }
+                end += 1;
+            }
+            let text: String = chars[pos + 2..end].iter().collect();
+            if let Some(comment) = translator_comment(&text) {
+                push(Token::TranslatorComment(comment));
+            }
+            pos = (end + 2).min(chars.len());
+        } else if c == '"' || (c == '\'' && syntax == Syntax::JavaScript) {
+            let (text, end) = read_string_literal(&chars, pos + 1, c, &mut line);
+            push(Token::Str(text));
+            pos = end;
+        } else if c == '`' && syntax == Syntax::JavaScript {
+            let (text, end) = read_template_literal(&chars, pos + 1, &mut line);
+            // Template literals with substitutions are not constant strings.
+            push(text.map_or(Token::Literal, Token::Str));
+            pos = end;
+        } else if c == '\'' {
+            pos += 1;
+            while pos < chars.len() && chars[pos] != '\'' && chars[pos] != '\n' {
+                pos += if chars[pos] == '\\' { 2 } else { 1 };
+            }
+            push(Token::Literal);
+            pos += 1;
+        } else if c.is_ascii_digit() || (c == '.' && next.is_some_and(|c| c.is_ascii_digit())) {
+            pos += 1;
+            while pos < chars.len() {
+                let c = chars[pos];
+                let is_exponent_sign = (c == '+' || c == '-') && matches!(chars[pos - 1], 'e' | 'E' | 'p' | 'P');
+                let is_digit_separator = c == '\'' && chars.get(pos + 1).is_some_and(|c| c.is_ascii_alphanumeric());
+                if !(c.is_ascii_alphanumeric() || c == '.' || c == '_' || is_exponent_sign || is_digit_separator) {
+                    break;
+                }
+                pos += 1;
+            }
+            push(Token::Literal);
+        } else if c.is_alphabetic() || c == '_' {
+            let start = pos;
+            while pos < chars.len() && (chars[pos].is_alphanumeric() || chars[pos] == '_') {
+                pos += 1;
+            }
+            let ident: String = chars[start..pos].iter().collect();
+            let string_prefix = chars.get(pos) == Some(&'"') && syntax == Syntax::C;
+            if string_prefix && matches!(ident.as_str(), "R" | "u8R" | "uR" | "UR" | "LR") {
+                let (text, end) = read_raw_string_literal(&chars, pos + 1, &mut line);
+                push(Token::Str(text));
+                pos = end;
+            } else if string_prefix && matches!(ident.as_str(), "u8" | "u" | "U" | "L") {
+                let (text, end) = read_string_literal(&chars, pos + 1, '"', &mut line);
+                push(Token::Str(text));
+                pos = end;
+            } else {
+                push(Token::Ident(ident));
+            }
+        } else if c == ':' && next == Some(':') {
+            push(Token::DoubleColon);
+            pos += 2;
+        } else if c == '-' && next == Some('>') {
+            push(Token::Arrow);
+            pos += 2;
+        } else {
+            push(Token::Punct(c));
+            pos += 1;
+        }
+    }
+    tokens
+}
+
+/// Read a string literal starting right after the opening `quote`, returns the decoded text and
+/// the position after the closing quote.
+fn read_string_literal(chars: &[char], mut pos: usize, quote: char, line: &mut usize) -> (String, usize) {
+    let mut bytes: Vec<u8> = vec![];
+    let push_char = |bytes: &mut Vec<u8>, c: char| {
+        let mut buffer = [0u8; 4];
+        bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+    };
+    let read_digits = |pos: &mut usize, radix: u32, max_len: usize| -> u32 {
+        let mut value: u32 = 0;
+        let mut len = 0;
+        while len < max_len && *pos < chars.len() && chars[*pos].is_digit(radix) {
+            value = value.wrapping_mul(radix).wrapping_add(chars[*pos].to_digit(radix).unwrap());
+            *pos += 1;
+            len += 1;
+        }
+        value
+    };
+    while pos < chars.len() {
+        let c = chars[pos];
+        pos += 1;
+        match c {
+            c if c == quote => break,
+            // Unterminated string literal
+            '\n' => {
+                *line += 1;
+                break;
+            },
+            '\\' if pos < chars.len() => {
+                let escaped = chars[pos];
+                pos += 1;
+                match escaped {
+                    'n' => bytes.push(b'\n'),
+                    't' => bytes.push(b'\t'),
+                    'r' => bytes.push(b'\r'),
+                    'a' => bytes.push(0x07),
+                    'b' => bytes.push(0x08),
+                    'f' => bytes.push(0x0c),
+                    'v' => bytes.push(0x0b),
+                    '\n' => *line += 1,
+                    '0'..='7' => {
+                        pos -= 1;
+                        bytes.push(read_digits(&mut pos, 8, 3) as u8);
+                    },
+                    'x' => bytes.push(read_digits(&mut pos, 16, usize::MAX) as u8),
+                    // `\u{1F600}` in JavaScript
+                    'u' if chars.get(pos) == Some(&'{') => {
+                        pos += 1;
+                        let value = read_digits(&mut pos, 16, 6);
+                        if chars.get(pos) == Some(&'}') {
+                            pos += 1;
+                        }
+                        push_char(&mut bytes, char::from_u32(value).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    },
+                    'u' | 'U' => {
+                        let len = if escaped == 'u' { 4 } else { 8 };
+                        let value = read_digits(&mut pos, 16, len);
+                        push_char(&mut bytes, char::from_u32(value).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    },
+                    other => push_char(&mut bytes, other),
+                }
+            },
+            other => push_char(&mut bytes, other),
+        }
+    }
+    (String::from_utf8_lossy(&bytes).to_string(), pos)
+}
+
+/// Read a JavaScript template literal starting right after the opening backtick.
+///
+/// Returns `None` as the text if the literal contains substitutions like `${name}`.
+fn read_template_literal(chars: &[char], mut pos: usize, line: &mut usize) -> (Option<String>, usize) {
+    let mut text = String::new();
+    let mut constant = true;
+    while pos < chars.len() {
+        let c = chars[pos];
+        pos += 1;
+        match c {
+            '`' => break,
+            '\\' if pos < chars.len() => {
+                let escaped = chars[pos];
+                pos += 1;
+                match escaped {
+                    'n' => text.push('\n'),
+                    't' => text.push('\t'),
+                    'r' => text.push('\r'),
+                    '\n' => *line += 1,
+                    other => text.push(other),
+                }
+            },
+            '$' if chars.get(pos) == Some(&'{') => {
+                constant = false;
+                text.push(c);
+            },
+            '\n' => {
+                *line += 1;
+                text.push(c);
+            },
+            other => text.push(other),
+        }
+    }
+    (constant.then_some(text), pos)
+}
+
+/// Read a raw string literal like `R"delim(...)delim"` starting right after the opening quote.
+fn read_raw_string_literal(chars: &[char], pos: usize, line: &mut usize) -> (String, usize) {
+    let Some(open_offset) = chars[pos..].iter().position(|&c| c == '(') else {
+        return (String::new(), chars.len());
+    };
+    let delimiter: String = chars[pos..pos + open_offset].iter().collect();
+    let terminator: Vec<char> = format!("){delimiter}\"").chars().collect();
+    let start = pos + open_offset + 1;
+    let end = chars[start..].windows(terminator.len())
+        .position(|window| window == terminator.as_slice())
+        .map_or(chars.len(), |offset| start + offset);
+    let text: String = chars[start..end].iter().collect();
+    *line += text.matches('\n').count();
+    (text, (end + terminator.len()).min(chars.len()))
+}
+
+/// Split arguments of the call whose `(` is at the given index.
+pub(super) fn call_arguments(tokens: &[SpannedToken], paren_index: usize) -> Vec<&[SpannedToken]> {
+    let mut arguments = vec![];
+    let mut depth = 0;
+    let mut start = paren_index + 1;
+    for index in paren_index..tokens.len() {
+        match tokens[index].token {
+            Token::Punct('(' | '[' | '{') => depth += 1,
+            Token::Punct(')' | ']' | '}') => {
+                depth -= 1;
+                if depth == 0 {
+                    if index > start {
+                        arguments.push(&tokens[start..index]);
+                    }
+                    break;
+                }
+            },
+            Token::Punct(',') if depth == 1 => {
+                arguments.push(&tokens[start..index]);
+                start = index + 1;
+            },
+            _ => {},
+        }
+    }
+    arguments
+}
+
+/// Get the text of an argument made of (possibly concatenated) string literals only.
+///
+/// Both adjacent literals like C and literals joined by `+` like JavaScript are accepted.
+pub(super) fn string_literal(argument: &[SpannedToken]) -> Option<String> {
+    if argument.is_empty() {
+        return None;
+    }
+    let mut text = String::new();
+    for (index, token) in argument.iter().enumerate() {
+        match &token.token {
+            Token::Str(part) => text.push_str(part),
+            Token::Punct('+') if index > 0 && index + 1 < argument.len() => continue,
+            _ => return None,
+        }
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_tokenize_string_literals() {
+        let tokens = tokenize(r#"u8"\xe4\xbd\xa0\u597d" L"a\101" /* note
+        here */ 1'000 'x' // ignored"#, Syntax::C, |text| text.contains("note").then(|| text.split_whitespace().collect::<Vec<_>>().join(" ")));
+        let tokens: Vec<Token> = tokens.into_iter().map(|token| token.token).collect();
+        assert_eq!(tokens, vec![
+            Token::Str("你好".to_string()),
+            Token::Str("aA".to_string()),
+            Token::TranslatorComment("note here".to_string()),
+            Token::Literal,
+            Token::Literal,
+        ]);
+
+        let tokens = tokenize("# 1\n'It\\'s' + \"\\u{1F600}\" `multi\nline` `${name}`", Syntax::JavaScript, |_| None);
+        assert_eq!(tokens[2].line, 2);
+        let tokens: Vec<Token> = tokens.into_iter().map(|token| token.token).collect();
+        assert_eq!(tokens, vec![
+            Token::Punct('#'),
+            Token::Literal,
+            Token::Str("It's".to_string()),
+            Token::Punct('+'),
+            Token::Str("😀".to_string()),
+            Token::Str("multi\nline".to_string()),
+            Token::Literal,
+        ]);
+    }
+}
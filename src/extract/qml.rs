@@ -0,0 +1,122 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+//! Extract translatable strings from QML and JavaScript sources.
+
+use std::path::Path;
+
+use super::{ExtractResult, ExtractedMessage, SourceFile};
+use super::cpp::translator_comment;
+use super::lexer::{call_arguments, string_literal, tokenize, Syntax, Token};
+
+/// File extensions that are treated as QML or JavaScript sources.
+pub const QML_EXTENSIONS: &[&str] = &["qml", "js", "mjs"];
+
+/// Extract strings marked by `qsTr()`, `qsTranslate()`, `QT_TR_NOOP()` and `QT_TRANSLATE_NOOP()`
+/// from the given QML or JavaScript files.
+///
+/// Like `lupdate`, the context of `qsTr()` and `QT_TR_NOOP()` is the file name without extension.
+pub fn extract_from_qml_sources(sources: &[SourceFile]) -> ExtractResult {
+    let mut result = ExtractResult::default();
+    for source in sources {
+        let file_context = Path::new(&source.filename).file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let tokens = tokenize(&source.content, Syntax::JavaScript, translator_comment);
+        let mut pending_comments: Vec<&str> = vec![];
+        for (index, spanned) in tokens.iter().enumerate() {
+            let function = match &spanned.token {
+                Token::TranslatorComment(comment) => {
+                    pending_comments.push(comment);
+                    continue;
+                },
+                Token::Punct(';' | '}') => {
+                    pending_comments.clear();
+                    continue;
+                },
+                Token::Ident(ident) if tokens.get(index + 1).is_some_and(|next| next.token == Token::Punct('(')) => ident.as_str(),
+                _ => continue,
+            };
+            // Member calls like `foo.qsTr()` are not the global functions.
+            if index > 0 && tokens[index - 1].token == Token::Punct('.') {
+                continue;
+            }
+            let arguments = call_arguments(&tokens, index + 1);
+            let text = |index: usize| arguments.get(index).and_then(|argument| string_literal(argument));
+
+            let parsed = match function {
+                "qsTr" | "QT_TR_NOOP" => text(0)
+                    .map(|source_text| (file_context.clone(), source_text, text(1), arguments.len() >= 3)),
+                "qsTranslate" | "QT_TRANSLATE_NOOP" => text(0).zip(text(1))
+                    .map(|(context, source_text)| (context, source_text, text(2), arguments.len() >= 4)),
+                _ => continue,
+            };
+            let Some((context, source_text, comment, numerus)) = parsed else {
+                result.warnings.push(format!("{}:{}: Non-constant argument of {function}(), ignored.", source.filename, spanned.line));
+                continue;
+            };
+
+            let extracomment = (!pending_comments.is_empty()).then(|| pending_comments.join(" "));
+            pending_comments.clear();
+            result.messages.push(ExtractedMessage {
+                context,
+                source: source_text,
+                comment: comment.filter(|comment| !comment.is_empty()),
+                extracomment,
+                numerus,
+                plural_source: None,
+                filename: source.filename.clone(),
+                line: spanned.line,
+            });
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_QML_CONTENT: &str = r#"
+import QtQuick 2.15
+import org.deepin.dtk 1.0 as D
+
+D.DialogWindow {
+    //: Title of the dialog
+    title: qsTr("Bluetooth Settings")
+    property var states: [QT_TR_NOOP('Connected'), QT_TRANSLATE_NOOP("BluetoothState", "Paired")]
+
+    D.Label {
+        text: qsTr("%n device(s)", "", deviceCount) + qsTr("Unpaired " + 'devices')
+    }
+    D.Button {
+        text: qsTranslate("Common", `Cancel`, "button")
+        onClicked: console.log(qsTr(`Hello ${name}`))
+    }
+}
+"#;
+
+    #[test]
+    fn tst_extract_from_qml_sources() {
+        let result = extract_from_qml_sources(&[
+            SourceFile { filename: "../qml/BluetoothDialog.qml".to_string(), content: TEST_QML_CONTENT.to_string() },
+        ]);
+        let found: Vec<(&str, &str, usize)> = result.messages.iter()
+            .map(|message| (message.context.as_str(), message.source.as_str(), message.line))
+            .collect();
+        assert_eq!(found, vec![
+            ("BluetoothDialog", "Bluetooth Settings", 7),
+            ("BluetoothDialog", "Connected", 8),
+            ("BluetoothState", "Paired", 8),
+            ("BluetoothDialog", "%n device(s)", 11),
+            ("BluetoothDialog", "Unpaired devices", 11),
+            ("Common", "Cancel", 14),
+        ]);
+        assert_eq!(result.messages[0].extracomment.as_deref(), Some("Title of the dialog"));
+        assert_eq!(result.messages[1].extracomment, None);
+        assert!(result.messages[3].numerus);
+        assert_eq!(result.messages[5].comment.as_deref(), Some("button"));
+        assert_eq!(result.warnings, vec!["../qml/BluetoothDialog.qml:15: Non-constant argument of qsTr(), ignored.".to_string()]);
+    }
+}
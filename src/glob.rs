@@ -0,0 +1,93 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+//! Globs used to pick files and contexts, shared by the library and the commandline.
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+/// Globs matching paths relative to the project root, e.g. `dcc-network/**` or `**/translations/*_[a-z][a-z].ts`,
+/// or other names like Linguist contexts, e.g. `dcc::*`.
+///
+/// `**` matches any number of directories, while `*`, `?` and character classes like `[a-z]` match within a path component.
+/// Globs starting with `!` exclude what the other globs match, if there are only excluding globs, everything else matches.
+#[derive(Debug, Clone, Default)]
+pub struct PathGlobs {
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+}
+
+impl PathGlobs {
+    pub fn new<S: AsRef<str>>(globs: &[S]) -> Result<Self, globset::Error> {
+        let (mut include, mut exclude) = (GlobSetBuilder::new(), GlobSetBuilder::new());
+        let (mut has_include, mut has_exclude) = (false, false);
+        for glob in globs {
+            let glob = glob.as_ref();
+            let (builder, glob) = match glob.strip_prefix('!') {
+                Some(glob) => {
+                    has_exclude = true;
+                    (&mut exclude, glob)
+                },
+                None => {
+                    has_include = true;
+                    (&mut include, glob)
+                },
+            };
+            let glob = glob.strip_prefix("./").unwrap_or(glob);
+            builder.add(GlobBuilder::new(glob).literal_separator(true).build()?);
+        }
+        Ok(Self {
+            include: has_include.then(|| include.build()).transpose()?,
+            exclude: has_exclude.then(|| exclude.build()).transpose()?,
+        })
+    }
+
+    /// Whether no glob is given, in which case every path matches.
+    pub fn is_empty(&self) -> bool {
+        self.include.is_none() && self.exclude.is_none()
+    }
+
+    /// Whether any glob includes the path, not counting globs that only exclude.
+    pub fn has_include(&self) -> bool {
+        self.include.is_some()
+    }
+
+    pub fn is_match(&self, path: &str) -> bool {
+        let path = path.strip_prefix("./").unwrap_or(path);
+        self.include.as_ref().is_none_or(|include| include.is_match(path))
+            && !self.exclude.as_ref().is_some_and(|exclude| exclude.is_match(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_path_globs() {
+        let globs = |globs: &[&str]| PathGlobs::new(globs).unwrap();
+        assert!(globs(&["dcc-network/**"]).is_match("dcc-network/translations/dcc-network.ts"));
+        assert!(!globs(&["dcc-network/**"]).is_match("dcc-bluetooth/translations/dcc-bluetooth.ts"));
+        assert!(globs(&["**/translations/*.ts"]).is_match("translations/demo.ts"));
+        assert!(globs(&["./**/translations/*.ts"]).is_match("plugins/demo/translations/demo.ts"));
+        assert!(!globs(&["translations/*.ts"]).is_match("translations/sub/demo.ts"));
+        assert!(globs(&["translations/demo_[a-z][a-z].ts"]).is_match("translations/demo_de.ts"));
+        assert!(!globs(&["translations/demo_[a-z][a-z].ts"]).is_match("translations/demo_zh_CN.ts"));
+
+        assert!(globs(&["dcc::*"]).is_match("dcc::MainWindow"));
+        assert!(!globs(&["dcc::*"]).is_match("ts::dcc::MainWindow"));
+        assert!(globs(&["Legal?"]).is_match("Legal2"));
+
+        let excluding = globs(&["dcc-*/**", "!dcc-bluetooth/**"]);
+        assert!(excluding.is_match("dcc-network/translations/dcc-network.ts"));
+        assert!(!excluding.is_match("dcc-bluetooth/translations/dcc-bluetooth.ts"));
+        assert!(!excluding.is_match("dde-dock/translations/dde-dock.ts"));
+        let only_excluding = globs(&["!dcc-bluetooth/**"]);
+        assert!(only_excluding.is_match("dde-dock/translations/dde-dock.ts"));
+        assert!(!only_excluding.has_include());
+
+        assert!(PathGlobs::default().is_empty());
+        assert!(PathGlobs::default().is_match("anything.ts"));
+        assert!(PathGlobs::new(&["translations/[a-z.ts"]).is_err());
+    }
+}
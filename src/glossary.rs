@@ -0,0 +1,375 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error as TeError;
+
+/// Language of source terms in TBX files, messages of deepin projects are written in English.
+const TBX_SOURCE_LANGUAGE: &str = "en";
+
+/// How serious a finding is, e.g. of a translation that doesn't use the target term of a glossary term.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    #[default]
+    Error,
+}
+
+impl std::str::FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Ok(Self::Error),
+            "warning" => Ok(Self::Warning),
+            "info" => Ok(Self::Info),
+            _ => Err(format!("unknown severity {s:?}, expected error, warning or info")),
+        }
+    }
+}
+
+#[derive(TeError, Debug)]
+pub enum GlossaryLoadError {
+    #[error("Can not read file")]
+    ReadFile(#[from] std::io::Error),
+    #[error("Fail to parse TBX file because: {0}")]
+    Xml(#[from] quick_xml::Error),
+    #[error("Line {0}: {1}")]
+    Invalid(usize, String),
+}
+
+/// A source term and the target terms translations of it must use.
+#[derive(Debug)]
+pub struct GlossaryTerm {
+    pub source: String,
+    /// Language of the targets, empty if the glossary applies to any language.
+    pub language: String,
+    /// Accepted target terms, a translation must use one of them.
+    pub targets: Vec<String>,
+    pub severity: Severity,
+    /// Matches the source term as whole words, ignoring case.
+    source_regex: Regex,
+}
+
+impl GlossaryTerm {
+    fn new(source: &str, language: &str, severity: Severity) -> Self {
+        let source = source.trim().to_string();
+        // Word boundaries only make sense next to word characters, terms like `C++` or `.deb` would never match otherwise.
+        let boundary = |c: Option<char>| match c {
+            Some(c) if c.is_alphanumeric() || c == '_' => r"\b",
+            _ => "",
+        };
+        let source_regex = Regex::new(&format!(r"(?i){}{}{}", boundary(source.chars().next()), regex::escape(&source), boundary(source.chars().last()))).unwrap();
+        Self { source, language: language.trim().replace('-', "_"), targets: vec![], severity, source_regex }
+    }
+
+    /// Whether the text uses the source term, `&` accelerators are ignored.
+    pub fn is_used_by(&self, text: &str) -> bool {
+        self.source_regex.is_match(&text.replace('&', ""))
+    }
+
+    /// Whether the translation uses one of the target terms, ignoring case and `&` accelerators.
+    pub fn is_followed_by(&self, translation: &str) -> bool {
+        let translation = translation.replace('&', "").to_lowercase();
+        self.targets.iter().any(|target| translation.contains(&target.to_lowercase()))
+    }
+}
+
+/// Terminology to enforce in translations, read from a TBX file or a CSV file.
+#[derive(Debug, Default)]
+pub struct Glossary {
+    pub terms: Vec<GlossaryTerm>,
+}
+
+impl Glossary {
+    /// Load a glossary, as CSV if the file name ends with `.csv`, as TBX otherwise.
+    pub fn load(path: &Path) -> Result<Self, GlossaryLoadError> {
+        let content = fs::read_to_string(path)?;
+        match path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("csv")) {
+            true => Self::from_csv(&content),
+            false => Self::from_tbx(&content),
+        }
+    }
+
+    /// Add a target of a source term, targets of the same source and language are merged into one term.
+    fn add(&mut self, source: &str, language: &str, target: &str, severity: Severity) {
+        let term = GlossaryTerm::new(source, language, severity);
+        if term.source.is_empty() || target.trim().is_empty() {
+            return;
+        }
+        let index = match self.terms.iter().position(|known| known.source.eq_ignore_ascii_case(&term.source) && known.language == term.language) {
+            Some(index) => index,
+            None => {
+                self.terms.push(term);
+                self.terms.len() - 1
+            },
+        };
+        let targets = &mut self.terms[index].targets;
+        if !targets.iter().any(|known| known == target.trim()) {
+            targets.push(target.trim().to_string());
+        }
+    }
+
+    /// Read a CSV glossary with a header row. `source` and `target` columns are required, `language` (the language of the
+    /// target, any language if empty) and `severity` (`error` or `warning`, defaults to `error`) are optional, other columns
+    /// like `note` are ignored. A source term with several accepted targets takes a row for each target.
+    pub fn from_csv(content: &str) -> Result<Self, GlossaryLoadError> {
+        let mut rows = parse_csv(content.trim_start_matches('\u{feff}'))?.into_iter();
+        let Some((_, header)) = rows.next() else {
+            return Ok(Self::default());
+        };
+        let column = |name: &str| header.iter().position(|column| column.trim().eq_ignore_ascii_case(name));
+        let (Some(source_column), Some(target_column)) = (column("source"), column("target")) else {
+            return Err(GlossaryLoadError::Invalid(1, "the header row should have a `source` and a `target` column".to_string()));
+        };
+        let (language_column, severity_column) = (column("language"), column("severity"));
+
+        let mut glossary = Self::default();
+        for (line, row) in rows {
+            let cell = |column: Option<usize>| column.and_then(|column| row.get(column)).map(String::as_str).unwrap_or_default();
+            let severity = match cell(severity_column).trim() {
+                "" => Severity::Error,
+                severity => severity.parse().map_err(|e| GlossaryLoadError::Invalid(line, e))?,
+            };
+            glossary.add(cell(Some(source_column)), cell(language_column), cell(Some(target_column)), severity);
+        }
+        Ok(glossary)
+    }
+
+    /// Read a TBX glossary, each `<termEntry>` (TBX 2) or `<conceptEntry>` (TBX 3) maps its English terms to the terms of its
+    /// other `<langSet>`s. The severity of an entry can be given as `<descrip type="severity">warning</descrip>`.
+    pub fn from_tbx(content: &str) -> Result<Self, GlossaryLoadError> {
+        let mut glossary = Self::default();
+        let mut reader = Reader::from_str(content);
+        let mut entry: Option<TbxEntry> = None;
+        let mut language = String::new();
+        let mut text: Option<String> = None;
+        loop {
+            let line = content.as_bytes()[..(reader.buffer_position() as usize).min(content.len())].iter().filter(|byte| **byte == b'\n').count() + 1;
+            match reader.read_event()? {
+                Event::Start(ref element) => match element.local_name().as_ref() {
+                    b"termEntry" | b"conceptEntry" => entry = Some(TbxEntry::default()),
+                    b"langSet" => language = language_attribute(element).unwrap_or_default(),
+                    b"term" => text = Some(String::new()),
+                    b"descrip" if type_attribute(element).as_deref() == Some("severity") => text = Some(String::new()),
+                    _ => (),
+                },
+                Event::Text(ref value) => {
+                    if let Some(text) = &mut text {
+                        text.push_str(&value.unescape()?);
+                    }
+                },
+                Event::CData(ref value) => {
+                    if let Some(text) = &mut text {
+                        text.push_str(&String::from_utf8_lossy(value));
+                    }
+                },
+                Event::End(ref element) => match element.local_name().as_ref() {
+                    b"term" => {
+                        if let (Some(entry), Some(term)) = (&mut entry, text.take()) {
+                            entry.terms.push((language.clone(), term));
+                        }
+                    },
+                    b"descrip" => {
+                        if let (Some(entry), Some(severity)) = (&mut entry, text.take()) {
+                            entry.severity = Some(severity);
+                        }
+                    },
+                    b"termEntry" | b"conceptEntry" => {
+                        let Some(TbxEntry { terms, severity }) = entry.take() else {
+                            continue;
+                        };
+                        let severity = match severity.as_deref().map(str::trim) {
+                            None | Some("") => Severity::Error,
+                            Some(severity) => severity.parse().map_err(|e| GlossaryLoadError::Invalid(line, e))?,
+                        };
+                        let is_source = |language: &str| language.split(['_', '-']).next() == Some(TBX_SOURCE_LANGUAGE);
+                        for (_, source) in terms.iter().filter(|(language, _)| is_source(language)) {
+                            for (language, target) in terms.iter().filter(|(language, _)| !is_source(language)) {
+                                glossary.add(source, language, target, severity);
+                            }
+                        }
+                    },
+                    _ => (),
+                },
+                Event::Eof => break,
+                _ => (),
+            }
+        }
+        Ok(glossary)
+    }
+
+    /// Terms that apply to translations of the given language. A term of the exact language takes precedence over one of
+    /// its base language (`zh` for `zh_CN`), which takes precedence over one of any language.
+    pub fn terms_for_language(&self, language: &str) -> Vec<&GlossaryTerm> {
+        let language = language.replace('-', "_");
+        let base_language = language.split(['_', '@']).next().unwrap_or_default();
+        let specificity = |term: &GlossaryTerm| match term.language.as_str() {
+            term_language if term_language == language => Some(2),
+            term_language if term_language == base_language => Some(1),
+            "" => Some(0),
+            _ => None,
+        };
+        let mut terms = BTreeMap::<String, (u8, &GlossaryTerm)>::new();
+        for term in &self.terms {
+            let Some(specificity) = specificity(term) else {
+                continue;
+            };
+            let best = terms.entry(term.source.to_lowercase()).or_insert((specificity, term));
+            if specificity > best.0 {
+                *best = (specificity, term);
+            }
+        }
+        terms.into_values().map(|(_, term)| term).collect()
+    }
+}
+
+/// A `<termEntry>` of a TBX file being read.
+#[derive(Default)]
+struct TbxEntry {
+    /// Terms as (language, term).
+    terms: Vec<(String, String)>,
+    severity: Option<String>,
+}
+
+/// `xml:lang` of TBX 2 and 3, or `lang` of older files.
+fn language_attribute(element: &BytesStart) -> Option<String> {
+    element.attributes().flatten()
+        .find(|attribute| matches!(attribute.key.as_ref(), b"xml:lang" | b"lang"))
+        .and_then(|attribute| attribute.unescape_value().ok().map(|value| value.trim().to_string()))
+}
+
+fn type_attribute(element: &BytesStart) -> Option<String> {
+    element.try_get_attribute("type").ok()?.and_then(|attribute| attribute.unescape_value().ok().map(|value| value.into_owned()))
+}
+
+/// Split CSV content (RFC 4180) into rows of cells, with the line each row starts at. Empty rows are skipped.
+fn parse_csv(content: &str) -> Result<Vec<(usize, Vec<String>)>, GlossaryLoadError> {
+    let mut rows = vec![];
+    let (mut row, mut cell) = (vec![], String::new());
+    let (mut line, mut row_line) = (1, 1);
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            },
+            ('"', true) => in_quotes = false,
+            ('"', false) if cell.is_empty() => in_quotes = true,
+            (',', false) => row.push(std::mem::take(&mut cell)),
+            ('\r', false) if chars.peek() == Some(&'\n') => (),
+            ('\n', false) => {
+                row.push(std::mem::take(&mut cell));
+                if row.iter().any(|cell| !cell.is_empty()) {
+                    rows.push((row_line, std::mem::take(&mut row)));
+                }
+                row.clear();
+                line += 1;
+                row_line = line;
+            },
+            _ => {
+                if c == '\n' {
+                    line += 1;
+                }
+                cell.push(c);
+            },
+        }
+    }
+    if in_quotes {
+        return Err(GlossaryLoadError::Invalid(row_line, "unterminated quoted cell".to_string()));
+    }
+    row.push(cell);
+    if row.iter().any(|cell| !cell.is_empty()) {
+        rows.push((row_line, row));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(glossary: &Glossary, language: &str) -> Vec<(String, Vec<String>, Severity)> {
+        glossary.terms_for_language(language).into_iter()
+            .map(|term| (term.source.clone(), term.targets.clone(), term.severity))
+            .collect()
+    }
+
+    #[test]
+    fn tst_glossary_from_csv() {
+        let content = "\u{feff}source,target,language,severity,note\n\
+            Control Center,控制中心,zh_CN,,\n\
+            Control Center,控制中心,zh_TW,,\"Not \"\"控制面板\"\"\"\n\
+            Launcher,启动器,zh,warning,\n\
+            Launcher,啟動器,zh_TW,warning,\n\
+            \n\
+            \"Wi-Fi\",\"Wi-Fi\",,,\"kept as is,\nin all languages\"\n\
+            Wi-Fi,WLAN,zh_CN,,\n";
+        let glossary = Glossary::from_csv(content).unwrap();
+        assert_eq!(terms(&glossary, "zh_CN"), [
+            ("Control Center".to_string(), vec!["控制中心".to_string()], Severity::Error),
+            ("Launcher".to_string(), vec!["启动器".to_string()], Severity::Warning),
+            ("Wi-Fi".to_string(), vec!["WLAN".to_string()], Severity::Error),
+        ]);
+        assert_eq!(terms(&glossary, "zh-TW"), [
+            ("Control Center".to_string(), vec!["控制中心".to_string()], Severity::Error),
+            ("Launcher".to_string(), vec!["啟動器".to_string()], Severity::Warning),
+            ("Wi-Fi".to_string(), vec!["Wi-Fi".to_string()], Severity::Error),
+        ]);
+        assert_eq!(terms(&glossary, "de"), [("Wi-Fi".to_string(), vec!["Wi-Fi".to_string()], Severity::Error)]);
+
+        let term = glossary.terms_for_language("zh_CN")[0];
+        assert!(term.is_used_by("Open &Control center"));
+        assert!(!term.is_used_by("Open Control Centers"));
+        assert!(term.is_followed_by("打开控制中心(&C)"));
+        assert!(!term.is_followed_by("打开控制面板"));
+        let term = GlossaryTerm::new("C++", "", Severity::Error);
+        assert!(term.is_used_by("Written in C++."));
+        assert!(!term.is_used_by("Written in ObjC++."));
+        let term = GlossaryTerm::new(".deb", "", Severity::Error);
+        assert!(term.is_used_by("Install the .deb package"));
+        assert!(!term.is_used_by("Install the .debian package"));
+
+        assert!(matches!(Glossary::from_csv("source,translation\n"), Err(GlossaryLoadError::Invalid(1, _))));
+        assert!(matches!(Glossary::from_csv("source,target,severity\nA,B,fatal\n"), Err(GlossaryLoadError::Invalid(2, _))));
+        assert!(matches!(Glossary::from_csv("source,target\n\"A,B\n"), Err(GlossaryLoadError::Invalid(2, _))));
+    }
+
+    #[test]
+    fn tst_glossary_from_tbx() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<martif type="TBX" xml:lang="en">
+<text><body>
+    <termEntry id="1">
+        <langSet xml:lang="en"><tig><term>Dock</term></tig></langSet>
+        <langSet xml:lang="zh-CN"><tig><term>任务栏</term></tig></langSet>
+        <langSet xml:lang="zh-TW"><tig><term>任務欄</term></tig><tig><term>工作列</term></tig></langSet>
+    </termEntry>
+    <termEntry id="2">
+        <descrip type="severity">warning</descrip>
+        <langSet xml:lang="en_US"><ntig><termGrp><term>Window</term></termGrp></ntig></langSet>
+        <langSet xml:lang="zh_CN"><ntig><termGrp><term>窗口</term></termGrp></ntig></langSet>
+    </termEntry>
+</body></text>
+</martif>
+"#;
+        let glossary = Glossary::from_tbx(content).unwrap();
+        assert_eq!(terms(&glossary, "zh_CN"), [
+            ("Dock".to_string(), vec!["任务栏".to_string()], Severity::Error),
+            ("Window".to_string(), vec!["窗口".to_string()], Severity::Warning),
+        ]);
+        assert_eq!(terms(&glossary, "zh_TW"), [("Dock".to_string(), vec!["任務欄".to_string(), "工作列".to_string()], Severity::Error)]);
+        assert!(Glossary::from_tbx("<martif><termEntry></martif>").is_err());
+    }
+}
@@ -5,3 +5,7 @@
 pub mod common;
 pub mod linguist;
 pub mod gettext;
+pub mod language_team;
+pub mod language_code;
+pub mod cache;
+pub mod diff;
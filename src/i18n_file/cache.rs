@@ -0,0 +1,168 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+use thiserror::Error as TeError;
+use super::common::{FlatMessage, I18nFileKind, MessageStats, UnknownI18nFileExtError};
+use super::gettext::{Po, PoLoadError};
+use super::linguist::{Ts, TsLoadError};
+
+/// A parsed translation file, shared between cache users.
+#[derive(Debug, Clone)]
+pub enum I18nFile {
+    Linguist(Arc<Ts>),
+    Gettext(Arc<Po>),
+}
+
+impl I18nFile {
+    pub fn kind(&self) -> I18nFileKind {
+        match self {
+            I18nFile::Linguist(_) => I18nFileKind::Linguist,
+            I18nFile::Gettext(_) => I18nFileKind::Gettext,
+        }
+    }
+
+    pub fn get_language(&self) -> Option<String> {
+        match self {
+            I18nFile::Linguist(ts) => ts.get_language(),
+            I18nFile::Gettext(po) => Some(po.get_language()).filter(|language| !language.is_empty()),
+        }
+    }
+
+    pub fn get_message_stats(&self) -> MessageStats {
+        match self {
+            I18nFile::Linguist(ts) => ts.get_message_stats(),
+            I18nFile::Gettext(po) => po.get_message_stats(),
+        }
+    }
+
+    pub fn flat_messages(&self) -> Vec<FlatMessage> {
+        match self {
+            I18nFile::Linguist(ts) => ts.flat_messages(),
+            I18nFile::Gettext(po) => po.flat_messages(),
+        }
+    }
+}
+
+#[derive(TeError, Debug)]
+pub enum I18nFileLoadError {
+    #[error("Can not guess translation file kind because: {0}")]
+    GuessKind(#[from] UnknownI18nFileExtError),
+    #[error("Can not read file metadata: {0}")]
+    ReadMetadata(#[from] std::io::Error),
+    #[error("Fail to load Qt Linguist TS file: {0}")]
+    LoadTs(#[from] TsLoadError),
+    #[error("Fail to load Gettext PO/POT file: {0}")]
+    LoadPo(#[from] PoLoadError),
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    modified: SystemTime,
+    len: u64,
+    file: I18nFile,
+}
+
+/// Cache of parsed translation files keyed by path, so that analyses running over the same files
+/// only deserialize each file once.
+///
+/// An entry is reused as long as the modification time and the size of the file are unchanged.
+#[derive(Debug, Default)]
+pub struct I18nFileCache {
+    entries: Mutex<HashMap<PathBuf, CacheEntry>>,
+}
+
+impl I18nFileCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The cache shared by all users inside the current process.
+    pub fn shared() -> &'static I18nFileCache {
+        static SHARED: OnceLock<I18nFileCache> = OnceLock::new();
+        SHARED.get_or_init(I18nFileCache::new)
+    }
+
+    /// Load the given file, or return the cached one if the file is not modified since then.
+    pub fn load(&self, path: &Path) -> Result<I18nFile, I18nFileLoadError> {
+        let kind = I18nFileKind::from_ext_hint(path)?;
+        let key = std::path::absolute(path)?;
+        let metadata = std::fs::metadata(path)?;
+        let modified = metadata.modified()?;
+        let len = metadata.len();
+        if let Some(entry) = self.entries.lock().unwrap().get(&key) {
+            if entry.modified == modified && entry.len == len {
+                return Ok(entry.file.clone());
+            }
+        }
+
+        // Parse without holding the lock, so files can be loaded in parallel.
+        let file = match kind {
+            I18nFileKind::Linguist => I18nFile::Linguist(Arc::new(Ts::load_from_file(path)?)),
+            I18nFileKind::Gettext => I18nFile::Gettext(Arc::new(Po::load_from_file(path)?)),
+        };
+        self.entries.lock().unwrap().insert(key, CacheEntry { modified, len, file: file.clone() });
+        Ok(file)
+    }
+
+    /// Drop the cached entry of the given file, e.g. after it's written by ourselves.
+    pub fn invalidate(&self, path: &Path) {
+        if let Ok(key) = std::path::absolute(path) {
+            self.entries.lock().unwrap().remove(&key);
+        }
+    }
+
+    /// Drop all entries whose files have been modified or removed.
+    pub fn prune(&self) {
+        self.entries.lock().unwrap().retain(|path, entry| {
+            std::fs::metadata(path).is_ok_and(|metadata| {
+                metadata.len() == entry.len && metadata.modified().is_ok_and(|modified| modified == entry.modified)
+            })
+        });
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n_file::linguist::tests::{TEST_EMPTY_TS_CONTENT, TEST_ZH_CN_TS_CONTENT};
+
+    #[test]
+    fn tst_i18n_file_cache() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let ts_file = temp_dir.path().join("sample_zh_CN.ts");
+        std::fs::write(&ts_file, TEST_ZH_CN_TS_CONTENT).unwrap();
+
+        let cache = I18nFileCache::new();
+        let I18nFile::Linguist(first) = cache.load(&ts_file).unwrap() else { panic!() };
+        let I18nFile::Linguist(second) = cache.load(&ts_file).unwrap() else { panic!() };
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(cache.len(), 1);
+
+        std::fs::write(&ts_file, TEST_EMPTY_TS_CONTENT).unwrap();
+        cache.prune();
+        assert!(cache.is_empty());
+        let reloaded = cache.load(&ts_file).unwrap();
+        assert_eq!(reloaded.get_message_stats(), MessageStats::default());
+        assert_eq!(reloaded.get_language(), None);
+
+        assert!(matches!(cache.load(&temp_dir.path().join("missing.ts")), Err(I18nFileLoadError::ReadMetadata(_))));
+        assert!(matches!(cache.load(&temp_dir.path().join("sample.txt")), Err(I18nFileLoadError::GuessKind(_))));
+    }
+}
@@ -6,6 +6,13 @@ use serde::Serialize;
 use std::path::Path;
 use thiserror::Error as TeError;
 
+/// Marker used to flag a translated message as manually reviewed.
+///
+/// `zhconv` never overwrites messages carrying this marker. For Qt Linguist TS file, the
+/// marker is stored inside the `<extracomment>` element. For GNU Gettext PO file, the marker
+/// is stored as a message flag (`#, zhconv-manual`).
+pub const MANUAL_REVIEW_MARKER: &str = "zhconv-manual";
+
 pub enum I18nFileKind {
     /// Qt Linguist translation file format (.ts)
     Linguist,
@@ -40,10 +47,116 @@ impl I18nFileKind {
             None => Err(UnknownI18nFileExtError { ext: String::new() }),
         }
     }
+
+    /// Check whether the beginning of a `.ts` file looks like a Qt Linguist file, i.e. an XML declaration,
+    /// a `<!DOCTYPE TS>` or the `<TS` root element, instead of e.g. a TypeScript source.
+    pub fn sniff_linguist(head: &[u8]) -> bool {
+        let head = String::from_utf8_lossy(head);
+        let head = head.trim_start_matches('\u{feff}').trim_start();
+        head.starts_with("<?xml") || head.starts_with("<!DOCTYPE TS") || head.starts_with("<TS")
+    }
+
+    /// Same as [`Self::sniff_linguist`], but reads the beginning of the given file. Unreadable files are not Linguist files.
+    pub fn sniff_linguist_file(path: &Path) -> bool {
+        use std::io::Read;
+        let mut head = [0u8; 256];
+        let Ok(mut file) = std::fs::File::open(path) else {
+            return false;
+        };
+        let Ok(len) = file.read(&mut head) else {
+            return false;
+        };
+        Self::sniff_linguist(&head[..len])
+    }
+}
+
+/// Similarity ratio of two strings in range `0.0..=1.0`, based on their longest common subsequence.
+///
+/// This is used to pick a "fuzzy" candidate when a source text has been slightly changed.
+pub fn similarity_ratio(lhs: &str, rhs: &str) -> f64 {
+    let lhs: Vec<char> = lhs.chars().collect();
+    let rhs: Vec<char> = rhs.chars().collect();
+    let total = lhs.len() + rhs.len();
+    if total == 0 {
+        return 1.0;
+    }
+    if lhs.is_empty() || rhs.is_empty() {
+        return 0.0;
+    }
+    let mut previous = vec![0usize; rhs.len() + 1];
+    let mut current = vec![0usize; rhs.len() + 1];
+    for lhs_char in &lhs {
+        for (index, rhs_char) in rhs.iter().enumerate() {
+            current[index + 1] = if lhs_char == rhs_char {
+                previous[index] + 1
+            } else {
+                current[index].max(previous[index + 1])
+            };
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    2.0 * previous[rhs.len()] as f64 / total as f64
+}
+
+/// Upper bound of [`similarity_ratio`] from the lengths of the strings only, so that callers with a threshold can skip
+/// the expensive comparison of strings that can never be similar enough.
+pub fn similarity_upper_bound(lhs: &str, rhs: &str) -> f64 {
+    let (lhs_len, rhs_len) = (lhs.chars().count(), rhs.chars().count());
+    match lhs_len + rhs_len {
+        0 => 1.0,
+        total => 2.0 * lhs_len.min(rhs_len) as f64 / total as f64,
+    }
+}
+
+/// State of a single message, see [`MessageStats`] for the meaning of each state.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageState {
+    Finished,
+    Unfinished,
+    Vanished,
+    Obsolete,
+    Fuzzy,
+}
+
+/// A message of any supported i18n file type, used to compare messages across files.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FlatMessage {
+    /// Context name for Qt Linguist TS file, or msgctxt for GNU Gettext PO file.
+    pub context: String,
+    pub source: String,
+    /// Disambiguation comment for Qt Linguist TS file, always `None` for GNU Gettext PO file.
+    pub disambiguation: Option<String>,
+    /// Translated texts, with one item per plural form for plural messages.
+    pub translations: Vec<String>,
+    pub state: MessageState,
+}
+
+/// Whether the character is a CJK ideograph, i.e. a Chinese character.
+pub fn is_cjk_ideograph(c: char) -> bool {
+    matches!(c, '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' | '\u{20000}'..='\u{2FA1F}')
+}
+
+/// Count words like translation vendors do: each CJK ideograph is a word, other words are separated by whitespace.
+pub fn count_words(text: &str) -> u64 {
+    let mut words = 0;
+    let mut in_word = false;
+    for c in text.chars() {
+        if is_cjk_ideograph(c) {
+            words += 1;
+            in_word = false;
+        } else if c.is_whitespace() {
+            in_word = false;
+        } else if !in_word {
+            words += 1;
+            in_word = true;
+        }
+    }
+    words
 }
 
 /// Universal message statistics infomations shared by all supported i18n file types.
-#[derive(Debug, Default, Serialize, PartialEq)]
+#[derive(Debug, Default, Clone, Serialize, PartialEq)]
 pub struct MessageStats {
     /// The source text has been translated.
     /// 
@@ -69,7 +182,7 @@ pub struct MessageStats {
     /// The only reason why split them is to keep they are different in Qt scope.
     /// 
     /// For Qt Linguist TS file, entries with "Obsolete" type should be grouped into this field.
-    /// For GNU Gettext PO file, no entry should be grouped into this.
+    /// For GNU Gettext PO file, obsolete `#~` entries should be grouped into this.
     pub obsolete: u64,
     /// The source text of this entry is still existing,
     /// but has slight difference with old one,
@@ -82,6 +195,14 @@ pub struct MessageStats {
     /// For Qt Linguist TS file, no entry should be grouped into this.
     /// For GNU Gettext PO file, all "fuzzy" entries should be grouped into this.
     pub fuzzy: u64,
+    /// Number of words in source texts of "finished" entries, see [`count_words`].
+    pub translated_words: u64,
+    /// Number of words in source texts of "unfinished" and "fuzzy" entries, see [`count_words`].
+    pub untranslated_words: u64,
+    /// Number of non-whitespace characters in source texts of "finished" entries.
+    pub translated_chars: u64,
+    /// Number of non-whitespace characters in source texts of "unfinished" and "fuzzy" entries.
+    pub untranslated_chars: u64,
 }
 
 impl MessageStats {
@@ -92,6 +213,23 @@ impl MessageStats {
             vanished: 0,
             obsolete: 0,
             fuzzy: 0,
+            translated_words: 0,
+            untranslated_words: 0,
+            translated_chars: 0,
+            untranslated_chars: 0,
+        }
+    }
+
+    /// Count words and characters of the source text of a "finished" (`translated`) or an "unfinished"/"fuzzy" entry.
+    pub fn count_source_text(&mut self, source: &str, translated: bool) {
+        let words = count_words(source);
+        let chars = source.chars().filter(|c| !c.is_whitespace()).count() as u64;
+        if translated {
+            self.translated_words += words;
+            self.translated_chars += chars;
+        } else {
+            self.untranslated_words += words;
+            self.untranslated_chars += chars;
         }
     }
 
@@ -102,7 +240,7 @@ impl MessageStats {
 
         let total = reference_total.unwrap_or(finished + unfinished);
         if total == 0 {
-            return 0.0;
+            0.0
         } else {
             (finished as f64 / total as f64) * 100.0
         }
@@ -131,5 +269,44 @@ impl std::ops::AddAssign<&Self> for MessageStats {
         self.vanished += rhs.vanished;
         self.obsolete += rhs.obsolete;
         self.fuzzy += rhs.fuzzy;
+        self.translated_words += rhs.translated_words;
+        self.untranslated_words += rhs.untranslated_words;
+        self.translated_chars += rhs.translated_chars;
+        self.untranslated_chars += rhs.untranslated_chars;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_sniff_linguist() {
+        assert!(I18nFileKind::sniff_linguist(b"<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<!DOCTYPE TS>"));
+        assert!(I18nFileKind::sniff_linguist(b"\xef\xbb\xbf\n<TS version=\"2.1\" language=\"zh_CN\">"));
+        assert!(!I18nFileKind::sniff_linguist(b"import { foo } from './foo';\n"));
+        assert!(!I18nFileKind::sniff_linguist(b"// <TS> in a comment\n"));
+        assert!(!I18nFileKind::sniff_linguist(b""));
+    }
+
+    #[test]
+    fn tst_similarity_ratio() {
+        assert_eq!(similarity_ratio("", ""), 1.0);
+        assert_eq!(similarity_ratio("Open", ""), 0.0);
+        assert_eq!(similarity_ratio("Open", "Open"), 1.0);
+        assert_eq!(similarity_ratio("Open file", "Open files"), 18.0 / 19.0);
+        assert!(similarity_ratio("Open", "Close") < 0.5);
+        for (lhs, rhs) in [("", ""), ("Open", ""), ("Open file", "Open files"), ("Open", "Close"), ("a", "a long text")] {
+            assert!(similarity_ratio(lhs, rhs) <= similarity_upper_bound(lhs, rhs));
+        }
+    }
+
+    #[test]
+    fn tst_count_words() {
+        assert_eq!(count_words(""), 0);
+        assert_eq!(count_words("  Open   file\n"), 2);
+        assert_eq!(count_words("%n photos"), 2);
+        assert_eq!(count_words("打开文件"), 4);
+        assert_eq!(count_words("打开deepin文件"), 5);
     }
 }
@@ -0,0 +1,115 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use serde::Serialize;
+use super::common::{FlatMessage, MessageState};
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeKind {
+    /// The message only exists in the new file.
+    Added,
+    /// The message only exists in the old file.
+    Removed,
+    /// The translation is changed, the state may be changed as well.
+    Changed,
+    /// Only the state is changed, e.g. a translation is marked as finished.
+    StateChanged,
+}
+
+/// A semantic change of a single message between two translation files.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MessageChange {
+    pub kind: ChangeKind,
+    pub context: String,
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disambiguation: Option<String>,
+    pub old_translations: Vec<String>,
+    pub new_translations: Vec<String>,
+    pub old_state: Option<MessageState>,
+    pub new_state: Option<MessageState>,
+}
+
+type MessageKey<'a> = (&'a str, &'a str, Option<&'a str>);
+
+fn message_key(message: &FlatMessage) -> MessageKey<'_> {
+    (message.context.as_str(), message.source.as_str(), message.disambiguation.as_deref())
+}
+
+/// Compare messages of two translation files, messages are matched by context, source text and
+/// disambiguation.
+///
+/// Changes are ordered as the new file, followed by removed messages in the order of the old file.
+pub fn diff_messages(old: &[FlatMessage], new: &[FlatMessage]) -> Vec<MessageChange> {
+    let old_by_key: HashMap<MessageKey, &FlatMessage> = old.iter().map(|message| (message_key(message), message)).collect();
+    let new_by_key: HashMap<MessageKey, &FlatMessage> = new.iter().map(|message| (message_key(message), message)).collect();
+    let change = |kind, old_message: Option<&FlatMessage>, new_message: Option<&FlatMessage>| {
+        let message = new_message.or(old_message).unwrap();
+        MessageChange {
+            kind,
+            context: message.context.clone(),
+            source: message.source.clone(),
+            disambiguation: message.disambiguation.clone(),
+            old_translations: old_message.map(|message| message.translations.clone()).unwrap_or_default(),
+            new_translations: new_message.map(|message| message.translations.clone()).unwrap_or_default(),
+            old_state: old_message.map(|message| message.state),
+            new_state: new_message.map(|message| message.state),
+        }
+    };
+
+    let mut changes = vec![];
+    for new_message in new {
+        match old_by_key.get(&message_key(new_message)) {
+            None => changes.push(change(ChangeKind::Added, None, Some(new_message))),
+            Some(old_message) if old_message.translations != new_message.translations => {
+                changes.push(change(ChangeKind::Changed, Some(old_message), Some(new_message)));
+            },
+            Some(old_message) if old_message.state != new_message.state => {
+                changes.push(change(ChangeKind::StateChanged, Some(old_message), Some(new_message)));
+            },
+            Some(_) => {},
+        }
+    }
+    for old_message in old {
+        if !new_by_key.contains_key(&message_key(old_message)) {
+            changes.push(change(ChangeKind::Removed, Some(old_message), None));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n_file::linguist::Ts;
+    use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+
+    #[test]
+    fn tst_diff_messages() {
+        let old_ts = Ts::load_from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
+        let mut new_ts = old_ts.clone();
+        let messages = &mut new_ts.contexts[0].messages;
+        messages[0].fill_translation("海內存知己");
+        messages[3].fill_translation("");
+        let removed = messages.remove(1);
+        let mut added = removed.clone();
+        added.source = "A new source".to_string();
+        messages.push(added);
+
+        let changes = diff_messages(&old_ts.flat_messages(), &new_ts.flat_messages());
+        let kinds: Vec<(ChangeKind, &str)> = changes.iter().map(|change| (change.kind, change.source.as_str())).collect();
+        assert_eq!(kinds, vec![
+            (ChangeKind::Changed, old_ts.contexts[0].messages[0].source.as_str()),
+            (ChangeKind::StateChanged, old_ts.contexts[0].messages[3].source.as_str()),
+            (ChangeKind::Added, "A new source"),
+            (ChangeKind::Removed, old_ts.contexts[0].messages[1].source.as_str()),
+        ]);
+        assert_eq!(changes[1].old_state, Some(MessageState::Unfinished));
+        assert_eq!(changes[1].new_state, Some(MessageState::Finished));
+        assert_eq!(changes[2].old_state, None);
+        assert!(diff_messages(&old_ts.flat_messages(), &old_ts.flat_messages()).is_empty());
+    }
+}
@@ -2,17 +2,28 @@
 //
 // SPDX-License-Identifier: MIT
 
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
 use std::path::Path;
-use polib::message::{MessageMutView, MessageView};
-use polib::po_file::{self, POParseError};
+use std::sync::LazyLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+use polib::message::{Message, MessageMutView, MessageView};
+use polib::po_file;
+use regex::Regex;
 use thiserror::Error as TeError;
-use super::common::MessageStats;
+use super::common::{is_cjk_ideograph, similarity_ratio, similarity_upper_bound, FlatMessage, MessageState, MessageStats, MANUAL_REVIEW_MARKER};
+use super::language_team::lookup_language_team;
 
 // ===== PO Basic =====
 
 #[derive(Debug, Clone)]
 pub struct Po {
-    pub inner: polib::catalog::Catalog,
+    pub(crate) inner: polib::catalog::Catalog,
+    /// Messages that are no longer used, i.e. `#~` entries of the file.
+    pub(crate) obsolete: Vec<Message>,
+    /// Header fields that are not handled by `polib`, e.g. `Report-Msgid-Bugs-To` and `X-*` fields.
+    pub extra_headers: Vec<(String, String)>,
 }
 
 impl Po {
@@ -26,6 +37,23 @@ impl Po {
             }
         }
     }
+
+    /// Sort messages by msgctxt and msgid like `msgcat --sort-output`, and obsolete messages as well.
+    pub fn normalize(&mut self) {
+        let mut messages: Vec<Message> = self.inner.messages()
+            .filter(|message| !message.msgid().is_empty())
+            .map(to_owned_message)
+            .collect();
+        let sort_key = |message: &Message| (message.msgctxt().unwrap_or_default().to_string(), message.msgid().to_string());
+        messages.sort_by_cached_key(sort_key);
+        self.obsolete.sort_by_cached_key(sort_key);
+        let mut catalog = polib::catalog::Catalog::new(self.inner.metadata.clone());
+        catalog.preheader = self.inner.preheader.clone();
+        for message in messages {
+            catalog.append_or_update(message);
+        }
+        self.inner = catalog;
+    }
 }
 
 impl Po {
@@ -37,6 +65,29 @@ impl Po {
         self.inner.metadata.language = language.to_string();
     }
 
+    /// Number of messages in the file, not counting obsolete ones.
+    pub fn message_count(&self) -> usize {
+        self.inner.count()
+    }
+
+    /// Drop obsolete messages, like `msgattrib --no-obsolete`.
+    pub fn clear_obsolete_messages(&mut self) {
+        self.obsolete.clear();
+    }
+
+    pub fn get_extra_header(&self, key: &str) -> Option<&str> {
+        self.extra_headers.iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn set_extra_header(&mut self, key: &str, value: &str) {
+        match self.extra_headers.iter_mut().find(|(name, _)| name == key) {
+            Some((_, old_value)) => *old_value = value.to_string(),
+            None => self.extra_headers.push((key.to_string(), value.to_string())),
+        }
+    }
+
     pub fn get_message_stats(&self) -> MessageStats {
         let mut stats = MessageStats::new();
         for message in self.inner.messages() {
@@ -47,17 +98,400 @@ impl Po {
             } else {
                 stats.unfinished += 1;
             }
+            stats.count_source_text(message.msgid(), message.is_translated());
+        }
+        stats.obsolete = self.obsolete.len() as u64;
+        stats
+    }
+
+    pub fn flat_messages(&self) -> Vec<FlatMessage> {
+        let flatten = |message: &dyn MessageView, state: MessageState| FlatMessage {
+            context: message.msgctxt().unwrap_or_default().to_string(),
+            source: message.msgid().to_string(),
+            disambiguation: None,
+            translations: match message.is_plural() {
+                true => message.msgstr_plural().cloned().unwrap_or_default(),
+                false => message.msgstr().map(|msgstr| vec![msgstr.to_string()]).unwrap_or_default(),
+            },
+            state,
+        };
+        let mut messages: Vec<FlatMessage> = self.inner.messages().filter(|message| !message.msgid().is_empty()).map(|message| {
+            let state = if message.is_translated() {
+                MessageState::Finished
+            } else if message.is_fuzzy() {
+                MessageState::Fuzzy
+            } else {
+                MessageState::Unfinished
+            };
+            flatten(message, state)
+        }).collect();
+        messages.extend(self.obsolete.iter().map(|message| flatten(message, MessageState::Obsolete)));
+        messages
+    }
+}
+
+/// Whether the message carries the manual review marker flag.
+pub(crate) fn is_message_manually_reviewed(message: &dyn MessageView) -> bool {
+    message.flags().contains(MANUAL_REVIEW_MARKER)
+}
+
+/// Add or remove the manual review marker flag of the given message.
+pub(crate) fn set_message_manually_reviewed(message: &mut dyn MessageMutView, reviewed: bool) {
+    if reviewed {
+        message.flags_mut().add_flag(MANUAL_REVIEW_MARKER);
+    } else {
+        message.flags_mut().remove_flag(MANUAL_REVIEW_MARKER);
+    }
+}
+
+// ===== PO Merge =====
+
+/// Minimal similarity ratio for a message to be used as a fuzzy match, same as `msgmerge`.
+const FUZZY_MATCH_THRESHOLD: f64 = 0.6;
+
+/// Statistics of a [`Po::merge_with_template`] run.
+#[derive(Debug, Default, PartialEq)]
+pub struct PoMergeStats {
+    pub exact: u64,
+    pub fuzzy: u64,
+    pub untranslated: u64,
+    pub obsolete: u64,
+}
+
+impl Po {
+    /// Update translations against the given template, like what `msgmerge` does.
+    ///
+    /// Messages are ordered as the template. Translations of exactly matched messages are kept,
+    /// otherwise the translation of the most similar message is used and marked as fuzzy if
+    /// `fuzzy_matching` is enabled. Translated messages that no longer exist in the template
+    /// are moved to the obsolete list, and obsolete messages that exist in the template again
+    /// are brought back.
+    pub fn merge_with_template(&self, template: &Po, fuzzy_matching: bool) -> (Po, PoMergeStats) {
+        let mut stats = PoMergeStats::default();
+        let mut metadata = self.inner.metadata.clone();
+        metadata.pot_creation_date = template.inner.metadata.pot_creation_date.clone();
+        let mut catalog = polib::catalog::Catalog::new(metadata);
+        catalog.preheader = self.inner.preheader.clone();
+
+        // Messages with empty msgid are leftovers of the header, ignore them.
+        let existing: Vec<&dyn MessageView> = self.inner.messages()
+            .filter(|message| !message.msgid().is_empty())
+            .collect();
+        let existing_by_key: HashMap<(Option<&str>, &str), usize> = existing.iter().enumerate()
+            .map(|(index, message)| ((message.msgctxt(), message.msgid()), index))
+            .collect();
+        let mut used = vec![false; existing.len()];
+        let mut obsolete = self.obsolete.clone();
+
+        for template_message in template.inner.messages() {
+            if template_message.msgid().is_empty() {
+                continue;
+            }
+            let mut message = to_owned_message(template_message);
+            message.flags_mut().remove_flag("fuzzy");
+            clear_message_translation(&mut message);
+
+            let key = (template_message.msgctxt(), template_message.msgid());
+            if let Some(&index) = existing_by_key.get(&key) {
+                used[index] = true;
+                let old_message = existing[index];
+                copy_message_translation(old_message, &mut message);
+                if old_message.is_fuzzy() || template_message.is_plural() != old_message.is_plural() {
+                    message.flags_mut().add_flag("fuzzy");
+                }
+                if is_message_manually_reviewed(old_message) {
+                    set_message_manually_reviewed(&mut message, true);
+                }
+                if message.is_translated() && !message.is_fuzzy() {
+                    stats.exact += 1;
+                } else if message.is_fuzzy() {
+                    stats.fuzzy += 1;
+                } else {
+                    stats.untranslated += 1;
+                }
+            } else if let Some(index) = obsolete.iter().position(|old_message| (old_message.msgctxt(), old_message.msgid()) == key) {
+                // Bring back obsolete translations of messages that are used again.
+                let old_message = obsolete.remove(index);
+                copy_message_translation(&old_message, &mut message);
+                if old_message.is_fuzzy() || template_message.is_plural() != old_message.is_plural() {
+                    message.flags_mut().add_flag("fuzzy");
+                }
+                match message.is_fuzzy() {
+                    true => stats.fuzzy += 1,
+                    false => stats.exact += 1,
+                }
+            } else if let Some(old_message) = fuzzy_matching.then(|| find_fuzzy_match(&existing, template_message)).flatten() {
+                copy_message_translation(old_message, &mut message);
+                message.flags_mut().add_flag("fuzzy");
+                stats.fuzzy += 1;
+            } else {
+                stats.untranslated += 1;
+            }
+            catalog.append_or_update(message);
+        }
+
+        for (index, old_message) in existing.iter().enumerate() {
+            if !used[index] && old_message.is_translated() {
+                obsolete.push(to_owned_message(*old_message));
+                stats.obsolete += 1;
+            }
+        }
+
+        (Po { inner: catalog, obsolete, extra_headers: self.extra_headers.clone() }, stats)
+    }
+}
+
+fn find_fuzzy_match<'a>(candidates: &[&'a dyn MessageView], template_message: &dyn MessageView) -> Option<&'a dyn MessageView> {
+    let mut best_match: Option<(f64, &dyn MessageView)> = None;
+    for candidate in candidates {
+        if !candidate.is_translated() || candidate.is_plural() != template_message.is_plural() {
+            continue;
+        }
+        let min_ratio = best_match.map_or(FUZZY_MATCH_THRESHOLD, |(best_ratio, _)| best_ratio);
+        if similarity_upper_bound(candidate.msgid(), template_message.msgid()) < min_ratio {
+            continue;
+        }
+        let ratio = similarity_ratio(candidate.msgid(), template_message.msgid());
+        if ratio >= FUZZY_MATCH_THRESHOLD && best_match.is_none_or(|(best_ratio, _)| ratio > best_ratio) {
+            best_match = Some((ratio, *candidate));
+        }
+    }
+    best_match.map(|(_, message)| message)
+}
+
+fn to_owned_message(message: &dyn MessageView) -> Message {
+    let mut builder = if message.is_plural() {
+        let mut builder = Message::build_plural();
+        builder
+            .with_msgid_plural(message.msgid_plural().unwrap().to_string())
+            .with_msgstr_plural(message.msgstr_plural().unwrap().clone());
+        builder
+    } else {
+        let mut builder = Message::build_singular();
+        builder.with_msgstr(message.msgstr().unwrap().to_string());
+        builder
+    };
+    builder
+        .with_translator_comments(message.translator_comments().to_string())
+        .with_extracted_comments(message.extracted_comments().to_string())
+        .with_source(message.source().to_string())
+        .with_flags(message.flags().clone())
+        .with_msgctxt(message.msgctxt().unwrap_or_default().to_string())
+        .with_msgid(message.msgid().to_string())
+        .done()
+}
+
+fn clear_message_translation(message: &mut Message) {
+    if message.is_plural() {
+        message.msgstr_plural_mut().unwrap().iter_mut().for_each(String::clear);
+    } else {
+        message.msgstr_mut().unwrap().clear();
+    }
+}
+
+/// Copy translation and translator comments from `from` to `to`.
+///
+/// If singular/plural kind is mismatched, the singular form is used as the first plural form, or vice versa.
+fn copy_message_translation(from: &dyn MessageView, to: &mut Message) {
+    *to.translator_comments_mut() = from.translator_comments().to_string();
+    match (from.is_plural(), to.is_plural()) {
+        (false, false) => *to.msgstr_mut().unwrap() = from.msgstr().unwrap().to_string(),
+        (true, true) => *to.msgstr_plural_mut().unwrap() = from.msgstr_plural().unwrap().clone(),
+        (false, true) => {
+            let forms = to.msgstr_plural_mut().unwrap();
+            if forms.is_empty() {
+                forms.push(String::new());
+            }
+            forms[0] = from.msgstr().unwrap().to_string();
+        },
+        (true, false) => {
+            let first_form = from.msgstr_plural().unwrap().first().cloned().unwrap_or_default();
+            *to.msgstr_mut().unwrap() = first_form;
+        },
+    }
+}
+
+// ===== PO Header =====
+
+/// Header fields that are stored in `polib`'s `CatalogMetadata`.
+const METADATA_HEADERS: &[&str] = &[
+    "Project-Id-Version", "POT-Creation-Date", "PO-Revision-Date", "Last-Translator", "Language-Team",
+    "MIME-Version", "Content-Type", "Content-Transfer-Encoding", "Language", "Plural-Forms",
+];
+
+const PLURAL_FORMS_PLACEHOLDER: &str = "nplurals=INTEGER; plural=EXPRESSION;";
+
+/// Format the given time in UTC as used by `POT-Creation-Date` and `PO-Revision-Date`, e.g.
+/// `2025-01-01 08:00+0000`.
+pub fn format_po_date(time: SystemTime) -> String {
+    let seconds = time.duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+    let (days, seconds_of_day) = (seconds / 86400, seconds % 86400);
+    // Convert days since epoch to a civil date, see https://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z / 146097;
+    let day_of_era = z % 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02} {:02}:{:02}+0000", seconds_of_day / 3600, seconds_of_day % 3600 / 60)
+}
+
+/// Project-wide header values used when creating a new PO file.
+#[derive(Debug, Default, Clone)]
+pub struct PoHeaderOptions {
+    /// Value of `Report-Msgid-Bugs-To`. The value of the template file is kept if not set.
+    pub report_msgid_bugs_to: Option<String>,
+}
+
+impl Po {
+    /// Create an empty translation of the given language based on this file.
+    ///
+    /// `Language-Team` and `Plural-Forms` are filled from the embedded language team table instead
+    /// of being copied from this file, since they are language specific.
+    pub fn new_translation(&self, language_code: &str, header_options: &PoHeaderOptions) -> Po {
+        let mut po = self.clone();
+        po.set_language(language_code);
+        po.clear_finished_messages();
+        po.obsolete.clear();
+        let metadata = &mut po.inner.metadata;
+        metadata.language_team = String::new();
+        if let Some(team) = lookup_language_team(language_code) {
+            metadata.language_team = team.name.to_string();
+            // Plural forms in the table are always valid, see the test of `language_team` module.
+            let plural_forms = format!("Plural-Forms: {}\n", team.plural_forms);
+            metadata.plural_rules = polib::metadata::CatalogMetadata::parse(&plural_forms).unwrap().plural_rules;
+        }
+        if let Some(report_msgid_bugs_to) = &header_options.report_msgid_bugs_to {
+            po.set_extra_header("Report-Msgid-Bugs-To", report_msgid_bugs_to);
+        }
+        po
+    }
+
+    fn write_header<W: Write>(&self, writer: &mut BufWriter<W>, options: &PoSaveOptions) -> Result<(), std::io::Error> {
+        for line in &self.inner.preheader {
+            if line.is_empty() {
+                writeln!(writer, "#")?;
+            } else {
+                writeln!(writer, "# {line}")?;
+            }
+        }
+        // Keep the field order of `msginit`, which places `Report-Msgid-Bugs-To` after `Project-Id-Version`.
+        let mut fields: Vec<String> = self.inner.metadata.export_for_po().lines().map(str::to_string).collect();
+        if let Some(report_msgid_bugs_to) = self.get_extra_header("Report-Msgid-Bugs-To") {
+            let position = usize::from(fields.first().is_some_and(|field| field.starts_with("Project-Id-Version:")));
+            fields.insert(position, format!("Report-Msgid-Bugs-To: {report_msgid_bugs_to}"));
+        }
+        fields.extend(self.extra_headers.iter()
+            .filter(|(key, _)| key != "Report-Msgid-Bugs-To")
+            .map(|(key, value)| format!("{key}: {value}")));
+        writeln!(writer, "msgid \"\"")?;
+        let header: String = fields.iter().map(|field| format!("{field}\n")).collect();
+        write_field(writer, "", "msgstr", &header, options.wrap_width)
+    }
+}
+
+static CHARSET: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"charset=([A-Za-z0-9_.:-]+)").unwrap()
+});
+
+/// Decode the file content by the charset of its `Content-Type` header like `msgconv`, declaring UTF-8 afterwards
+/// since the file is always saved as UTF-8.
+fn decode_po_content(content: Vec<u8>) -> Result<String, PoLoadError> {
+    let content = match String::from_utf8(content) {
+        Ok(content) => return Ok(content),
+        Err(err) => err.into_bytes(),
+    };
+    // The header entry ends at the first blank line, and charsets used by PO files are ASCII compatible, so the header
+    // can be read before decoding.
+    let mut offset = 0;
+    let header_end = content.split(|byte| *byte == b'\n')
+        .map(|line| {
+            offset += line.len() + 1;
+            (offset, line.trim_ascii().is_empty())
+        })
+        .skip_while(|(_, blank)| *blank)
+        .find(|(_, blank)| *blank)
+        .map_or(content.len(), |(end, _)| end.min(content.len()));
+    let charset = CHARSET.captures(&String::from_utf8_lossy(&content[..header_end]))
+        .map_or_else(|| "UTF-8".to_string(), |captures| captures[1].to_string());
+    let encoding = encoding_rs::Encoding::for_label(charset.as_bytes())
+        .ok_or_else(|| PoLoadError::DecodeCharset(charset.clone()))?;
+    let (decoded, _, had_errors) = encoding.decode(&content);
+    if had_errors {
+        return Err(PoLoadError::DecodeCharset(charset));
+    }
+    Ok(CHARSET.replacen(&decoded, 1, "charset=UTF-8").into_owned())
+}
+
+/// Read header fields that are not handled by `polib` from the header entry of the PO content.
+fn parse_extra_headers(content: &str) -> Vec<(String, String)> {
+    let mut lines = content.lines()
+        .map(str::trim)
+        .skip_while(|line| line.is_empty() || line.starts_with('#'));
+    if lines.next() != Some("msgid \"\"") {
+        return vec![];
+    }
+    let Some(first_line) = lines.next().and_then(|line| line.strip_prefix("msgstr ")) else {
+        return vec![];
+    };
+    let mut header = String::new();
+    for quoted in std::iter::once(first_line).chain(lines.take_while(|line| line.starts_with('"'))) {
+        if let Some(text) = quoted.strip_prefix('"').and_then(|text| text.strip_suffix('"')) {
+            header.push_str(&unescape_po_string(text));
         }
-        return stats;
     }
+    header.lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .filter(|(key, _)| !key.is_empty() && !METADATA_HEADERS.contains(key))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
 }
 
 // ===== PO Load & Save =====
 
 #[derive(TeError, Debug)]
 pub enum PoLoadError {
+    #[error("Can not read file: {0}")]
+    ReadFile(#[from] std::io::Error),
     #[error("Fail to parse PO file: {0}")]
-    ParsePo(#[from] POParseError),
+    ParsePo(String),
+    #[error("Can not decode PO file as its declared charset {0}")]
+    DecodeCharset(String),
+}
+
+/// Default line width of `msgmerge` and `msgcat`.
+pub const DEFAULT_WRAP_WIDTH: usize = 79;
+
+/// How `#:` reference comments are written, like `--add-location` of `msgmerge`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum PoLocationStyle {
+    /// File names with line numbers, e.g. `#: src/main.cpp:42`.
+    #[default]
+    Full,
+    /// File names only, e.g. `#: src/main.cpp`, so that moving code around does not change the file.
+    File,
+    /// No reference comments.
+    Never,
+}
+
+/// Options of how to write PO files, the defaults produce the same layout as `msgmerge`.
+#[derive(Debug, Clone)]
+pub struct PoSaveOptions {
+    /// Maximum width of lines like `msgmerge --width`, `None` to only break strings after `\n` like `--no-wrap`.
+    pub wrap_width: Option<usize>,
+    /// Do not write obsolete `#~` entries, like `--no-obsolete`.
+    pub no_obsolete: bool,
+    pub locations: PoLocationStyle,
+}
+
+impl Default for PoSaveOptions {
+    fn default() -> Self {
+        Self { wrap_width: Some(DEFAULT_WRAP_WIDTH), no_obsolete: false, locations: PoLocationStyle::default() }
+    }
 }
 
 #[derive(TeError, Debug)]
@@ -68,36 +502,295 @@ pub enum PoSaveError {
 
 impl Po {
     pub fn load_from_file(po_file: &Path) -> Result<Po, PoLoadError> {
-        Ok(Po {
-            inner: po_file::parse(po_file)?,
-        })
+        let content = fs::read(po_file)?;
+        Self::load_from_str(&decode_po_content(content)?)
     }
 
-    #[cfg(test)]
     pub fn load_from_str(content: &str) -> Result<Po, PoLoadError> {
-        let reader = std::io::Cursor::new(content.as_bytes());
+        // Templates generated by xgettext come with a placeholder `Plural-Forms` that polib can not parse.
+        let content = match content.contains(PLURAL_FORMS_PLACEHOLDER) {
+            true => std::borrow::Cow::Owned(content.replacen(PLURAL_FORMS_PLACEHOLDER, "nplurals=1; plural=0;", 1)),
+            false => std::borrow::Cow::Borrowed(content),
+        };
+        let (active, obsolete) = split_obsolete_entries(&content);
+        let inner = po_file::parse_from_reader(std::io::Cursor::new(active.as_bytes()))
+            .map_err(|err| PoLoadError::ParsePo(err.to_string()))?;
+        let obsolete = match obsolete.is_empty() {
+            true => vec![],
+            // polib ignores `#~` lines, so parse the uncommented entries again after the header of the file.
+            false => {
+                let header = format!("msgid \"\"\nmsgstr \"{}\"\n\n", escape_po_string(&inner.metadata.export_for_po()));
+                po_file::parse_from_reader(std::io::Cursor::new(format!("{header}{obsolete}").as_bytes()))
+                    .map_err(|err| PoLoadError::ParsePo(err.to_string()))?
+                    .messages()
+                    .filter(|message| !message.msgid().is_empty())
+                    .map(to_owned_message)
+                    .collect()
+            },
+        };
         Ok(Po {
-            inner: po_file::parse_from_reader(reader)?
+            inner,
+            obsolete,
+            extra_headers: parse_extra_headers(&content),
         })
     }
 
-    pub fn load_from_file_or_default(po_file: &Path, fallback: &Po, fallback_language_code: &str) -> Result<Po, PoLoadError> {
+    pub fn load_from_file_or_default(po_file: &Path, fallback: &Po, fallback_language_code: &str, header_options: &PoHeaderOptions) -> Result<Po, PoLoadError> {
         if !po_file.exists() {
-            let mut po = fallback.clone();
-            po.set_language(fallback_language_code);
-            po.clear_finished_messages();
-            return Ok(po);
+            Ok(fallback.new_translation(fallback_language_code, header_options))
         } else {
-            return Self::load_from_file(po_file);
+            Self::load_from_file(po_file)
         }
     }
 
-    pub fn save_into_file(&self, po_file: &Path) -> Result<(), PoSaveError> {
-        po_file::write_to_file(&self.inner, po_file)?;
+    pub fn save_into_file(&self, po_file: &Path, options: &PoSaveOptions) -> Result<(), PoSaveError> {
+        let file = File::create(po_file)?;
+        let mut writer = BufWriter::new(file);
+        self.write_into(&mut writer, options)?;
+        Ok(())
+    }
+
+    /// Write the file by ourselves instead of using polib, which drops header fields it does not know and always wraps
+    /// lines at its own width.
+    pub fn write_into<W: Write>(&self, writer: &mut BufWriter<W>, options: &PoSaveOptions) -> Result<(), PoSaveError> {
+        self.write_header(writer, options)?;
+        for message in self.inner.messages() {
+            write_message(writer, message, "", options)?;
+        }
+        if !options.no_obsolete {
+            for message in &self.obsolete {
+                write_message(writer, message, "#~ ", options)?;
+            }
+        }
+        writer.flush()?;
         Ok(())
     }
 }
 
+/// Split the content into active entries and obsolete (`#~`) entries, the prefix of obsolete entries is removed.
+///
+/// An entry is obsolete if it only consists of comment lines and some of them start with `#~`. `#~|` previous msgid
+/// lines of obsolete entries are dropped.
+fn split_obsolete_entries(content: &str) -> (std::borrow::Cow<'_, str>, String) {
+    if !content.lines().any(|line| line.starts_with("#~")) {
+        return (std::borrow::Cow::Borrowed(content), String::new());
+    }
+    let (mut active, mut obsolete) = (String::new(), String::new());
+    let mut entry: Vec<&str> = vec![];
+    for line in content.lines().chain(std::iter::once("")) {
+        if !line.trim().is_empty() {
+            entry.push(line);
+            continue;
+        }
+        if entry.is_empty() {
+            continue;
+        }
+        let is_obsolete = entry.iter().all(|line| line.starts_with('#')) && entry.iter().any(|line| line.starts_with("#~"));
+        match is_obsolete {
+            true => {
+                for line in entry.drain(..).filter(|line| !line.starts_with("#~|")) {
+                    obsolete.push_str(line.strip_prefix("#~").map_or(line, str::trim_start));
+                    obsolete.push('\n');
+                }
+                obsolete.push('\n');
+            },
+            false => {
+                for line in entry.drain(..) {
+                    active.push_str(line);
+                    active.push('\n');
+                }
+                active.push('\n');
+            },
+        }
+    }
+    (std::borrow::Cow::Owned(active), obsolete)
+}
+
+pub(crate) fn escape_po_string(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t")
+}
+
+pub(crate) fn unescape_po_string(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('t') => result.push('\t'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Display width of a character, East Asian wide and fullwidth characters take two columns.
+fn char_width(c: char) -> usize {
+    match is_cjk_ideograph(c) || matches!(c, '\u{1100}'..='\u{115F}' | '\u{2E80}'..='\u{A4CF}' | '\u{AC00}'..='\u{D7A3}' | '\u{FE30}'..='\u{FE4F}' | '\u{FF00}'..='\u{FF60}' | '\u{FFE0}'..='\u{FFE6}') {
+        true => 2,
+        false => 1,
+    }
+}
+
+/// Whether a line may not start with the character, i.e. closing punctuation, even after spaces.
+fn is_no_break_before(c: char) -> bool {
+    ")]}!,.;:/?，。、；：！？）」』】》〉…".contains(c)
+}
+
+/// Whether a line may not end with the character, i.e. opening punctuation.
+fn is_no_break_after(c: char) -> bool {
+    "([{（「『【《〈".contains(c)
+}
+
+/// Split an escaped string into the strings written on each line: after each `\n`, and with `width`, at spaces, after
+/// hyphens and around CJK characters so that each line fits in `width` columns, which is close to the line breaking rules
+/// used by `msgmerge`. A part without a place to break is kept on one line.
+fn wrap_po_string(escaped: &str, width: Option<usize>) -> Vec<String> {
+    // Unbreakable parts with their width and whether the line must end after them.
+    let mut parts: Vec<(String, usize, bool)> = vec![];
+    let mut part = (String::new(), 0);
+    let mut chars = escaped.chars().peekable();
+    while let Some(c) = chars.next() {
+        let mut token = c.to_string();
+        if c == '\\' {
+            token.extend(chars.next());
+        }
+        part.0.push_str(&token);
+        part.1 += token.chars().map(char_width).sum::<usize>();
+        let next = chars.peek().copied();
+        let forced = token == "\\n" && next.is_some();
+        let breakable = match next {
+            None => false,
+            Some(next) if next == '\\' || is_no_break_before(next) || is_no_break_after(c) => false,
+            Some(next) => match c {
+                ' ' => next != ' ',
+                '-' => next.is_alphabetic(),
+                '|' => next != '|',
+                _ => char_width(c) == 2 || char_width(next) == 2,
+            },
+        };
+        if forced || breakable {
+            let (text, part_width) = std::mem::take(&mut part);
+            parts.push((text, part_width, forced));
+        }
+    }
+    parts.push((part.0, part.1, false));
+
+    let mut lines = vec![];
+    let mut line = (String::new(), 0);
+    for (text, part_width, forced) in parts {
+        if width.is_some_and(|width| line.1 + part_width > width) && !line.0.is_empty() {
+            lines.push(std::mem::take(&mut line).0);
+        }
+        line.0.push_str(&text);
+        line.1 += part_width;
+        if forced {
+            lines.push(std::mem::take(&mut line).0);
+        }
+    }
+    lines.push(line.0);
+    lines
+}
+
+/// Write a keyword like `msgid` with its value, on one line if it fits, otherwise with an empty first string like `msgmerge`.
+fn write_field<W: Write>(writer: &mut BufWriter<W>, prefix: &str, keyword: &str, value: &str, width: Option<usize>) -> Result<(), std::io::Error> {
+    let lines = wrap_po_string(&escape_po_string(value), width.map(|width| width.saturating_sub(prefix.len() + 2)));
+    let line_width = |line: &str| line.chars().map(char_width).sum::<usize>();
+    match lines.as_slice() {
+        [line] if width.is_none_or(|width| prefix.len() + keyword.len() + 3 + line_width(line) <= width) => writeln!(writer, "{prefix}{keyword} \"{line}\""),
+        _ => {
+            writeln!(writer, "{prefix}{keyword} \"\"")?;
+            for line in lines {
+                writeln!(writer, "{prefix}\"{line}\"")?;
+            }
+            Ok(())
+        },
+    }
+}
+
+/// Write `#:` reference comments in the given style, as many references per line as the width allows. Like `msgmerge`,
+/// references are wrapped at the default width even if strings are not wrapped.
+fn write_references<W: Write>(writer: &mut BufWriter<W>, source: &str, options: &PoSaveOptions) -> Result<(), std::io::Error> {
+    let width = options.wrap_width.unwrap_or(DEFAULT_WRAP_WIDTH);
+    let mut references: Vec<&str> = vec![];
+    for reference in source.split_whitespace() {
+        let reference = match options.locations {
+            PoLocationStyle::Full => reference,
+            PoLocationStyle::File => reference.rsplit_once(':')
+                .filter(|(_, line)| !line.is_empty() && line.chars().all(|c| c.is_ascii_digit()))
+                .map_or(reference, |(file, _)| file),
+            PoLocationStyle::Never => return Ok(()),
+        };
+        if !references.contains(&reference) {
+            references.push(reference);
+        }
+    }
+    let mut line = String::new();
+    for reference in references {
+        if !line.is_empty() && line.chars().count() + 1 + reference.chars().count() > width {
+            writeln!(writer, "{line}")?;
+            line.clear();
+        }
+        if line.is_empty() {
+            line.push_str("#:");
+        }
+        line.push(' ');
+        line.push_str(reference);
+    }
+    if !line.is_empty() {
+        writeln!(writer, "{line}")?;
+    }
+    Ok(())
+}
+
+/// Write a message after an empty line, `prefix` is `#~ ` for obsolete messages.
+fn write_message<W: Write>(writer: &mut BufWriter<W>, message: &dyn MessageView, prefix: &str, options: &PoSaveOptions) -> Result<(), std::io::Error> {
+    writeln!(writer)?;
+    if !message.translator_comments().is_empty() {
+        for line in message.translator_comments().split('\n') {
+            match line.is_empty() {
+                true => writeln!(writer, "#")?,
+                false => writeln!(writer, "# {line}")?,
+            }
+        }
+    }
+    if !message.extracted_comments().is_empty() {
+        for line in message.extracted_comments().split('\n') {
+            writeln!(writer, "#. {line}")?;
+        }
+    }
+    write_references(writer, message.source(), options)?;
+    if !message.flags().is_empty() {
+        // `fuzzy` always comes first like `msgmerge` does, since it is the flag that comes and goes.
+        let flags = message.flags().iter().filter(|flag| *flag == "fuzzy")
+            .chain(message.flags().iter().filter(|flag| *flag != "fuzzy"))
+            .map(String::as_str).collect::<Vec<_>>();
+        writeln!(writer, "#, {}", flags.join(", "))?;
+    }
+    if let Some(msgctxt) = message.msgctxt() {
+        write_field(writer, prefix, "msgctxt", msgctxt, options.wrap_width)?;
+    }
+    write_field(writer, prefix, "msgid", message.msgid(), options.wrap_width)?;
+    if message.is_plural() {
+        write_field(writer, prefix, "msgid_plural", message.msgid_plural().unwrap(), options.wrap_width)?;
+        for (index, form) in message.msgstr_plural().unwrap().iter().enumerate() {
+            write_field(writer, prefix, &format!("msgstr[{index}]"), form, options.wrap_width)?;
+        }
+    } else {
+        write_field(writer, prefix, "msgstr", message.msgstr().unwrap(), options.wrap_width)?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::super::common::MessageStats;
@@ -141,9 +834,232 @@ msgstr ""
             finished: 2,
             unfinished: 1,
             vanished: 0,
-            obsolete: 0,
-            fuzzy: 1,
+            obsolete: 1,
+            fuzzy: 0,
+            translated_words: 19,
+            untranslated_words: 1,
+            translated_chars: 86,
+            untranslated_chars: 7,
         });
-        assert_eq!(po.get_message_stats().completeness_percentage(None), 2.0 / 4.0 * 100.0);
+        assert_eq!(po.get_message_stats().completeness_percentage(None), 2.0 / 3.0 * 100.0);
+        assert_eq!(po.obsolete.len(), 1);
+        assert_eq!(po.obsolete[0].msgid(), "TV band");
+        assert_eq!(po.obsolete[0].msgstr().unwrap(), "电视频段");
+        assert!(po.obsolete[0].is_fuzzy());
+    }
+
+    pub const TEST_POT_CONTENT: &str = r#"msgid ""
+msgstr ""
+"POT-Creation-Date: 2025-01-01 00:00+0800\n"
+"MIME-Version: 1.0\n"
+"Content-Type: text/plain; charset=UTF-8\n"
+"Content-Transfer-Encoding: 8bit\n"
+
+#: ../../widget/mainwindow.ui:17
+msgctxt "ts::SampleContext|"
+msgid "A friend in need is a friend indeed"
+msgstr ""
+
+#: ../../widget/mainwindow.cpp:70
+msgctxt "ts::SampleContext|"
+msgid "Software engineer using mouse to manipulate the cursor on the screens"
+msgstr ""
+
+#: ../../widget/mainwindow.cpp:80
+msgctxt "ts::SampleContext|"
+msgid "Scotland"
+msgstr ""
+"#;
+
+    #[test]
+    fn tst_merge_with_template() {
+        let po = Po::load_from_str(TEST_ZH_CN_PO_CONTENT).unwrap();
+        let template = Po::load_from_str(TEST_POT_CONTENT).unwrap();
+
+        let (merged, stats) = po.merge_with_template(&template, true);
+        assert_eq!(stats, PoMergeStats { exact: 1, fuzzy: 1, untranslated: 1, obsolete: 1 });
+        assert_eq!(merged.get_language(), "zh_CN");
+        assert_eq!(merged.inner.metadata.pot_creation_date, "2025-01-01 00:00+0800");
+        let mut msgs = merged.inner.messages();
+        let msg = msgs.next().unwrap();
+        assert_eq!(msg.msgstr().unwrap(), "海内存知己");
+        assert!(!msg.is_fuzzy());
+        let msg = msgs.next().unwrap();
+        assert_eq!(msg.msgstr().unwrap(), "软件开发工程师在使用鼠标操作屏幕上的光标");
+        assert!(msg.is_fuzzy());
+        assert_eq!(msg.source(), "../../widget/mainwindow.cpp:70");
+        let msg = msgs.next().unwrap();
+        assert_eq!(msg.msgid(), "Scotland");
+        assert_eq!(msg.msgstr().unwrap(), "");
+        assert!(msgs.next().is_none());
+        assert_eq!(merged.obsolete.len(), 2);
+        assert_eq!(merged.obsolete[0].msgid(), "TV band");
+        assert_eq!(merged.obsolete[1].msgid(), "Software engineer using mouse to manipulate the cursor on the screen");
+
+        let (merged, stats) = po.merge_with_template(&template, false);
+        assert_eq!(stats, PoMergeStats { exact: 1, fuzzy: 0, untranslated: 2, obsolete: 1 });
+        let mut writer = BufWriter::new(Vec::new());
+        merged.write_into(&mut writer, &PoSaveOptions::default()).unwrap();
+        let content = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert!(content.ends_with(r#"#~ msgctxt "ts::SampleContext|"
+#~ msgid "Software engineer using mouse to manipulate the cursor on the screen"
+#~ msgstr "软件开发工程师在使用鼠标操作屏幕上的光标"
+"#));
+    }
+
+    #[test]
+    fn tst_new_translation_headers() {
+        let po = Po::load_from_str(&TEST_ZH_CN_PO_CONTENT.replace("msgstr \"\"\n\"MIME-Version", "msgstr \"\"\n\"Project-Id-Version: sample\\n\"\n\"Report-Msgid-Bugs-To: old@example.com\\n\"\n\"Language-Team: Chinese (China)\\n\"\n\"MIME-Version")).unwrap();
+        assert_eq!(po.get_extra_header("Report-Msgid-Bugs-To"), Some("old@example.com"));
+        assert_eq!(po.get_extra_header("X-Qt-Contexts"), Some("true"));
+
+        let new_po = po.new_translation("zh_TW", &PoHeaderOptions::default());
+        assert_eq!(new_po.inner.metadata.language_team, "Chinese (Taiwan)");
+        assert_eq!(new_po.get_extra_header("Report-Msgid-Bugs-To"), Some("old@example.com"));
+        assert_eq!(new_po.get_message_stats().finished, 0);
+
+        let new_po = po.new_translation("fr", &PoHeaderOptions { report_msgid_bugs_to: Some("https://bugs.example.com".to_string()) });
+        assert_eq!(new_po.inner.metadata.language_team, "French");
+        assert_eq!(new_po.inner.metadata.plural_rules.dump(), "nplurals=2; plural=(n > 1);");
+        let mut writer = BufWriter::new(Vec::new());
+        new_po.write_into(&mut writer, &PoSaveOptions::default()).unwrap();
+        let content = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert!(content.starts_with(r#"msgid ""
+msgstr ""
+"Project-Id-Version: sample\n"
+"Report-Msgid-Bugs-To: https://bugs.example.com\n"
+"Language-Team: French\n"
+"#));
+        assert!(content.contains("\"X-Qt-Contexts: true\\n\"\n\n#: "));
+        let reloaded = Po::load_from_str(&content).unwrap();
+        assert_eq!(reloaded.extra_headers, new_po.extra_headers);
+        assert_eq!(reloaded.get_message_stats(), new_po.get_message_stats());
+
+        let template = TEST_ZH_CN_PO_CONTENT.replace("msgstr \"\"\n\"MIME-Version", "msgstr \"\"\n\"Plural-Forms: nplurals=INTEGER; plural=EXPRESSION;\\n\"\n\"MIME-Version");
+        assert!(Po::load_from_str(&template).is_ok());
+    }
+
+    #[test]
+    fn tst_obsolete_entries_round_trip() {
+        let content = r#"msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+"Language: zh_CN\n"
+"Plural-Forms: nplurals=1; plural=0;\n"
+
+#: main.cpp:1
+msgid "Open"
+msgstr "打开"
+
+# Removed in 2.0
+#, fuzzy
+#~ msgctxt "menu"
+#~ msgid "Close"
+#~ msgstr "关闭"
+
+#~ msgid "%d file"
+#~ msgid_plural "%d files"
+#~ msgstr[0] "%d 个文件"
+"#;
+        let po = Po::load_from_str(content).unwrap();
+        assert_eq!(po.message_count(), 1);
+        assert_eq!(po.obsolete.len(), 2);
+        assert_eq!(po.obsolete[0].translator_comments(), "Removed in 2.0");
+        assert_eq!(po.obsolete[0].msgctxt(), Some("menu"));
+        assert_eq!(po.obsolete[1].msgstr_plural().unwrap(), &vec!["%d 个文件".to_string()]);
+
+        let mut writer = BufWriter::new(Vec::new());
+        po.write_into(&mut writer, &PoSaveOptions::default()).unwrap();
+        assert_eq!(String::from_utf8(writer.into_inner().unwrap()).unwrap(), content);
+
+        // Obsolete messages used again by the template are brought back.
+        let template = Po::load_from_str(&content.replace("msgid \"Open\"", "msgctxt \"menu\"\nmsgid \"Close\"")).unwrap();
+        let (merged, stats) = po.merge_with_template(&template, false);
+        assert_eq!(stats, PoMergeStats { exact: 0, fuzzy: 1, untranslated: 0, obsolete: 1 });
+        assert_eq!(merged.inner.messages().next().unwrap().msgstr().unwrap(), "关闭");
+        assert_eq!(merged.obsolete.iter().map(|message| message.msgid()).collect::<Vec<_>>(), vec!["%d file", "Open"]);
+    }
+
+    #[test]
+    fn tst_load_po_with_declared_charset() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let po_file = temp_dir.path().join("zh_CN.po");
+        let content = TEST_ZH_CN_PO_CONTENT.replace("charset=UTF-8", "charset=GBK");
+        fs::write(&po_file, encoding_rs::GBK.encode(&content).0).unwrap();
+        let po = Po::load_from_file(&po_file).unwrap();
+        assert_eq!(po.flat_messages(), Po::load_from_str(TEST_ZH_CN_PO_CONTENT).unwrap().flat_messages());
+        let mut writer = BufWriter::new(Vec::new());
+        po.write_into(&mut writer, &PoSaveOptions::default()).unwrap();
+        assert!(String::from_utf8(writer.into_inner().unwrap()).unwrap().contains("charset=UTF-8"));
+
+        fs::write(&po_file, encoding_rs::GBK.encode(&content.replace("charset=GBK", "charset=UTF-8")).0).unwrap();
+        assert!(matches!(Po::load_from_file(&po_file), Err(PoLoadError::DecodeCharset(charset)) if charset == "UTF-8"));
+        fs::write(&po_file, encoding_rs::GBK.encode(&content.replace("charset=GBK", "charset=NO-SUCH")).0).unwrap();
+        assert!(matches!(Po::load_from_file(&po_file), Err(PoLoadError::DecodeCharset(charset)) if charset == "NO-SUCH"));
+    }
+
+    #[test]
+    fn tst_wrap_po_string() {
+        assert_eq!(wrap_po_string("a b c", Some(3)), ["a ", "b c"]);
+        assert_eq!(wrap_po_string("one\\ntwo\\n", None), ["one\\n", "two\\n"]);
+        assert_eq!(wrap_po_string("Zurück-Button (see: here)", Some(13)), ["Zurück-", "Button (see: ", "here)"]);
+        assert_eq!(wrap_po_string("很长的中文，句子", Some(10)), ["很长的中", "文，句子"]);
+        assert_eq!(wrap_po_string("say \\\"hi\\\"", Some(6)), ["say \\\"hi\\\""]);
+        assert_eq!(wrap_po_string("unbreakable", Some(4)), ["unbreakable"]);
+    }
+
+    #[test]
+    fn tst_po_save_options() {
+        let po = Po::load_from_str(TEST_ZH_CN_PO_CONTENT).unwrap();
+        let template = Po::load_from_str(&TEST_ZH_CN_PO_CONTENT.replace("msgid \"Software engineer", "msgid \"Software developer")).unwrap();
+        let (merged, _) = po.merge_with_template(&template, false);
+        let write = |options: &PoSaveOptions| {
+            let mut writer = BufWriter::new(Vec::new());
+            merged.write_into(&mut writer, options).unwrap();
+            String::from_utf8(writer.into_inner().unwrap()).unwrap()
+        };
+
+        let content = write(&PoSaveOptions { wrap_width: Some(40), ..Default::default() });
+        assert!(content.contains("msgid \"\"\n\"Software developer using mouse to \"\n\"manipulate the cursor on the screen\"\n"));
+        assert!(content.contains("#~ msgid \"\"\n#~ \"Software engineer using mouse to \"\n"));
+        assert!(content.contains("\"Content-Type: text/plain; \"\n\"charset=UTF-8\\n\"\n"));
+
+        let content = write(&PoSaveOptions { wrap_width: None, no_obsolete: true, locations: PoLocationStyle::File });
+        assert!(content.contains("msgid \"Software developer using mouse to manipulate the cursor on the screen\"\n"));
+        assert!(!content.contains("#~ "));
+        assert!(content.contains("#: ../../widget/mainwindow.ui ../../widget/mainwindow.cpp\n"));
+
+        let content = write(&PoSaveOptions { locations: PoLocationStyle::Never, ..Default::default() });
+        assert!(!content.contains("#: "));
+        assert_eq!(Po::load_from_str(&content).unwrap().get_message_stats(), merged.get_message_stats());
+    }
+
+    #[test]
+    fn tst_format_po_date() {
+        assert_eq!(format_po_date(UNIX_EPOCH), "1970-01-01 00:00+0000");
+        assert_eq!(format_po_date(UNIX_EPOCH + std::time::Duration::from_secs(1709210096)), "2024-02-29 12:34+0000");
+    }
+
+    #[test]
+    fn tst_manual_review_marker() {
+        let mut po = Po::load_from_str(TEST_ZH_CN_PO_CONTENT).unwrap();
+        let mut message = po.inner.messages_mut().next().unwrap();
+        assert!(!is_message_manually_reviewed(&message));
+        set_message_manually_reviewed(&mut message, true);
+        assert!(is_message_manually_reviewed(&message));
+        set_message_manually_reviewed(&mut message, true);
+        assert_eq!(message.flags().count(), 1);
+        set_message_manually_reviewed(&mut message, false);
+        assert!(!is_message_manually_reviewed(&message));
+
+        let po = Po::load_from_str(r#"msgid ""
+msgstr ""
+"Language: zh_TW\n"
+
+#, zhconv-manual
+msgid "England"
+msgstr "英格蘭"
+"#).unwrap();
+        assert!(is_message_manually_reviewed(po.inner.messages().next().unwrap()));
     }
 }
\ No newline at end of file
@@ -0,0 +1,106 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Subtags are taken from ISO 639-1 (plus the 3-letter ISO 639-2/3 codes of CLDR and glibc locales), ISO 15924,
+// ISO 3166-1 and UN M.49, and the modifiers of glibc locales.
+
+/// ISO 639 language subtags.
+const LANGUAGES: &[&str] = &[
+    // ISO 639-1.
+    "aa", "ab", "ae", "af", "ak", "am", "an", "ar", "as", "av", "ay", "az", "ba", "be", "bg", "bh", "bi", "bm", "bn", "bo",
+    "br", "bs", "ca", "ce", "ch", "co", "cr", "cs", "cu", "cv", "cy", "da", "de", "dv", "dz", "ee", "el", "en", "eo", "es",
+    "et", "eu", "fa", "ff", "fi", "fj", "fo", "fr", "fy", "ga", "gd", "gl", "gn", "gu", "gv", "ha", "he", "hi", "ho", "hr",
+    "ht", "hu", "hy", "hz", "ia", "id", "ie", "ig", "ii", "ik", "io", "is", "it", "iu", "ja", "jv", "ka", "kg", "ki", "kj",
+    "kk", "kl", "km", "kn", "ko", "kr", "ks", "ku", "kv", "kw", "ky", "la", "lb", "lg", "li", "ln", "lo", "lt", "lu", "lv",
+    "mg", "mh", "mi", "mk", "ml", "mn", "mr", "ms", "mt", "my", "na", "nb", "nd", "ne", "ng", "nl", "nn", "no", "nr", "nv",
+    "ny", "oc", "oj", "om", "or", "os", "pa", "pi", "pl", "ps", "pt", "qu", "rm", "rn", "ro", "ru", "rw", "sa", "sc", "sd",
+    "se", "sg", "si", "sk", "sl", "sm", "sn", "so", "sq", "sr", "ss", "st", "su", "sv", "sw", "ta", "te", "tg", "th", "ti",
+    "tk", "tl", "tn", "to", "tr", "ts", "tt", "tw", "ty", "ug", "uk", "ur", "uz", "ve", "vi", "vo", "wa", "wo", "xh", "yi",
+    "yo", "za", "zh", "zu",
+    // Deprecated ISO 639-1 codes still used by Java and older projects.
+    "in", "iw", "ji",
+    // ISO 639-2/3 codes of CLDR and glibc locales.
+    "ach", "agq", "ang", "anp", "arn", "asa", "ast", "ayc", "bas", "bem", "ber", "bez", "bho", "brx", "byn", "ccp", "ceb",
+    "cgg", "chr", "ckb", "crh", "csb", "dav", "dje", "doi", "dsb", "dua", "dyo", "ebu", "ewo", "fil", "frp", "fur", "gez",
+    "gsw", "guz", "hak", "haw", "hif", "hne", "hsb", "jgo", "jmc", "kab", "kam", "kde", "kea", "kgp", "khq", "kkj", "kln",
+    "kmr", "kok", "ksb", "ksf", "ksh", "lag", "lij", "lkt", "lrc", "ltg", "luo", "luy", "lzh", "mai", "mas", "mer", "mfe",
+    "mgh", "mgo", "mhr", "miq", "mjw", "mni", "mua", "mzn", "nan", "naq", "nds", "nhn", "niu", "nmg", "nnh", "nso", "nus",
+    "nyn", "pcm", "pms", "quz", "rof", "rwk", "sah", "saq", "sat", "sbp", "sco", "seh", "ses", "sgs", "shi", "shn", "shs",
+    "sid", "sma", "smj", "smn", "sms", "szl", "tcy", "teo", "tet", "the", "tig", "tok", "tpi", "twq", "tzm", "unm", "vai",
+    "vun", "wae", "wal", "xog", "yav", "yrl", "yue", "yuw", "zgh",
+];
+
+/// ISO 15924 script subtags in use by locales.
+const SCRIPTS: &[&str] = &[
+    "Adlm", "Arab", "Armn", "Beng", "Bopo", "Cans", "Cher", "Cyrl", "Deva", "Ethi", "Geor", "Grek", "Gujr", "Guru", "Hang",
+    "Hani", "Hans", "Hant", "Hebr", "Hira", "Jpan", "Kana", "Khmr", "Knda", "Kore", "Laoo", "Latn", "Mlym", "Mong", "Mtei",
+    "Mymr", "Nkoo", "Olck", "Orya", "Rohg", "Sinh", "Taml", "Telu", "Tfng", "Thaa", "Thai", "Tibt", "Vaii", "Yiii",
+];
+
+/// ISO 3166-1 region subtags, plus `XK` (Kosovo) which CLDR uses.
+const REGIONS: &[&str] = &[
+    "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX", "AZ", "BA", "BB", "BD", "BE",
+    "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS", "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD",
+    "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN", "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM",
+    "DO", "DZ", "EC", "EE", "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR", "GA", "GB", "GD", "GE", "GF",
+    "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY", "HK", "HM", "HN", "HR", "HT", "HU",
+    "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR", "IS", "IT", "JE", "JM", "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN",
+    "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC", "LI", "LK", "LR", "LS", "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME",
+    "MF", "MG", "MH", "MK", "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA",
+    "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG", "PH", "PK", "PL", "PM",
+    "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW", "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI",
+    "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS", "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK",
+    "TL", "TM", "TN", "TO", "TR", "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+    "VN", "VU", "WF", "WS", "XK", "YE", "YT", "ZA", "ZM", "ZW",
+];
+
+/// UN M.49 area subtags in use by locales, e.g. `es_419` for Latin American Spanish.
+const AREAS: &[&str] = &["001", "150", "419"];
+
+/// Modifiers of glibc locales, e.g. `sr@latin`.
+const MODIFIERS: &[&str] = &["abegede", "cyrillic", "devanagari", "euro", "hebrew", "iqtelif", "latin", "saaho", "valencia"];
+
+/// Whether the given language code is made of known subtags: a language, optionally followed by a script and a region
+/// separated by `_` or `-`, and a glibc modifier, e.g. `zh_CN`, `pt-BR`, `zh-Hans`, `zh_Hant_TW`, `es_419` or `sr@latin`.
+///
+/// Subtags must be in their usual case, `zh_cn` and `zh-hans` are not accepted since translation files are looked up by
+/// exact file names.
+pub fn is_valid_language_code(language_code: &str) -> bool {
+    let (language_code, modifier) = match language_code.split_once('@') {
+        Some((language_code, modifier)) => (language_code, Some(modifier)),
+        None => (language_code, None),
+    };
+    if modifier.is_some_and(|modifier| !MODIFIERS.contains(&modifier)) {
+        return false;
+    }
+    let mut subtags = language_code.split(['_', '-']);
+    if !subtags.next().is_some_and(|language| LANGUAGES.contains(&language)) {
+        return false;
+    }
+    let mut subtags = subtags.peekable();
+    subtags.next_if(|script| SCRIPTS.contains(script));
+    subtags.next_if(|region| REGIONS.contains(region) || AREAS.contains(region));
+    subtags.next().is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_is_valid_language_code() {
+        for language_code in ["en", "zh_CN", "pt-BR", "zh-Hans", "zh_Hant_TW", "es_419", "sr@latin", "ca@valencia", "kab", "ast", "in"] {
+            assert!(is_valid_language_code(language_code), "{language_code}");
+        }
+        for language_code in ["", "EN", "zh_cn", "zh-hans", "app", "xx", "en_XX", "zh_CN_Hans", "en_US_US", "sr@", "sr@foo", "backup"] {
+            assert!(!is_valid_language_code(language_code), "{language_code}");
+        }
+        for list in [LANGUAGES, SCRIPTS, REGIONS] {
+            let mut sorted = list.to_vec();
+            sorted.sort();
+            sorted.dedup();
+            assert_eq!(sorted.len(), list.len());
+        }
+    }
+}
@@ -0,0 +1,159 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Plural forms are taken from the GNU gettext manual and the Unicode CLDR plural rules, using the
+// same expressions Transifex writes into the PO files it generates.
+
+const PLURAL_ONE_FORM: &str = "nplurals=1; plural=0;";
+const PLURAL_NOT_ONE: &str = "nplurals=2; plural=(n != 1);";
+const PLURAL_GREATER_THAN_ONE: &str = "nplurals=2; plural=(n > 1);";
+const PLURAL_EAST_SLAVIC: &str = "nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);";
+const PLURAL_CZECH: &str = "nplurals=3; plural=(n==1) ? 0 : (n>=2 && n<=4) ? 1 : 2;";
+
+#[derive(Debug, PartialEq)]
+pub struct LanguageTeam {
+    /// Language code in the form used by translation file names, e.g. `zh_CN`.
+    pub code: &'static str,
+    /// Value of the `Language-Team` header.
+    pub name: &'static str,
+    /// Value of the `Plural-Forms` header.
+    pub plural_forms: &'static str,
+}
+
+impl LanguageTeam {
+    /// Number of plural forms of the language.
+    pub fn nplurals(&self) -> usize {
+        parse_nplurals(self.plural_forms).unwrap_or(1)
+    }
+}
+
+/// The `nplurals` of a `Plural-Forms` value like `nplurals=2; plural=(n != 1);`.
+pub fn parse_nplurals(plural_forms: &str) -> Option<usize> {
+    plural_forms.split(';')
+        .find_map(|field| field.trim().strip_prefix("nplurals")?.trim_start().strip_prefix('='))
+        .and_then(|nplurals| nplurals.trim().parse().ok())
+}
+
+const fn team(code: &'static str, name: &'static str, plural_forms: &'static str) -> LanguageTeam {
+    LanguageTeam { code, name, plural_forms }
+}
+
+const LANGUAGE_TEAMS: &[LanguageTeam] = &[
+    team("af", "Afrikaans", PLURAL_NOT_ONE),
+    team("am", "Amharic", PLURAL_GREATER_THAN_ONE),
+    team("ar", "Arabic", "nplurals=6; plural=(n==0 ? 0 : n==1 ? 1 : n==2 ? 2 : n%100>=3 && n%100<=10 ? 3 : n%100>=11 && n%100<=99 ? 4 : 5);"),
+    team("ast", "Asturian", PLURAL_NOT_ONE),
+    team("az", "Azerbaijani", PLURAL_NOT_ONE),
+    team("be", "Belarusian", PLURAL_EAST_SLAVIC),
+    team("bg", "Bulgarian", PLURAL_NOT_ONE),
+    team("bn", "Bengali", PLURAL_NOT_ONE),
+    team("bo", "Tibetan", PLURAL_ONE_FORM),
+    team("bs", "Bosnian", PLURAL_EAST_SLAVIC),
+    team("ca", "Catalan", PLURAL_NOT_ONE),
+    team("cs", "Czech", PLURAL_CZECH),
+    team("da", "Danish", PLURAL_NOT_ONE),
+    team("de", "German", PLURAL_NOT_ONE),
+    team("el", "Greek", PLURAL_NOT_ONE),
+    team("en", "English", PLURAL_NOT_ONE),
+    team("en_AU", "English (Australia)", PLURAL_NOT_ONE),
+    team("en_GB", "English (United Kingdom)", PLURAL_NOT_ONE),
+    team("en_US", "English (United States)", PLURAL_NOT_ONE),
+    team("eo", "Esperanto", PLURAL_NOT_ONE),
+    team("es", "Spanish", PLURAL_NOT_ONE),
+    team("et", "Estonian", PLURAL_NOT_ONE),
+    team("eu", "Basque", PLURAL_NOT_ONE),
+    team("fa", "Persian", PLURAL_GREATER_THAN_ONE),
+    team("fi", "Finnish", PLURAL_NOT_ONE),
+    team("fil", "Filipino", PLURAL_GREATER_THAN_ONE),
+    team("fr", "French", PLURAL_GREATER_THAN_ONE),
+    team("gl", "Galician", PLURAL_NOT_ONE),
+    team("he", "Hebrew", PLURAL_NOT_ONE),
+    team("hi", "Hindi", PLURAL_NOT_ONE),
+    team("hr", "Croatian", PLURAL_EAST_SLAVIC),
+    team("hu", "Hungarian", PLURAL_NOT_ONE),
+    team("hy", "Armenian", PLURAL_GREATER_THAN_ONE),
+    team("id", "Indonesian", PLURAL_ONE_FORM),
+    team("it", "Italian", PLURAL_NOT_ONE),
+    team("ja", "Japanese", PLURAL_ONE_FORM),
+    team("ka", "Georgian", PLURAL_NOT_ONE),
+    team("kab", "Kabyle", PLURAL_GREATER_THAN_ONE),
+    team("kk", "Kazakh", PLURAL_NOT_ONE),
+    team("km", "Khmer", PLURAL_ONE_FORM),
+    team("kn", "Kannada", PLURAL_GREATER_THAN_ONE),
+    team("ko", "Korean", PLURAL_ONE_FORM),
+    team("ku", "Kurdish", PLURAL_NOT_ONE),
+    team("ky", "Kyrgyz", PLURAL_NOT_ONE),
+    team("lo", "Lao", PLURAL_ONE_FORM),
+    team("lt", "Lithuanian", "nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n%10>=2 && (n%100<10 || n%100>=20) ? 1 : 2);"),
+    team("lv", "Latvian", "nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n != 0 ? 1 : 2);"),
+    team("mk", "Macedonian", "nplurals=2; plural=(n%10==1 && n%100!=11) ? 0 : 1;"),
+    team("ml", "Malayalam", PLURAL_NOT_ONE),
+    team("mn", "Mongolian", PLURAL_NOT_ONE),
+    team("mr", "Marathi", PLURAL_NOT_ONE),
+    team("ms", "Malay", PLURAL_ONE_FORM),
+    team("my", "Burmese", PLURAL_ONE_FORM),
+    team("nb", "Norwegian Bokmål", PLURAL_NOT_ONE),
+    team("ne", "Nepali", PLURAL_NOT_ONE),
+    team("nl", "Dutch", PLURAL_NOT_ONE),
+    team("pa", "Punjabi", PLURAL_GREATER_THAN_ONE),
+    team("pl", "Polish", "nplurals=3; plural=(n==1 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);"),
+    team("pt", "Portuguese", PLURAL_GREATER_THAN_ONE),
+    team("pt_BR", "Portuguese (Brazil)", PLURAL_GREATER_THAN_ONE),
+    team("ro", "Romanian", "nplurals=3; plural=(n==1 ? 0 : (n==0 || (n%100>0 && n%100<20)) ? 1 : 2);"),
+    team("ru", "Russian", PLURAL_EAST_SLAVIC),
+    team("si", "Sinhala", PLURAL_GREATER_THAN_ONE),
+    team("sk", "Slovak", PLURAL_CZECH),
+    team("sl", "Slovenian", "nplurals=4; plural=(n%100==1 ? 0 : n%100==2 ? 1 : n%100==3 || n%100==4 ? 2 : 3);"),
+    team("sq", "Albanian", PLURAL_NOT_ONE),
+    team("sr", "Serbian", PLURAL_EAST_SLAVIC),
+    team("sv", "Swedish", PLURAL_NOT_ONE),
+    team("sw", "Swahili", PLURAL_NOT_ONE),
+    team("ta", "Tamil", PLURAL_NOT_ONE),
+    team("te", "Telugu", PLURAL_NOT_ONE),
+    team("th", "Thai", PLURAL_ONE_FORM),
+    team("tr", "Turkish", PLURAL_GREATER_THAN_ONE),
+    team("ug", "Uyghur", PLURAL_NOT_ONE),
+    team("uk", "Ukrainian", PLURAL_EAST_SLAVIC),
+    team("ur", "Urdu", PLURAL_NOT_ONE),
+    team("uz", "Uzbek", PLURAL_NOT_ONE),
+    team("vi", "Vietnamese", PLURAL_ONE_FORM),
+    team("zh_CN", "Chinese (China)", PLURAL_ONE_FORM),
+    team("zh_HK", "Chinese (Hong Kong)", PLURAL_ONE_FORM),
+    team("zh_TW", "Chinese (Taiwan)", PLURAL_ONE_FORM),
+];
+
+/// Find the language team information of the given language code.
+///
+/// Both `zh_TW` and `zh-TW` style codes are accepted. If the code carries a region that is not
+/// listed, the entry of the base language is used (e.g. `es_MX` uses `es`).
+pub fn lookup_language_team(language_code: &str) -> Option<&'static LanguageTeam> {
+    let language_code = language_code.replace('-', "_");
+    let find = |code: &str| LANGUAGE_TEAMS.iter().find(|team| team.code.eq_ignore_ascii_case(code));
+    find(&language_code).or_else(|| {
+        let (base_language, _) = language_code.split_once('_')?;
+        find(base_language)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_lookup_language_team() {
+        assert_eq!(lookup_language_team("zh_TW").unwrap().name, "Chinese (Taiwan)");
+        assert_eq!(lookup_language_team("zh-HK").unwrap().name, "Chinese (Hong Kong)");
+        assert_eq!(lookup_language_team("es_MX").unwrap().code, "es");
+        assert_eq!(lookup_language_team("pt_BR").unwrap().plural_forms, PLURAL_GREATER_THAN_ONE);
+        assert!(lookup_language_team("xx").is_none());
+        assert_eq!(lookup_language_team("ar").unwrap().nplurals(), 6);
+        assert_eq!(lookup_language_team("zh_CN").unwrap().nplurals(), 1);
+        assert_eq!(parse_nplurals("nplurals = 2; plural=(n != 1);"), Some(2));
+        assert_eq!(parse_nplurals("nplurals=INTEGER; plural=EXPRESSION;"), None);
+        for team in LANGUAGE_TEAMS {
+            let header = format!("Plural-Forms: {}\n", team.plural_forms);
+            assert!(polib::metadata::CatalogMetadata::parse(&header).is_ok(), "{}", team.code);
+        }
+    }
+}
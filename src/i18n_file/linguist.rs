@@ -4,7 +4,9 @@
 
 // Linguist .ts XML file spec: https://doc.qt.io/qt-6/linguist-ts-file-format.html
 
-use std::fs::File;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
 use std::path::Path;
 use thiserror::Error as TeError;
 use serde::{Deserialize, Serialize};
@@ -12,24 +14,65 @@ use quick_xml::DeError;
 use quick_xml::se::SeError;
 use quick_xml::Writer;
 use quick_xml::events::{BytesDecl, BytesText, Event};
-use super::common::MessageStats;
+use super::common::{FlatMessage, MessageState, MessageStats, MANUAL_REVIEW_MARKER};
 
 // ===== TS Basic =====
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename = "TS")]
 pub struct Ts {
-    #[serde(rename = "@language")]
-    pub language: Option<String>,
     #[serde(rename = "@version")]
     pub version: String,
+    #[serde(rename = "@language", skip_serializing_if = "Option::is_none", default)]
+    pub language: Option<String>,
+    #[serde(rename = "@sourcelanguage", skip_serializing_if = "Option::is_none", default)]
+    pub source_language: Option<String>,
     #[serde(rename = "context", default)]
     pub contexts: Vec<Context>,
+    /// How the file was laid out when loaded, so saving it does not change more than its content.
+    #[serde(skip)]
+    pub layout: TsLayout,
+}
+
+/// Layout details of a TS file that are not part of its content.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TsLayout {
+    /// Whether the `language` attribute comes before `version`, as written by earlier versions of this program.
+    pub language_first: bool,
+    pub trailing_newline: bool,
+}
+
+impl Default for TsLayout {
+    /// The layout of files written by Qt Linguist and lupdate.
+    fn default() -> Self {
+        Self { language_first: false, trailing_newline: true }
+    }
+}
+
+impl TsLayout {
+    fn detect(content: &str) -> Self {
+        let start_tag = content.find("<TS")
+            .map(|start| &content[start..])
+            .map(|tag| &tag[..tag.find('>').unwrap_or(tag.len())])
+            .unwrap_or_default();
+        let attribute_position = |name: &str| start_tag.split_whitespace().position(|part| part.starts_with(name));
+        Self {
+            language_first: match (attribute_position("language="), attribute_position("version=")) {
+                (Some(language), Some(version)) => language < version,
+                _ => false,
+            },
+            trailing_newline: content.ends_with('\n'),
+        }
+    }
 }
 
 // === TS Unique ===
 
 impl Ts {
+    /// Versions of the format whose files are written back as they were read, other versions are written in the
+    /// structure of these ones while keeping the `version` attribute.
+    pub const SUPPORTED_VERSIONS: &[&str] = &["2.0", "2.1"];
+
     pub fn clear_finished_messages(&mut self) {
         for context in &mut self.contexts {
             for message in &mut context.messages {
@@ -41,6 +84,88 @@ impl Ts {
             }
         }
     }
+
+    /// Merge contexts with the same name, then sort contexts by name and messages by source text
+    /// and disambiguation, so files written by different tools are in the same order. The file is
+    /// also laid out like the ones written by lupdate.
+    pub fn normalize(&mut self) {
+        self.layout = TsLayout::default();
+        self.merge_same_name_contexts();
+        self.contexts.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+        for context in &mut self.contexts {
+            context.messages.sort_by(|lhs, rhs| (&lhs.source, &lhs.comment).cmp(&(&rhs.source, &rhs.comment)));
+        }
+    }
+
+    /// Merge messages with the same context, source text and disambiguation into the first one.
+    ///
+    /// The best translation among the duplicates is kept, and their locations are consolidated.
+    /// Returns the number of removed messages.
+    pub fn dedupe(&mut self) -> usize {
+        self.merge_same_name_contexts();
+        let mut removed = 0;
+        for context in &mut self.contexts {
+            let mut messages: Vec<Message> = vec![];
+            let mut indexes: HashMap<(String, Option<String>), usize> = HashMap::new();
+            for message in context.messages.drain(..) {
+                let key = (message.source.clone(), message.comment.clone());
+                match indexes.get(&key) {
+                    Some(&index) => {
+                        messages[index].merge_duplicate(message);
+                        removed += 1;
+                    },
+                    None => {
+                        indexes.insert(key, messages.len());
+                        messages.push(message);
+                    },
+                }
+            }
+            context.messages = messages;
+        }
+        removed
+    }
+
+    /// Split into one file per context, each keeps the languages and version of this file.
+    pub fn split_by_context(&self) -> Vec<Ts> {
+        let mut ts = self.clone();
+        ts.merge_same_name_contexts();
+        ts.contexts.into_iter()
+            .map(|context| Ts {
+                version: self.version.clone(),
+                language: self.language.clone(),
+                source_language: self.source_language.clone(),
+                contexts: vec![context],
+                layout: self.layout,
+            })
+            .collect()
+    }
+
+    /// Append contexts of another file into this one, contexts with the same name are merged.
+    pub fn append(&mut self, other: Ts) {
+        self.contexts.extend(other.contexts);
+        self.merge_same_name_contexts();
+    }
+
+    /// Remove all `<location>` elements.
+    pub fn strip_locations(&mut self) {
+        for context in &mut self.contexts {
+            for message in &mut context.messages {
+                message.location.clear();
+            }
+        }
+    }
+
+    /// Move messages of contexts with the same name into the first one of them.
+    fn merge_same_name_contexts(&mut self) {
+        let mut contexts: Vec<Context> = vec![];
+        for context in self.contexts.drain(..) {
+            match contexts.iter_mut().find(|existing| existing.name == context.name) {
+                Some(existing) => existing.messages.extend(context.messages),
+                None => contexts.push(context),
+            }
+        }
+        self.contexts = contexts;
+    }
 }
 
 // === TS Common ===
@@ -59,15 +184,48 @@ impl Ts {
         for context in &self.contexts {
             for message in &context.messages {
                 match message.translation.type_attr {
-                    Some(TranslationType::Unfinished) => rv.unfinished += 1,
+                    Some(TranslationType::Unfinished) => {
+                        rv.unfinished += 1;
+                        rv.count_source_text(&message.source, false);
+                    },
                     Some(TranslationType::Vanished) => rv.vanished += 1,
                     Some(TranslationType::Obsolete) => rv.obsolete += 1,
-                    None => rv.finished += 1,
+                    None => {
+                        rv.finished += 1;
+                        rv.count_source_text(&message.source, true);
+                    },
                 }
             }
         }
         rv
     }
+
+    pub fn flat_messages(&self) -> Vec<FlatMessage> {
+        let mut messages = vec![];
+        for context in &self.contexts {
+            for message in &context.messages {
+                let translation = &message.translation;
+                let translations = match &translation.numerus_forms {
+                    numerus_forms if !numerus_forms.is_empty() => numerus_forms.clone(),
+                    // Same as an empty msgstr of PO files.
+                    _ => vec![translation.value.clone().unwrap_or_default()],
+                };
+                messages.push(FlatMessage {
+                    context: context.name.clone(),
+                    source: message.source.clone(),
+                    disambiguation: message.comment.clone(),
+                    translations,
+                    state: match translation.type_attr {
+                        Some(TranslationType::Unfinished) => MessageState::Unfinished,
+                        Some(TranslationType::Vanished) => MessageState::Vanished,
+                        Some(TranslationType::Obsolete) => MessageState::Obsolete,
+                        None => MessageState::Finished,
+                    },
+                });
+            }
+        }
+        messages
+    }
 }
 
 // === Sub Structs ===
@@ -86,10 +244,14 @@ pub struct Message {
     pub location: Vec<Location>,
     #[serde(rename = "source")]
     pub source: String,
-    #[serde(rename = "translation")]
-    pub translation: Translation,
     #[serde(rename = "comment", skip_serializing_if = "Option::is_none", default)]
     pub comment: Option<String>,
+    #[serde(rename = "extracomment", skip_serializing_if = "Option::is_none", default)]
+    pub extracomment: Option<String>,
+    #[serde(rename = "translatorcomment", skip_serializing_if = "Option::is_none", default)]
+    pub translatorcomment: Option<String>,
+    #[serde(rename = "translation")]
+    pub translation: Translation,
     #[serde(rename = "@numerus", skip_serializing_if = "Option::is_none", default)]
     pub numerus: Option<String>,
 }
@@ -99,6 +261,74 @@ impl Message {
         self.translation.value = Some(translation.to_string());
         self.translation.type_attr = None;
     }
+
+    /// Whether the message carries the manual review marker inside its `<extracomment>`.
+    pub fn is_manually_reviewed(&self) -> bool {
+        self.extracomment.as_ref().is_some_and(|comment| {
+            comment.split_whitespace().any(|word| word == MANUAL_REVIEW_MARKER)
+        })
+    }
+
+    /// Used to pick the best translation among duplicated messages, the greater the better.
+    ///
+    /// Non-empty translations win over empty ones, then manually reviewed ones, then finished ones.
+    fn translation_rank(&self) -> (bool, bool, u8) {
+        let translation = &self.translation;
+        let has_text = translation.value.as_ref().is_some_and(|value| !value.is_empty())
+            || translation.numerus_forms.iter().any(|form| !form.is_empty());
+        let state = match translation.type_attr {
+            None => 2,
+            Some(TranslationType::Unfinished) => 1,
+            Some(TranslationType::Vanished | TranslationType::Obsolete) => 0,
+        };
+        (has_text, self.is_manually_reviewed(), state)
+    }
+
+    /// Merge a duplicate of this message into it, see [`Ts::dedupe`].
+    fn merge_duplicate(&mut self, duplicate: Message) {
+        if duplicate.translation_rank() > self.translation_rank() {
+            let reviewed = duplicate.is_manually_reviewed();
+            self.translation = duplicate.translation;
+            self.set_manually_reviewed(reviewed);
+        }
+        if self.extracomment.is_none() {
+            self.extracomment = duplicate.extracomment;
+        }
+        if self.translatorcomment.is_none() {
+            self.translatorcomment = duplicate.translatorcomment;
+        }
+        if self.numerus.is_none() {
+            self.numerus = duplicate.numerus;
+        }
+        for location in duplicate.location {
+            let exists = self.location.iter()
+                .any(|existing| existing.filename == location.filename && existing.line == location.line);
+            if !exists {
+                self.location.push(location);
+            }
+        }
+    }
+
+    /// Add or remove the manual review marker, other `<extracomment>` content is preserved.
+    pub fn set_manually_reviewed(&mut self, reviewed: bool) {
+        if reviewed == self.is_manually_reviewed() {
+            return;
+        }
+        if reviewed {
+            self.extracomment = Some(match self.extracomment.take() {
+                Some(comment) if !comment.trim().is_empty() => format!("{comment}\n{MANUAL_REVIEW_MARKER}"),
+                _ => MANUAL_REVIEW_MARKER.to_string(),
+            });
+        } else {
+            let remaining = self.extracomment.take().unwrap_or_default()
+                .lines()
+                .map(|line| line.split_whitespace().filter(|word| *word != MANUAL_REVIEW_MARKER).collect::<Vec<_>>().join(" "))
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n");
+            self.extracomment = (!remaining.is_empty()).then_some(remaining);
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -143,10 +373,37 @@ impl<W: std::io::Write> WriterExt for Writer<W> {
     ) -> Result<(), SeError> {
         self.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
         self.write_event(Event::DocType(BytesText::new("TS")))?;
-        self.write_serializable("TS", content)
+        match content.layout.language_first {
+            true => self.write_serializable("TS", &LanguageFirstTs {
+                language: content.language.as_ref(),
+                version: &content.version,
+                source_language: content.source_language.as_ref(),
+                contexts: &content.contexts,
+            })?,
+            false => self.write_serializable("TS", content)?,
+        }
+        if content.layout.trailing_newline {
+            self.get_mut().write_all(b"\n")?;
+        }
+        self.get_mut().flush()?;
+        Ok(())
     }
 }
 
+/// [`Ts`] with the `language` attribute written before `version`.
+#[derive(Serialize)]
+#[serde(rename = "TS")]
+struct LanguageFirstTs<'a> {
+    #[serde(rename = "@language", skip_serializing_if = "Option::is_none")]
+    language: Option<&'a String>,
+    #[serde(rename = "@version")]
+    version: &'a str,
+    #[serde(rename = "@sourcelanguage", skip_serializing_if = "Option::is_none")]
+    source_language: Option<&'a String>,
+    #[serde(rename = "context")]
+    contexts: &'a [Context],
+}
+
 #[derive(TeError, Debug)]
 pub enum TsLoadError {
     #[error("Can not open file")]
@@ -155,6 +412,13 @@ pub enum TsLoadError {
     Serde(#[from] DeError),
 }
 
+/// Options of how to write TS files.
+#[derive(Debug, Default, Clone)]
+pub struct TsSaveOptions {
+    /// Do not write `<location>` elements, for projects that consider them noise in VCS.
+    pub strip_locations: bool,
+}
+
 #[derive(TeError, Debug)]
 pub enum TsSaveError {
     #[error("Can not create file")]
@@ -165,14 +429,13 @@ pub enum TsSaveError {
 
 impl Ts {
     pub fn load_from_file(linguist_ts_file: &Path) -> Result<Ts, TsLoadError> {
-        let file = File::open(linguist_ts_file)?;
-        let file_reader = std::io::BufReader::new(file);
-        Ok(quick_xml::de::from_reader::<_, Ts>(file_reader)?)
+        Self::load_from_str(&fs::read_to_string(linguist_ts_file)?)
     }
 
-    #[cfg(test)]
     pub fn load_from_str(content: &str) -> Result<Ts, TsLoadError> {
-        Ok(quick_xml::de::from_str(content)?)
+        let mut ts: Ts = quick_xml::de::from_str(content)?;
+        ts.layout = TsLayout::detect(content);
+        Ok(ts)
     }
 
     pub fn load_from_file_or_default(linguist_ts_file: &Path, fallback: &Ts, fallback_language_code: &str) -> Result<Ts, TsLoadError> {
@@ -180,16 +443,26 @@ impl Ts {
             let mut clone = fallback.clone();
             clone.set_language(fallback_language_code);
             clone.clear_finished_messages();
-            return Ok(clone);
+            Ok(clone)
         } else {
-            return Self::load_from_file(linguist_ts_file);
+            Self::load_from_file(linguist_ts_file)
         }
     }
 
-    pub fn save_into_file(&self, linguist_ts_file: &Path) -> Result<(), TsSaveError> {
+    pub fn save_into_file(&self, linguist_ts_file: &Path, options: &TsSaveOptions) -> Result<(), TsSaveError> {
         let target_file = File::create(linguist_ts_file)?;
-        let mut writer = Writer::new_with_indent(&target_file, b' ', 4);
-        writer.write_linguist_ts_file(self)?;
+        self.write_into(BufWriter::new(target_file), options)
+    }
+
+    pub fn write_into<W: Write>(&self, writer: W, options: &TsSaveOptions) -> Result<(), TsSaveError> {
+        let mut writer = Writer::new_with_indent(writer, b' ', 4);
+        if options.strip_locations {
+            let mut stripped = self.clone();
+            stripped.strip_locations();
+            writer.write_linguist_ts_file(&stripped)?;
+        } else {
+            writer.write_linguist_ts_file(self)?;
+        }
         Ok(())
     }
 }
@@ -246,7 +519,7 @@ pub mod tests {
         assert_eq!(ts.contexts.len(), 1);
         assert_eq!(ts.contexts[0].name, "ts::SampleContext");
         assert_eq!(ts.contexts[0].messages.len(), 5);
-        assert!(matches!(ts.contexts[0].messages[1].translation.type_attr, None));
+        assert!(ts.contexts[0].messages[1].translation.type_attr.is_none());
         assert!(matches!(ts.contexts[0].messages[2].translation.type_attr, Some(TranslationType::Obsolete)));
         assert!(matches!(ts.contexts[0].messages[3].translation.type_attr, Some(TranslationType::Unfinished)));
         assert_eq!(ts.get_message_stats(), MessageStats {
@@ -255,10 +528,182 @@ pub mod tests {
             vanished: 0,
             obsolete: 1,
             fuzzy: 0,
+            translated_words: 21,
+            untranslated_words: 1,
+            translated_chars: 94,
+            untranslated_chars: 7,
         });
         assert_eq!(ts.get_message_stats().completeness_percentage(None), 3.0 / 4.0 * 100.0);
     }
 
+    #[test]
+    fn tst_manual_review_marker() {
+        let mut ts = Ts::load_from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
+        let message = &mut ts.contexts[0].messages[0];
+        assert!(!message.is_manually_reviewed());
+        message.set_manually_reviewed(true);
+        assert!(message.is_manually_reviewed());
+        assert_eq!(message.extracomment, Some(MANUAL_REVIEW_MARKER.to_string()));
+        message.set_manually_reviewed(false);
+        assert_eq!(message.extracomment, None);
+
+        // Existing developer comments should be kept as-is.
+        message.extracomment = Some("Shown in the title bar".to_string());
+        message.set_manually_reviewed(true);
+        assert_eq!(message.extracomment, Some(format!("Shown in the title bar\n{MANUAL_REVIEW_MARKER}")));
+        message.set_manually_reviewed(false);
+        assert_eq!(message.extracomment, Some("Shown in the title bar".to_string()));
+
+        let marked_ts = Ts::load_from_str(r#"<TS language="zh_TW" version="2.1">
+<context>
+    <name>ts::SampleContext</name>
+    <message>
+        <source>England</source>
+        <extracomment>zhconv-manual</extracomment>
+        <translation>英格蘭</translation>
+    </message>
+</context>
+</TS>"#).unwrap();
+        assert!(marked_ts.contexts[0].messages[0].is_manually_reviewed());
+    }
+
+    #[test]
+    fn tst_normalize() {
+        let mut ts = Ts::load_from_str(r#"<TS version="2.1" language="zh_CN" sourcelanguage="en_US">
+<context>
+    <name>Zebra</name>
+    <message>
+        <source>Open</source>
+        <translation>打开</translation>
+    </message>
+</context>
+<context>
+    <name>Apple</name>
+    <message>
+        <location filename="../src/apple.cpp" line="12"/>
+        <source>Save</source>
+        <translation type="unfinished"></translation>
+    </message>
+</context>
+<context>
+    <name>Zebra</name>
+    <message>
+        <source>Close</source>
+        <translation>关闭</translation>
+        <comment>window</comment>
+    </message>
+</context>
+</TS>"#).unwrap();
+        ts.normalize();
+        let found: Vec<(&str, &str)> = ts.contexts.iter()
+            .flat_map(|context| context.messages.iter().map(|message| (context.name.as_str(), message.source.as_str())))
+            .collect();
+        assert_eq!(found, vec![("Apple", "Save"), ("Zebra", "Close"), ("Zebra", "Open")]);
+
+        let mut content = vec![];
+        ts.write_into(&mut content, &TsSaveOptions::default()).unwrap();
+        let content = String::from_utf8(content).unwrap();
+        assert!(content.contains(r#"<TS version="2.1" language="zh_CN" sourcelanguage="en_US">"#));
+        assert!(content.contains("<source>Close</source>\n            <comment>window</comment>\n            <translation>"));
+        assert!(content.ends_with("</TS>\n"));
+        assert!(content.contains("<location "));
+
+        // Normalizing the written content again should be a no-op.
+        let mut normalized = Ts::load_from_str(&content).unwrap();
+        normalized.normalize();
+        let mut again = vec![];
+        normalized.write_into(&mut again, &TsSaveOptions::default()).unwrap();
+        assert_eq!(String::from_utf8(again).unwrap(), content);
+
+        let mut stripped = vec![];
+        normalized.write_into(&mut stripped, &TsSaveOptions { strip_locations: true }).unwrap();
+        assert!(!String::from_utf8(stripped).unwrap().contains("<location "));
+        assert!(!normalized.contexts[0].messages[0].location.is_empty());
+    }
+
+    #[test]
+    fn tst_keep_layout() {
+        let write = |ts: &Ts| {
+            let mut content = vec![];
+            ts.write_into(&mut content, &TsSaveOptions::default()).unwrap();
+            String::from_utf8(content).unwrap()
+        };
+        let content = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE TS>\n<TS language=\"zh_CN\" version=\"2.1\">\n    <context>\n        <name>MainWindow</name>\n        <message>\n            <source>Open</source>\n            <translation>打开</translation>\n        </message>\n    </context>\n</TS>";
+        let mut ts = Ts::load_from_str(content).unwrap();
+        assert_eq!(ts.layout, TsLayout { language_first: true, trailing_newline: false });
+        assert_eq!(write(&ts), content);
+        assert_eq!(write(&ts.split_by_context()[0]), content);
+
+        let lupdate_content = content.replace(r#"language="zh_CN" version="2.1""#, r#"version="2.1" language="zh_CN""#) + "\n";
+        assert_eq!(Ts::load_from_str(&lupdate_content).unwrap().layout, TsLayout::default());
+        ts.normalize();
+        assert_eq!(write(&ts), lupdate_content);
+    }
+
+    #[test]
+    fn tst_dedupe() {
+        let mut ts = Ts::load_from_str(r#"<TS version="2.1" language="zh_CN">
+<context>
+    <name>MainWindow</name>
+    <message>
+        <location filename="../src/mainwindow.cpp" line="10"/>
+        <source>Open</source>
+        <translation type="unfinished"></translation>
+    </message>
+    <message>
+        <location filename="../src/mainwindow.cpp" line="20"/>
+        <source>Open</source>
+        <comment>menu</comment>
+        <translation>打开菜单</translation>
+    </message>
+</context>
+<context>
+    <name>MainWindow</name>
+    <message>
+        <location filename="../src/mainwindow.cpp" line="10"/>
+        <location filename="../src/mainwindow.cpp" line="30"/>
+        <source>Open</source>
+        <extracomment>zhconv-manual</extracomment>
+        <translation>打开</translation>
+    </message>
+    <message>
+        <location filename="../src/mainwindow.cpp" line="40"/>
+        <source>Open</source>
+        <translation>开启</translation>
+    </message>
+</context>
+</TS>"#).unwrap();
+        assert_eq!(ts.dedupe(), 2);
+        assert_eq!(ts.contexts.len(), 1);
+        let messages = &ts.contexts[0].messages;
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].translation.value, Some("打开".to_string()));
+        assert!(messages[0].translation.type_attr.is_none());
+        assert!(messages[0].is_manually_reviewed());
+        let lines: Vec<&str> = messages[0].location.iter().map(|location| location.line.as_str()).collect();
+        assert_eq!(lines, vec!["10", "30", "40"]);
+        assert_eq!(messages[1].comment, Some("menu".to_string()));
+        assert_eq!(ts.dedupe(), 0);
+    }
+
+    #[test]
+    fn tst_split_and_append() {
+        let mut ts = Ts::load_from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
+        ts.contexts.push(Context { name: "MainWindow".to_string(), messages: ts.contexts[0].messages[..1].to_vec() });
+        let parts = ts.split_by_context();
+        assert_eq!(parts.len(), 2);
+        assert!(parts.iter().all(|part| part.language == ts.language && part.version == ts.version && part.contexts.len() == 1));
+        assert_eq!(parts[1].contexts[0].name, "MainWindow");
+
+        let mut joined = Ts { version: "2.1".to_string(), language: None, source_language: None, contexts: vec![], layout: TsLayout::default() };
+        for part in parts.into_iter().rev() {
+            joined.append(part);
+        }
+        assert_eq!(joined.contexts.len(), 2);
+        assert_eq!(joined.contexts[0].name, "MainWindow");
+        assert_eq!(joined.contexts[1].messages.len(), 5);
+    }
+
     #[test]
     fn tst_serialized_context_formatting() {
         let ts = Ts::load_from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
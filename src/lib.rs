@@ -2,7 +2,45 @@
 //
 // SPDX-License-Identifier: MIT
 
+//! Work with Qt Linguist TS and GNU Gettext PO translation files, and Transifex configurations used in deepin's workflow.
+//!
+//! Besides the `deepin-translation-utils` binary, the parsing, conversion and statistics logic can be used as a library:
+//!
+//! - [`i18n_file`]: load, save and inspect TS and PO files.
+//! - [`transifex`]: Transifex project files (`.tx/config`, `transifex.yaml`), and the REST API client with the
+//!   `transifex-api` feature.
+//! - [`conversion`]: convert translations between Chinese variants.
+//! - [`stats`]: message statistics of translation files and the `statistics` report schema.
+//! - [`glob`]: globs picking files and contexts, e.g. `dcc-*/**` or `!dcc::Legal*`.
+//! - [`glossary`]: terminology to enforce in translations, read from TBX or CSV files.
+//!
+//! The `cli` feature (enabled by default) builds the binary together with the `cli`, `config` and `subcmd` modules, which
+//! are not covered by semver. Library users should disable default features:
+//!
+//! ```toml
+//! deepin-translation-utils = { version = "0.6", default-features = false }
+//! ```
+//!
+//! ```no_run
+//! use deepin_translation_utils::stats::file_stats;
+//!
+//! let stats = file_stats(std::path::Path::new("translations/demo_zh_CN.ts")).unwrap();
+//! println!("{:.1}% translated", stats.completeness_percentage(None));
+//! ```
+
+#[cfg(feature = "cli")]
 pub mod cli;
+#[cfg(feature = "cli")]
+pub mod config;
+pub mod conversion;
+#[cfg(feature = "cli")]
+pub(crate) mod extract;
+pub mod glob;
+pub mod glossary;
 pub mod i18n_file;
+#[cfg(feature = "cli")]
+pub(crate) mod parallel;
+pub mod stats;
 pub mod transifex;
-pub mod subcmd;
\ No newline at end of file
+#[cfg(feature = "cli")]
+pub mod subcmd;
@@ -0,0 +1,89 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+//! Bounded parallelism for batch operations, configured by the global `--jobs` option.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use thiserror::Error as TeError;
+
+static JOBS: AtomicUsize = AtomicUsize::new(0);
+
+/// Set the maximum number of worker threads used by [`map`], 0 means the number of available CPUs.
+pub fn set_jobs(jobs: usize) {
+    JOBS.store(jobs, Ordering::Relaxed);
+}
+
+/// The maximum number of worker threads used by [`map`].
+pub fn jobs() -> usize {
+    match JOBS.load(Ordering::Relaxed) {
+        0 => std::thread::available_parallelism().map_or(1, |jobs| jobs.get()),
+        jobs => jobs,
+    }
+}
+
+/// Apply `f` to all items with up to [`jobs()`] worker threads.
+///
+/// Results are in the same order as `items`. A panic inside `f` is propagated to the caller.
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let workers = jobs().min(items.len());
+    if workers <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, R)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers).map(|_| scope.spawn(|| {
+            let mut results = vec![];
+            loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break results;
+                };
+                results.push((index, f(item)));
+            }
+        })).collect();
+        handles.into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect()
+    });
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[derive(TeError, Debug)]
+#[error("Can not set nice level to {0} because: {1}")]
+pub struct SetNiceError(i32, #[source] std::io::Error);
+
+/// Set the scheduling priority of the current process, like `nice(1)` and `renice(1)`.
+///
+/// Lowering the value below the current one usually requires privileges.
+#[cfg(unix)]
+pub fn set_nice(nice: i32) -> Result<(), SetNiceError> {
+    // SAFETY: setpriority only reads its integer arguments, `who = 0` means the calling process.
+    match unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, nice) } {
+        0 => Ok(()),
+        _ => Err(SetNiceError(nice, std::io::Error::last_os_error())),
+    }
+}
+
+#[cfg(not(unix))]
+pub fn set_nice(nice: i32) -> Result<(), SetNiceError> {
+    Err(SetNiceError(nice, std::io::ErrorKind::Unsupported.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_parallel_map() {
+        let items: Vec<u64> = (0..100).collect();
+        set_jobs(4);
+        assert_eq!(jobs(), 4);
+        assert_eq!(map(&items, |item| item * 2), items.iter().map(|item| item * 2).collect::<Vec<_>>());
+        set_jobs(0);
+        assert!(jobs() >= 1);
+        assert!(map(&Vec::<u64>::new(), |item| *item).is_empty());
+    }
+}
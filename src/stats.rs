@@ -0,0 +1,167 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+//! Message statistics of translation files, and the schema of the JSON and YAML output of `statistics`.
+//!
+//! Dashboards may rely on the report structs: fields are only added within the same [`STATS_SCHEMA_VERSION`],
+//! renaming or removing a field, or changing its meaning, bumps the version. The structs are `#[non_exhaustive]`, so
+//! new fields don't break code reading them.
+//!
+//! ```json
+//! {
+//!   "schema_version": 1,
+//!   "project_path": "/path/to/project",
+//!   "metric": "strings",
+//!   "source": { "resources": 1, "translated": 0, "unfinished": 4, "fuzzy": 0, "vanished": 0, "translated_words": 0, "unfinished_words": 12 },
+//!   "languages": [
+//!     { "language": "zh_CN", "completeness": 75.0, "resources": 1, "translated": 3, "unfinished": 1, "fuzzy": 0, "vanished": 0, "translated_words": 9, "unfinished_words": 3 }
+//!   ],
+//!   "resources": [
+//!     {
+//!       "source_path": "translations/demo.ts",
+//!       "source_language": "en",
+//!       "source": { "resources": 1, "translated": 0, "unfinished": 4, "fuzzy": 0, "vanished": 0, "translated_words": 0, "unfinished_words": 12 },
+//!       "translations": [
+//!         { "language": "zh_CN", "path": "translations/demo_zh_CN.ts", "completeness": 75.0, "resources": 1, "translated": 3, "unfinished": 1, "fuzzy": 0, "vanished": 0, "translated_words": 9, "unfinished_words": 3 }
+//!       ]
+//!     }
+//!   ],
+//!   "missing_source_paths": []
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use crate::i18n_file::cache::{I18nFileCache, I18nFileLoadError};
+
+pub use crate::i18n_file::common::{count_words, MessageState, MessageStats};
+
+/// Version of the statistics output schema, see the module documentation.
+pub const STATS_SCHEMA_VERSION: u32 = 1;
+
+/// Count messages of a Qt Linguist TS or GNU Gettext PO file, loaded through the shared [`I18nFileCache`].
+pub fn file_stats(file_path: &Path) -> Result<MessageStats, I18nFileLoadError> {
+    Ok(I18nFileCache::shared().load(file_path)?.get_message_stats())
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[non_exhaustive]
+pub struct StatsReport {
+    pub schema_version: u32,
+    pub project_path: PathBuf,
+    /// How `completeness` values are measured, `strings`, `words` or `chars`.
+    pub metric: String,
+    /// Counts of the source files, all their (non-vanished) strings are to be translated.
+    pub source: StringCounts,
+    /// Totals of each target language over all resources, in the requested sort order.
+    pub languages: Vec<LanguageReport>,
+    pub resources: Vec<ResourceReport>,
+    /// Source files listed in the project file but missing on disk, relative to the project path.
+    pub missing_source_paths: Vec<PathBuf>,
+}
+
+/// String, word and character counts, `unfinished` does not include `fuzzy` ones.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct StringCounts {
+    /// Number of files counted.
+    pub resources: u64,
+    pub translated: u64,
+    pub unfinished: u64,
+    pub fuzzy: u64,
+    /// Vanished and obsolete strings, which are not counted in completeness.
+    pub vanished: u64,
+    pub translated_words: u64,
+    /// Source words of unfinished and fuzzy strings.
+    pub unfinished_words: u64,
+    /// Non-whitespace source characters of translated strings.
+    #[serde(default)]
+    pub translated_chars: u64,
+    /// Non-whitespace source characters of unfinished and fuzzy strings.
+    #[serde(default)]
+    pub unfinished_chars: u64,
+}
+
+impl StringCounts {
+    pub fn new(resources: u64, stats: &MessageStats) -> Self {
+        Self {
+            resources,
+            translated: stats.shown_translated(),
+            unfinished: stats.unfinished,
+            fuzzy: stats.fuzzy,
+            vanished: stats.shown_obsolete(),
+            translated_words: stats.translated_words,
+            unfinished_words: stats.untranslated_words,
+            translated_chars: stats.translated_chars,
+            unfinished_chars: stats.untranslated_chars,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[non_exhaustive]
+pub struct LanguageReport {
+    pub language: String,
+    /// Completeness percentage from 0 to 100.
+    pub completeness: f64,
+    #[serde(flatten)]
+    pub counts: StringCounts,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[non_exhaustive]
+pub struct ResourceReport {
+    /// Relative to the project path.
+    pub source_path: PathBuf,
+    pub source_language: String,
+    /// Repository of the resource, only present with `--group-by-project`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    pub source: StringCounts,
+    /// Sorted by language code.
+    pub translations: Vec<TranslationReport>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[non_exhaustive]
+pub struct TranslationReport {
+    pub language: String,
+    /// Relative to the project path.
+    pub path: PathBuf,
+    /// Completeness percentage from 0 to 100.
+    pub completeness: f64,
+    /// Completeness percentage on Transifex, only present with `--with-remote`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_completeness: Option<f64>,
+    #[serde(flatten)]
+    pub counts: StringCounts,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_report_round_trip() {
+        use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("demo_zh_CN.ts");
+        std::fs::write(&path, TEST_ZH_CN_TS_CONTENT).unwrap();
+        let stats = file_stats(&path).unwrap();
+
+        let report = TranslationReport {
+            language: "zh_CN".to_string(),
+            path: PathBuf::from("translations/demo_zh_CN.ts"),
+            completeness: stats.completeness_percentage(None),
+            remote_completeness: None,
+            counts: StringCounts::new(1, &stats),
+        };
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(!json.contains("remote_completeness"));
+        let parsed: TranslationReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.counts, report.counts);
+        assert_eq!(parsed.completeness, report.completeness);
+    }
+}
@@ -2,16 +2,49 @@
 //
 // SPDX-License-Identifier: MIT
 
+pub mod common;
 pub mod zhconv;
 pub mod statistics;
+pub mod stats_history;
 pub mod yaml2txconfig;
 pub mod txconfig2yaml;
 pub mod monotxconfig;
 pub mod gentxcfg;
+pub mod orgs;
+pub mod orphans;
+pub mod pull;
+pub mod push;
+pub mod status;
+pub mod verify_sources;
+pub mod lint;
+pub mod lint_config;
+pub mod validate;
+pub mod updatepo;
+pub mod extract;
+pub mod diff;
+pub mod dedupe;
+pub mod normalize;
+pub mod split;
 
-pub use self::zhconv::{subcmd_zhconv, subcmd_zhconv_plain};
-pub use statistics::subcmd_statistics;
-pub use yaml2txconfig::{subcmd_yaml2txconfig, create_linked_resources_table};
+pub use self::zhconv::{subcmd_zhconv, subcmd_zhconv_project, subcmd_zhconv_plain, subcmd_mark_manual};
+pub use statistics::{subcmd_remote_statistics, subcmd_statistics};
+pub use stats_history::subcmd_statistics_trend;
+pub use yaml2txconfig::{subcmd_yaml2txconfig, create_linked_resources_table, create_filtered_linked_resources_table};
 pub use txconfig2yaml::subcmd_txconfig2yaml;
 pub use monotxconfig::subcmd_monotxconfig;
 pub use gentxcfg::subcmd_gentxcfg;
+pub use orgs::{subcmd_orgs, subcmd_projects};
+pub use orphans::subcmd_orphans;
+pub use pull::subcmd_pull;
+pub use push::subcmd_push;
+pub use status::subcmd_status;
+pub use verify_sources::subcmd_verify_sources;
+pub use lint::subcmd_lint;
+pub use lint_config::subcmd_lint_config;
+pub use validate::subcmd_validate;
+pub use updatepo::subcmd_updatepo;
+pub use extract::subcmd_extract;
+pub use diff::subcmd_diff;
+pub use dedupe::subcmd_dedupe;
+pub use normalize::subcmd_normalize;
+pub use split::{subcmd_split, subcmd_join};
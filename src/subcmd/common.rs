@@ -0,0 +1,9 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+/// FNV-1a hash of the file content, only meant to tell versions of a file apart at a glance.
+pub fn content_hash(content: &[u8]) -> String {
+    let hash = content.iter().fold(0xcbf29ce484222325u64, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3));
+    format!("{hash:016x}")
+}
@@ -0,0 +1,68 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::path::{Path, PathBuf};
+use thiserror::Error as TeError;
+use crate::i18n_file::cache::I18nFileCache;
+use crate::i18n_file::common::{I18nFileKind, UnknownI18nFileExtError};
+use crate::i18n_file::linguist::{Ts, TsLoadError, TsSaveError, TsSaveOptions};
+use crate::parallel;
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Provided path {0:?} does not exist")]
+    PathNotFound(PathBuf),
+    #[error("Fail to guess translation file type of {0:?} because: {1}")]
+    GuessI18nFileType(PathBuf, #[source] UnknownI18nFileExtError),
+    #[error("File {0:?} is not a Qt Linguist TS file, only TS files can be deduplicated")]
+    NotLinguistFile(PathBuf),
+    #[error("Fail to load TS file {0:?} because: {1}")]
+    LoadTsFile(PathBuf, #[source] TsLoadError),
+    #[error("Fail to save TS file {0:?} because: {1}")]
+    SaveTsFile(PathBuf, #[source] TsSaveError),
+    #[error("{0} file(s) contain duplicated messages")]
+    HasDuplicates(usize),
+}
+
+/// Deduplicate the given file, returns the number of merged duplicates.
+fn dedupe_file(file: &Path, check: bool) -> Result<usize, CmdError> {
+    let kind = I18nFileKind::from_ext_hint(file)
+        .map_err(|e| CmdError::GuessI18nFileType(file.to_path_buf(), e))?;
+    if !matches!(kind, I18nFileKind::Linguist) {
+        return Err(CmdError::NotLinguistFile(file.to_path_buf()));
+    }
+    let mut ts = Ts::load_from_file(file).map_err(|e| CmdError::LoadTsFile(file.to_path_buf(), e))?;
+    let removed = ts.dedupe();
+    if removed == 0 {
+        return Ok(0);
+    }
+    if check {
+        println!("Found {removed} duplicated message(s) in {file:?}");
+    } else {
+        ts.save_into_file(file, &TsSaveOptions::default()).map_err(|e| CmdError::SaveTsFile(file.to_path_buf(), e))?;
+        I18nFileCache::shared().invalidate(file);
+        println!("Merged {removed} duplicated message(s) in {file:?}");
+    }
+    Ok(removed)
+}
+
+pub fn subcmd_dedupe(paths: &[PathBuf], check: bool) -> Result<(), CmdError> {
+    if let Some(path) = paths.iter().find(|path| !path.exists()) {
+        return Err(CmdError::PathNotFound(path.clone()));
+    }
+    let files = crate::extract::collect_source_files(paths, &["ts"]);
+    let results = parallel::map(&files, |file| dedupe_file(file, check));
+    let mut affected_files = 0;
+    for result in results {
+        if result? > 0 {
+            affected_files += 1;
+        }
+    }
+
+    if check && affected_files > 0 {
+        return Err(CmdError::HasDuplicates(affected_files));
+    }
+    println!("{} of {} file(s) {}.", affected_files, files.len(), if check { "contain duplicated messages" } else { "deduplicated" });
+    Ok(())
+}
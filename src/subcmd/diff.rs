@@ -0,0 +1,134 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+use thiserror::Error as TeError;
+use std::path::{Path, PathBuf};
+use crate::i18n_file::cache::{I18nFileCache, I18nFileLoadError};
+use crate::i18n_file::common::{FlatMessage, MessageState};
+use crate::i18n_file::diff::{diff_messages, ChangeKind, MessageChange};
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Fail to load translation file {0:?} because: {1}")]
+    LoadI18nFile(PathBuf, #[source] I18nFileLoadError),
+    #[error("Fail to serialize diff to JSON: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+}
+
+#[derive(clap::ValueEnum, Clone, Default, Copy, Debug)]
+pub enum DiffFormat {
+    #[default]
+    PlainTable,
+    Json,
+}
+
+#[derive(Default, Serialize)]
+struct DiffSummary {
+    added: u64,
+    removed: u64,
+    changed: u64,
+    state_changed: u64,
+}
+
+impl DiffSummary {
+    fn from_changes(changes: &[MessageChange]) -> Self {
+        let mut summary = DiffSummary::default();
+        for change in changes {
+            match change.kind {
+                ChangeKind::Added => summary.added += 1,
+                ChangeKind::Removed => summary.removed += 1,
+                ChangeKind::Changed => summary.changed += 1,
+                ChangeKind::StateChanged => summary.state_changed += 1,
+            }
+        }
+        summary
+    }
+}
+
+#[derive(Serialize)]
+struct DiffReport {
+    old_file: PathBuf,
+    new_file: PathBuf,
+    summary: DiffSummary,
+    changes: Vec<MessageChange>,
+}
+
+fn load_flat_messages(file_path: &Path) -> Result<Vec<FlatMessage>, CmdError> {
+    Ok(I18nFileCache::shared().load(file_path)
+        .map_err(|e| CmdError::LoadI18nFile(file_path.to_path_buf(), e))?
+        .flat_messages())
+}
+
+/// Make the text fit in a single table cell.
+pub(crate) fn escape_cell(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\n', "\\n").replace('|', "\\|")
+}
+
+pub(crate) fn state_name(state: MessageState) -> &'static str {
+    match state {
+        MessageState::Finished => "finished",
+        MessageState::Unfinished => "unfinished",
+        MessageState::Vanished => "vanished",
+        MessageState::Obsolete => "obsolete",
+        MessageState::Fuzzy => "fuzzy",
+    }
+}
+
+fn translation_cell(translations: &[String], state: Option<MessageState>) -> String {
+    let Some(state) = state else {
+        return String::new();
+    };
+    let text = translations.iter().map(|text| escape_cell(text)).collect::<Vec<_>>().join(" / ");
+    format!("{text} ({})", state_name(state))
+}
+
+/// Print changes as a markdown table, followed by a summary line.
+pub(crate) fn print_changes_table(changes: &[MessageChange]) {
+    if !changes.is_empty() {
+        println!("| Change        | Context | Source | Old | New |");
+        println!("| ------------- | ------- | ------ | --- | --- |");
+    }
+    for change in changes {
+        let kind = match change.kind {
+            ChangeKind::Added => "Added",
+            ChangeKind::Removed => "Removed",
+            ChangeKind::Changed => "Changed",
+            ChangeKind::StateChanged => "State changed",
+        };
+        let source = match &change.disambiguation {
+            Some(disambiguation) => format!("{} ({})", escape_cell(&change.source), escape_cell(disambiguation)),
+            None => escape_cell(&change.source),
+        };
+        println!("| {kind:13} | {} | {source} | {} | {} |", escape_cell(&change.context),
+            translation_cell(&change.old_translations, change.old_state),
+            translation_cell(&change.new_translations, change.new_state));
+    }
+    let summary = DiffSummary::from_changes(changes);
+    println!("{} added, {} removed, {} changed, {} state changed.", summary.added, summary.removed, summary.changed, summary.state_changed);
+}
+
+impl DiffReport {
+    fn print_plain_table(&self) {
+        println!("Comparing {:?} with {:?}:", self.old_file, self.new_file);
+        print_changes_table(&self.changes);
+    }
+}
+
+pub fn subcmd_diff(old_file: &Path, new_file: &Path, format: DiffFormat) -> Result<(), CmdError> {
+    let changes = diff_messages(&load_flat_messages(old_file)?, &load_flat_messages(new_file)?);
+    let report = DiffReport {
+        old_file: old_file.to_path_buf(),
+        new_file: new_file.to_path_buf(),
+        summary: DiffSummary::from_changes(&changes),
+        changes,
+    };
+
+    match format {
+        DiffFormat::PlainTable => report.print_plain_table(),
+        DiffFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    Ok(())
+}
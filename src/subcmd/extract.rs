@@ -0,0 +1,165 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use thiserror::Error as TeError;
+use crate::extract::{self, cpp, qml, SourceFile};
+use crate::extract::gettext::{self, GettextExtractOptions, Keyword, KeywordParseError};
+use crate::i18n_file::common::{I18nFileKind, UnknownI18nFileExtError};
+use crate::i18n_file::gettext::{format_po_date, Po, PoHeaderOptions, PoLoadError, PoSaveError, PoSaveOptions};
+use crate::i18n_file::linguist::{Ts, TsLayout, TsLoadError, TsSaveError, TsSaveOptions};
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Provided path {0:?} does not exist")]
+    PathNotFound(PathBuf),
+    #[error("No source file found in the provided paths")]
+    NoSourceFile,
+    #[error("Can not resolve path {0:?} because: {1}")]
+    ResolvePath(PathBuf, #[source] std::io::Error),
+    #[error("Can not read source file {0:?} because: {1}")]
+    ReadSourceFile(PathBuf, #[source] std::io::Error),
+    #[error("Fail to guess translation file type of {0:?} because: {1}")]
+    GuessI18nFileType(PathBuf, #[source] UnknownI18nFileExtError),
+    #[error("Invalid keyword because: {0}")]
+    InvalidKeyword(#[from] KeywordParseError),
+    #[error("Fail to load TS file {0:?} because: {1}")]
+    LoadTsFile(PathBuf, #[source] TsLoadError),
+    #[error("Fail to save TS file {0:?} because: {1}")]
+    SaveTsFile(PathBuf, #[source] TsSaveError),
+    #[error("Fail to load PO file {0:?} because: {1}")]
+    LoadPoFile(PathBuf, #[source] PoLoadError),
+    #[error("Fail to save PO file {0:?} because: {1}")]
+    SavePoFile(PathBuf, #[source] PoSaveError),
+}
+
+fn absolute_path(path: &Path) -> Result<PathBuf, CmdError> {
+    std::path::absolute(path).map_err(|e| CmdError::ResolvePath(path.to_path_buf(), e))
+}
+
+/// Read source files, with file names relative to the directory of the output file.
+fn read_source_files(files: &[PathBuf], output: &Path) -> Result<Vec<SourceFile>, CmdError> {
+    let output = absolute_path(output)?;
+    let base_dir = output.parent().unwrap_or(Path::new("/"));
+    let mut sources = vec![];
+    for file in files {
+        let content = fs::read(file).map_err(|e| CmdError::ReadSourceFile(file.clone(), e))?;
+        sources.push(SourceFile {
+            filename: extract::relative_location_path(base_dir, &absolute_path(file)?),
+            content: String::from_utf8_lossy(&content).to_string(),
+        });
+    }
+    Ok(sources)
+}
+
+fn extract_into_ts(files: &[PathBuf], output: &Path, language: Option<&str>, no_obsolete: bool, ts_save_options: &TsSaveOptions) -> Result<(), CmdError> {
+    let sources = read_source_files(files, output)?;
+    let (qml_sources, cpp_sources): (Vec<SourceFile>, Vec<SourceFile>) = sources.iter().cloned()
+        .partition(|source| Path::new(&source.filename).extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| qml::QML_EXTENSIONS.contains(&ext)));
+    let mut result = cpp::extract_from_cpp_sources(&cpp_sources);
+    let qml_result = qml::extract_from_qml_sources(&qml_sources);
+    result.messages.extend(qml_result.messages);
+    result.warnings.extend(qml_result.warnings);
+    for warning in &result.warnings {
+        eprintln!("Warning: {warning}");
+    }
+
+    let mut ts = if output.exists() {
+        Ts::load_from_file(output).map_err(|e| CmdError::LoadTsFile(output.to_path_buf(), e))?
+    } else {
+        Ts { language: None, source_language: None, version: "2.1".to_string(), contexts: vec![], layout: TsLayout::default() }
+    };
+    if let Some(language) = language {
+        ts.set_language(language);
+    }
+
+    let (ts, stats) = extract::merge_into_ts(&ts, &result.messages, no_obsolete);
+    ts.save_into_file(output, ts_save_options).map_err(|e| CmdError::SaveTsFile(output.to_path_buf(), e))?;
+    println!("Scanned {} source file(s), found {} source text(s) ({} new and {} already existing).",
+        sources.len(), stats.new + stats.existing, stats.new, stats.existing);
+    println!("Updated {output:?}: {} vanished message(s) kept, {} message(s) removed.", stats.vanished, stats.removed);
+
+    Ok(())
+}
+
+/// Extract into a POT template, or update a PO file against the extracted template like `msgmerge`.
+fn extract_into_po(files: &[PathBuf], output: &Path, language: Option<&str>, no_obsolete: bool, options: &GettextExtractOptions) -> Result<(), CmdError> {
+    let sources = read_source_files(files, output)?;
+    let result = gettext::extract_from_gettext_sources(&sources, options);
+    for warning in &result.warnings {
+        eprintln!("Warning: {warning}");
+    }
+
+    let existing = if output.exists() {
+        Some(Po::load_from_file(output).map_err(|e| CmdError::LoadPoFile(output.to_path_buf(), e))?)
+    } else {
+        None
+    };
+    let is_template = output.extension().is_some_and(|ext| ext == "pot");
+    let template = gettext::build_pot(&result.messages, existing.as_ref().filter(|_| is_template), &format_po_date(SystemTime::now()));
+    println!("Scanned {} source file(s), found {} source text(s).", sources.len(), template.message_count());
+
+    let po = match existing.filter(|_| !is_template) {
+        Some(po) => {
+            let (mut po, stats) = po.merge_with_template(&template, true);
+            if no_obsolete {
+                po.clear_obsolete_messages();
+            }
+            if let Some(language) = language {
+                po.set_language(language);
+            }
+            println!("Merged into {output:?}: {} exact, {} fuzzy, {} untranslated and {} obsolete message(s).",
+                stats.exact, stats.fuzzy, stats.untranslated, stats.obsolete);
+            po
+        },
+        None => match language.filter(|_| !is_template) {
+            Some(language) => template.new_translation(language, &PoHeaderOptions::default()),
+            None => template,
+        },
+    };
+    po.save_into_file(output, &PoSaveOptions::default()).map_err(|e| CmdError::SavePoFile(output.to_path_buf(), e))?;
+    println!("Updated {output:?}.");
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn subcmd_extract(source_paths: &[PathBuf], output: &Path, language: Option<&str>, no_obsolete: bool,
+                      keywords: &[String], no_default_keywords: bool, comment_tag: Option<&str>,
+                      ts_save_options: &TsSaveOptions) -> Result<(), CmdError> {
+    if let Some(path) = source_paths.iter().find(|path| !path.exists()) {
+        return Err(CmdError::PathNotFound(path.clone()));
+    }
+    let output_kind = I18nFileKind::from_ext_hint(output)
+        .map_err(|e| CmdError::GuessI18nFileType(output.to_path_buf(), e))?;
+
+    match output_kind {
+        I18nFileKind::Linguist => {
+            let extensions = [cpp::CPP_EXTENSIONS, qml::QML_EXTENSIONS].concat();
+            let files = extract::collect_source_files(source_paths, &extensions);
+            if files.is_empty() {
+                return Err(CmdError::NoSourceFile);
+            }
+            extract_into_ts(&files, output, language, no_obsolete, ts_save_options)
+        },
+        I18nFileKind::Gettext => {
+            let mut options = GettextExtractOptions {
+                keywords: if no_default_keywords { vec![] } else { gettext::default_keywords() },
+                comment_tag: comment_tag.map(str::to_string),
+            };
+            for keyword in keywords {
+                options.keywords.push(keyword.parse::<Keyword>()?);
+            }
+            let files = extract::collect_source_files(source_paths, gettext::GETTEXT_EXTENSIONS);
+            if files.is_empty() {
+                return Err(CmdError::NoSourceFile);
+            }
+            extract_into_po(&files, output, language, no_obsolete, &options)
+        },
+    }
+}
@@ -2,13 +2,19 @@
 //
 // SPDX-License-Identifier: MIT
 
-use std::{fs, path::PathBuf};
+use std::{fs, path::{Path, PathBuf}};
+use std::io::{stdin, BufRead};
 use thiserror::Error as TeError;
 use walkdir::WalkDir;
 use regex::Regex;
 
 use crate::i18n_file::common::I18nFileKind;
-use crate::transifex::yaml_file::{TransifexYaml, Filter, Settings};
+use crate::i18n_file::language_code::is_valid_language_code;
+use crate::subcmd::lint_config::IN_SOURCE_FILE_FORMATS;
+use crate::glob::PathGlobs;
+use crate::subcmd::common::content_hash;
+use crate::transifex::tx_config_file::{load_tx_config_file, write_file_atomically, LoadTxConfigError, TxConfig, PLACEHOLDER_SLUG_PREFIX};
+use crate::transifex::yaml_file::{load_tx_yaml_file, LoadTxYamlError, TransifexYaml, Filter, Settings};
 
 #[derive(TeError, Debug)]
 pub enum CmdError {
@@ -18,16 +24,51 @@ pub enum CmdError {
     SerializeYaml(#[from] serde_yaml2::ser::Errors),
     #[error("Unknown translation file type: {path:?}")]
     UnknownI18nFileType { path: PathBuf },
+    #[error("Failed to load existing .tx/config file: {0}")]
+    LoadTxConfig(#[from] LoadTxConfigError),
+    #[error("Failed to load existing transifex.yaml file: {0}")]
+    LoadTxYaml(#[from] LoadTxYamlError),
+    #[error("Invalid ignore path: {0}")]
+    InvalidIgnorePath(#[source] globset::Error),
+    #[error("Failed to read user input: {0}")]
+    ReadInput(#[source] std::io::Error),
 }
 
-pub fn subcmd_gentxcfg(project_root: &PathBuf, format: crate::cli::TxConfigFormat, ignore_paths: Vec<String>) -> Result<(), CmdError> {
-    println!("Scanning directory: {:?}", project_root);
+/// Write the generated configuration, creating the parent directory if needed, or print it if `output_path` is `None`
+/// (i.e. `--output -`). With `dry_run`, only print what would be written. Returns whether a file was written.
+fn write_output(output_path: Option<&Path>, content: &str, dry_run: bool) -> Result<bool, CmdError> {
+    let Some(output_path) = output_path else {
+        print!("{}", content);
+        return Ok(false);
+    };
+    if dry_run {
+        println!("Dry run, would write {:?} with the following content:\n", output_path);
+        println!("{}", content);
+        return Ok(false);
+    }
+    if let Some(parent_dir) = output_path.parent().filter(|parent_dir| !parent_dir.as_os_str().is_empty() && !parent_dir.exists()) {
+        fs::create_dir_all(parent_dir)?;
+        eprintln!("Created directory {:?}", parent_dir);
+    }
+    write_file_atomically(output_path, content)?;
+    Ok(true)
+}
+
+/// Scan the project for translation files and write the inferred Transifex configuration into .tx/.
+///
+/// With `output`, the configuration is written to (or merged into) the given file instead, overwriting it without `merge`,
+/// or printed for `-`. With `-` and `merge`, the result of merging into the file in .tx/ is printed.
+///
+/// `minimum_perc` only applies to the .tx/config format, transifex.yaml has no such setting.
+#[allow(clippy::too_many_arguments)]
+pub fn subcmd_gentxcfg(project_root: &PathBuf, format: crate::cli::TxConfigFormat, ignore_paths: Vec<String>, follow_symlinks: bool, source_language: Option<&str>, minimum_perc: Option<i64>, merge: bool, interactive: bool, dry_run: bool, output: Option<&Path>) -> Result<(), CmdError> {
+    eprintln!("Scanning directory: {:?}", project_root);
 
     // Scan for all translation files in the project root directory
-    let all_translation_files = scan_all_translation_files(project_root, &ignore_paths)?;
+    let all_translation_files = scan_all_translation_files(project_root, &ignore_paths, follow_symlinks)?;
 
     if all_translation_files.is_empty() {
-        println!("No translation files (.ts or .po) found");
+        eprintln!("No translation files (.ts, .po, .desktop, dconfig or AppStream metainfo) found");
         return Ok(());
     }
 
@@ -35,49 +76,72 @@ pub fn subcmd_gentxcfg(project_root: &PathBuf, format: crate::cli::TxConfigForma
     let source_files = identify_source_files(project_root, &all_translation_files)?;
 
     if source_files.is_empty() {
-        println!("No source translation files found");
+        eprintln!("No source translation files found");
         return Ok(());
     }
 
-    println!("Found {} source translation files:", source_files.len());
+    eprintln!("Found {} source translation files:", source_files.len());
     for file in &source_files {
-        println!("- {:?}", file);
+        eprintln!("- {:?}", file);
     }
 
     // Generate transifex configuration
-    let tx_yaml = generate_transifex_yaml(project_root, &source_files)?;
+    let mut tx_yaml = generate_transifex_yaml(project_root, &source_files, source_language)?;
+    if interactive {
+        tx_yaml.filters = review_filters(tx_yaml.filters, &mut stdin().lock())?;
+        if tx_yaml.filters.is_empty() {
+            eprintln!("All resources skipped, nothing to write");
+            return Ok(());
+        }
+    }
 
-    // Create .tx directory if it doesn't exist
     let tx_dir = project_root.join(".tx");
-    if !tx_dir.exists() {
-        fs::create_dir_all(&tx_dir)?;
-        println!("Created .tx directory");
-    }
+    let to_stdout = output == Some(Path::new("-"));
+    let output_path = |default_file_name: &str| match output {
+        Some(output) if !to_stdout => output.to_path_buf(),
+        _ => tx_dir.join(default_file_name),
+    };
+    let write_target = |output_path: &PathBuf| if to_stdout { None } else { Some(output_path.clone()) };
 
     // Generate and save file based on format
     match format {
         crate::cli::TxConfigFormat::Yaml => {
-            let output_path = tx_dir.join("transifex.yaml");
-            if output_path.exists() {
-                println!("Note: {:?} file already exists, not overwriting.", output_path);
+            let output_path = output_path("transifex.yaml");
+            if output_path.exists() && merge {
+                let mut existing_tx_yaml = load_tx_yaml_file(&output_path)?;
+                let stats = existing_tx_yaml.merge(tx_yaml);
+                if write_output(write_target(&output_path).as_deref(), &serde_yaml2::to_string(&existing_tx_yaml)?, dry_run)? {
+                    println!("Merged into transifex.yaml file: {}, {} filter(s) added", output_path.display(), stats.added);
+                }
+                print_removed_sources(&stats.removed);
+            } else if output_path.exists() && output.is_none() {
+                println!("Note: {:?} file already exists, not overwriting. Use --merge to update it.", output_path);
                 println!("You can use the following content to update the file manually:\n");
                 println!("{}", serde_yaml2::to_string(&tx_yaml)?);
-            } else {
-                let yaml_content = serde_yaml2::to_string(&tx_yaml)?;
-                fs::write(&output_path, yaml_content)?;
+            } else if write_output(write_target(&output_path).as_deref(), &serde_yaml2::to_string(&tx_yaml)?, dry_run)? {
                 println!("Generated transifex.yaml file: {}", output_path.display());
             }
         },
         crate::cli::TxConfigFormat::Txconfig => {
-            let tx_config = tx_yaml.to_tx_config("".to_string(), vec![]);
-            let output_path = tx_dir.join("config");
-            if output_path.exists() {
-                println!("Note: {:?} file already exists, not overwriting.", output_path);
+            let mut tx_config = tx_yaml.to_tx_config("".to_string(), None, vec![]);
+            assign_stable_resource_slugs(&mut tx_config);
+            tx_config.main_section.minimum_prec = minimum_perc;
+            let output_path = output_path("config");
+            if output_path.exists() && merge {
+                let mut existing_tx_config = load_tx_config_file(&output_path)?;
+                let stats = existing_tx_config.merge(tx_config);
+                if minimum_perc.is_some() {
+                    existing_tx_config.main_section.minimum_prec = minimum_perc;
+                }
+                if write_output(write_target(&output_path).as_deref(), &existing_tx_config.to_str(), dry_run)? {
+                    println!("Merged into .tx/config file: {}, {} section(s) added, {} section(s) kept", output_path.display(), stats.added, stats.kept);
+                }
+                print_removed_sources(&stats.removed);
+            } else if output_path.exists() && output.is_none() {
+                println!("Note: {:?} file already exists, not overwriting. Use --merge to update it.", output_path);
                 println!("You can use the following content to update the file manually:\n");
                 println!("{}", tx_config.to_str());
-            } else {
-                let config_content = tx_config.to_str();
-                fs::write(&output_path, config_content)?;
+            } else if write_output(write_target(&output_path).as_deref(), &tx_config.to_str(), dry_run)? {
                 println!("Generated .tx/config file: {}", output_path.display());
             }
         },
@@ -86,61 +150,312 @@ pub fn subcmd_gentxcfg(project_root: &PathBuf, format: crate::cli::TxConfigForma
     Ok(())
 }
 
-fn scan_all_translation_files(project_root: &PathBuf, ignore_paths: &[String]) -> Result<Vec<PathBuf>, CmdError> {
+/// Scan the project for translation files and infer a Transifex configuration from them, without
+/// writing anything.
+pub fn discover_transifex_yaml(project_root: &PathBuf, ignore_paths: &[String]) -> Result<TransifexYaml, CmdError> {
+    let all_translation_files = scan_all_translation_files(project_root, ignore_paths, false)?;
+    let source_files = identify_source_files(project_root, &all_translation_files)?;
+    generate_transifex_yaml(project_root, &source_files, None)
+}
+
+/// Kinds of translatable files recognized when scanning.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ResourceFileType {
+    /// Qt Linguist translation file (.ts)
+    Linguist,
+    /// GNU Gettext translation file (.po/.pot)
+    Gettext,
+    /// Freedesktop desktop entry (.desktop)
+    Desktop,
+    /// DTK dconfig meta file (.json with `"magic": "dsg.config.meta"`)
+    DConfig,
+    /// AppStream metainfo (.metainfo.xml or .appdata.xml)
+    AppStream,
+}
+
+impl ResourceFileType {
+    fn detect(path: &std::path::Path) -> Option<Self> {
+        if let Ok(kind) = I18nFileKind::from_ext_hint(path) {
+            return Some(match kind {
+                I18nFileKind::Linguist => Self::Linguist,
+                I18nFileKind::Gettext => Self::Gettext,
+            });
+        }
+        let file_name = path.file_name()?.to_str()?;
+        // Templates like `foo.desktop.in` are what gets translated in many projects.
+        let file_name = file_name.strip_suffix(".in").unwrap_or(file_name);
+        if file_name.ends_with(".desktop") {
+            Some(Self::Desktop)
+        } else if file_name.ends_with(".metainfo.xml") || file_name.ends_with(".appdata.xml") {
+            Some(Self::AppStream)
+        } else if file_name.ends_with(".json") && fs::read_to_string(path).is_ok_and(|content| content.contains("\"dsg.config.meta\"")) {
+            Some(Self::DConfig)
+        } else {
+            None
+        }
+    }
+
+    /// The `file_format` of the resource on Transifex, if any. dconfig metas and AppStream metainfo keep the translations
+    /// of all languages in the source file, like .desktop files do, but Transifex can only handle that for the latter.
+    fn transifex_format(self) -> Option<&'static str> {
+        match self {
+            Self::Linguist => Some("QT"),
+            Self::Gettext => Some("PO"),
+            Self::Desktop => Some("DESKTOP"),
+            Self::DConfig | Self::AppStream => None,
+        }
+    }
+}
+
+/// Paths ignored by default when scanning, i.e. when no `--ignore-paths` is given.
+pub const DEFAULT_IGNORE_PATHS: &[&str] = &["build", "node_modules"];
+
+/// Directories never worth scanning, regardless of the ignored paths.
+const ALWAYS_IGNORED_DIRS: &[&str] = &[".git"];
+
+/// Find translation files in the project.
+///
+/// With `follow_symlinks`, symlinked directories are scanned too. Symlink loops are reported and skipped, and files reachable
+/// from several paths are only listed once.
+fn scan_all_translation_files(project_root: &PathBuf, ignore_paths: &[String], follow_symlinks: bool) -> Result<Vec<PathBuf>, CmdError> {
     let mut translation_files = Vec::new();
+    let mut build_files = Vec::new();
+    let ignore_patterns = ignore_patterns(ignore_paths).map_err(CmdError::InvalidIgnorePath)?;
+
+    for path in walk_project_files(project_root, &ignore_patterns, follow_symlinks) {
+        let path = path.as_path();
+
+        // Check if it's a translation file
+        let file_type = ResourceFileType::detect(path);
+        if file_type == Some(ResourceFileType::Linguist) && !I18nFileKind::sniff_linguist_file(path) {
+            eprintln!("Note: Skipping {:?}, it does not look like a Qt Linguist file (TypeScript source?)", path.strip_prefix(project_root).unwrap_or(path));
+            continue;
+        }
+        if file_type.is_some() {
+            translation_files.push(path.to_path_buf());
+        } else if BuildFileKind::detect(path).is_some() {
+            build_files.push(path.to_path_buf());
+        }
+    }
+
+    // TS files declared by CMake or qmake are authoritative, scanning is only a fallback for them.
+    let declared_ts_files = declared_ts_files(project_root, &build_files, &translation_files);
+    if !declared_ts_files.is_empty() {
+        translation_files.retain(|file| ResourceFileType::detect(file) != Some(ResourceFileType::Linguist));
+        translation_files.extend(declared_ts_files);
+    }
+
+    Ok(translation_files)
+}
+
+/// All files of the project that are not ignored. When following symlinks, a file reachable from several paths is listed once,
+/// under its own path if it's inside the project.
+fn walk_project_files(project_root: &PathBuf, ignore_patterns: &PathGlobs, follow_symlinks: bool) -> Vec<PathBuf> {
+    let canonical_root = fs::canonicalize(project_root).unwrap_or_else(|_| project_root.clone());
+    let mut files: Vec<PathBuf> = Vec::new();
+    let mut visited_files = std::collections::HashMap::new();
 
     for entry in WalkDir::new(project_root)
-        .follow_links(false)
+        .follow_links(follow_symlinks)
         .into_iter()
-        .filter_entry(|e| !should_ignore_entry(e, project_root, ignore_paths))
-        .filter_map(|e| e.ok())
+        .filter_entry(|e| !should_ignore_entry(e, project_root, ignore_patterns))
     {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                if let Some(loop_ancestor) = e.loop_ancestor() {
+                    eprintln!("Warning: Skipping {:?}, it is a symlink loop back to {:?}", e.path().unwrap_or(loop_ancestor), loop_ancestor);
+                }
+                continue;
+            },
+        };
         let path = entry.path();
 
         // Skip directories
         if !path.is_file() {
             continue;
         }
-
-        // Check if it's a translation file
-        if let Ok(_) = I18nFileKind::from_ext_hint(path) {
-            translation_files.push(path.to_path_buf());
+        if !follow_symlinks {
+            files.push(path.to_path_buf());
+            continue;
+        }
+        let canonical_path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let is_own_path = path.strip_prefix(project_root).is_ok_and(|relative| canonical_root.join(relative) == canonical_path);
+        match visited_files.get(&canonical_path) {
+            Some(&index) if is_own_path => files[index] = path.to_path_buf(),
+            Some(_) => (),
+            None => {
+                visited_files.insert(canonical_path, files.len());
+                files.push(path.to_path_buf());
+            },
         }
     }
+    files
+}
 
-    Ok(translation_files)
+/// Build system files that may declare the TS files of a project.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BuildFileKind {
+    /// CMakeLists.txt, e.g. `qt_add_translations(app TS_FILES translations/app_zh_CN.ts)`
+    CMake,
+    /// qmake .pro/.pri files, e.g. `TRANSLATIONS += translations/app_zh_CN.ts`
+    QMake,
 }
 
-fn should_ignore_entry(entry: &walkdir::DirEntry, project_root: &PathBuf, ignore_paths: &[String]) -> bool {
-    let path = entry.path();
+impl BuildFileKind {
+    fn detect(path: &std::path::Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        if file_name == "CMakeLists.txt" {
+            Some(Self::CMake)
+        } else if file_name.ends_with(".pro") || file_name.ends_with(".pri") {
+            Some(Self::QMake)
+        } else {
+            None
+        }
+    }
+}
 
-    // Get relative path from project root
-    if let Ok(relative_path) = path.strip_prefix(project_root) {
-        let relative_path_str = relative_path.to_string_lossy();
+/// CMake commands whose arguments list TS files.
+const CMAKE_TRANSLATION_COMMANDS: &[&str] = &[
+    "qt_add_translations", "qt6_add_translations", "qt_add_translation", "qt5_add_translation", "qt6_add_translation",
+    "qt_create_translation", "qt5_create_translation", "qt6_create_translation", "qt_add_lupdate", "qt6_add_lupdate",
+];
+
+/// Extract TS file paths (possibly globs) declared in a CMakeLists.txt, relative to its directory unless absolute.
+///
+/// Arguments of the Qt translation commands are taken, with `${VAR}` expanded from `set()` and `file(GLOB)` in the same file.
+fn parse_cmake_ts_files(content: &str) -> Vec<String> {
+    let content: String = content.lines().map(|line| line.split('#').next().unwrap_or_default()).collect::<Vec<_>>().join("\n");
+    let command_regex = Regex::new(r"(?s)\b([A-Za-z_][A-Za-z0-9_]*)\s*\(([^()]*)\)").unwrap();
+    let variable_regex = Regex::new(r"\$\{([A-Za-z0-9_]+)\}").unwrap();
+    let mut variables: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
+    let mut ts_files = Vec::new();
+    for captures in command_regex.captures_iter(&content) {
+        let command = captures[1].to_lowercase();
+        let args: Vec<String> = captures[2].split_whitespace().map(|arg| arg.trim_matches('"').to_string()).collect();
+        // Expand variables known so far, unknown ones like `${CMAKE_CURRENT_SOURCE_DIR}` are resolved later.
+        let args: Vec<String> = args.into_iter().flat_map(|arg| match variable_regex.captures(&arg) {
+            Some(var) if arg == var[0] && variables.contains_key(&var[1]) => variables[&var[1]].clone(),
+            _ => vec![arg],
+        }).collect();
+        match command.as_str() {
+            "set" if !args.is_empty() => {
+                variables.insert(args[0].clone(), args[1..].to_vec());
+            },
+            "file" if args.len() > 2 && (args[0] == "GLOB" || args[0] == "GLOB_RECURSE") => {
+                let globs = args[2..].iter().filter(|arg| !matches!(arg.as_str(), "LIST_DIRECTORIES" | "RELATIVE" | "CONFIGURE_DEPENDS")).cloned().collect();
+                variables.insert(args[1].clone(), globs);
+            },
+            command if CMAKE_TRANSLATION_COMMANDS.contains(&command) => {
+                ts_files.extend(args.into_iter().filter(|arg| arg.ends_with(".ts")));
+            },
+            _ => (),
+        }
+    }
+    ts_files
+}
 
-        for ignore_pattern in ignore_paths {
-            // Skip empty patterns
-            if ignore_pattern.is_empty() {
-                continue;
-            }
+/// Extract TS file paths (possibly globs) of the `TRANSLATIONS` variable in a qmake project file.
+fn parse_qmake_ts_files(content: &str) -> Vec<String> {
+    let content = content.replace("\\\r\n", " ").replace("\\\n", " ");
+    let assignment_regex = Regex::new(r"^\s*TRANSLATIONS\s*\+?=\s*(.*)$").unwrap();
+    content.lines()
+        .filter_map(|line| assignment_regex.captures(line.split('#').next().unwrap_or_default()))
+        .flat_map(|captures| captures[1].split_whitespace().map(|arg| arg.trim_matches('"').to_string()).collect::<Vec<_>>())
+        .filter(|arg| arg.ends_with(".ts"))
+        .collect()
+}
 
-            // Check if the relative path starts with the ignore pattern
-            if relative_path_str.starts_with(ignore_pattern) {
-                return true;
-            }
+/// Resolve a path declared in a build file to a path relative to the project root, `None` if it uses unknown variables
+/// or points outside the project.
+fn resolve_declared_path(declared: &str, build_file_dir: &str) -> Option<String> {
+    let (base, rest) = match declared.split_once('}').filter(|_| declared.starts_with("${")) {
+        Some(("${CMAKE_CURRENT_SOURCE_DIR" | "${CMAKE_CURRENT_LIST_DIR", rest)) => (build_file_dir, rest),
+        Some(("${PROJECT_SOURCE_DIR" | "${CMAKE_SOURCE_DIR", rest)) => ("", rest),
+        Some(_) => return None,
+        None => match declared.strip_prefix("$$PWD").or_else(|| declared.strip_prefix("$${PWD}")) {
+            Some(rest) => (build_file_dir, rest),
+            None => (build_file_dir, declared),
+        },
+    };
+    if rest.contains('$') {
+        return None;
+    }
+    let mut components: Vec<&str> = base.split('/').filter(|component| !component.is_empty()).collect();
+    for component in rest.split('/') {
+        match component {
+            "" | "." => (),
+            ".." => {
+                components.pop()?;
+            },
+            component => components.push(component),
+        }
+    }
+    Some(components.join("/"))
+}
 
-            // Check if any component of the path matches the ignore pattern
-            for component in relative_path.components() {
-                if let std::path::Component::Normal(name) = component {
-                    if name.to_string_lossy() == ignore_pattern.as_str() {
-                        return true;
-                    }
+/// TS files declared in the build files, as existing files among the scanned ones. Globs are matched against them too.
+fn declared_ts_files(project_root: &PathBuf, build_files: &[PathBuf], translation_files: &[PathBuf]) -> Vec<PathBuf> {
+    let relative = |path: &PathBuf| path.strip_prefix(project_root).unwrap_or(path).to_string_lossy().replace('\\', "/");
+    let mut declared_files = Vec::new();
+    for build_file in build_files {
+        let Ok(content) = fs::read_to_string(build_file) else {
+            continue;
+        };
+        let declared = match BuildFileKind::detect(build_file) {
+            Some(BuildFileKind::CMake) => parse_cmake_ts_files(&content),
+            Some(BuildFileKind::QMake) => parse_qmake_ts_files(&content),
+            None => continue,
+        };
+        let build_file_dir = build_file.parent().map(|dir| relative(&dir.to_path_buf())).unwrap_or_default();
+        for declared_path in declared.iter().filter_map(|declared| resolve_declared_path(declared, &build_file_dir)) {
+            // Paths that are not valid globs can't match any file either.
+            let Ok(pattern) = PathGlobs::new(&[&declared_path]) else {
+                continue;
+            };
+            for file in translation_files.iter().filter(|file| pattern.is_match(&relative(file))) {
+                if !declared_files.contains(file) {
+                    declared_files.push(file.clone());
                 }
             }
         }
     }
+    declared_files.sort();
+    declared_files
+}
 
-    false
+/// Compile the ignore patterns, which are globs relative to the project root like `third_party/**` or `**/tests/**`.
+///
+/// Like in .gitignore, a pattern without a slash (e.g. `build`) matches at any depth, a trailing slash is ignored, and
+/// a pattern starting with `!` keeps what the others would ignore. Files in an ignored directory can't be kept this way,
+/// since the directory is not scanned at all.
+fn ignore_patterns(ignore_paths: &[String]) -> Result<PathGlobs, globset::Error> {
+    let globs: Vec<String> = ignore_paths.iter()
+        .map(|ignore_path| {
+            let (negation, glob) = ignore_path.strip_prefix('!').map_or(("", ignore_path.as_str()), |glob| ("!", glob));
+            (negation, glob.trim_end_matches('/'))
+        })
+        .filter(|(_, glob)| !glob.is_empty())
+        .map(|(negation, glob)| match glob.contains('/') {
+            true => format!("{negation}{glob}"),
+            false => format!("{negation}**/{glob}"),
+        })
+        .collect();
+    PathGlobs::new(&globs)
+}
+
+fn should_ignore_entry(entry: &walkdir::DirEntry, project_root: &PathBuf, ignore_patterns: &PathGlobs) -> bool {
+    // Get relative path from project root
+    let Ok(relative_path) = entry.path().strip_prefix(project_root) else {
+        return false;
+    };
+    if entry.file_type().is_dir() && entry.file_name().to_str().is_some_and(|name| ALWAYS_IGNORED_DIRS.contains(&name)) {
+        return true;
+    }
+    let relative_path_str = relative_path.to_string_lossy().replace('\\', "/");
+    // Children of a matched directory are never visited, since `filter_entry` skips the whole directory.
+    // Only keeping patterns ignore nothing.
+    ignore_patterns.has_include() && ignore_patterns.is_match(&relative_path_str)
 }
 
 fn identify_source_files(project_root: &PathBuf, all_files: &[PathBuf]) -> Result<Vec<PathBuf>, CmdError> {
@@ -157,7 +472,7 @@ fn identify_source_files(project_root: &PathBuf, all_files: &[PathBuf]) -> Resul
             let pattern_key = get_translation_pattern_with_inference(relative_path, all_files, project_root);
 
             pattern_candidates.entry(pattern_key)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(file_path.clone());
         }
     }
@@ -204,7 +519,7 @@ fn select_best_source_file(candidates: &[PathBuf]) -> Option<PathBuf> {
 /// Get priority score for source file selection
 /// Higher score means higher priority
 /// Priority: no language code > en > en_US > en_GB
-fn get_source_file_priority(file_path: &PathBuf) -> u32 {
+fn get_source_file_priority(file_path: &std::path::Path) -> u32 {
     let filename = file_path.file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("");
@@ -218,7 +533,7 @@ fn get_source_file_priority(file_path: &PathBuf) -> u32 {
     }
 
     // Check for specific English variants in priority order
-    for lang_code in &detected_langs {
+    if let Some(lang_code) = detected_langs.first() {
         match lang_code.as_str() {
             "en" => return 90,           // en has higher priority than en_US/en_GB
             "en_US" => return 80,        // en_US has higher priority than en_GB
@@ -384,6 +699,12 @@ fn is_likely_source_file(project_root: &PathBuf, file_path: &PathBuf, all_files:
         return false;
     }
 
+    // Other translatable assets like .desktop files are rarely split per language, and their reverse-DNS names like
+    // `org.deepin.app.json` would be mistaken for language codes below
+    if ResourceFileType::detect(file_path).is_some_and(|file_type| !matches!(file_type, ResourceFileType::Linguist | ResourceFileType::Gettext)) {
+        return true;
+    }
+
     // Case 3: Filename contains obvious non-English language codes, not a source file
     let has_non_english = contains_non_english_language_code(filename);
     if has_non_english {
@@ -423,7 +744,7 @@ fn get_language_folder_in_path(path: &std::path::Path) -> Option<String> {
         if let std::path::Component::Normal(name) = component {
             let name_str = name.to_string_lossy();
             // Skip directory names that are file extensions
-            if !is_file_extension(&name_str) && is_language_code(&name_str) {
+            if !is_file_extension(&name_str) && is_valid_language_code(&name_str) {
                 // Verify this is actually a language code by checking if similar files exist
                 if verify_language_code_in_path(path, &name_str) {
                     return Some(name_str.to_string());
@@ -435,7 +756,7 @@ fn get_language_folder_in_path(path: &std::path::Path) -> Option<String> {
 }
 
 fn is_english_language_code(lang_code: &str) -> bool {
-    matches!(lang_code, "en" | "en_US" | "en_GB")
+    matches!(lang_code, "en" | "en_US" | "en_GB" | "en-US" | "en-GB")
 }
 
 fn contains_non_english_language_code(filename: &str) -> bool {
@@ -482,7 +803,7 @@ fn has_related_translation_files(_project_root: &PathBuf, source_file: &PathBuf,
             }
             // Check if suffix is a language code
             let lang_part = suffix.split('.').next().unwrap_or("");
-            if is_language_code(lang_part) {
+            if is_valid_language_code(lang_part) {
                 return true;
             }
         }
@@ -500,16 +821,9 @@ fn is_common_source_po_file(filename: &str) -> bool {
     filename == "base.po"
 }
 
-/// Check if a string matches ISO 639/3166 language code format
-/// Supports formats: xx (ISO 639 language) or xx_YY (language_REGION)
-fn is_language_code(code: &str) -> bool {
-    // Regex for ISO 639/3166 format: xx or xx_YY where:
-    // - xx is 2 lowercase letters (ISO 639 language code), note that some files
-    //      use 3 letters language codes (kab, ast), so we use 2-3 letters for now.
-    // - YY is 2 or 3 uppercase letters (ISO 3166 country/region code)
-    let lang_regex = Regex::new(r"^[a-z]{2,3}(_[A-Z]{2,3})?$").unwrap();
-    lang_regex.is_match(code)
-}
+/// Regex of a language code candidate: an ISO 639 language, optionally followed by an ISO 15924 script and an ISO 3166 (or UN M.49)
+/// region, separated by `_` or `-`, and a glibc locale modifier, e.g. `zh_CN`, `pt-BR`, `zh-Hans`, `sr@latin` or `ca@valencia`.
+const LANGUAGE_CODE_PATTERN: &str = r"[a-z]{2,3}(?:[-_][A-Z][a-z]{3})?(?:[-_](?:[A-Z]{2,3}|[0-9]{3}))?(?:@[a-z]+)?";
 
 /// Find all language codes in a file path (both filename and directory components)
 fn find_language_codes_in_path(path: &std::path::Path) -> Vec<String> {
@@ -525,7 +839,7 @@ fn find_language_codes_in_path(path: &std::path::Path) -> Vec<String> {
         if let std::path::Component::Normal(name) = component {
             let name_str = name.to_string_lossy();
             // Skip directory names that are file extensions
-            if !is_file_extension(&name_str) && is_language_code(&name_str) {
+            if !is_file_extension(&name_str) && is_valid_language_code(&name_str) {
                 // Verify this is actually a language code by checking if similar files exist
                 if verify_language_code_in_path(path, &name_str) {
                     codes.push(name_str.to_string());
@@ -556,7 +870,7 @@ fn is_file_extension(s: &str) -> bool {
 
 
 /// Find language codes in a filename using strict patterns (only at the end before extension)
-fn find_language_codes_in_filename(filename: &str) -> Vec<String> {
+pub(crate) fn find_language_codes_in_filename(filename: &str) -> Vec<String> {
     let mut codes = Vec::new();
 
     // Get the file stem (filename without extension) to avoid matching extensions
@@ -567,7 +881,7 @@ fn find_language_codes_in_filename(filename: &str) -> Vec<String> {
 
     // Only match language codes that are at the end of the filename (just before extension)
     // Pattern 1: filename_xx or filename_xx_YY (underscore separated, at the end)
-    let underscore_regex = Regex::new(r"_([a-z]{2,3}(?:_[A-Z]{2,3})?)$").unwrap();
+    let underscore_regex = Regex::new(&format!("_({LANGUAGE_CODE_PATTERN})$")).unwrap();
     if let Some(cap) = underscore_regex.captures(file_stem) {
         if let Some(code) = cap.get(1) {
             codes.push(code.as_str().to_string());
@@ -575,13 +889,16 @@ fn find_language_codes_in_filename(filename: &str) -> Vec<String> {
     }
 
     // Pattern 2: filename.xx or filename.xx_YY (dot separated, at the end)
-    let dot_regex = Regex::new(r"\.([a-z]{2,3}(?:_[A-Z]{2,3})?)$").unwrap();
+    let dot_regex = Regex::new(&format!(r"\.({LANGUAGE_CODE_PATTERN})$")).unwrap();
     if let Some(cap) = dot_regex.captures(file_stem) {
         if let Some(code) = cap.get(1) {
             codes.push(code.as_str().to_string());
         }
     }
 
+    // The regex only finds candidates, only keep the ones made of known subtags
+    codes.retain(|code| is_valid_language_code(code));
+
     // Remove duplicates
     codes.sort();
     codes.dedup();
@@ -595,7 +912,7 @@ fn verify_language_code_in_path(_file_path: &std::path::Path, suspected_lang_cod
     #[cfg(test)]
     {
         println!("Not verifying language code in path because of test mode: {}", suspected_lang_code);
-        return true;
+        true
     }
 
     #[cfg(not(test))]
@@ -618,7 +935,7 @@ fn verify_language_code_in_path(_file_path: &std::path::Path, suspected_lang_cod
                                     if file_type.is_dir() {
                                         let file_name = entry.file_name();
                                         let dir_name = file_name.to_string_lossy();
-                                        if dir_name != suspected_lang_code && is_language_code(&dir_name) {
+                                        if dir_name != suspected_lang_code && is_valid_language_code(&dir_name) {
                                             // Found another language directory at the same level
                                             // Check if the same file structure exists there
                                             let mut test_components = parent_components.clone();
@@ -647,7 +964,107 @@ fn verify_language_code_in_path(_file_path: &std::path::Path, suspected_lang_cod
     }
 }
 
-fn generate_transifex_yaml(project_root: &PathBuf, translation_files: &[PathBuf]) -> Result<TransifexYaml, CmdError> {
+fn print_removed_sources(removed: &[String]) {
+    if removed.is_empty() {
+        return;
+    }
+    eprintln!("The following source files are configured but were not found anymore, please remove them manually if they are gone:");
+    for source in removed {
+        eprintln!("- {}", source);
+    }
+}
+
+/// Maximum length of resource slugs accepted by Transifex.
+const MAX_RESOURCE_SLUG_LEN: usize = 50;
+
+/// Derive a resource slug from the source path, e.g. `translations/dde-dock_en.ts` becomes `translations-dde-dock-en`.
+fn sanitize_resource_slug(source_file: &str) -> String {
+    let source_file = source_file.strip_prefix("./").unwrap_or(source_file);
+    let without_ext = source_file.rsplit_once('.').map_or(source_file, |(stem, _)| stem);
+    let mut slug = String::new();
+    for c in without_ext.chars() {
+        match c.is_ascii_alphanumeric() {
+            true => slug.push(c.to_ascii_lowercase()),
+            false if !slug.is_empty() && !slug.ends_with('-') => slug.push('-'),
+            false => (),
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+fn hash_suffixed_slug(slug: &str, source_file: &str) -> String {
+    let hash = &content_hash(source_file.as_bytes())[..8];
+    let prefix: String = slug.chars().take(MAX_RESOURCE_SLUG_LEN - hash.len() - 1).collect();
+    format!("{}-{}", prefix.trim_end_matches('-'), hash)
+}
+
+/// Replace the `unknown-res-N` placeholders with slugs derived from the source path, so the same project always yields
+/// the same slugs. Slugs that collide or are too long get a hash of the source path as suffix.
+fn assign_stable_resource_slugs(tx_config: &mut TxConfig) {
+    let slugs: Vec<String> = tx_config.resource_sections.iter().map(|section| sanitize_resource_slug(&section.source_file)).collect();
+    for (index, section) in tx_config.resource_sections.iter_mut().enumerate() {
+        let slug = &slugs[index];
+        let collides = slugs.iter().filter(|other| *other == slug).count() > 1;
+        let slug = match collides || slug.is_empty() || slug.len() > MAX_RESOURCE_SLUG_LEN {
+            true => hash_suffixed_slug(slug, &section.source_file),
+            false => slug.clone(),
+        };
+        section.resource_full_slug = format!("{PLACEHOLDER_SLUG_PREFIX}p:unknown-proj:r:{slug}");
+    }
+}
+
+/// Let the user accept, edit the target pattern of, or skip each detected resource, reading answers from `input`.
+fn review_filters(filters: Vec<Filter>, input: &mut impl BufRead) -> Result<Vec<Filter>, CmdError> {
+    let mut read_line = || {
+        let mut line = String::new();
+        input.read_line(&mut line).map_err(CmdError::ReadInput)?;
+        Ok::<_, CmdError>(line.trim().to_string())
+    };
+    let mut reviewed = Vec::new();
+    for mut filter in filters {
+        eprintln!("\nSource file: {}\nTarget pattern: {}", filter.source, filter.target_pattern);
+        loop {
+            eprintln!("[A]ccept, [e]dit the target pattern, or [s]kip this resource?");
+            match read_line()?.to_lowercase().as_str() {
+                "" | "a" | "accept" => {
+                    reviewed.push(filter);
+                    break;
+                },
+                "e" | "edit" => {
+                    eprintln!("Enter the new target pattern, which must contain <lang>:");
+                    let target_pattern = read_line()?;
+                    if target_pattern.contains("<lang>") {
+                        filter.target_pattern = target_pattern;
+                        reviewed.push(filter);
+                        break;
+                    }
+                    eprintln!("Invalid target pattern {:?}, it does not contain <lang>", target_pattern);
+                },
+                "s" | "skip" => break,
+                answer => eprintln!("Unknown answer {:?}", answer),
+            }
+        }
+    }
+    Ok(reviewed)
+}
+
+/// Infer the language of a source file from its name (e.g. `app_en.ts` is `en`) or a language folder in its path
+/// (e.g. `en_US/app.po`).
+fn infer_source_language(relative_path: &str) -> Option<String> {
+    let path = std::path::Path::new(relative_path);
+    let file_name = path.file_name()?.to_string_lossy();
+    if let Some(code) = find_language_codes_in_filename(&file_name).into_iter().find(|code| !is_file_extension(code)) {
+        return Some(code);
+    }
+    path.parent()?.components().rev().find_map(|component| match component {
+        std::path::Component::Normal(name) if is_english_language_code(&name.to_string_lossy()) => Some(name.to_string_lossy().to_string()),
+        _ => None,
+    })
+}
+
+/// Generate filters for the source files. The source language is `source_language` if given, otherwise inferred
+/// from each source file, falling back to `en_US`.
+fn generate_transifex_yaml(project_root: &PathBuf, translation_files: &[PathBuf], source_language: Option<&str>) -> Result<TransifexYaml, CmdError> {
     let mut filters = Vec::new();
 
     for file_path in translation_files {
@@ -658,22 +1075,27 @@ fn generate_transifex_yaml(project_root: &PathBuf, translation_files: &[PathBuf]
             .to_string();
 
         // Determine file format
-        let file_kind = I18nFileKind::from_ext_hint(file_path)
-            .map_err(|_| CmdError::UnknownI18nFileType { path: file_path.clone() })?;
-
-        let file_format = match file_kind {
-            I18nFileKind::Linguist => "QT",
-            I18nFileKind::Gettext => "PO",
+        let Some(file_format) = ResourceFileType::detect(file_path)
+            .ok_or_else(|| CmdError::UnknownI18nFileType { path: file_path.clone() })?
+            .transifex_format() else {
+            eprintln!("Note: Skipping {relative_path:?}, Transifex has no file format that keeps its translations in the file itself");
+            continue;
         };
 
         // Generate translation file expression
-        let translation_expression = generate_translation_expression(&relative_path);
+        let translation_expression = match IN_SOURCE_FILE_FORMATS.contains(&file_format) {
+            true => relative_path.clone(),
+            false => generate_translation_expression(&relative_path),
+        };
+        let source_lang = source_language.map(ToOwned::to_owned)
+            .or_else(|| infer_source_language(&relative_path))
+            .unwrap_or_else(|| "en_US".to_string());
 
         let filter = Filter {
             type_attr: "file".to_string(),
             source: relative_path,
             format: file_format.to_string(),
-            source_lang: "en_US".to_string(),
+            source_lang,
             target_pattern: translation_expression,
         };
 
@@ -735,34 +1157,248 @@ fn generate_translation_expression(source_file: &str) -> String {
 mod tests {
     use super::*;
 
+    const TS_HEADER: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<!DOCTYPE TS>\n<TS version=\"2.1\"/>\n";
+
+    #[test]
+    fn tst_parse_build_file_ts_files() {
+        let cmake = r#"
+file(GLOB TS_FILES "translations/*.ts") # all translations
+set(EXTRA_TS ${CMAKE_CURRENT_SOURCE_DIR}/../misc/desktop.ts)
+qt5_add_translation(QM_FILES ${TS_FILES})
+qt_add_translations(app
+    TS_FILES
+        ${EXTRA_TS}
+        "po/ignored.po"
+)
+"#;
+        assert_eq!(parse_cmake_ts_files(cmake), ["translations/*.ts", "${CMAKE_CURRENT_SOURCE_DIR}/../misc/desktop.ts"]);
+
+        let qmake = "TRANSLATIONS += $$PWD/translations/app.ts \\\n    translations/app_zh_CN.ts\n# TRANSLATIONS += old.ts\nSOURCES += main.cpp\n";
+        assert_eq!(parse_qmake_ts_files(qmake), ["$$PWD/translations/app.ts", "translations/app_zh_CN.ts"]);
+
+        assert_eq!(resolve_declared_path("${CMAKE_CURRENT_SOURCE_DIR}/../misc/desktop.ts", "src").as_deref(), Some("misc/desktop.ts"));
+        assert_eq!(resolve_declared_path("${PROJECT_SOURCE_DIR}/translations/app.ts", "src").as_deref(), Some("translations/app.ts"));
+        assert_eq!(resolve_declared_path("$$PWD/app.ts", "src").as_deref(), Some("src/app.ts"));
+        assert_eq!(resolve_declared_path("${UNKNOWN}/app.ts", "src"), None);
+        assert_eq!(resolve_declared_path("../../app.ts", "src"), None);
+    }
+
+    #[test]
+    fn tst_scan_with_declared_ts_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_root = temp_dir.path().to_path_buf();
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        fs::create_dir_all(project_root.join("tests/data")).unwrap();
+        for file in ["translations/app.ts", "translations/app_zh_CN.ts", "tests/data/sample.ts"] {
+            fs::write(project_root.join(file), TS_HEADER).unwrap();
+        }
+        fs::write(project_root.join("CMakeLists.txt"), "file(GLOB TS_FILES translations/*.ts)\nqt_add_translations(app TS_FILES ${TS_FILES})\n").unwrap();
+        let files = scan_all_translation_files(&project_root, &[], false).unwrap();
+        assert_eq!(files, [project_root.join("translations/app.ts"), project_root.join("translations/app_zh_CN.ts")]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn tst_scan_with_symlinks() {
+        let project_temp_dir = tempfile::tempdir().unwrap();
+        let project_root = project_temp_dir.path().to_path_buf();
+        let external_temp_dir = tempfile::tempdir().unwrap();
+        let external_dir = external_temp_dir.path().to_path_buf();
+        for dir in ["translations", ".git", "node_modules/pkg"] {
+            fs::create_dir_all(project_root.join(dir)).unwrap();
+            fs::write(project_root.join(dir).join("app.ts"), TS_HEADER).unwrap();
+        }
+        fs::write(external_dir.join("shared.ts"), TS_HEADER).unwrap();
+        std::os::unix::fs::symlink(project_root.join("translations"), project_root.join("linked")).unwrap();
+        std::os::unix::fs::symlink(&project_root, project_root.join("translations/loop")).unwrap();
+        std::os::unix::fs::symlink(&external_dir, project_root.join("external")).unwrap();
+        let ignore_paths: Vec<String> = DEFAULT_IGNORE_PATHS.iter().map(|path| path.to_string()).collect();
+        let scan = |follow_symlinks: bool| {
+            let mut files: Vec<String> = scan_all_translation_files(&project_root, &ignore_paths, follow_symlinks).unwrap().iter()
+                .map(|file| file.strip_prefix(&project_root).unwrap().to_string_lossy().to_string())
+                .collect();
+            files.sort();
+            files
+        };
+        let not_followed = scan(false);
+        let followed = scan(true);
+        assert_eq!(not_followed, ["translations/app.ts"]);
+        assert_eq!(followed, ["external/shared.ts", "translations/app.ts"]);
+    }
+
+    #[test]
+    fn tst_detect_resource_file_types() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_root = temp_dir.path().to_path_buf();
+        fs::create_dir_all(project_root.join("misc/configs")).unwrap();
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        fs::create_dir_all(project_root.join("src")).unwrap();
+        let files = [
+            ("translations/app.ts", TS_HEADER),
+            ("translations/app_zh_CN.ts", TS_HEADER),
+            ("src/main.ts", "import { app } from './app';\n"),
+            ("misc/app.desktop", "[Desktop Entry]\nName=App\n"),
+            ("misc/org.deepin.app.metainfo.xml.in", "<component/>"),
+            ("misc/configs/org.deepin.app.json", r#"{"magic": "dsg.config.meta", "version": "1.0", "contents": {}}"#),
+            ("misc/package.json", r#"{"name": "app"}"#),
+        ];
+        for (file, content) in files {
+            fs::write(project_root.join(file), content).unwrap();
+        }
+        let tx_yaml = discover_transifex_yaml(&project_root, &[]).unwrap();
+        let formats: Vec<(&str, &str, &str)> = tx_yaml.filters.iter()
+            .map(|filter| (filter.source.as_str(), filter.format.as_str(), filter.target_pattern.as_str()))
+            .collect();
+        assert_eq!(formats, [
+            ("misc/app.desktop", "DESKTOP", "misc/app.desktop"),
+            ("translations/app.ts", "QT", "translations/app_<lang>.ts"),
+        ]);
+
+        // What gentxcfg generates should pass lint-config
+        let tx_yaml_content = serde_yaml2::to_string(&tx_yaml).unwrap();
+        assert_eq!(crate::subcmd::lint_config::lint_transifex_yaml(&tx_yaml_content, Some(&project_root)), []);
+        let mut tx_config = tx_yaml.to_tx_config("".to_string(), None, vec![]);
+        assign_stable_resource_slugs(&mut tx_config);
+        assert_eq!(crate::subcmd::lint_config::lint_tx_config(&tx_config.to_str(), Some(&project_root)), []);
+    }
+    use crate::transifex::tx_config_file::TxConfigSectionResource;
+
+    #[test]
+    fn tst_assign_stable_resource_slugs() {
+        let section = |source_file: &str| TxConfigSectionResource {
+            source_file: source_file.to_string(),
+            ..TxConfigSectionResource::default()
+        };
+        let mut tx_config = TxConfig {
+            resource_sections: vec![
+                section("translations/dde-dock_en.ts"),
+                section("./po/app.pot"),
+                section("po/app.po"),
+                section("a/very/deeply/nested/directory/structure/with/translations/app.ts"),
+            ],
+            ..TxConfig::default()
+        };
+        assign_stable_resource_slugs(&mut tx_config);
+        let slugs: Vec<String> = tx_config.resource_sections.iter().map(|section| section.get_opr_slugs().unwrap().2).collect();
+        assert_eq!(slugs[0], "translations-dde-dock-en");
+        assert!(slugs[1].starts_with("po-app-") && slugs[2].starts_with("po-app-") && slugs[1] != slugs[2]);
+        assert!(slugs[3].len() <= MAX_RESOURCE_SLUG_LEN);
+        assert!(tx_config.resource_sections[0].is_placeholder());
+
+        // Re-running yields the same slugs.
+        let rerun_slugs: Vec<String> = {
+            assign_stable_resource_slugs(&mut tx_config);
+            tx_config.resource_sections.iter().map(|section| section.get_opr_slugs().unwrap().2).collect()
+        };
+        assert_eq!(slugs, rerun_slugs);
+    }
+
+    #[test]
+    fn tst_discover_with_language_variants() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_root = temp_dir.path().to_path_buf();
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        for lang in ["", "_sr@latin", "_zh-Hans", "_pt-BR", "_ca@valencia"] {
+            fs::write(project_root.join(format!("translations/app{lang}.ts")), TS_HEADER).unwrap();
+        }
+        let tx_yaml = discover_transifex_yaml(&project_root, &[]).unwrap();
+        let filters: Vec<(&str, &str)> = tx_yaml.filters.iter().map(|filter| (filter.source.as_str(), filter.target_pattern.as_str())).collect();
+        assert_eq!(filters, [("translations/app.ts", "translations/app_<lang>.ts")]);
+    }
+
+    #[test]
+    fn tst_infer_source_language() {
+        assert_eq!(infer_source_language("translations/app_en.ts").as_deref(), Some("en"));
+        assert_eq!(infer_source_language("translations/app_en_US.ts").as_deref(), Some("en_US"));
+        assert_eq!(infer_source_language("po/app.en.po").as_deref(), Some("en"));
+        assert_eq!(infer_source_language("locale/en_GB/app.po").as_deref(), Some("en_GB"));
+        assert_eq!(infer_source_language("translations/app.ts"), None);
+    }
+
+    #[test]
+    fn tst_review_filters() {
+        let filter = |source: &str| Filter {
+            type_attr: "file".to_string(),
+            source: source.to_string(),
+            format: "QT".to_string(),
+            source_lang: "en_US".to_string(),
+            target_pattern: source.replace(".ts", "_<lang>.ts"),
+        };
+        let filters = vec![filter("a.ts"), filter("b.ts"), filter("c.ts"), filter("d.ts")];
+        // Accept with Enter, edit with an invalid pattern first, skip, then an unknown answer before accepting.
+        let mut input = "\ne\nb.ts\ne\nlang/b_<lang>.ts\ns\nx\na\n".as_bytes();
+        let reviewed = review_filters(filters, &mut input).unwrap();
+        assert_eq!(reviewed.iter().map(|filter| filter.target_pattern.as_str()).collect::<Vec<_>>(), ["a_<lang>.ts", "lang/b_<lang>.ts", "d_<lang>.ts"]);
+    }
+
+    #[test]
+    fn tst_scan_with_ignore_patterns() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_root = temp_dir.path().to_path_buf();
+        for dir in ["translations", "build/translations", "third_party/lib/translations", "src/tests/translations", "src/buildtools"] {
+            fs::create_dir_all(project_root.join(dir)).unwrap();
+            fs::write(project_root.join(dir).join("app.ts"), TS_HEADER).unwrap();
+        }
+        let scan = |ignore_paths: &[&str]| {
+            let ignore_paths: Vec<String> = ignore_paths.iter().map(|path| path.to_string()).collect();
+            let mut files: Vec<String> = scan_all_translation_files(&project_root, &ignore_paths, false).unwrap().iter()
+                .map(|file| file.strip_prefix(&project_root).unwrap().to_string_lossy().replace('\\', "/"))
+                .collect();
+            files.sort();
+            files
+        };
+        let files = scan(&["build", "third_party/**", "**/tests/**"]);
+        assert_eq!(files, ["src/buildtools/app.ts", "translations/app.ts"]);
+        let files = scan(&["**/translations/**", "!third_party/**"]);
+        assert_eq!(files, ["src/buildtools/app.ts", "third_party/lib/translations/app.ts"]);
+        let files = scan(&["[bt]*/*"]);
+        assert_eq!(files, ["src/buildtools/app.ts", "src/tests/translations/app.ts"]);
+        assert!(matches!(scan_all_translation_files(&project_root, &["[".to_string()], false), Err(CmdError::InvalidIgnorePath(_))));
+    }
+
     #[test]
     fn test_language_code_detection() {
         // Test ISO 639/3166 language code format validation
-        assert!(is_language_code("en"));
-        assert!(is_language_code("zh"));
-        assert!(is_language_code("en_US"));
-        assert!(is_language_code("zh_CN"));
-        assert!(is_language_code("zh_TW"));
-        assert!(is_language_code("pt_BR"));
-        assert!(is_language_code("fr"));
-        assert!(is_language_code("de"));
-        assert!(is_language_code("ja"));
+        assert!(is_valid_language_code("en"));
+        assert!(is_valid_language_code("zh"));
+        assert!(is_valid_language_code("en_US"));
+        assert!(is_valid_language_code("zh_CN"));
+        assert!(is_valid_language_code("zh_TW"));
+        assert!(is_valid_language_code("pt_BR"));
+        assert!(is_valid_language_code("fr"));
+        assert!(is_valid_language_code("de"));
+        assert!(is_valid_language_code("ja"));
         // Test 3-letter "language codes"
-        assert!(is_language_code("kab"));
-        assert!(is_language_code("ast"));
+        assert!(is_valid_language_code("kab"));
+        assert!(is_valid_language_code("ast"));
 
         // Test invalid formats
-        assert!(!is_language_code("english"));
-        assert!(!is_language_code("EN"));
-        assert!(!is_language_code("en_us"));
-        assert!(!is_language_code("zh_cn"));
-        assert!(!is_language_code(""));
+        assert!(!is_valid_language_code("english"));
+        assert!(!is_valid_language_code("EN"));
+        assert!(!is_valid_language_code("en_us"));
+        assert!(!is_valid_language_code("zh_cn"));
+        assert!(!is_valid_language_code(""));
 
         // Test valid language codes that might look like file extensions
-        assert!(is_language_code("so")); // Somali language
-        assert!(is_language_code("in")); // Indonesian language (deprecated, but valid)
-        assert!(is_language_code("os")); // Ossetian language
-        assert!(is_language_code("io")); // Ido language
+        assert!(is_valid_language_code("so")); // Somali language
+        assert!(is_valid_language_code("in")); // Indonesian language (deprecated, but valid)
+        assert!(is_valid_language_code("os")); // Ossetian language
+        assert!(is_valid_language_code("io")); // Ido language
+
+        // Test script subtags, hyphenated regions and modifiers
+        assert!(is_valid_language_code("sr@latin"));
+        assert!(is_valid_language_code("ca@valencia"));
+        assert!(is_valid_language_code("zh-Hans"));
+        assert!(is_valid_language_code("zh_Hant_TW"));
+        assert!(is_valid_language_code("pt-BR"));
+        assert!(is_valid_language_code("es_419"));
+        assert!(!is_valid_language_code("zh-hans"));
+        assert!(!is_valid_language_code("sr@"));
+
+        // Test codes that match the format but are made of unknown subtags
+        assert!(!is_valid_language_code("xx"));
+        assert!(!is_valid_language_code("en_XX"));
+        assert!(!is_valid_language_code("sr@foo"));
 
         // Test file extension detection
         assert!(is_file_extension("po"));
@@ -785,6 +1421,12 @@ mod tests {
         assert_eq!(find_language_codes_in_filename("fr.po"), Vec::<String>::new()); // Language code as whole filename not supported
         assert_eq!(find_language_codes_in_filename("app.ts"), Vec::<String>::new());
         assert_eq!(find_language_codes_in_filename("strings_so.po"), vec!["so"]); // Somali language
+        assert_eq!(find_language_codes_in_filename("app_sr@latin.ts"), vec!["sr@latin"]);
+        assert_eq!(find_language_codes_in_filename("app.ca@valencia.po"), vec!["ca@valencia"]);
+        assert_eq!(find_language_codes_in_filename("app_zh-Hans.ts"), vec!["zh-Hans"]);
+        assert_eq!(find_language_codes_in_filename("app_pt-BR.ts"), vec!["pt-BR"]);
+        assert_eq!(find_language_codes_in_filename("app_old.ts"), Vec::<String>::new()); // Not a known language
+        assert_eq!(find_language_codes_in_filename("app_main_XX.ts"), Vec::<String>::new()); // Not a known region
 
         // Test that file extensions are not detected as language codes
         assert_eq!(find_language_codes_in_filename("po.po"), Vec::<String>::new()); // 'po' should be filtered out as extension
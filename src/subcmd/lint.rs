@@ -0,0 +1,238 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use thiserror::Error as TeError;
+
+use crate::transifex::rest_api::{TransifexRestApi, TransifexRestApiError};
+use crate::transifex::tx_config_file::{load_project_tx_config_file, LoadTxConfigError, TxConfig};
+use crate::transifex::yaml_file::{try_load_transifex_yaml_file, TxResourceLookupEntry};
+
+use super::statistics::{language_mismatch_warnings, local_languages};
+use super::yaml2txconfig::{create_linked_resources_table, detect_github_repository};
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Fail to load .tx/config file because: {0}")]
+    LoadTxConfig(#[source] LoadTxConfigError),
+    #[error("Fail to load ~/.transifexrc or TX_TOKEN because: {0}")]
+    LoadTransifexRc(#[source] LoadTxConfigError),
+    #[error("Fail to fetch resources of {0} from Transifex because: {1}")]
+    FetchResources(String, #[source] TransifexRestApiError),
+    #[error("Fail to fetch languages of {0} from Transifex because: {1}")]
+    FetchLanguages(String, #[source] TransifexRestApiError),
+    #[error("{0} problem(s) found")]
+    Findings(usize),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LintFinding {
+    /// Name of the check, e.g. `placeholder-slug`.
+    pub rule: &'static str,
+    /// Where the problem is, e.g. a file path or a .tx/config section.
+    pub location: String,
+    pub message: String,
+}
+
+/// Resources whose slug was never resolved, they can not be pushed or pulled.
+fn lint_placeholder_slugs(tx_config: &TxConfig) -> Vec<LintFinding> {
+    tx_config.resource_sections.iter()
+        .filter(|section| section.is_placeholder())
+        .map(|section| LintFinding {
+            rule: "placeholder-slug",
+            location: format!("[{}]", section.resource_full_slug),
+            message: format!("resource of {} has no Transifex slug yet, see `yaml2txconfig --merge`", section.source_file),
+        })
+        .collect()
+}
+
+/// Resources in .tx/config that do not exist on Transifex, e.g. because of a typo or a renamed resource.
+fn lint_remote_slugs(tx_config: &TxConfig, remote_resource_ids: &BTreeSet<String>) -> Vec<LintFinding> {
+    tx_config.resource_sections.iter()
+        .filter(|section| !section.is_placeholder() && !remote_resource_ids.contains(&section.resource_full_slug))
+        .map(|section| LintFinding {
+            rule: "remote-slug",
+            location: format!("[{}]", section.resource_full_slug),
+            message: format!("resource of {} does not exist on Transifex", section.source_file),
+        })
+        .collect()
+}
+
+/// Resources that Transifex has linked to the GitHub repository of the project, but are not configured locally.
+fn lint_unconfigured_linked_resources(tx_config: &TxConfig, local_source_files: &BTreeSet<String>, github_repository: &str, linked_resources: &[TxResourceLookupEntry]) -> Vec<LintFinding> {
+    let mut reported = BTreeSet::new();
+    linked_resources.iter()
+        .filter(|entry| entry.repository == github_repository)
+        .filter(|entry| !local_source_files.contains(normalize_source_file(&entry.resource)))
+        .filter(|entry| !tx_config.resource_sections.iter().any(|section| section.resource_full_slug == entry.transifex_resource_id))
+        .filter(|entry| reported.insert(entry.transifex_resource_id.clone()))
+        .map(|entry| LintFinding {
+            rule: "unconfigured-linked-resource",
+            location: entry.resource.clone(),
+            message: format!("linked to {github_repository} on Transifex as {} (branch {}) but not configured locally", entry.transifex_resource_id, entry.branch),
+        })
+        .collect()
+}
+
+fn normalize_source_file(source_file: &str) -> &str {
+    source_file.strip_prefix("./").unwrap_or(source_file)
+}
+
+/// Source files configured locally, in .tx/config and transifex.yaml (if any).
+fn local_source_files(project_root: &Path, tx_config: &TxConfig) -> BTreeSet<String> {
+    let mut source_files: BTreeSet<String> = tx_config.resource_sections.iter()
+        .map(|section| normalize_source_file(&section.source_file).to_string())
+        .collect();
+    if let Ok((_, tx_yaml)) = try_load_transifex_yaml_file(project_root) {
+        source_files.extend(tx_yaml.filters.iter().map(|filter| normalize_source_file(&filter.source).to_string()));
+    }
+    source_files
+}
+
+struct RemoteResources {
+    /// IDs of all resources in the projects used by .tx/config.
+    resource_ids: BTreeSet<String>,
+    /// Resources of these projects that are linked to a GitHub repository.
+    linked_resources: Vec<TxResourceLookupEntry>,
+    /// Target languages enabled in these projects.
+    languages: BTreeSet<String>,
+}
+
+/// Collect resources on Transifex in the projects used by .tx/config.
+///
+/// The cached linked resource list of each project is tried first, all resources of the project are only fetched
+/// if some slug is not in there, e.g. for resources that are not linked to a GitHub repository.
+fn fetch_remote_resources(tx_config: &TxConfig, force_online: bool) -> Result<RemoteResources, CmdError> {
+    let mut projects = BTreeMap::<(String, String), Vec<&str>>::new();
+    for section in tx_config.resource_sections.iter().filter(|section| !section.is_placeholder()) {
+        let (organization_slug, project_slug, _) = section.get_opr_slugs().map_err(CmdError::LoadTxConfig)?;
+        projects.entry((organization_slug, project_slug)).or_default().push(&section.resource_full_slug);
+    }
+
+    let api = TransifexRestApi::new_from_transifexrc().map_err(CmdError::LoadTransifexRc)?;
+    let mut remote_resources = RemoteResources { resource_ids: BTreeSet::new(), linked_resources: vec![], languages: BTreeSet::new() };
+    for ((organization_slug, project_slug), resource_ids) in projects {
+        let languages = api.get_project_languages(&organization_slug, &project_slug)
+            .map_err(|e| CmdError::FetchLanguages(format!("o:{organization_slug}:p:{project_slug}"), e))?;
+        remote_resources.languages.extend(languages.into_iter().map(|language| language.attributes.code));
+        let linked_resources = create_linked_resources_table(&organization_slug, Some(project_slug.clone()), force_online)
+            .map_err(CmdError::LoadTransifexRc)?;
+        let cached: BTreeSet<String> = linked_resources.iter().map(|entry| entry.transifex_resource_id.clone()).collect();
+        remote_resources.linked_resources.extend(linked_resources);
+        if resource_ids.iter().all(|resource_id| cached.contains(*resource_id)) {
+            remote_resources.resource_ids.extend(cached);
+            continue;
+        }
+        let resources = api.get_all_resources(&organization_slug, &project_slug)
+            .map_err(|e| CmdError::FetchResources(format!("o:{organization_slug}:p:{project_slug}"), e))?;
+        remote_resources.resource_ids.extend(resources.into_iter().map(|resource| resource.id));
+    }
+    Ok(remote_resources)
+}
+
+/// Check the Transifex configuration of the project, and with `remote`, also check it against Transifex in both directions.
+pub fn subcmd_lint(project_root: &Path, tx_config_file: Option<&Path>, remote: bool, force_online: bool) -> Result<(), CmdError> {
+    let (tx_config_file, tx_config) = load_project_tx_config_file(project_root, tx_config_file).map_err(CmdError::LoadTxConfig)?;
+    println!("Found Transifex project config file at: {tx_config_file:?}");
+
+    let mut findings = lint_placeholder_slugs(&tx_config);
+    if remote {
+        let remote_resources = fetch_remote_resources(&tx_config, force_online)?;
+        findings.extend(lint_remote_slugs(&tx_config, &remote_resources.resource_ids));
+        match detect_github_repository(project_root) {
+            Some(github_repository) => {
+                let local_source_files = local_source_files(project_root, &tx_config);
+                findings.extend(lint_unconfigured_linked_resources(&tx_config, &local_source_files, &github_repository, &remote_resources.linked_resources));
+            },
+            None => eprintln!("Warning: Fail to detect the GitHub repository from git remote, skipping check of linked resources."),
+        }
+        // Languages only differ as the project evolves, so they are warnings instead of findings.
+        for warning in language_mismatch_warnings(&local_languages(project_root, &tx_config), &remote_resources.languages, &[], &[]) {
+            eprintln!("Warning: {warning}.");
+        }
+    }
+
+    for finding in &findings {
+        println!("{}: {} [{}]", finding.location, finding.message, finding.rule);
+    }
+    match findings.len() {
+        0 => {
+            println!("No problem found.");
+            Ok(())
+        },
+        count => Err(CmdError::Findings(count)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn tst_lint_slugs() {
+        let tx_config = TxConfig::from_str(r#"[main]
+host = https://www.transifex.com
+
+[o:linuxdeepin:p:deepin-home:r:deepin-home]
+file_filter = translations/deepin-home_<lang>.ts
+source_file = translations/deepin-home.ts
+source_lang = en
+type = QT
+
+[o:linuxdeepin:p:deepin-home:r:deepin-home-dekstop]
+file_filter = misc/desktop_<lang>.ts
+source_file = misc/desktop.ts
+source_lang = en
+type = QT
+
+[o:unknown-org:p:unknown-proj:r:unknown-res-1]
+file_filter = po/<lang>.po
+source_file = po/deepin-home.pot
+source_lang = en
+type = PO
+"#).unwrap();
+        let findings = lint_placeholder_slugs(&tx_config);
+        assert_eq!(findings.iter().map(|finding| finding.location.as_str()).collect::<Vec<_>>(), ["[o:unknown-org:p:unknown-proj:r:unknown-res-1]"]);
+
+        let remote_resource_ids = BTreeSet::from([
+            "o:linuxdeepin:p:deepin-home:r:deepin-home".to_string(),
+            "o:linuxdeepin:p:deepin-home:r:deepin-home-desktop".to_string(),
+        ]);
+        assert_eq!(lint_remote_slugs(&tx_config, &remote_resource_ids), vec![LintFinding {
+            rule: "remote-slug",
+            location: "[o:linuxdeepin:p:deepin-home:r:deepin-home-dekstop]".to_string(),
+            message: "resource of misc/desktop.ts does not exist on Transifex".to_string(),
+        }]);
+    }
+
+    #[test]
+    fn tst_lint_unconfigured_linked_resources() {
+        let tx_config = TxConfig::from_str(r#"[main]
+host = https://www.transifex.com
+
+[o:linuxdeepin:p:deepin-home:r:deepin-home]
+file_filter = translations/deepin-home_<lang>.ts
+source_file = translations/deepin-home.ts
+source_lang = en
+type = QT
+"#).unwrap();
+        let entry = |repository: &str, resource: &str, slug: &str| TxResourceLookupEntry {
+            repository: repository.to_string(),
+            branch: "master".to_string(),
+            resource: resource.to_string(),
+            transifex_resource_id: format!("o:linuxdeepin:p:deepin-home:r:{slug}"),
+        };
+        let linked_resources = vec![
+            entry("linuxdeepin/deepin-home", "translations/deepin-home.ts", "deepin-home"),
+            entry("linuxdeepin/deepin-home", "misc/desktop.ts", "desktop"),
+            entry("linuxdeepin/deepin-home", "po/deepin-home.pot", "po"),
+            entry("linuxdeepin/other", "translations/other.ts", "other"),
+        ];
+        let local_source_files = BTreeSet::from(["translations/deepin-home.ts".to_string(), "po/deepin-home.pot".to_string()]);
+        let findings = lint_unconfigured_linked_resources(&tx_config, &local_source_files, "linuxdeepin/deepin-home", &linked_resources);
+        assert_eq!(findings.iter().map(|finding| finding.location.as_str()).collect::<Vec<_>>(), ["misc/desktop.ts"]);
+    }
+}
@@ -0,0 +1,771 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error as TeError;
+use yaml_rust2::parser::{MarkedEventReceiver, Parser};
+use yaml_rust2::scanner::Marker;
+use yaml_rust2::Event;
+use regex::Regex;
+
+use crate::i18n_file::language_code::is_valid_language_code;
+use crate::subcmd::validate::{count_failures, print_findings, severity_counts, FailOn, Severity, ValidateFormat, ValidationFinding};
+use crate::transifex::yaml_file::{find_transifex_yaml_file, Filter};
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Neither transifex.yaml nor .tx/config found in {0:?}")]
+    FileNotFound(PathBuf),
+    #[error("Fail to read {0:?} because: {1}")]
+    ReadFile(PathBuf, #[source] std::io::Error),
+    #[error("Fail to serialize findings because: {0}")]
+    Serialize(#[source] serde_json::Error),
+    #[error("{0} problem(s) found")]
+    Findings(usize),
+}
+
+/// Values of `filter_type`.
+const FILTER_TYPES: &[&str] = &["file", "dir"];
+
+/// Values of `file_format`, i.e. the i18n types of Transifex resources.
+const FILE_FORMATS: &[&str] = &[
+    "ANDROID", "CHROME", "DESKTOP", "DTD", "GITHUBMARKDOWN", "HTML", "INI", "KEYVALUEJSON", "PHP_ARRAY", "PO", "PROPERTIES",
+    "QT", "RESX", "STRINGS", "STRINGSDICT", "STRUCTURED_JSON", "XLIFF", "YAML_GENERIC", "YML",
+];
+
+/// Values of `file_format` whose translations are kept in the source file, so the translation file expression is the source
+/// file itself instead of a pattern with `<lang>`.
+pub(crate) const IN_SOURCE_FILE_FORMATS: &[&str] = &["DESKTOP"];
+
+/// Keys of a filter, whether they are required for `file` and `dir` filters.
+const FILTER_KEYS: &[(&str, bool, bool)] = &[
+    ("filter_type", true, true),
+    ("file_format", true, true),
+    ("source_language", true, true),
+    ("translation_files_expression", true, true),
+    ("source_file", true, false),
+    ("source_file_dir", false, true),
+    ("source_file_extension", false, true),
+    ("language_mapping", false, false),
+];
+
+/// Keys of `settings`, whether they are required.
+const SETTINGS_KEYS: &[(&str, bool)] = &[
+    ("pr_branch_name", true),
+    ("language_mapping", false),
+];
+
+/// Keys of the `[main]` section of .tx/config, whether they are required.
+const TX_CONFIG_MAIN_KEYS: &[(&str, bool)] = &[
+    ("host", true),
+    ("minimum_perc", false),
+    ("mode", false),
+    ("lang_map", false),
+];
+
+/// Keys of resource sections of .tx/config besides `trans.<lang>`, whether they are required.
+const TX_CONFIG_RESOURCE_KEYS: &[(&str, bool)] = &[
+    ("file_filter", true),
+    ("source_file", true),
+    ("source_lang", true),
+    ("type", true),
+    ("minimum_perc", false),
+    ("resource_name", false),
+    ("lang_map", false),
+    ("replace_edited_strings", false),
+    ("keep_translations", false),
+];
+
+/// Every rule with the default severity of its findings. Rules based on heuristics only warn, so that they don't fail the
+/// check out of the box.
+pub const RULES: &[(&str, Severity)] = &[
+    ("yaml-syntax", Severity::Error),
+    ("invalid-structure", Severity::Error),
+    ("unknown-key", Severity::Error),
+    ("duplicate-key", Severity::Error),
+    ("missing-key", Severity::Error),
+    ("invalid-value", Severity::Error),
+    ("unknown-file-format", Severity::Error),
+    ("missing-lang-placeholder", Severity::Error),
+    ("duplicate-source", Severity::Error),
+    ("duplicate-section", Severity::Error),
+    ("invalid-section-name", Severity::Error),
+    ("invalid-language-code", Severity::Error),
+    ("missing-source-file", Severity::Error),
+    ("case-mismatch", Severity::Error),
+    ("unresolved-file-filter", Severity::Warning),
+    ("suspicious-language-code", Severity::Warning),
+    ("inconsistent-source-lang", Severity::Warning),
+];
+
+/// A finding with the default severity of its rule, see [`RULES`]. The file is set by [`subcmd_lint_config`].
+fn finding(line: usize, rule: &'static str, message: String) -> ValidationFinding {
+    let severity = RULES.iter().find(|(name, _)| *name == rule).map_or(Severity::Error, |(_, severity)| *severity);
+    ValidationFinding { file: PathBuf::new(), line, rule, severity, message }
+}
+
+#[derive(Debug)]
+enum YamlValue {
+    Scalar(String),
+    Sequence(Vec<YamlNode>),
+    Mapping(Vec<(YamlNode, YamlNode)>),
+    Alias,
+}
+
+/// A YAML node with the line it starts at, serde_yaml2 does not keep track of lines.
+#[derive(Debug)]
+struct YamlNode {
+    line: usize,
+    value: YamlValue,
+}
+
+impl YamlNode {
+    fn as_scalar(&self) -> Option<&str> {
+        match &self.value {
+            YamlValue::Scalar(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self.value {
+            YamlValue::Scalar(_) => "a scalar",
+            YamlValue::Sequence(_) => "a sequence",
+            YamlValue::Mapping(_) => "a mapping",
+            YamlValue::Alias => "an alias",
+        }
+    }
+}
+
+/// Builds the nodes of the first document from parser events.
+#[derive(Default)]
+struct YamlNodeBuilder {
+    /// Sequences and mappings being read, with their line and children so far.
+    stack: Vec<(usize, bool, Vec<YamlNode>)>,
+    root: Option<YamlNode>,
+}
+
+impl YamlNodeBuilder {
+    fn push(&mut self, node: YamlNode) {
+        match self.stack.last_mut() {
+            Some((_, _, children)) => children.push(node),
+            None => {
+                self.root.get_or_insert(node);
+            },
+        }
+    }
+}
+
+impl MarkedEventReceiver for YamlNodeBuilder {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        match event {
+            Event::Scalar(value, ..) => self.push(YamlNode { line: mark.line(), value: YamlValue::Scalar(value) }),
+            Event::Alias(_) => self.push(YamlNode { line: mark.line(), value: YamlValue::Alias }),
+            Event::SequenceStart(..) => self.stack.push((mark.line(), false, vec![])),
+            Event::MappingStart(..) => self.stack.push((mark.line(), true, vec![])),
+            Event::SequenceEnd | Event::MappingEnd => {
+                let Some((line, is_mapping, children)) = self.stack.pop() else {
+                    return;
+                };
+                let value = match is_mapping {
+                    true => {
+                        let mut children = children.into_iter();
+                        let mut pairs = vec![];
+                        while let (Some(key), Some(value)) = (children.next(), children.next()) {
+                            pairs.push((key, value));
+                        }
+                        YamlValue::Mapping(pairs)
+                    },
+                    false => YamlValue::Sequence(children),
+                };
+                self.push(YamlNode { line, value });
+            },
+            _ => (),
+        }
+    }
+}
+
+fn parse_yaml(content: &str) -> Result<Option<YamlNode>, ValidationFinding> {
+    let mut builder = YamlNodeBuilder::default();
+    Parser::new_from_str(content).load(&mut builder, false)
+        .map_err(|e| finding(e.marker().line(), "yaml-syntax", e.info().to_string()))?;
+    Ok(builder.root)
+}
+
+/// Entries of a mapping by key, reporting duplicate and unknown keys, non-scalar keys, and required keys that are missing.
+fn check_mapping_keys<'a>(node: &'a YamlNode, name: &str, keys: &[(&str, bool)], findings: &mut Vec<ValidationFinding>) -> BTreeMap<&'a str, &'a YamlNode> {
+    let mut entries = BTreeMap::new();
+    let YamlValue::Mapping(pairs) = &node.value else {
+        findings.push(finding(node.line, "invalid-structure", format!("{name} should be a mapping instead of {}", node.kind())));
+        return entries;
+    };
+    for (key, value) in pairs {
+        let Some(key_name) = key.as_scalar() else {
+            findings.push(finding(key.line, "invalid-structure", format!("keys of {name} should be scalars")));
+            continue;
+        };
+        if !keys.iter().any(|(known_key, _)| *known_key == key_name) {
+            findings.push(finding(key.line, "unknown-key", format!("unknown key {key_name:?} in {name}, expected one of {}",
+                keys.iter().map(|(known_key, _)| *known_key).collect::<Vec<_>>().join(", "))));
+        } else if entries.insert(key_name, value).is_some() {
+            findings.push(finding(key.line, "duplicate-key", format!("key {key_name:?} appears more than once in {name}")));
+        }
+    }
+    for (key_name, _) in keys.iter().filter(|(key_name, required)| *required && !entries.contains_key(key_name)) {
+        findings.push(finding(node.line, "missing-key", format!("{name} has no {key_name:?}")));
+    }
+    entries
+}
+
+/// Value of a key that should be a non-empty scalar, reporting it otherwise.
+fn scalar_value<'a>(entries: &BTreeMap<&str, &'a YamlNode>, key: &str, name: &str, findings: &mut Vec<ValidationFinding>) -> Option<&'a str> {
+    let node = entries.get(key)?;
+    match node.as_scalar() {
+        Some(value) if !value.trim().is_empty() => Some(value),
+        Some(_) => {
+            findings.push(finding(node.line, "invalid-value", format!("{key} of {name} is empty")));
+            None
+        },
+        None => {
+            findings.push(finding(node.line, "invalid-structure", format!("{key} of {name} should be a scalar instead of {}", node.kind())));
+            None
+        },
+    }
+}
+
+/// Report a language code which is not made of known ISO 639/3166 subtags, e.g. `cn` for `zh_CN`.
+fn check_language_code(line: usize, language_code: &str, what: &str, findings: &mut Vec<ValidationFinding>) {
+    if !is_valid_language_code(language_code) {
+        findings.push(finding(line, "invalid-language-code", format!("{what} is {language_code:?}, which is not a known language code")));
+    }
+}
+
+/// Check a `language_mapping`, whose keys are Transifex language codes and values are the codes used by the project.
+fn check_language_mapping(entries: &BTreeMap<&str, &YamlNode>, name: &str, findings: &mut Vec<ValidationFinding>) {
+    let Some(node) = entries.get("language_mapping") else {
+        return;
+    };
+    let YamlValue::Mapping(pairs) = &node.value else {
+        findings.push(finding(node.line, "invalid-structure", format!("language_mapping of {name} should be a mapping instead of {}", node.kind())));
+        return;
+    };
+    for (key, value) in pairs {
+        match (key.as_scalar(), value.as_scalar()) {
+            (Some(language_code), Some(_)) => check_language_code(key.line, language_code, &format!("language_mapping key of {name}"), findings),
+            _ => findings.push(finding(key.line.min(value.line), "invalid-structure", format!("language_mapping of {name} should map language codes to language codes"))),
+        }
+    }
+}
+
+/// Check that a translation file pattern matches existing files, and that the language codes they are matched with look like
+/// language codes, e.g. a misspelled directory, or `app_<lang>.ts` matching `app_backup.ts`. Returns the rule and message of each problem.
+fn check_target_files(project_root: &Path, source_file: &str, target_pattern: &str) -> Vec<(&'static str, String)> {
+    let filter = Filter {
+        type_attr: "file".to_string(),
+        source: source_file.to_string(),
+        format: String::new(),
+        source_lang: String::new(),
+        target_pattern: target_pattern.to_string(),
+    };
+    let matched_files = match filter.match_target_files(project_root) {
+        Ok(matched_files) if matched_files.is_empty() => return vec![("unresolved-file-filter", format!("{target_pattern:?} matches no existing file"))],
+        Ok(matched_files) => matched_files,
+        Err(e) => return vec![("unresolved-file-filter", format!("{target_pattern:?} can not be resolved: {e}"))],
+    };
+    let mut language_codes: Vec<&str> = matched_files.iter()
+        .map(|(language_code, _)| language_code.as_str())
+        .filter(|language_code| !is_valid_language_code(language_code))
+        .collect();
+    language_codes.sort();
+    language_codes.dedup();
+    language_codes.into_iter()
+        .map(|language_code| ("suspicious-language-code", format!("{target_pattern:?} matches a file with {language_code:?}, which does not look like a language code")))
+        .collect()
+}
+
+/// Check that a source file exists, and if not, whether it does with a different case, which only works on case-insensitive
+/// file systems. Returns the rule and message of the problem if any.
+fn check_source_file(project_root: &Path, source_file: &str) -> Option<(&'static str, String)> {
+    if project_root.join(source_file).is_file() {
+        return None;
+    }
+    let mut actual_path = PathBuf::new();
+    for component in Path::new(source_file.trim_start_matches("./")).components() {
+        let name = component.as_os_str();
+        if project_root.join(&actual_path).join(name).exists() {
+            actual_path.push(name);
+            continue;
+        }
+        let entries = fs::read_dir(project_root.join(&actual_path)).ok()?;
+        let name = name.to_string_lossy().to_lowercase();
+        let entry = entries.flatten().find(|entry| entry.file_name().to_string_lossy().to_lowercase() == name);
+        let Some(entry) = entry else {
+            return Some(("missing-source-file", format!("{source_file:?} does not exist")));
+        };
+        actual_path.push(entry.file_name());
+    }
+    match project_root.join(&actual_path).is_file() {
+        true => Some(("case-mismatch", format!("{source_file:?} does not exist, but {:?} does", actual_path.display().to_string()))),
+        false => Some(("missing-source-file", format!("{source_file:?} does not exist"))),
+    }
+}
+
+fn check_filter(node: &YamlNode, index: usize, project_root: Option<&Path>, findings: &mut Vec<ValidationFinding>) -> Option<String> {
+    let name = format!("filters[{index}]");
+    let filter_type = match &node.value {
+        YamlValue::Mapping(pairs) => pairs.iter()
+            .find(|(key, _)| key.as_scalar() == Some("filter_type"))
+            .and_then(|(_, value)| value.as_scalar()),
+        _ => None,
+    };
+    let is_dir = filter_type == Some("dir");
+    let keys: Vec<(&str, bool)> = FILTER_KEYS.iter().map(|(key, file_required, dir_required)| (*key, if is_dir { *dir_required } else { *file_required })).collect();
+    let entries = check_mapping_keys(node, &name, &keys, findings);
+
+    if let Some(filter_type) = scalar_value(&entries, "filter_type", &name, findings) {
+        if !FILTER_TYPES.contains(&filter_type) {
+            findings.push(finding(entries["filter_type"].line, "invalid-value",
+                format!("filter_type of {name} is {filter_type:?}, expected one of {}", FILTER_TYPES.join(", "))));
+        }
+    }
+    if let Some(file_format) = scalar_value(&entries, "file_format", &name, findings) {
+        if !FILE_FORMATS.contains(&file_format) {
+            let suggestion = FILE_FORMATS.iter()
+                .find(|known| known.eq_ignore_ascii_case(file_format))
+                .map(|known| format!(", did you mean {known:?}?"))
+                .unwrap_or_default();
+            findings.push(finding(entries["file_format"].line, "unknown-file-format",
+                format!("file_format of {name} is {file_format:?}, which is not a known Transifex file format{suggestion}")));
+        }
+    }
+    let in_source = entries.get("file_format").and_then(|node| node.as_scalar()).is_some_and(|file_format| IN_SOURCE_FILE_FORMATS.contains(&file_format));
+    if let Some(expression) = scalar_value(&entries, "translation_files_expression", &name, findings).filter(|_| !in_source) {
+        let line = entries["translation_files_expression"].line;
+        if !expression.contains("<lang>") {
+            findings.push(finding(line, "missing-lang-placeholder",
+                format!("translation_files_expression of {name} has no <lang>, all languages would be written to {expression:?}")));
+        } else if let (Some(project_root), false) = (project_root, is_dir) {
+            let source_file = entries.get("source_file").and_then(|node| node.as_scalar()).unwrap_or_default();
+            for (rule, problem) in check_target_files(project_root, source_file, expression) {
+                findings.push(finding(line, rule, format!("translation_files_expression of {name}: {problem}")));
+            }
+        }
+    }
+    if let Some(source_language) = scalar_value(&entries, "source_language", &name, findings) {
+        check_language_code(entries["source_language"].line, source_language, &format!("source_language of {name}"), findings);
+    }
+    for key in ["source_file_dir", "source_file_extension"] {
+        scalar_value(&entries, key, &name, findings);
+    }
+    check_language_mapping(&entries, &name, findings);
+    let source_file = scalar_value(&entries, "source_file", &name, findings)?;
+    if let Some((rule, problem)) = project_root.and_then(|project_root| check_source_file(project_root, source_file)) {
+        findings.push(finding(entries["source_file"].line, rule, format!("source_file of {name}: {problem}")));
+    }
+    Some(source_file.to_string())
+}
+
+/// Check the structure of transifex.yaml, see <https://help.transifex.com/en/articles/6265125-github-installation-and-configuration>.
+/// With `project_root`, also check that translation file patterns match existing files.
+pub(crate) fn lint_transifex_yaml(content: &str, project_root: Option<&Path>) -> Vec<ValidationFinding> {
+    let mut findings = vec![];
+    let root = match parse_yaml(content) {
+        Ok(Some(root)) => root,
+        Ok(None) => return vec![finding(0, "invalid-structure", "the file is empty".to_string())],
+        Err(finding) => return vec![finding],
+    };
+    let entries = check_mapping_keys(&root, "the file", &[("filters", true), ("settings", true)], &mut findings);
+
+    if let Some(filters) = entries.get("filters") {
+        match &filters.value {
+            YamlValue::Sequence(filters) => {
+                let mut source_files = BTreeMap::new();
+                for (index, filter) in filters.iter().enumerate() {
+                    let Some(source_file) = check_filter(filter, index, project_root, &mut findings) else {
+                        continue;
+                    };
+                    let source_file = source_file.trim_start_matches("./").to_string();
+                    if let Some(first) = source_files.insert(source_file.clone(), index) {
+                        findings.push(finding(filter.line, "duplicate-source",
+                            format!("source_file {source_file:?} of filters[{index}] is also used by filters[{first}]")));
+                    }
+                }
+            },
+            _ => findings.push(finding(filters.line, "invalid-structure", format!("filters should be a sequence instead of {}", filters.kind()))),
+        }
+    }
+    if let Some(settings) = entries.get("settings") {
+        let settings_entries = check_mapping_keys(settings, "settings", SETTINGS_KEYS, &mut findings);
+        scalar_value(&settings_entries, "pr_branch_name", "settings", &mut findings);
+        check_language_mapping(&settings_entries, "settings", &mut findings);
+    }
+    findings.sort_by_key(|finding| finding.line);
+    findings
+}
+
+/// A section of .tx/config with its line, and its keys with their line and value.
+struct TxConfigSection<'a> {
+    line: usize,
+    name: &'a str,
+    entries: Vec<(usize, &'a str, &'a str)>,
+}
+
+/// Read the sections of .tx/config line by line, configparser does not keep track of lines.
+fn scan_tx_config<'a>(content: &'a str, findings: &mut Vec<ValidationFinding>) -> Vec<TxConfigSection<'a>> {
+    let mut sections: Vec<TxConfigSection> = vec![];
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') || line.starts_with(char::is_whitespace) {
+            continue;
+        }
+        if let Some(name) = trimmed.strip_prefix('[').and_then(|name| name.strip_suffix(']')) {
+            sections.push(TxConfigSection { line: line_number, name: name.trim(), entries: vec![] });
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once(['=', ':']) else {
+            findings.push(finding(line_number, "invalid-structure", format!("unexpected line {trimmed:?}, expected `key = value`")));
+            continue;
+        };
+        match sections.last_mut() {
+            Some(section) => section.entries.push((line_number, key.trim(), value.trim())),
+            None => findings.push(finding(line_number, "invalid-structure", format!("key {:?} is not in a section", key.trim()))),
+        }
+    }
+    sections
+}
+
+/// Check the keys of a .tx/config section, returning the value of each known key.
+fn check_tx_config_keys<'a>(section: &TxConfigSection<'a>, keys: &[(&str, bool)], findings: &mut Vec<ValidationFinding>) -> BTreeMap<&'a str, (usize, &'a str)> {
+    let mut values = BTreeMap::new();
+    for (line, key, value) in &section.entries {
+        let is_known = keys.iter().any(|(known_key, _)| known_key == key) || (section.name != "main" && key.starts_with("trans."));
+        if !is_known {
+            findings.push(finding(*line, "unknown-key", format!("unknown key {key:?} in [{}]", section.name)));
+        } else if values.insert(*key, (*line, *value)).is_some() {
+            findings.push(finding(*line, "duplicate-key", format!("key {key:?} appears more than once in [{}]", section.name)));
+        } else if value.is_empty() {
+            findings.push(finding(*line, "invalid-value", format!("{key} of [{}] is empty", section.name)));
+        }
+    }
+    for (key, _) in keys.iter().filter(|(key, required)| *required && !values.contains_key(key)) {
+        findings.push(finding(section.line, "missing-key", format!("[{}] has no {key:?}", section.name)));
+    }
+    values
+}
+
+/// Check the language codes of a .tx/config section: `source_lang`, `trans.<lang>` keys and the Transifex side of `lang_map`.
+fn check_tx_config_language_codes(section: &TxConfigSection, values: &BTreeMap<&str, (usize, &str)>, findings: &mut Vec<ValidationFinding>) {
+    if let Some((line, source_lang)) = values.get("source_lang").filter(|(_, source_lang)| !source_lang.is_empty()) {
+        check_language_code(*line, source_lang, &format!("source_lang of [{}]", section.name), findings);
+    }
+    for (line, key, _) in &section.entries {
+        if let Some(language_code) = key.strip_prefix("trans.") {
+            check_language_code(*line, language_code, &format!("{key} of [{}]", section.name), findings);
+        }
+    }
+    if let Some((line, lang_map)) = values.get("lang_map") {
+        for language_code in lang_map.split(',').filter_map(|pair| pair.split_once(':')).map(|(language_code, _)| language_code.trim()) {
+            check_language_code(*line, language_code, &format!("lang_map key of [{}]", section.name), findings);
+        }
+    }
+}
+
+fn check_minimum_perc(values: &BTreeMap<&str, (usize, &str)>, section_name: &str, findings: &mut Vec<ValidationFinding>) {
+    let Some((line, value)) = values.get("minimum_perc") else {
+        return;
+    };
+    if !value.parse::<i64>().is_ok_and(|minimum_perc| (0..=100).contains(&minimum_perc)) {
+        findings.push(finding(*line, "invalid-value", format!("minimum_perc of [{section_name}] is {value:?}, expected an integer from 0 to 100")));
+    }
+}
+
+/// Check the structure of .tx/config, see <https://developers.transifex.com/docs/using-the-client>.
+/// With `project_root`, also check that translation file patterns match existing files.
+pub(crate) fn lint_tx_config(content: &str, project_root: Option<&Path>) -> Vec<ValidationFinding> {
+    let mut findings = vec![];
+    let sections = scan_tx_config(content, &mut findings);
+    let section_name_regex = Regex::new(r"^o:[^:\s]+:p:[^:\s]+:r:[^:\s]+$").unwrap();
+
+    let mut seen = BTreeSet::new();
+    for section in sections.iter().filter(|section| !seen.insert(section.name)) {
+        findings.push(finding(section.line, "duplicate-section", format!("section [{}] appears more than once", section.name)));
+    }
+    match sections.iter().find(|section| section.name == "main") {
+        Some(main_section) => {
+            let values = check_tx_config_keys(main_section, TX_CONFIG_MAIN_KEYS, &mut findings);
+            check_minimum_perc(&values, "main", &mut findings);
+            check_tx_config_language_codes(main_section, &values, &mut findings);
+        },
+        None => findings.push(finding(0, "missing-key", "there is no [main] section".to_string())),
+    }
+
+    let mut source_langs: Vec<(usize, &str, &str)> = vec![];
+    for section in sections.iter().filter(|section| section.name != "main") {
+        if !section_name_regex.is_match(section.name) {
+            findings.push(finding(section.line, "invalid-section-name",
+                format!("section name [{}] is not in the form of [o:<organization>:p:<project>:r:<resource>]", section.name)));
+        }
+        let values = check_tx_config_keys(section, TX_CONFIG_RESOURCE_KEYS, &mut findings);
+        check_minimum_perc(&values, section.name, &mut findings);
+        check_tx_config_language_codes(section, &values, &mut findings);
+        let in_source = values.get("type").is_some_and(|(_, type_attr)| IN_SOURCE_FILE_FORMATS.contains(type_attr));
+        if let Some((line, file_filter)) = values.get("file_filter").filter(|_| !in_source) {
+            if !file_filter.is_empty() && !file_filter.contains("<lang>") {
+                findings.push(finding(*line, "missing-lang-placeholder",
+                    format!("file_filter of [{}] has no <lang>, all languages would be written to {file_filter:?}", section.name)));
+            } else if let Some(project_root) = project_root.filter(|_| !file_filter.is_empty()) {
+                let source_file = values.get("source_file").map(|(_, source_file)| *source_file).unwrap_or_default();
+                for (rule, problem) in check_target_files(project_root, source_file, file_filter) {
+                    findings.push(finding(*line, rule, format!("file_filter of [{}]: {problem}", section.name)));
+                }
+            }
+        }
+        if let (Some((line, source_file)), Some(project_root)) = (values.get("source_file"), project_root) {
+            if let Some((rule, problem)) = check_source_file(project_root, source_file).filter(|_| !source_file.is_empty()) {
+                findings.push(finding(*line, rule, format!("source_file of [{}]: {problem}", section.name)));
+            }
+        }
+        if let Some((line, source_lang)) = values.get("source_lang").filter(|(_, source_lang)| !source_lang.is_empty()) {
+            source_langs.push((*line, section.name, source_lang));
+        }
+    }
+
+    // Resources of a project are usually written in one language, others are likely typos like `en_US` for `en`.
+    let mut counts = BTreeMap::<&str, usize>::new();
+    for (_, _, source_lang) in &source_langs {
+        *counts.entry(source_lang).or_default() += 1;
+    }
+    if let Some((common, _)) = counts.iter().rev().max_by_key(|(_, count)| **count) {
+        for (line, section_name, source_lang) in source_langs.iter().filter(|(_, _, source_lang)| source_lang != common) {
+            findings.push(finding(*line, "inconsistent-source-lang",
+                format!("source_lang of [{section_name}] is {source_lang:?} while other resources use {common:?}")));
+        }
+    }
+    findings.sort_by_key(|finding| finding.line);
+    findings
+}
+
+/// Check transifex.yaml and .tx/config of the project for structural problems, before Transifex or the Transifex CLI
+/// reject them.
+pub fn subcmd_lint_config(project_root: &Path, format: ValidateFormat, fail_on: FailOn) -> Result<(), CmdError> {
+    let transifex_yaml_file = find_transifex_yaml_file(project_root);
+    let tx_config_file = Some(project_root.join(".tx").join("config")).filter(|tx_config_file| tx_config_file.is_file());
+    if transifex_yaml_file.is_none() && tx_config_file.is_none() {
+        return Err(CmdError::FileNotFound(project_root.to_path_buf()));
+    }
+
+    let is_text = matches!(format, ValidateFormat::Text);
+    let mut findings = vec![];
+    if let Some(transifex_yaml_file) = transifex_yaml_file {
+        if is_text {
+            println!("Found Transifex GitHub integration config file at: {transifex_yaml_file:?}");
+        }
+        let content = fs::read_to_string(&transifex_yaml_file).map_err(|e| CmdError::ReadFile(transifex_yaml_file.clone(), e))?;
+        findings.extend(lint_transifex_yaml(&content, Some(project_root)).into_iter()
+            .map(|finding| ValidationFinding { file: transifex_yaml_file.clone(), ..finding }));
+    }
+    if let Some(tx_config_file) = tx_config_file {
+        if is_text {
+            println!("Found Transifex project config file at: {tx_config_file:?}");
+        }
+        let content = fs::read_to_string(&tx_config_file).map_err(|e| CmdError::ReadFile(tx_config_file.clone(), e))?;
+        findings.extend(lint_tx_config(&content, Some(project_root)).into_iter()
+            .map(|finding| ValidationFinding { file: tx_config_file.clone(), ..finding }));
+    }
+    print_findings(&findings, format).map_err(CmdError::Serialize)?;
+    match count_failures(&findings, fail_on) {
+        0 => {
+            if is_text {
+                match severity_counts(&findings) {
+                    counts if counts.is_empty() => println!("No problem found."),
+                    counts => println!("Found {counts}, none of them fails the check."),
+                }
+            }
+            Ok(())
+        },
+        count => Err(CmdError::Findings(count)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transifex::tx_config_file::tests::TEST_TX_CONFIG_CONTENT;
+    use crate::transifex::yaml_file::tests::TEST_TX_YAML_CONTENT;
+
+    fn rules_and_lines(findings: &[ValidationFinding]) -> Vec<(&str, usize)> {
+        findings.iter().map(|finding| (finding.rule, finding.line)).collect()
+    }
+
+    #[test]
+    fn tst_lint_transifex_yaml() {
+        assert_eq!(lint_transifex_yaml(TEST_TX_YAML_CONTENT, None), []);
+
+        let content = r#"filters:
+  - filter_type: files
+    source_file: translations/app.ts
+    file_format: qt
+    source_language: en_US
+    translation_files_expression: translations/app.ts
+  - filter_type: file
+    source_file: ./translations/app.ts
+    file_format: PO
+    source_language: ""
+    translation_files_expression: po/<lang>.po
+    extra: 1
+  - filter_type: dir
+    source_file_dir: po
+    file_format: PO
+    source_language: en
+    translation_files_expression: po/<lang>/
+settings:
+  pr_branch_name: [transifex]
+  language_mapping:
+    pt_BR: pt-br
+    zh_CHS: zh_CN
+"#;
+        let findings = lint_transifex_yaml(content, None);
+        assert_eq!(rules_and_lines(&findings), [
+            ("invalid-value", 2),
+            ("unknown-file-format", 4),
+            ("missing-lang-placeholder", 6),
+            ("duplicate-source", 7),
+            ("invalid-value", 10),
+            ("unknown-key", 12),
+            ("missing-key", 13),
+            ("invalid-structure", 19),
+            ("invalid-language-code", 22),
+        ]);
+        assert_eq!(findings[8].message, "language_mapping key of settings is \"zh_CHS\", which is not a known language code");
+        assert_eq!(findings[1].message, "file_format of filters[0] is \"qt\", which is not a known Transifex file format, did you mean \"QT\"?");
+        assert_eq!(findings[6].message, "filters[2] has no \"source_file_extension\"");
+
+        assert_eq!(rules_and_lines(&lint_transifex_yaml("filters:\n  - filter_type: file\n   oops: [\n", None)), [("yaml-syntax", 3)]);
+        assert_eq!(rules_and_lines(&lint_transifex_yaml("filters: {}\n", None)), [("missing-key", 1), ("invalid-structure", 1)]);
+    }
+
+    #[test]
+    fn tst_lint_tx_config() {
+        assert_eq!(lint_tx_config(TEST_TX_CONFIG_CONTENT, None), []);
+
+        let content = r#"[main]
+host = https://www.transifex.com
+minimum_perc = 120
+
+[o:linuxdeepin:p:deepin-home:r:deepin-home]
+file_filter = translations/deepin-home_<lang>.ts
+source_file = translations/deepin-home.ts
+source_lang = en
+type = QT
+trans.zh_CN = translations/deepin-home_zh_CN.ts
+
+[deepin-home.desktop]
+file_filter = misc/desktop.ts
+source_file = misc/desktop.ts
+source_lang = en_US
+typ = QT
+minimum_perc = abc
+
+[o:linuxdeepin:p:deepin-home:r:deepin-home]
+file_filter = po/<lang>.po
+source_file = po/deepin-home.pot
+source_lang = en
+type = PO
+"#;
+        let findings = lint_tx_config(content, None);
+        assert_eq!(rules_and_lines(&findings), [
+            ("invalid-value", 3),
+            ("invalid-section-name", 12),
+            ("missing-key", 12),
+            ("missing-lang-placeholder", 13),
+            ("inconsistent-source-lang", 15),
+            ("unknown-key", 16),
+            ("invalid-value", 17),
+            ("duplicate-section", 19),
+        ]);
+        assert_eq!(findings[4].message, "source_lang of [deepin-home.desktop] is \"en_US\" while other resources use \"en\"");
+        assert_eq!(findings[4].severity, Severity::Warning);
+        assert_eq!(findings[3].severity, Severity::Error);
+        assert_eq!(rules_and_lines(&lint_tx_config("host = x\n[o:a:p:b:r:c]\n", None)), [
+            ("missing-key", 0),
+            ("invalid-structure", 1),
+            ("missing-key", 2),
+            ("missing-key", 2),
+            ("missing-key", 2),
+            ("missing-key", 2),
+        ]);
+
+        let content = r#"[main]
+host = https://www.transifex.com
+lang_map = pt_BR: pt-br, cn: zh_CN
+
+[o:linuxdeepin:p:app:r:app]
+file_filter = po/<lang>.po
+source_file = po/app.pot
+source_lang = EN
+type = PO
+trans.zh-CN = po/zh_CN.po
+trans.zh_XX = po/zh_XX.po
+"#;
+        let findings = lint_tx_config(content, None);
+        assert_eq!(rules_and_lines(&findings), [("invalid-language-code", 3), ("invalid-language-code", 8), ("invalid-language-code", 11)]);
+        assert_eq!(findings[1].message, "source_lang of [o:linuxdeepin:p:app:r:app] is \"EN\", which is not a known language code");
+    }
+    #[test]
+    fn tst_check_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_root = temp_dir.path().to_path_buf();
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        for file_name in ["App.ts", "app_zh_CN.ts", "app_pt_BR.ts", "app_backup.ts"] {
+            fs::write(project_root.join("translations").join(file_name), "").unwrap();
+        }
+        let tx_config = r#"[main]
+host = https://www.transifex.com
+
+[o:linuxdeepin:p:app:r:app]
+file_filter = translations/app_<lang>.ts
+source_file = translations/app.ts
+source_lang = en
+type = QT
+
+[o:linuxdeepin:p:app:r:app-typo]
+file_filter = translation/app_<lang>.ts
+source_file = translations/app.ts
+source_lang = en
+type = QT
+
+[o:linuxdeepin:p:app:r:app-missing]
+file_filter = po/<lang>.po
+source_file = po/app.pot
+source_lang = en
+type = PO
+"#;
+        let findings = lint_tx_config(tx_config, Some(&project_root));
+        let yaml_findings = lint_transifex_yaml(r#"filters:
+  - filter_type: file
+    source_file: translations/app.ts
+    file_format: QT
+    source_language: en
+    translation_files_expression: translations/app_<lang>.ts
+settings:
+  pr_branch_name: transifex_update_<br_unique_id>
+"#, Some(&project_root));
+
+        assert_eq!(rules_and_lines(&findings), [
+            ("suspicious-language-code", 5),
+            ("case-mismatch", 6),
+            ("unresolved-file-filter", 11),
+            ("case-mismatch", 12),
+            ("unresolved-file-filter", 17),
+            ("missing-source-file", 18),
+        ]);
+        assert_eq!(findings[1].message, "source_file of [o:linuxdeepin:p:app:r:app]: \"translations/app.ts\" does not exist, but \"translations/App.ts\" does");
+        assert_eq!(findings[0].message, "file_filter of [o:linuxdeepin:p:app:r:app]: \"translations/app_<lang>.ts\" matches a file with \"backup\", which does not look like a language code");
+        assert_eq!(rules_and_lines(&yaml_findings), [("case-mismatch", 3), ("suspicious-language-code", 6)]);
+        assert_eq!(yaml_findings.iter().map(|finding| finding.severity).collect::<Vec<_>>(), [Severity::Error, Severity::Warning]);
+    }
+}
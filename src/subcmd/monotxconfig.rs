@@ -2,14 +2,53 @@
 //
 // SPDX-License-Identifier: MIT
 
-use std::{fs, path::PathBuf};
+use std::{fs, path::{Path, PathBuf}};
+use thiserror::Error as TeError;
 
-use crate::transifex::tx_config_file::{TxConfig, TxConfigSectionMain, TxConfigSectionResource};
+use crate::transifex::tx_config_file::{LoadTxConfigError, TxConfig, TxConfigSectionMain, TxConfigSectionResource};
 
-use super::yaml2txconfig::create_linked_resources_table;
+use crate::glob::PathGlobs;
+use super::yaml2txconfig::create_filtered_linked_resources_table;
 
-pub fn subcmd_monotxconfig(project_root: &PathBuf, force_online: bool, organization_slug: String) {
-    let linked_resources = create_linked_resources_table(&organization_slug, None, force_online);
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Fail to create directory {0:?} because: {1}")]
+    CreateDir(PathBuf, #[source] std::io::Error),
+    #[error("Fail to write {0:?} because: {1}")]
+    WriteFile(PathBuf, #[source] std::io::Error),
+    #[error("Fail to load ~/.transifexrc or TX_TOKEN because: {0}")]
+    LoadTransifexRc(#[source] LoadTxConfigError),
+    #[error("Invalid project glob: {0}")]
+    InvalidGlob(#[source] globset::Error),
+}
+
+/// Project slug filter built from glob patterns like `dde-*`.
+///
+/// A project is accepted if it matches any of `include_projects` (or that's empty), and none of `exclude_projects`.
+struct ProjectFilter {
+    globs: PathGlobs,
+}
+
+impl ProjectFilter {
+    fn new(include_projects: &[String], exclude_projects: &[String]) -> Result<Self, globset::Error> {
+        let globs: Vec<String> = include_projects.iter().cloned()
+            .chain(exclude_projects.iter().map(|glob| format!("!{glob}")))
+            .collect();
+        Ok(ProjectFilter { globs: PathGlobs::new(&globs)? })
+    }
+
+    fn accepts(&self, project_slug: &str) -> bool {
+        self.globs.is_match(project_slug)
+    }
+}
+
+/// Generate a .tx/config with the linked resources of the organization.
+///
+/// It's written to `output` if given (`-` for stdout), otherwise to .tx/config of the project root unless that already exists.
+pub fn subcmd_monotxconfig(project_root: &Path, force_online: bool, organization_slug: String, include_projects: &[String], exclude_projects: &[String], minimum_perc: Option<i64>, output: Option<&Path>) -> Result<(), CmdError> {
+    let project_filter = ProjectFilter::new(include_projects, exclude_projects).map_err(CmdError::InvalidGlob)?;
+    let linked_resources = create_filtered_linked_resources_table(&organization_slug, |project_slug| project_filter.accepts(project_slug), force_online)
+        .map_err(CmdError::LoadTransifexRc)?;
 
     let mut resource_sections = Vec::<TxConfigSectionResource>::new();
 
@@ -39,20 +78,48 @@ pub fn subcmd_monotxconfig(project_root: &PathBuf, force_online: bool, organizat
     let txconfig_file = TxConfig {
         main_section: TxConfigSectionMain {
             host: "https://www.transifex.com".to_string(),
+            minimum_prec: minimum_perc,
             ..TxConfigSectionMain::default()
         },
         resource_sections,
     };
 
-    let tx_config_file = project_root.join(".tx/config");
-    if tx_config_file.exists() {
+    if output == Some(Path::new("-")) {
+        print!("{}", txconfig_file.to_str());
+        return Ok(());
+    }
+    let tx_config_file = match output {
+        Some(output) => output.to_path_buf(),
+        None => project_root.join(".tx/config"),
+    };
+    if output.is_none() && tx_config_file.exists() {
         println!("Note: {tx_config_file:?} file already exists, not overwriting it.");
         println!("You can use the following context to update the file manually:\n");
         println!("{}", txconfig_file.to_str());
     } else {
-        let parent_dir = tx_config_file.parent().unwrap();
-        fs::create_dir_all(&parent_dir).expect("Failed to create .tx directory");
-        fs::write(&tx_config_file, txconfig_file.to_str()).expect("Failed to write .tx/config file");
+        if let Some(parent_dir) = tx_config_file.parent().filter(|parent_dir| !parent_dir.as_os_str().is_empty()) {
+            fs::create_dir_all(parent_dir).map_err(|e| CmdError::CreateDir(parent_dir.to_path_buf(), e))?;
+        }
+        fs::write(&tx_config_file, txconfig_file.to_str()).map_err(|e| CmdError::WriteFile(tx_config_file.clone(), e))?;
         println!("Generated .tx/config file at: {tx_config_file:?}");
     }
-}
\ No newline at end of file
+
+    Ok(())
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_project_filter() {
+        let project_filter = ProjectFilter::new(&["dde-*".to_string(), "deepin-home".to_string()], &["dde-*-legacy".to_string()]).unwrap();
+        assert!(project_filter.accepts("dde-control-center"));
+        assert!(project_filter.accepts("deepin-home"));
+        assert!(!project_filter.accepts("dde-dock-legacy"));
+        assert!(!project_filter.accepts("deepin-music"));
+
+        let project_filter = ProjectFilter::new(&[], &["deepin-*".to_string()]).unwrap();
+        assert!(project_filter.accepts("dde-control-center"));
+        assert!(!project_filter.accepts("deepin-home"));
+    }
+}
@@ -0,0 +1,95 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::fs;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use thiserror::Error as TeError;
+use crate::i18n_file::cache::I18nFileCache;
+use crate::i18n_file::common::{I18nFileKind, UnknownI18nFileExtError};
+use crate::i18n_file::gettext::{Po, PoLoadError, PoSaveError, PoSaveOptions};
+use crate::i18n_file::linguist::{Ts, TsLoadError, TsSaveError, TsSaveOptions};
+use crate::parallel;
+
+/// Extensions of files that are picked up when a directory is given.
+const TRANSLATION_FILE_EXTENSIONS: &[&str] = &["ts", "po", "pot"];
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Provided path {0:?} does not exist")]
+    PathNotFound(PathBuf),
+    #[error("Fail to guess translation file type of {0:?} because: {1}")]
+    GuessI18nFileType(PathBuf, #[source] UnknownI18nFileExtError),
+    #[error("Fail to read file {0:?} because: {1}")]
+    ReadFile(PathBuf, #[source] std::io::Error),
+    #[error("Fail to write file {0:?} because: {1}")]
+    WriteFile(PathBuf, #[source] std::io::Error),
+    #[error("Fail to load TS file {0:?} because: {1}")]
+    LoadTsFile(PathBuf, #[source] TsLoadError),
+    #[error("Fail to serialize TS file {0:?} because: {1}")]
+    SaveTsFile(PathBuf, #[source] TsSaveError),
+    #[error("Fail to load PO file {0:?} because: {1}")]
+    LoadPoFile(PathBuf, #[source] PoLoadError),
+    #[error("Fail to serialize PO file {0:?} because: {1}")]
+    SavePoFile(PathBuf, #[source] PoSaveError),
+    #[error("{0} file(s) are not normalized")]
+    NotNormalized(usize),
+}
+
+/// Get the normalized content of the given file.
+fn normalized_content(file: &Path) -> Result<Vec<u8>, CmdError> {
+    let kind = I18nFileKind::from_ext_hint(file)
+        .map_err(|e| CmdError::GuessI18nFileType(file.to_path_buf(), e))?;
+    match kind {
+        I18nFileKind::Linguist => {
+            let mut ts = Ts::load_from_file(file).map_err(|e| CmdError::LoadTsFile(file.to_path_buf(), e))?;
+            ts.normalize();
+            let mut content = vec![];
+            ts.write_into(&mut content, &TsSaveOptions::default()).map_err(|e| CmdError::SaveTsFile(file.to_path_buf(), e))?;
+            Ok(content)
+        },
+        I18nFileKind::Gettext => {
+            let mut po = Po::load_from_file(file).map_err(|e| CmdError::LoadPoFile(file.to_path_buf(), e))?;
+            po.normalize();
+            let mut writer = BufWriter::new(vec![]);
+            po.write_into(&mut writer, &PoSaveOptions::default()).map_err(|e| CmdError::SavePoFile(file.to_path_buf(), e))?;
+            writer.into_inner().map_err(|e| CmdError::SavePoFile(file.to_path_buf(), e.into_error().into()))
+        },
+    }
+}
+
+/// Normalize the given file, returns whether the file is changed (or needs to be changed in `check` mode).
+fn normalize_file(file: &Path, check: bool) -> Result<bool, CmdError> {
+    let original = fs::read(file).map_err(|e| CmdError::ReadFile(file.to_path_buf(), e))?;
+    let normalized = normalized_content(file)?;
+    if original == normalized {
+        return Ok(false);
+    }
+    if check {
+        println!("Not normalized: {file:?}");
+    } else {
+        fs::write(file, normalized).map_err(|e| CmdError::WriteFile(file.to_path_buf(), e))?;
+        I18nFileCache::shared().invalidate(file);
+        println!("Normalized: {file:?}");
+    }
+    Ok(true)
+}
+
+pub fn subcmd_normalize(paths: &[PathBuf], check: bool) -> Result<(), CmdError> {
+    if let Some(path) = paths.iter().find(|path| !path.exists()) {
+        return Err(CmdError::PathNotFound(path.clone()));
+    }
+    let files = crate::extract::collect_source_files(paths, TRANSLATION_FILE_EXTENSIONS);
+    let results = parallel::map(&files, |file| normalize_file(file, check));
+    let mut changed = 0;
+    for result in results {
+        changed += usize::from(result?);
+    }
+
+    if check && changed > 0 {
+        return Err(CmdError::NotNormalized(changed));
+    }
+    println!("{} of {} file(s) {}.", changed, files.len(), if check { "need to be normalized" } else { "normalized" });
+    Ok(())
+}
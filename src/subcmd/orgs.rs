@@ -0,0 +1,62 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use thiserror::Error as TeError;
+
+use crate::transifex::rest_api::{TransifexData, TransifexRestApi, TransifexRestApiError};
+use crate::transifex::tx_config_file::LoadTxConfigError;
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Fail to load ~/.transifexrc or TX_TOKEN because: {0}")]
+    LoadTransifexRc(#[source] LoadTxConfigError),
+    #[error("Fail to fetch from Transifex because: {0}")]
+    Fetch(#[from] TransifexRestApiError),
+}
+
+/// Render a table of the slugs and names, the slug is the last part of the id like `o:linuxdeepin:p:deepin-home`.
+fn render_slug_table(entries: &[TransifexData]) -> String {
+    let mut rows: Vec<(&str, &str)> = entries.iter()
+        .map(|entry| (entry.id.rsplit(':').next().unwrap_or_default(), entry.attributes.name.as_deref().unwrap_or_default()))
+        .collect();
+    rows.sort();
+    let mut table = String::from("| Slug | Name |\n| ---- | ---- |\n");
+    for (slug, name) in rows {
+        table.push_str(&format!("| {slug} | {name} |\n"));
+    }
+    table
+}
+
+/// Print organizations accessible with the API token, to find out the value of `--organization-slug`.
+pub fn subcmd_orgs() -> Result<(), CmdError> {
+    let api = TransifexRestApi::new_from_transifexrc().map_err(CmdError::LoadTransifexRc)?;
+    print!("{}", render_slug_table(&api.get_all_organizations()?));
+    Ok(())
+}
+
+/// Print projects of the organization, to find out the value of `--project-slug`.
+pub fn subcmd_projects(organization_slug: &str) -> Result<(), CmdError> {
+    let api = TransifexRestApi::new_from_transifexrc().map_err(CmdError::LoadTransifexRc)?;
+    print!("{}", render_slug_table(&api.get_all_projects(organization_slug)?));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transifex::rest_api::TransifexDataAttributes;
+
+    #[test]
+    fn tst_render_slug_table() {
+        let entry = |id: &str, name: Option<&str>| TransifexData {
+            id: id.to_string(),
+            attributes: TransifexDataAttributes { name: name.map(str::to_string), categories: None },
+        };
+        let table = render_slug_table(&[
+            entry("o:linuxdeepin:p:deepin-home", Some("Deepin Home")),
+            entry("o:linuxdeepin:p:dde-control-center", None),
+        ]);
+        assert_eq!(table, "| Slug | Name |\n| ---- | ---- |\n| dde-control-center |  |\n| deepin-home | Deepin Home |\n");
+    }
+}
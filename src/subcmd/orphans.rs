@@ -0,0 +1,197 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::collections::BTreeSet;
+use std::io::stdin;
+use std::path::Path;
+use thiserror::Error as TeError;
+
+use crate::transifex::rest_api::{TransifexData, TransifexRestApi, TransifexRestApiError};
+use crate::transifex::tx_config_file::{load_project_tx_config_file, LoadTxConfigError, TxConfig};
+
+use super::yaml2txconfig::{detect_git_branch, detect_github_repository};
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Fail to load .tx/config file because: {0}")]
+    LoadTxConfig(#[source] LoadTxConfigError),
+    #[error("Fail to load ~/.transifexrc or TX_TOKEN because: {0}")]
+    LoadTransifexRc(#[source] LoadTxConfigError),
+    #[error("Fail to fetch resources from Transifex because: {0}")]
+    FetchResources(#[source] TransifexRestApiError),
+    #[error("Fail to read user input because: {0}")]
+    ReadInput(#[source] std::io::Error),
+    #[error("Fail to delete resource {0} because: {1}")]
+    Delete(String, #[source] TransifexRestApiError),
+    #[error("Refuse to delete resources since the GitHub repository or branch of the project is unknown, please provide them with `--github-repository` and `--branch`")]
+    UnknownOwner,
+}
+
+#[derive(Debug, PartialEq)]
+struct OrphanedResource {
+    resource_id: String,
+    name: String,
+    reason: &'static str,
+}
+
+/// Whether the resource is linked to the given `(repository, branch)` through its `github#repository:` category.
+fn is_owned_by(resource: &TransifexData, owner: (&str, &str)) -> bool {
+    resource.parse_linked_resource_category()
+        .is_some_and(|entry| entry.repository == owner.0 && entry.branch == owner.1)
+}
+
+/// Find resources on Transifex that have no section in .tx/config, or whose source file no longer exists locally.
+///
+/// Transifex projects are shared by many repositories (and branches of them), so only resources linked to the `owner`
+/// repository and branch are considered. If the owner is unknown, only resources of .tx/config with a missing source file
+/// are reported.
+fn find_orphaned_resources(project_root: &Path, tx_config: &TxConfig, remote_resources: &[TransifexData], owner: Option<(&str, &str)>) -> Vec<OrphanedResource> {
+    remote_resources.iter().filter_map(|resource| {
+        if owner.is_some_and(|owner| !is_owned_by(resource, owner)) {
+            return None;
+        }
+        let section = tx_config.resource_sections.iter().find(|section| section.resource_full_slug == resource.id);
+        let reason = match section {
+            None if owner.is_some() => "not in .tx/config",
+            None => return None,
+            Some(section) if !project_root.join(&section.source_file).is_file() => "source file missing",
+            Some(_) => return None,
+        };
+        Some(OrphanedResource {
+            resource_id: resource.id.clone(),
+            name: resource.attributes.name.clone().unwrap_or_default(),
+            reason,
+        })
+    }).collect()
+}
+
+fn confirm(prompt: &str) -> Result<bool, CmdError> {
+    println!("{prompt} [y/N]");
+    let mut user_input = String::new();
+    stdin().read_line(&mut user_input).map_err(CmdError::ReadInput)?;
+    Ok(matches!(user_input.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// List resources in the Transifex projects of .tx/config that are not backed by a local resource anymore, e.g. after the
+/// source file was deleted or renamed. With `delete`, they are deleted from Transifex after confirmation (unless `yes` is set).
+///
+/// The GitHub repository and branch are detected from git if not provided, deleting is refused if either is unknown.
+pub fn subcmd_orphans(project_root: &Path, tx_config_file: Option<&Path>, github_repository: Option<String>, branch: Option<String>, delete: bool, yes: bool) -> Result<(), CmdError> {
+    let (tx_config_file, tx_config) = load_project_tx_config_file(project_root, tx_config_file).map_err(CmdError::LoadTxConfig)?;
+    println!("Found Transifex project config file at: {tx_config_file:?}");
+    let github_repository = github_repository.or_else(|| detect_github_repository(project_root));
+    let branch = branch.or_else(|| detect_git_branch(project_root));
+    let owner = match (&github_repository, &branch) {
+        (Some(github_repository), Some(branch)) => Some((github_repository.as_str(), branch.as_str())),
+        _ if delete => return Err(CmdError::UnknownOwner),
+        _ => {
+            eprintln!("Warning: Fail to detect the GitHub repository or branch, only resources with a missing source file are checked.");
+            None
+        },
+    };
+    let api = TransifexRestApi::new_from_transifexrc().map_err(CmdError::LoadTransifexRc)?;
+
+    let mut projects = BTreeSet::new();
+    for resource_section in &tx_config.resource_sections {
+        let (organization_slug, project_slug, _) = resource_section.get_opr_slugs().map_err(CmdError::LoadTxConfig)?;
+        projects.insert((organization_slug, project_slug));
+    }
+    let mut remote_resources = vec![];
+    for (organization_slug, project_slug) in &projects {
+        remote_resources.extend(api.get_all_resources(organization_slug, project_slug).map_err(CmdError::FetchResources)?);
+    }
+
+    let orphans = find_orphaned_resources(project_root, &tx_config, &remote_resources, owner);
+    if orphans.is_empty() {
+        println!("No orphaned resource found in {} resource(s) on Transifex.", remote_resources.len());
+        return Ok(());
+    }
+    println!("| Resource | Name | Reason |");
+    println!("| -------- | ---- | ------ |");
+    for orphan in &orphans {
+        println!("| {} | {} | {} |", orphan.resource_id, orphan.name, orphan.reason);
+    }
+    println!("\n{} orphaned resource(s) found in {} resource(s) on Transifex.", orphans.len(), remote_resources.len());
+
+    if !delete {
+        return Ok(());
+    }
+    if !yes && !confirm(&format!("Delete {} resource(s) and all their translations from Transifex? This cannot be undone.", orphans.len()))? {
+        println!("Nothing deleted.");
+        return Ok(());
+    }
+    for orphan in &orphans {
+        println!("Deleting resource {}...", orphan.resource_id);
+        api.delete_resource(&orphan.resource_id).map_err(|e| CmdError::Delete(orphan.resource_id.clone(), e))?;
+    }
+    println!("Deleted {} resource(s).", orphans.len());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::str::FromStr;
+    use crate::transifex::rest_api::TransifexDataAttributes;
+
+    #[test]
+    fn tst_find_orphaned_resources() {
+        let tx_config = TxConfig::from_str(r#"[main]
+host = https://www.transifex.com
+
+[o:linuxdeepin:p:deepin-home:r:deepin-home]
+file_filter = translations/deepin-home_<lang>.ts
+source_file = translations/deepin-home.ts
+source_lang = en
+type = QT
+
+[o:linuxdeepin:p:deepin-home:r:renamed]
+file_filter = translations/old-name_<lang>.ts
+source_file = translations/old-name.ts
+source_lang = en
+type = QT
+"#).unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_root = temp_dir.path().to_path_buf();
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        fs::write(project_root.join("translations/deepin-home.ts"), "").unwrap();
+
+        let resource = |slug: &str, category: Option<&str>| TransifexData {
+            id: format!("o:linuxdeepin:p:deepin-home:r:{slug}"),
+            attributes: TransifexDataAttributes {
+                name: Some(format!("{slug}.ts")),
+                categories: category.map(|category| vec![format!("github#repository:{category}#path:translations/{slug}.ts")]),
+            },
+        };
+        let remote_resources = vec![
+            resource("deepin-home", Some("linuxdeepin/deepin-home#branch:master")),
+            resource("renamed", Some("linuxdeepin/deepin-home#branch:master")),
+            resource("deleted", Some("linuxdeepin/deepin-home#branch:master")),
+            // Resources of other repositories, other branches and unlinked ones in the same Transifex project.
+            resource("foreign", Some("linuxdeepin/dde-control-center#branch:master")),
+            resource("release", Some("linuxdeepin/deepin-home#branch:release/1.0")),
+            resource("unlinked", None),
+        ];
+        let orphans = find_orphaned_resources(&project_root, &tx_config, &remote_resources, Some(("linuxdeepin/deepin-home", "master")));
+
+        assert_eq!(orphans, vec![
+            OrphanedResource {
+                resource_id: "o:linuxdeepin:p:deepin-home:r:renamed".to_string(),
+                name: "renamed.ts".to_string(),
+                reason: "source file missing",
+            },
+            OrphanedResource {
+                resource_id: "o:linuxdeepin:p:deepin-home:r:deleted".to_string(),
+                name: "deleted.ts".to_string(),
+                reason: "not in .tx/config",
+            },
+        ]);
+
+        // Without knowing the owner, only resources of .tx/config are checked.
+        let orphans = find_orphaned_resources(&project_root, &tx_config, &remote_resources, None);
+        assert_eq!(orphans.iter().map(|orphan| orphan.resource_id.as_str()).collect::<Vec<_>>(), vec!["o:linuxdeepin:p:deepin-home:r:renamed"]);
+    }
+}
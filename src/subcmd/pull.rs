@@ -0,0 +1,159 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error as TeError;
+
+use crate::parallel;
+use crate::transifex::rest_api::{TransifexLanguageStats, TransifexRestApi, TransifexRestApiError};
+use crate::transifex::tx_config_file::{load_project_tx_config_file, LoadTxConfigError, TxConfig};
+
+use super::status::SyncState;
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Fail to load .tx/config file because: {0}")]
+    LoadTxConfig(#[source] LoadTxConfigError),
+    #[error("Fail to load ~/.transifexrc or TX_TOKEN because: {0}")]
+    LoadTransifexRc(#[source] LoadTxConfigError),
+    #[error("Fail to fetch statistics from Transifex because: {0}")]
+    FetchStats(#[source] TransifexRestApiError),
+    #[error("Fail to download {0:?} because: {1}")]
+    Download(PathBuf, #[source] TransifexRestApiError),
+    #[error("Fail to write {0:?} because: {1}")]
+    WriteFile(PathBuf, #[source] std::io::Error),
+}
+
+#[derive(Debug, PartialEq)]
+struct PullJob {
+    resource_id: String,
+    language_code: String,
+    target_file: PathBuf,
+}
+
+/// Decide which translations to download according to their completeness on Transifex.
+///
+/// Languages below `minimum_perc` of the resource (or the main section) are skipped, so are languages
+/// without a local file unless `all` is set. An empty `languages` means any language.
+fn plan_pull_jobs(project_root: &Path, tx_config: &TxConfig, remote_stats: &[TransifexLanguageStats], languages: &[String], all: bool) -> Vec<PullJob> {
+    let mut jobs = vec![];
+    for resource_section in &tx_config.resource_sections {
+        let minimum_perc = resource_section.minimum_prec.or(tx_config.main_section.minimum_prec).unwrap_or(0);
+        for stats in remote_stats {
+            let Some((resource_id, language_code)) = stats.resource_and_language() else {
+                continue;
+            };
+            if resource_id != resource_section.resource_full_slug || language_code == resource_section.source_lang {
+                continue;
+            }
+            if !languages.is_empty() && !languages.iter().any(|lang| lang == language_code) {
+                continue;
+            }
+            if stats.completeness_percentage() < minimum_perc as f64 {
+                continue;
+            }
+            let target_file = project_root.join(resource_section.file_filter.replace("<lang>", language_code));
+            if !all && !target_file.is_file() {
+                continue;
+            }
+            jobs.push(PullJob {
+                resource_id: resource_id.to_string(),
+                language_code: language_code.to_string(),
+                target_file,
+            });
+        }
+    }
+    jobs.sort_by(|a, b| a.target_file.cmp(&b.target_file));
+    jobs
+}
+
+/// Download translations of all resources in .tx/config from Transifex, and write them to the paths given by `file_filter`.
+pub fn subcmd_pull(project_root: &Path, tx_config_file: Option<&Path>, languages: &[String], all: bool, mode: Option<&str>) -> Result<(), CmdError> {
+    let (tx_config_file, tx_config) = load_project_tx_config_file(project_root, tx_config_file).map_err(CmdError::LoadTxConfig)?;
+    println!("Found Transifex project config file at: {tx_config_file:?}");
+    let api = TransifexRestApi::new_from_transifexrc().map_err(CmdError::LoadTransifexRc)?;
+
+    let mut projects = vec![];
+    for resource_section in &tx_config.resource_sections {
+        let (organization_slug, project_slug, _) = resource_section.get_opr_slugs().map_err(CmdError::LoadTxConfig)?;
+        if !projects.contains(&(organization_slug.clone(), project_slug.clone())) {
+            projects.push((organization_slug, project_slug));
+        }
+    }
+    let mut remote_stats = vec![];
+    for (organization_slug, project_slug) in &projects {
+        remote_stats.extend(api.get_resource_language_stats(organization_slug, project_slug).map_err(CmdError::FetchStats)?);
+    }
+
+    let jobs = plan_pull_jobs(project_root, &tx_config, &remote_stats, languages, all);
+    let mode = mode.or(tx_config.main_section.mode.as_deref());
+    let contents = parallel::map(&jobs, |job| {
+        println!("Pulling {} translation into {:?}...", job.language_code, job.target_file);
+        api.download_resource_translation(&job.resource_id, &job.language_code, mode)
+    });
+    let mut sync_state = SyncState::load(project_root);
+    let written = jobs.iter().zip(contents).try_for_each(|(job, content)| {
+        let content = content.map_err(|e| CmdError::Download(job.target_file.clone(), e))?;
+        if let Some(parent) = job.target_file.parent() {
+            fs::create_dir_all(parent).map_err(|e| CmdError::WriteFile(job.target_file.clone(), e))?;
+        }
+        fs::write(&job.target_file, &content).map_err(|e| CmdError::WriteFile(job.target_file.clone(), e))?;
+        let remote_updated = remote_stats.iter()
+            .find(|stats| stats.resource_and_language() == Some((job.resource_id.as_str(), job.language_code.as_str())))
+            .and_then(|stats| stats.attributes.last_update.clone());
+        sync_state.record(project_root, &job.target_file, content.as_bytes(), remote_updated);
+        Ok(())
+    });
+    // Files written before a failure are recorded too, so `status` knows they are up to date.
+    sync_state.save(project_root).map_err(|e| CmdError::WriteFile(SyncState::state_file(project_root), e))?;
+    written?;
+    println!("Pulled {} translation file(s).", jobs.len());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use crate::transifex::rest_api::TransifexLanguageStatsAttributes;
+
+    #[test]
+    fn tst_plan_pull_jobs() {
+        let tx_config = TxConfig::from_str(r#"[main]
+host = https://www.transifex.com
+minimum_perc = 50
+
+[o:linuxdeepin:p:deepin-home:r:deepin-home]
+file_filter = translations/deepin-home_<lang>.ts
+source_file = translations/deepin-home.ts
+source_lang = en
+type = QT
+"#).unwrap();
+        let stats = |lang: &str, translated_strings: u64| TransifexLanguageStats {
+            id: format!("o:linuxdeepin:p:deepin-home:r:deepin-home:l:{lang}"),
+            attributes: TransifexLanguageStatsAttributes { total_strings: 10, translated_strings, ..Default::default() },
+        };
+        let remote_stats = vec![stats("en", 10), stats("zh_CN", 10), stats("zh_TW", 5), stats("fr", 2)];
+        let project_root = Path::new("/nonexistent/deepin-home");
+
+        let jobs = plan_pull_jobs(project_root, &tx_config, &remote_stats, &[], true);
+        assert_eq!(jobs, vec![
+            PullJob {
+                resource_id: "o:linuxdeepin:p:deepin-home:r:deepin-home".to_string(),
+                language_code: "zh_CN".to_string(),
+                target_file: project_root.join("translations/deepin-home_zh_CN.ts"),
+            },
+            PullJob {
+                resource_id: "o:linuxdeepin:p:deepin-home:r:deepin-home".to_string(),
+                language_code: "zh_TW".to_string(),
+                target_file: project_root.join("translations/deepin-home_zh_TW.ts"),
+            },
+        ]);
+        assert_eq!(plan_pull_jobs(project_root, &tx_config, &remote_stats, &["zh_TW".to_string()], true).len(), 1);
+        // Without `all`, only existing files are updated.
+        assert!(plan_pull_jobs(project_root, &tx_config, &remote_stats, &[], false).is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error as TeError;
+
+use crate::parallel;
+use crate::transifex::rest_api::{TransifexRestApi, TransifexRestApiError};
+use crate::transifex::tx_config_file::{load_project_tx_config_file, LoadTxConfigError, TxConfig};
+
+use crate::glob::PathGlobs;
+use super::status::SyncState;
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Fail to load .tx/config file because: {0}")]
+    LoadTxConfig(#[source] LoadTxConfigError),
+    #[error("Fail to load ~/.transifexrc or TX_TOKEN because: {0}")]
+    LoadTransifexRc(#[source] LoadTxConfigError),
+    #[error("Fail to find translation files of {0:?} because: {1}")]
+    MatchTargetFiles(String, #[source] std::io::Error),
+    #[error("Fail to read {0:?} because: {1}")]
+    ReadFile(PathBuf, #[source] std::io::Error),
+    #[error("Fail to upload {0:?} because: {1}")]
+    Upload(PathBuf, #[source] TransifexRestApiError),
+    #[error("Fail to write {0:?} because: {1}")]
+    WriteFile(PathBuf, #[source] std::io::Error),
+    #[error("Invalid resource glob: {0}")]
+    InvalidGlob(#[source] globset::Error),
+}
+
+#[derive(Debug, PartialEq)]
+struct PushJob {
+    resource_id: String,
+    /// `None` for the source file.
+    language_code: Option<String>,
+    file: PathBuf,
+}
+
+/// Decide which files to upload.
+///
+/// Only resources whose source file matches one of `resources` (or whose slug equals one of them) are pushed,
+/// all resources are pushed if it's empty. An empty `languages` means any translation found locally.
+fn plan_push_jobs(project_root: &Path, tx_config: &TxConfig, source: bool, translations: bool, resources: &[String], languages: &[String]) -> Result<Vec<PushJob>, CmdError> {
+    let resource_patterns = PathGlobs::new(resources).map_err(CmdError::InvalidGlob)?;
+    let filters = tx_config.to_transifex_yaml().filters;
+    let mut jobs = vec![];
+    for (resource_section, filter) in tx_config.resource_sections.iter().zip(filters) {
+        let source_file = resource_section.source_file.strip_prefix("./").unwrap_or(&resource_section.source_file);
+        let resource_slug = resource_section.resource_full_slug.rsplit(":r:").next().unwrap_or_default();
+        if !resources.is_empty()
+            && !resource_patterns.is_match(source_file)
+            && !resources.iter().any(|resource| resource == resource_slug) {
+            continue;
+        }
+        if source {
+            jobs.push(PushJob {
+                resource_id: resource_section.resource_full_slug.clone(),
+                language_code: None,
+                file: project_root.join(source_file),
+            });
+        }
+        if translations {
+            let target_files = match filter.match_target_files(project_root) {
+                Ok(target_files) => target_files,
+                // No translation yet.
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+                Err(e) => return Err(CmdError::MatchTargetFiles(resource_section.resource_full_slug.clone(), e)),
+            };
+            let mut translation_jobs: Vec<PushJob> = target_files.into_iter()
+                .filter(|(language_code, _)| *language_code != resource_section.source_lang)
+                .filter(|(language_code, _)| languages.is_empty() || languages.contains(language_code))
+                .map(|(language_code, file)| PushJob {
+                    resource_id: resource_section.resource_full_slug.clone(),
+                    language_code: Some(language_code),
+                    file,
+                })
+                .collect();
+            translation_jobs.sort_by(|a, b| a.file.cmp(&b.file));
+            jobs.extend(translation_jobs);
+        }
+    }
+    Ok(jobs)
+}
+
+/// Upload source files, and optionally translation files, of the resources in .tx/config to Transifex.
+///
+/// Source files are pushed if neither `source` nor `translations` is set, like `tx push` does.
+pub fn subcmd_push(project_root: &Path, tx_config_file: Option<&Path>, source: bool, translations: bool, resources: &[String], languages: &[String]) -> Result<(), CmdError> {
+    let (tx_config_file, tx_config) = load_project_tx_config_file(project_root, tx_config_file).map_err(CmdError::LoadTxConfig)?;
+    println!("Found Transifex project config file at: {tx_config_file:?}");
+    let api = TransifexRestApi::new_from_transifexrc().map_err(CmdError::LoadTransifexRc)?;
+
+    let source = source || !translations;
+    let jobs = plan_push_jobs(project_root, &tx_config, source, translations, resources, languages)?;
+    // Sources go first, so new strings exist on Transifex before their translations are uploaded.
+    let (source_jobs, translation_jobs): (Vec<PushJob>, Vec<PushJob>) = jobs.into_iter().partition(|job| job.language_code.is_none());
+    let mut sync_state = SyncState::load(project_root);
+    for jobs in [&source_jobs, &translation_jobs] {
+        let results = parallel::map(jobs, |job| {
+            let content = fs::read_to_string(&job.file).map_err(|e| CmdError::ReadFile(job.file.clone(), e))?;
+            match &job.language_code {
+                None => {
+                    println!("Pushing source file {:?}...", job.file);
+                    api.upload_resource_strings(&job.resource_id, &content)
+                },
+                Some(language_code) => {
+                    println!("Pushing {language_code} translation {:?}...", job.file);
+                    api.upload_resource_translation(&job.resource_id, language_code, &content)
+                },
+            }.map(|_| content).map_err(|e| CmdError::Upload(job.file.clone(), e))
+        });
+        let mut failure = None;
+        for (job, result) in jobs.iter().zip(results) {
+            match result {
+                Ok(content) => sync_state.record(project_root, &job.file, content.as_bytes(), None),
+                Err(e) => { failure.get_or_insert(e); },
+            }
+        }
+        // Files uploaded before a failure are recorded too, so `status` knows they are up to date.
+        sync_state.save(project_root).map_err(|e| CmdError::WriteFile(SyncState::state_file(project_root), e))?;
+        if let Some(e) = failure {
+            return Err(e);
+        }
+    }
+    println!("Pushed {} source file(s) and {} translation file(s).", source_jobs.len(), translation_jobs.len());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn tst_plan_push_jobs() {
+        let tx_config = TxConfig::from_str(r#"[main]
+host = https://www.transifex.com
+
+[o:linuxdeepin:p:deepin-home:r:deepin-home]
+file_filter = translations/deepin-home_<lang>.ts
+source_file = translations/deepin-home.ts
+source_lang = en
+type = QT
+
+[o:linuxdeepin:p:deepin-home:r:deepin-home-desktop]
+file_filter = misc/desktop_<lang>.ts
+source_file = misc/desktop.ts
+source_lang = en
+type = QT
+"#).unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_root = temp_dir.path().to_path_buf();
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        for file_name in ["deepin-home.ts", "deepin-home_en.ts", "deepin-home_zh_CN.ts", "deepin-home_zh_TW.ts"] {
+            fs::write(project_root.join("translations").join(file_name), "").unwrap();
+        }
+
+        let jobs = plan_push_jobs(&project_root, &tx_config, true, false, &[], &[]).unwrap();
+        assert_eq!(jobs.iter().map(|job| job.file.clone()).collect::<Vec<_>>(),
+            vec![project_root.join("translations/deepin-home.ts"), project_root.join("misc/desktop.ts")]);
+
+        let jobs = plan_push_jobs(&project_root, &tx_config, false, true, &[], &["zh_CN".to_string()]).unwrap();
+        assert_eq!(jobs, vec![PushJob {
+            resource_id: "o:linuxdeepin:p:deepin-home:r:deepin-home".to_string(),
+            language_code: Some("zh_CN".to_string()),
+            file: project_root.join("translations/deepin-home_zh_CN.ts"),
+        }]);
+
+        // Resources are filtered by source file glob or resource slug.
+        assert_eq!(plan_push_jobs(&project_root, &tx_config, true, true, &["translations/**".to_string()], &[]).unwrap().len(), 3);
+        assert_eq!(plan_push_jobs(&project_root, &tx_config, true, true, &["deepin-home-desktop".to_string()], &[]).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,125 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error as TeError;
+use crate::i18n_file::linguist::{Ts, TsLoadError, TsSaveError, TsSaveOptions};
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Provided path {0:?} does not exist")]
+    PathNotFound(PathBuf),
+    #[error("Failed to get file name")]
+    NoFileName,
+    #[error("No TS file found in the given paths")]
+    NoTsFile,
+    #[error("Fail to create directory {0:?} because: {1}")]
+    CreateDir(PathBuf, #[source] std::io::Error),
+    #[error("Fail to load TS file {0:?} because: {1}")]
+    LoadTsFile(PathBuf, #[source] TsLoadError),
+    #[error("Fail to save TS file {0:?} because: {1}")]
+    SaveTsFile(PathBuf, #[source] TsSaveError),
+    #[error("Context {0:?} and {1:?} would be written into the same file {2:?}")]
+    ConflictedFileName(String, String, String),
+    #[error("TS file {0:?} has language {2:?}, which is different from {1:?} of other files. Use `--language` to override it.")]
+    MismatchedLanguage(PathBuf, String, String),
+}
+
+/// File name of the split file for the given context, e.g. `dcc::MainWindow` becomes `dcc_MainWindow.ts`.
+fn context_file_name(context: &str) -> String {
+    let name: String = context.chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    if name.is_empty() {
+        "_.ts".to_string()
+    } else {
+        format!("{name}.ts")
+    }
+}
+
+pub fn subcmd_split(ts_file: &Path, output_dir: Option<&Path>) -> Result<(), CmdError> {
+    if !ts_file.is_file() {
+        return Err(CmdError::PathNotFound(ts_file.to_path_buf()));
+    }
+    // Default to a directory next to the file with the same name, e.g. `dcc_zh_CN.ts` into `dcc_zh_CN/`.
+    let output_dir = match output_dir {
+        Some(output_dir) => output_dir.to_path_buf(),
+        None => ts_file.with_file_name(ts_file.file_stem().ok_or(CmdError::NoFileName)?),
+    };
+
+    let ts = Ts::load_from_file(ts_file).map_err(|e| CmdError::LoadTsFile(ts_file.to_path_buf(), e))?;
+    let parts = ts.split_by_context();
+    let mut file_names: HashMap<String, &str> = HashMap::new();
+    for part in &parts {
+        let context = part.contexts[0].name.as_str();
+        let file_name = context_file_name(context);
+        if let Some(existing) = file_names.insert(file_name.clone(), context) {
+            return Err(CmdError::ConflictedFileName(existing.to_string(), context.to_string(), file_name));
+        }
+    }
+
+    fs::create_dir_all(&output_dir).map_err(|e| CmdError::CreateDir(output_dir.clone(), e))?;
+    for part in &parts {
+        let part_file = output_dir.join(context_file_name(&part.contexts[0].name));
+        part.save_into_file(&part_file, &TsSaveOptions::default())
+            .map_err(|e| CmdError::SaveTsFile(part_file.clone(), e))?;
+    }
+    println!("Split {ts_file:?} into {} file(s) under {output_dir:?}.", parts.len());
+
+    Ok(())
+}
+
+pub fn subcmd_join(paths: &[PathBuf], output: &Path, language: Option<&str>) -> Result<(), CmdError> {
+    if let Some(path) = paths.iter().find(|path| !path.exists()) {
+        return Err(CmdError::PathNotFound(path.clone()));
+    }
+    // The output file may be inside one of the given directories, it is not a part to join.
+    let files: Vec<PathBuf> = crate::extract::collect_source_files(paths, &["ts"]).into_iter()
+        .filter(|file| file != output)
+        .collect();
+    if files.is_empty() {
+        return Err(CmdError::NoTsFile);
+    }
+
+    let mut joined: Option<Ts> = None;
+    let mut joined_language = language.map(str::to_string);
+    for file in &files {
+        let part = Ts::load_from_file(file).map_err(|e| CmdError::LoadTsFile(file.clone(), e))?;
+        match (&joined_language, &part.language) {
+            (Some(expected), Some(found)) if language.is_none() && expected != found => {
+                return Err(CmdError::MismatchedLanguage(file.clone(), expected.clone(), found.clone()));
+            },
+            (None, Some(found)) => joined_language = Some(found.clone()),
+            _ => {},
+        }
+        match joined.as_mut() {
+            Some(joined) => joined.append(part),
+            None => joined = Some(part),
+        }
+    }
+
+    let Some(mut joined) = joined else {
+        return Err(CmdError::NoTsFile);
+    };
+    joined.language = joined_language;
+    joined.save_into_file(output, &TsSaveOptions::default())
+        .map_err(|e| CmdError::SaveTsFile(output.to_path_buf(), e))?;
+    println!("Joined {} file(s) into {output:?}.", files.len());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_context_file_name() {
+        assert_eq!(context_file_name("MainWindow"), "MainWindow.ts");
+        assert_eq!(context_file_name("dcc::update::UpdateWidget"), "dcc__update__UpdateWidget.ts");
+        assert_eq!(context_file_name(""), "_.ts");
+    }
+}
@@ -4,65 +4,208 @@
 
 use serde::Serialize;
 use thiserror::Error as TeError;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
+use crate::parallel;
+use crate::stats::{file_stats, LanguageReport, ResourceReport, StatsReport, StringCounts, TranslationReport, STATS_SCHEMA_VERSION};
+use crate::glob::PathGlobs;
+use crate::subcmd::stats_history::{record_snapshot, LanguageSnapshot, StatsSnapshot};
 use crate::transifex::project_file::*;
-use crate::i18n_file::{self, common::{MessageStats, I18nFileKind}};
+use crate::transifex::rest_api::{TransifexLanguageStats, TransifexRestApi, TransifexRestApiError};
+use crate::transifex::tx_config_file::{load_tx_config_file, try_load_tx_config_file, LoadTxConfigError, TxConfig};
+use crate::i18n_file::{cache::{I18nFileCache, I18nFileLoadError}, common::{MessageState, MessageStats}, language_code::is_valid_language_code};
+use crate::subcmd::diff::{escape_cell, state_name};
 
 #[derive(TeError, Debug)]
 pub enum CmdError {
-    #[error("Can not guess translation file kind from path {0:?} because: {1}")]
-    GuessI18nFileType(PathBuf, #[source] i18n_file::common::UnknownI18nFileExtError),
-    #[error("Fail to load Qt Linguist TS file {0:?} because: {1}")]
-    LoadTsFile(PathBuf, #[source] i18n_file::linguist::TsLoadError),
-    #[error("Fail to load Gettext PO/POT file {0:?} because: {1}")]
-    LoadPoFile(PathBuf, #[source] i18n_file::gettext::PoLoadError),
+    #[error("Fail to load translation file {0:?} because: {1}")]
+    LoadI18nFile(PathBuf, #[source] I18nFileLoadError),
     #[error("Fail to load Transifex project file because: {0}")]
     LoadTxProjectFile(#[from] TxProjectFileLoadError),
     #[error("Fail to match resources because: {0}")]
     MatchResources(#[source] std::io::Error),
+    #[error("Invalid resource glob: {0}")]
+    InvalidGlob(#[source] globset::Error),
+    #[error("Fail to discover resources because: {0}")]
+    DiscoverResources(#[source] crate::subcmd::gentxcfg::CmdError),
     #[error("Fail to serialize stats to YAML: {0}")]
     SerdeYaml(#[from] serde_yaml2::ser::Errors),
     #[error("Fail to serialize stats to JSON: {0}")]
     SerdeJson(#[from] serde_json::Error),
+    #[error("Fail to write report to {0:?} because: {1}")]
+    WriteOutput(PathBuf, #[source] std::io::Error),
+    #[error("Comparing with Transifex requires resource slugs from .tx/config, try `yaml2txconfig` first: {0}")]
+    LoadTxConfig(#[source] LoadTxConfigError),
+    #[error("Fail to load ~/.transifexrc or TX_TOKEN because: {0}")]
+    LoadTransifexRc(#[source] LoadTxConfigError),
+    #[error("Fail to fetch statistics from Transifex because: {0}")]
+    FetchRemoteStats(#[from] TransifexRestApiError),
+    #[error("Fail to record statistics history because: {0}")]
+    RecordHistory(#[source] crate::subcmd::stats_history::CmdError),
+    #[error("Completeness of {languages} is below {0}%", languages = .1.join(", "))]
+    BelowThreshold(f64, Vec<String>),
+    #[error("Transifex does not report character counts, use `--metric strings` or `--metric words` instead")]
+    RemoteCharsMetric,
+    #[error("Grouping by project needs a config covering many repositories like the one from `monotxconfig`, but {0:?} is not in an `owner/repo` directory")]
+    NotMonoConfig(String),
 }
 
-#[derive(clap::ValueEnum, Clone, Default, Copy, Debug)]
+#[derive(clap::ValueEnum, serde::Deserialize, Clone, Default, Copy, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub enum StatsFormat {
     #[default]
     PlainTable,
     Yaml,
     Json,
+    /// One row per language, e.g. for spreadsheets
+    Csv,
+    /// Standalone HTML page with progress bars, e.g. for publishing on GitHub Pages
+    Html,
+    /// SVG bar chart of the completeness of each language, e.g. for embedding in wikis
+    Svg,
 }
 
-#[derive(clap::ValueEnum, Clone, Default, Copy, Debug)]
+#[derive(clap::ValueEnum, serde::Deserialize, Clone, Default, Copy, Debug)]
+#[serde(rename_all = "kebab-case")]
 pub enum StatsSortBy {
     LanguageCode,
     #[default]
     Completeness,
 }
 
-#[derive(Default, Serialize)]
+/// How the completeness of a language is measured.
+#[derive(clap::ValueEnum, serde::Deserialize, Clone, Default, Copy, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompletenessMetric {
+    /// Percentage of translated strings
+    #[default]
+    Strings,
+    /// Percentage of translated source words, so missing long paragraphs weigh more than missing button labels
+    Words,
+    /// Percentage of translated non-whitespace source characters, like words but for languages written without spaces
+    Chars,
+}
+
+impl CompletenessMetric {
+    /// Total of the stats to measure completeness against.
+    fn total(self, stats: &MessageStats) -> u64 {
+        match self {
+            CompletenessMetric::Strings => stats.shown_translated() + stats.shown_unfinished(),
+            CompletenessMetric::Words => stats.translated_words + stats.untranslated_words,
+            CompletenessMetric::Chars => stats.translated_chars + stats.untranslated_chars,
+        }
+    }
+
+    fn percentage(self, stats: &MessageStats, reference_total: Option<u64>) -> f64 {
+        match self {
+            CompletenessMetric::Strings => stats.completeness_percentage(reference_total),
+            CompletenessMetric::Words => match reference_total.unwrap_or(self.total(stats)) {
+                0 => 0.0,
+                total => stats.translated_words as f64 / total as f64 * 100.0,
+            },
+            CompletenessMetric::Chars => match reference_total.unwrap_or(self.total(stats)) {
+                0 => 0.0,
+                total => stats.translated_chars as f64 / total as f64 * 100.0,
+            },
+        }
+    }
+}
+
+/// Completeness percentages deciding bar colors of SVG charts.
+#[derive(Clone, Copy, Debug)]
+pub struct ChartThresholds {
+    /// Languages at or above this are shown in green.
+    pub good: f64,
+    /// Languages at or above this (but below `good`) are shown in yellow, the rest in red.
+    pub fair: f64,
+}
+
+impl Default for ChartThresholds {
+    fn default() -> Self {
+        Self { good: 90.0, fair: 50.0 }
+    }
+}
+
+impl ChartThresholds {
+    fn color(&self, completeness: f64) -> &'static str {
+        match completeness {
+            c if c >= self.good => "#2da44e",
+            c if c >= self.fair => "#d4a72c",
+            _ => "#cf222e",
+        }
+    }
+}
+
+/// Options of how the statistics report is rendered.
+#[derive(Clone, Debug, Default)]
+pub struct StatsReportOptions {
+    pub format: StatsFormat,
+    pub sort_by: StatsSortBy,
+    /// Calculate the completeness of each language against its own total instead of the source total.
+    pub standalone_percentage: bool,
+    /// Render a shields.io badge JSON of the given language instead of the report.
+    pub badge: Option<String>,
+    pub chart_thresholds: ChartThresholds,
+    pub metric: CompletenessMetric,
+    /// Also show completeness of each resource on Transifex.
+    pub with_remote: bool,
+    /// Write the report into the given file instead of stdout.
+    pub output: Option<PathBuf>,
+    /// Append a snapshot of the completeness of each language to the given history file.
+    pub record: Option<PathBuf>,
+    /// Only count resources whose source file (relative to the project root) matches one of the patterns,
+    /// all resources are counted if empty.
+    pub resources: PathGlobs,
+    /// Show sub-totals of each repository, for configs covering many repositories like the one from `monotxconfig`.
+    pub group_by_project: bool,
+    /// List unfinished and fuzzy messages of the given languages after the plain table, or of all languages if empty.
+    pub list_untranslated: Option<Vec<String>>,
+    /// Break the completeness of each resource down by context (msgctxt for PO files) after the plain table.
+    pub by_context: bool,
+}
+
+/// Repository of the source file in a config covering many repositories, i.e. its leading `owner/repo` directories,
+/// like `source_file`s written by `monotxconfig`. `None` if the file is not inside such directories.
+fn project_of_source(source_path: &str) -> Option<String> {
+    let mut components = source_path.splitn(3, '/');
+    match (components.next(), components.next(), components.next()) {
+        (Some(owner), Some(repo), Some(_)) if !owner.is_empty() && !repo.is_empty() => Some(format!("{owner}/{repo}")),
+        _ => None,
+    }
+}
+
+/// Project of the source file if grouping by project, which is only possible for configs covering many repositories.
+fn group_project(source_path: &str, group_by_project: bool) -> Result<Option<String>, CmdError> {
+    match group_by_project {
+        true => project_of_source(source_path).map(Some).ok_or_else(|| CmdError::NotMonoConfig(source_path.to_string())),
+        false => Ok(None),
+    }
+}
+
+#[derive(Default)]
 struct ProjectResourceStats {
     project_path: PathBuf,
     target_lang_codes: Vec<String>,
     resource_groups: Vec<TsResourceGroupStats>,
+    /// Source files listed in the project file but missing on disk.
+    missing_source_paths: Vec<PathBuf>,
+    metric: CompletenessMetric,
 }
 
 fn load_file_stats(file_path: &Path) -> Result<MessageStats, CmdError> {
-    let kind = i18n_file::common::I18nFileKind::from_ext_hint(&file_path)
-        .map_err(|e| CmdError::GuessI18nFileType(file_path.to_path_buf(), e))?;
-
-    Ok(match kind {
-        I18nFileKind::Linguist => i18n_file::linguist::Ts::load_from_file(&file_path)
-            .map_err(|e| CmdError::LoadTsFile(file_path.to_path_buf(), e))?
-            .get_message_stats(),
-        I18nFileKind::Gettext => i18n_file::gettext::Po::load_from_file(&file_path)
-            .map_err(|e| CmdError::LoadPoFile(file_path.to_path_buf(), e))?
-            .get_message_stats(),
-    })
+    file_stats(file_path).map_err(|e| CmdError::LoadI18nFile(file_path.to_path_buf(), e))
 }
 
 impl ProjectResourceStats {
+    /// Total to measure completeness of every language against, or `None` to measure each against its own total.
+    fn reference_total(&self, source_stats: &MessageStats, standalone_percentage: bool) -> Option<u64> {
+        (!standalone_percentage).then_some(self.metric.total(source_stats))
+    }
+
+    fn completeness(&self, stats: &MessageStats, reference_total: Option<u64>) -> f64 {
+        self.metric.percentage(stats, reference_total)
+    }
+
     pub fn get_source_stats(&self) -> (i32, MessageStats) {
         let mut total_resources = 0;
         let mut total_stats = MessageStats::default();
@@ -85,116 +228,719 @@ impl ProjectResourceStats {
         (total_resources, total_stats)
     }
 
-    pub fn print_state_plain_table(&self, standalone_percentage: bool, sort_by: StatsSortBy) {
-        println!("| No. | Lang   | Completeness | Resources | Translated | Unfinished | Vanished |");
-        println!("| --- | ------ | ------------ | --------- | ---------- | ---------- | -------- |");
+    /// Target language codes in the order they should be shown.
+    fn sorted_language_codes(&self, reference_total: Option<u64>, sort_by: StatsSortBy) -> Vec<String> {
+        let mut sorted_langs = self.target_lang_codes.clone();
+        if matches!(sort_by, StatsSortBy::Completeness) {
+            sorted_langs.sort_by(|a, b| {
+                let (_, a_stats) = self.get_target_stats_by_language_code(a);
+                let (_, b_stats) = self.get_target_stats_by_language_code(b);
+                let a_completeness = self.completeness(&a_stats, reference_total);
+                let b_completeness = self.completeness(&b_stats, reference_total);
+                b_completeness.total_cmp(&a_completeness)
+            });
+        }
+        sorted_langs
+    }
+
+    /// Split the stats into one per project, resources without a project are left out.
+    fn split_by_project(&self) -> BTreeMap<String, ProjectResourceStats> {
+        let mut projects: BTreeMap<String, ProjectResourceStats> = BTreeMap::new();
+        for resource_group in &self.resource_groups {
+            let Some(project) = &resource_group.project else {
+                continue;
+            };
+            let project_stats = projects.entry(project.clone()).or_insert_with(|| ProjectResourceStats {
+                project_path: self.project_path.join(project),
+                metric: self.metric,
+                ..ProjectResourceStats::default()
+            });
+            for lang in &resource_group.target_lang_codes {
+                if !project_stats.target_lang_codes.contains(lang) {
+                    project_stats.target_lang_codes.push(lang.clone());
+                }
+            }
+            project_stats.resource_groups.push(resource_group.clone());
+        }
+        for project_stats in projects.values_mut() {
+            project_stats.target_lang_codes.sort();
+        }
+        projects
+    }
+
+    /// Render the completeness of each context per resource and language, contexts are in the order they appear in the file.
+    fn render_context_breakdown(&self) -> Result<String, CmdError> {
+        let mut table = String::new();
+        table.push_str("\n| Resource | Context | Lang   | Completeness | Translated | Unfinished |\n");
+        table.push_str("| -------- | ------- | ------ | ------------ | ---------- | ---------- |\n");
+        for resource_group in &self.resource_groups {
+            let resource = resource_group.source_path.strip_prefix(&self.project_path).unwrap_or(&resource_group.source_path).display().to_string();
+            let mut language_codes = resource_group.target_lang_codes.clone();
+            language_codes.sort();
+            for lang in &language_codes {
+                let Some(target) = resource_group.target_stats.get(lang) else {
+                    continue;
+                };
+                let messages = I18nFileCache::shared().load(&target.resource_path)
+                    .map_err(|e| CmdError::LoadI18nFile(target.resource_path.clone(), e))?
+                    .flat_messages();
+                // (context, translated, unfinished)
+                let mut contexts: Vec<(String, u64, u64)> = Vec::new();
+                for message in messages {
+                    let (translated, unfinished) = match message.state {
+                        MessageState::Finished => (1, 0),
+                        MessageState::Unfinished | MessageState::Fuzzy => (0, 1),
+                        MessageState::Vanished | MessageState::Obsolete => continue,
+                    };
+                    match contexts.iter_mut().find(|(context, _, _)| *context == message.context) {
+                        Some(counts) => {
+                            counts.1 += translated;
+                            counts.2 += unfinished;
+                        },
+                        None => contexts.push((message.context, translated, unfinished)),
+                    }
+                }
+                for (context, translated, unfinished) in contexts {
+                    let completeness = translated as f64 / (translated + unfinished) as f64 * 100.0;
+                    table.push_str(&format!("| {resource} | {} | {lang:>6} | {completeness:>11.2}% | {translated:10} | {unfinished:10} |\n", escape_cell(&context)));
+                }
+            }
+        }
+        Ok(table)
+    }
+
+    /// Render a table of unfinished and fuzzy messages per language, as a work list for translators.
+    fn render_untranslated(&self, language_codes: &[String]) -> Result<String, CmdError> {
+        let language_codes = if language_codes.is_empty() { &self.target_lang_codes } else { language_codes };
+        let mut table = String::new();
+        for lang in language_codes {
+            let mut rows = Vec::new();
+            for resource_group in &self.resource_groups {
+                let Some(target) = resource_group.target_stats.get(lang) else {
+                    continue;
+                };
+                let file = target.resource_path.strip_prefix(&self.project_path).unwrap_or(&target.resource_path).display().to_string();
+                let messages = I18nFileCache::shared().load(&target.resource_path)
+                    .map_err(|e| CmdError::LoadI18nFile(target.resource_path.clone(), e))?
+                    .flat_messages();
+                for message in messages.into_iter().filter(|message| matches!(message.state, MessageState::Unfinished | MessageState::Fuzzy)) {
+                    rows.push(format!("| {file} | {} | {} | {} |\n", escape_cell(&message.context), escape_cell(&message.source), state_name(message.state)));
+                }
+            }
+            table.push_str(&format!("\nUntranslated strings of {lang} ({}):\n", rows.len()));
+            if !rows.is_empty() {
+                table.push_str("| File | Context | Source | State |\n");
+                table.push_str("| ---- | ------- | ------ | ----- |\n");
+                table.push_str(&rows.concat());
+            }
+        }
+        Ok(table)
+    }
+
+    /// Completeness of each target language, to be recorded in the statistics history.
+    fn snapshot(&self, standalone_percentage: bool) -> StatsSnapshot {
+        let (_, source_stats) = self.get_source_stats();
+        let reference_total = self.reference_total(&source_stats, standalone_percentage);
+        let languages = self.target_lang_codes.iter().map(|lang| {
+            let (_, target_stats) = self.get_target_stats_by_language_code(lang);
+            (lang.clone(), LanguageSnapshot {
+                completeness: self.completeness(&target_stats, reference_total),
+                translated: target_stats.shown_translated(),
+                unfinished: target_stats.shown_unfinished(),
+            })
+        }).collect();
+        StatsSnapshot::now(languages)
+    }
+
+    pub fn render_plain_table(&self, standalone_percentage: bool, sort_by: StatsSortBy) -> String {
+        let mut table = String::new();
+        table.push_str("| No. | Lang   | Completeness | Resources | Translated | Unfinished | Fuzzy | Vanished | Translated Words | Unfinished Words | Translated Chars | Unfinished Chars |\n");
+        table.push_str("| --- | ------ | ------------ | --------- | ---------- | ---------- | ----- | -------- | ---------------- | ---------------- | ---------------- | ---------------- |\n");
         let (source_resources, source_stats) = self.get_source_stats();
         let total_strings = source_stats.shown_translated() + source_stats.shown_unfinished();
-        let reference_total = (!standalone_percentage).then_some(total_strings);
-        println!("|   0 | Source | {0:>11.2}% | {1:9} | {2:10} | {3:10} | {4:8} |", 
-            100.0, source_resources, total_strings, 0, source_stats.shown_obsolete());
-        let language_codes = match sort_by {
-            StatsSortBy::LanguageCode => {
-                self.target_lang_codes.clone()
-            }
-            StatsSortBy::Completeness => {
-                let mut sorted_langs = self.target_lang_codes.clone();
-                sorted_langs.sort_by(|a, b| {
-                    let (_, a_stats) = self.get_target_stats_by_language_code(&a);
-                    let (_, b_stats) = self.get_target_stats_by_language_code(&b);
-                    let a_completeness = a_stats.completeness_percentage(reference_total);
-                    let b_completeness = b_stats.completeness_percentage(reference_total);
-                    if a_completeness > b_completeness {
-                        std::cmp::Ordering::Less
-                    } else if a_completeness < b_completeness {
-                        std::cmp::Ordering::Greater
-                    } else {
-                        std::cmp::Ordering::Equal
+        let total_words = source_stats.translated_words + source_stats.untranslated_words;
+        let total_chars = source_stats.translated_chars + source_stats.untranslated_chars;
+        let reference_total = self.reference_total(&source_stats, standalone_percentage);
+        table.push_str(&format!("|   0 | Source | {0:>11.2}% | {1:9} | {2:10} | {3:10} | {4:5} | {5:8} | {6:16} | {7:16} | {8:16} | {9:16} |\n",
+            100.0, source_resources, total_strings, 0, 0, source_stats.shown_obsolete(), total_words, 0, total_chars, 0));
+
+        let (mut all_resources, mut all_stats) = (0, MessageStats::default());
+        for (idx, lang) in self.sorted_language_codes(reference_total, sort_by).iter().enumerate() {
+            let (target_resources, target_stats) = self.get_target_stats_by_language_code(lang);
+            // Unknown language codes (e.g. a typo in the file name) are marked so they stand out in the report.
+            let lang = if is_valid_language_code(lang) { lang.clone() } else { format!("{lang}?") };
+            // Fuzzy entries (e.g. from `msgmerge`) are shown apart from the truly missing ones, both count as untranslated.
+            table.push_str(&format!("| {0:3} | {1:>6} | {2:>11.2}% | {3:9} | {4:10} | {5:10} | {6:5} | {7:8} | {8:16} | {9:16} | {10:16} | {11:16} |\n",
+                idx + 1, lang, self.completeness(&target_stats, reference_total), target_resources, target_stats.shown_translated(), target_stats.unfinished, target_stats.fuzzy,
+                target_stats.shown_obsolete(), target_stats.translated_words, target_stats.untranslated_words, target_stats.translated_chars, target_stats.untranslated_chars));
+            all_resources += target_resources;
+            all_stats += &target_stats;
+        }
+        // All languages together, measured against the source total of every language.
+        let all_reference_total = reference_total.map(|total| total * self.target_lang_codes.len() as u64);
+        table.push_str(&format!("|     |  Total | {0:>11.2}% | {1:9} | {2:10} | {3:10} | {4:5} | {5:8} | {6:16} | {7:16} | {8:16} | {9:16} |\n",
+            self.completeness(&all_stats, all_reference_total), all_resources, all_stats.shown_translated(), all_stats.unfinished, all_stats.fuzzy,
+            all_stats.shown_obsolete(), all_stats.translated_words, all_stats.untranslated_words, all_stats.translated_chars, all_stats.untranslated_chars));
+
+        let untranslated_resources = self.resource_groups.iter()
+            .filter(|group| group.target_stats.values().all(|target| target.stats.shown_translated() == 0))
+            .count();
+        table.push_str(&format!("\n{source_resources} resource(s), {untranslated_resources} without any translated string, {} missing source file(s).\n",
+            self.missing_source_paths.len()));
+        if self.target_lang_codes.iter().any(|lang| !is_valid_language_code(lang)) {
+            table.push_str("Language codes marked with `?` are not known ISO 639/3166 codes.\n");
+        }
+
+        let projects = self.split_by_project();
+        if !projects.is_empty() {
+            table.push_str("\n| Project | Lang   | Completeness | Resources | Translated | Unfinished |\n");
+            table.push_str("| ------- | ------ | ------------ | --------- | ---------- | ---------- |\n");
+        }
+        for (project, project_stats) in &projects {
+            let (source_resources, source_stats) = project_stats.get_source_stats();
+            let total_strings = source_stats.shown_translated() + source_stats.shown_unfinished();
+            let reference_total = project_stats.reference_total(&source_stats, standalone_percentage);
+            table.push_str(&format!("| {project} | Source | {0:>11.2}% | {1:9} | {2:10} | {3:10} |\n", 100.0, source_resources, total_strings, 0));
+            for lang in project_stats.sorted_language_codes(reference_total, sort_by) {
+                let (target_resources, target_stats) = project_stats.get_target_stats_by_language_code(&lang);
+                table.push_str(&format!("| {project} | {lang:>6} | {0:>11.2}% | {1:9} | {2:10} | {3:10} |\n",
+                    project_stats.completeness(&target_stats, reference_total), target_resources, target_stats.shown_translated(), target_stats.shown_unfinished()));
+            }
+        }
+
+        if self.resource_groups.iter().any(|group| !group.remote_completeness.is_empty()) {
+            let percentage = |value: Option<f64>| value.map_or("-".to_string(), |value| format!("{value:.2}%"));
+            table.push_str("\n| Resource | Lang | Local | Transifex | Status |\n");
+            table.push_str("| -------- | ---- | ----- | --------- | ------ |\n");
+            for group in self.resource_groups.iter().filter(|group| !group.remote_completeness.is_empty()) {
+                let resource = group.source_path.strip_prefix(&self.project_path).unwrap_or(&group.source_path).display();
+                for (lang, local, remote, status) in group.remote_comparison(self.metric, standalone_percentage) {
+                    table.push_str(&format!("| {resource} | {lang} | {} | {} | {status} |\n", percentage(local), percentage(remote)));
+                }
+            }
+        }
+        table
+    }
+
+    /// Convert into the stable schema of the JSON and YAML output.
+    fn to_report(&self, standalone_percentage: bool, sort_by: StatsSortBy) -> StatsReport {
+        let relative_path = |path: &Path| path.strip_prefix(&self.project_path).unwrap_or(path).to_path_buf();
+        let (source_resources, source_stats) = self.get_source_stats();
+        let reference_total = self.reference_total(&source_stats, standalone_percentage);
+        let languages = self.sorted_language_codes(reference_total, sort_by).into_iter().map(|lang| {
+            let (target_resources, target_stats) = self.get_target_stats_by_language_code(&lang);
+            LanguageReport {
+                completeness: self.completeness(&target_stats, reference_total),
+                counts: StringCounts::new(target_resources as u64, &target_stats),
+                language: lang,
+            }
+        }).collect();
+        let resources = self.resource_groups.iter().map(|resource_group| {
+            let reference_total = self.reference_total(&resource_group.source_stats, standalone_percentage);
+            let mut language_codes: Vec<&String> = resource_group.target_stats.keys().collect();
+            language_codes.sort();
+            ResourceReport {
+                source_path: relative_path(&resource_group.source_path),
+                source_language: resource_group.source_lang_code.clone(),
+                project: resource_group.project.clone(),
+                source: StringCounts::new(1, &resource_group.source_stats),
+                translations: language_codes.into_iter().map(|lang| {
+                    let target = &resource_group.target_stats[lang];
+                    TranslationReport {
+                        language: lang.clone(),
+                        path: relative_path(&target.resource_path),
+                        completeness: self.completeness(&target.stats, reference_total),
+                        remote_completeness: resource_group.remote_completeness.get(lang).copied(),
+                        counts: StringCounts::new(1, &target.stats),
                     }
-                });
-                sorted_langs
+                }).collect(),
             }
-        };
-        
-        for (idx, lang) in language_codes.iter().enumerate() {
+        }).collect();
+        StatsReport {
+            schema_version: STATS_SCHEMA_VERSION,
+            project_path: self.project_path.clone(),
+            metric: clap::ValueEnum::to_possible_value(&self.metric).map_or_else(String::new, |value| value.get_name().to_string()),
+            source: StringCounts::new(source_resources as u64, &source_stats),
+            languages,
+            resources,
+            missing_source_paths: self.missing_source_paths.iter().map(|path| relative_path(path)).collect(),
+        }
+    }
+
+    /// Render a CSV table with a row per language, the source row comes first.
+    pub fn render_csv(&self, standalone_percentage: bool, sort_by: StatsSortBy) -> String {
+        let mut csv = String::from("language,completeness,resources,translated,unfinished,fuzzy,vanished,translated_words,unfinished_words,translated_chars,unfinished_chars\n");
+        let (source_resources, source_stats) = self.get_source_stats();
+        let total_strings = source_stats.shown_translated() + source_stats.shown_unfinished();
+        let reference_total = self.reference_total(&source_stats, standalone_percentage);
+        csv.push_str(&format!("source,100.00,{source_resources},{total_strings},0,0,{},{},0,{},0\n",
+            source_stats.shown_obsolete(), source_stats.translated_words + source_stats.untranslated_words, source_stats.translated_chars + source_stats.untranslated_chars));
+        for lang in self.sorted_language_codes(reference_total, sort_by) {
             let (target_resources, target_stats) = self.get_target_stats_by_language_code(&lang);
-            println!("| {0:3} | {1:>6} | {2:>11.2}% | {3:9} | {4:10} | {5:10} | {6:8} |", 
-                idx + 1, lang, target_stats.completeness_percentage(reference_total), target_resources, target_stats.shown_translated(), target_stats.shown_unfinished(), target_stats.shown_obsolete());
+            csv.push_str(&format!("{lang},{:.2},{target_resources},{},{},{},{},{},{},{},{}\n",
+                self.completeness(&target_stats, reference_total), target_stats.shown_translated(), target_stats.unfinished, target_stats.fuzzy,
+                target_stats.shown_obsolete(), target_stats.translated_words, target_stats.untranslated_words, target_stats.translated_chars, target_stats.untranslated_chars));
         }
+        csv
     }
 
-    pub fn print_stats_yaml(&self) -> Result<(), serde_yaml2::ser::Errors> {
-        let yaml_str = serde_yaml2::to_string(self)?;
-        println!("{}", yaml_str);
-        Ok(())
+    /// Render a standalone HTML page with a progress bar per language, followed by a table per resource.
+    pub fn render_html(&self, standalone_percentage: bool, sort_by: StatsSortBy) -> String {
+        let (source_resources, source_stats) = self.get_source_stats();
+        let total_strings = source_stats.shown_translated() + source_stats.shown_unfinished();
+        let reference_total = self.reference_total(&source_stats, standalone_percentage);
+        let project_name = self.project_path.file_name().unwrap_or(self.project_path.as_os_str()).to_string_lossy();
+        let relative_path = |path: &Path| path.strip_prefix(&self.project_path).unwrap_or(path).display().to_string();
+
+        let mut html = String::new();
+        html.push_str(&format!(r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Translation statistics of {0}</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; color: #24292f; }}
+table {{ border-collapse: collapse; margin-bottom: 2em; }}
+th, td {{ border: 1px solid #d0d7de; padding: 4px 8px; text-align: right; }}
+th:first-child, td:first-child {{ text-align: left; }}
+.bar {{ width: 200px; height: 12px; background: #eaeef2; border-radius: 6px; overflow: hidden; }}
+.bar > div {{ height: 100%; background: #2da44e; }}
+</style>
+</head>
+<body>
+<h1>Translation statistics of {0}</h1>
+<p>{1} resource(s), {2} source string(s), {3} source word(s), {4} source character(s).</p>
+"#, escape_html(&project_name), source_resources, total_strings, source_stats.translated_words + source_stats.untranslated_words,
+            source_stats.translated_chars + source_stats.untranslated_chars));
+
+        html.push_str("<h2>Languages</h2>\n<table>\n<tr><th>Language</th><th>Progress</th><th>Completeness</th><th>Resources</th><th>Translated</th><th>Unfinished</th><th>Fuzzy</th><th>Vanished</th><th>Translated Words</th><th>Unfinished Words</th><th>Translated Chars</th><th>Unfinished Chars</th></tr>\n");
+        for lang in self.sorted_language_codes(reference_total, sort_by) {
+            let (target_resources, target_stats) = self.get_target_stats_by_language_code(&lang);
+            let completeness = self.completeness(&target_stats, reference_total);
+            html.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{completeness:.2}%</td><td>{target_resources}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&lang), progress_bar(completeness), target_stats.shown_translated(), target_stats.unfinished, target_stats.fuzzy, target_stats.shown_obsolete(),
+                target_stats.translated_words, target_stats.untranslated_words, target_stats.translated_chars, target_stats.untranslated_chars));
+        }
+        html.push_str("</table>\n");
+
+        for resource_group in &self.resource_groups {
+            let source_total = resource_group.source_stats.shown_translated() + resource_group.source_stats.shown_unfinished();
+            let reference_total = self.reference_total(&resource_group.source_stats, standalone_percentage);
+            html.push_str(&format!("<h2>{}</h2>\n<p>{source_total} source string(s) in {}.</p>\n",
+                escape_html(&relative_path(&resource_group.source_path)), escape_html(&resource_group.source_lang_code)));
+            html.push_str("<table>\n<tr><th>Language</th><th>Progress</th><th>Completeness</th><th>Translated</th><th>Unfinished</th><th>Vanished</th><th>File</th></tr>\n");
+            let mut target_lang_codes = resource_group.target_lang_codes.clone();
+            target_lang_codes.sort();
+            for lang in &target_lang_codes {
+                let target = &resource_group.target_stats[lang];
+                let completeness = self.completeness(&target.stats, reference_total);
+                html.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{completeness:.2}%</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    escape_html(lang), progress_bar(completeness), target.stats.shown_translated(), target.stats.shown_unfinished(), target.stats.shown_obsolete(),
+                    escape_html(&relative_path(&target.resource_path))));
+            }
+            html.push_str("</table>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    /// Completeness of the given languages (or all target languages if empty) that is below the threshold.
+    ///
+    /// Languages without any translation file count as 0% complete.
+    fn languages_below(&self, threshold: f64, language_codes: &[String], standalone_percentage: bool) -> Vec<(String, f64)> {
+        let (_, source_stats) = self.get_source_stats();
+        let reference_total = self.reference_total(&source_stats, standalone_percentage);
+        let language_codes = if language_codes.is_empty() { &self.target_lang_codes } else { language_codes };
+        language_codes.iter()
+            .map(|lang| (lang.clone(), self.completeness(&self.get_target_stats_by_language_code(lang).1, reference_total)))
+            .filter(|(_, completeness)| *completeness < threshold)
+            .collect()
+    }
+
+    /// Render an SVG chart with a horizontal bar per language.
+    pub fn render_svg(&self, standalone_percentage: bool, sort_by: StatsSortBy, thresholds: ChartThresholds) -> String {
+        const ROW_HEIGHT: usize = 24;
+        const LABEL_WIDTH: usize = 80;
+        const BAR_WIDTH: usize = 300;
+        let (_, source_stats) = self.get_source_stats();
+        let reference_total = self.reference_total(&source_stats, standalone_percentage);
+        let language_codes = self.sorted_language_codes(reference_total, sort_by);
+
+        let (width, height) = (LABEL_WIDTH + BAR_WIDTH + 70, ROW_HEIGHT * language_codes.len().max(1) + 8);
+        let mut svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="12">
+"#);
+        for (index, lang) in language_codes.iter().enumerate() {
+            let (_, target_stats) = self.get_target_stats_by_language_code(lang);
+            let completeness = self.completeness(&target_stats, reference_total);
+            let bar_y = index * ROW_HEIGHT + 9;
+            let text_y = bar_y + 11;
+            svg.push_str(&format!(r##"  <text x="0" y="{text_y}">{}</text>
+  <rect x="{LABEL_WIDTH}" y="{bar_y}" width="{BAR_WIDTH}" height="14" rx="3" fill="#eaeef2"/>
+  <rect x="{LABEL_WIDTH}" y="{bar_y}" width="{:.1}" height="14" rx="3" fill="{}"/>
+  <text x="{}" y="{text_y}">{completeness:.2}%</text>
+"##, escape_html(lang), BAR_WIDTH as f64 * completeness.clamp(0.0, 100.0) / 100.0, thresholds.color(completeness), LABEL_WIDTH + BAR_WIDTH + 8));
+        }
+        svg.push_str("</svg>\n");
+        svg
     }
 
-    pub fn print_stats_json(&self) -> Result<(), serde_json::Error> {
-        let json_str = serde_json::to_string_pretty(self)?;
-        println!("{}", json_str);
-        Ok(())
+    /// Render a shields.io endpoint JSON showing the completeness of the given language.
+    pub fn render_badge(&self, language_code: &str, standalone_percentage: bool) -> Result<String, serde_json::Error> {
+        let (_, source_stats) = self.get_source_stats();
+        let reference_total = self.reference_total(&source_stats, standalone_percentage);
+        let (_, target_stats) = self.get_target_stats_by_language_code(&language_code.to_string());
+        let completeness = self.completeness(&target_stats, reference_total);
+        let badge = ShieldsBadge {
+            schema_version: 1,
+            label: format!("translation {language_code}"),
+            // Round down so that the badge only shows 100% when everything is translated.
+            message: format!("{}%", completeness.floor()),
+            color: match completeness {
+                c if c >= 90.0 => "brightgreen",
+                c if c >= 75.0 => "green",
+                c if c >= 50.0 => "yellow",
+                c if c >= 25.0 => "orange",
+                _ => "red",
+            },
+        };
+        Ok(serde_json::to_string_pretty(&badge)? + "\n")
     }
 }
 
-#[derive(Default, Serialize)]
+/// Endpoint JSON of shields.io, see <https://shields.io/badges/endpoint-badge>.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ShieldsBadge {
+    schema_version: u8,
+    label: String,
+    message: String,
+    color: &'static str,
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn progress_bar(percentage: f64) -> String {
+    format!(r#"<div class="bar"><div style="width: {:.2}%"></div></div>"#, percentage.clamp(0.0, 100.0))
+}
+
+#[derive(Default, Clone)]
 struct TsResourceGroupStats {
+    /// Repository the resource belongs to, only filled when grouping by project.
+    project: Option<String>,
     source_path: PathBuf,
     source_lang_code: String,
     source_stats: MessageStats,
     target_lang_codes: Vec<String>,
-    target_stats: std::collections::HashMap<String, TsResourceStats>,
+    target_stats: HashMap<String, TsResourceStats>,
+    /// Completeness percentage of each language on Transifex, only filled when comparing with remote.
+    remote_completeness: BTreeMap<String, f64>,
 }
 
-#[derive(Default, Serialize)]
+impl TsResourceGroupStats {
+    /// Local and remote completeness of every language known locally or on Transifex, with a note on which side is behind.
+    fn remote_comparison(&self, metric: CompletenessMetric, standalone_percentage: bool) -> Vec<(String, Option<f64>, Option<f64>, &'static str)> {
+        let reference_total = (!standalone_percentage).then_some(metric.total(&self.source_stats));
+        let mut language_codes: Vec<&String> = self.target_lang_codes.iter().chain(self.remote_completeness.keys()).collect();
+        language_codes.sort();
+        language_codes.dedup();
+        language_codes.into_iter().map(|lang| {
+            let local = self.target_stats.get(lang).map(|target| metric.percentage(&target.stats, reference_total));
+            let remote = self.remote_completeness.get(lang).copied();
+            let status = match (local, remote) {
+                (None, _) => "missing locally",
+                (_, None) => "not on Transifex",
+                // Ignore differences that are not visible with two decimals.
+                (Some(local), Some(remote)) if local + 0.005 < remote => "behind",
+                (Some(local), Some(remote)) if local > remote + 0.005 => "ahead",
+                _ => "",
+            };
+            (lang.clone(), local, remote, status)
+        }).collect()
+    }
+}
+
+#[derive(Default, Clone)]
 struct TsResourceStats {
     resource_path: PathBuf,
     stats: MessageStats,
 }
 
-pub fn subcmd_statistics(project_root: &PathBuf, format: StatsFormat, sort_by: StatsSortBy, standalone_percentage: bool, accept_languages: Vec<String>, ignore_languages: Vec<String>) -> Result<(), CmdError> {
-    let (transifex_yaml_file, tx_yaml) = try_load_transifex_project_file(project_root)?;
-    if matches!(format, StatsFormat::PlainTable) {
-        println!("Found Transifex project config file at: {transifex_yaml_file:?}");
+/// Load .tx/config of the project, or the given project file if it is a .tx/config file, which has the resource slugs.
+fn load_remote_tx_config(project_root: &Path, project_file: Option<&Path>) -> Result<TxConfig, CmdError> {
+    match project_file {
+        Some(project_file) if !is_transifex_yaml_file(project_file) => load_tx_config_file(&project_file.to_path_buf()),
+        _ => try_load_tx_config_file(project_root).map(|(_, tx_config)| tx_config),
+    }.map_err(CmdError::LoadTxConfig)
+}
+
+/// Fetch stats of every language of the resources in .tx/config from Transifex, keyed by resource id.
+fn fetch_remote_language_stats(tx_config: &TxConfig) -> Result<HashMap<String, Vec<TransifexLanguageStats>>, CmdError> {
+    let mut projects = BTreeSet::new();
+    for resource_section in &tx_config.resource_sections {
+        let (organization_slug, project_slug, _) = resource_section.get_opr_slugs().map_err(CmdError::LoadTxConfig)?;
+        projects.insert((organization_slug, project_slug));
+    }
+
+    let api = TransifexRestApi::new_from_transifexrc().map_err(CmdError::LoadTransifexRc)?;
+    let mut remote_stats: HashMap<String, Vec<TransifexLanguageStats>> = HashMap::new();
+    for (organization_slug, project_slug) in projects {
+        for stats in api.get_resource_language_stats(&organization_slug, &project_slug)? {
+            if let Some((resource_id, _)) = stats.resource_and_language() {
+                remote_stats.entry(resource_id.to_string()).or_default().push(stats);
+            }
+        }
     }
-    let mut project_stats = ProjectResourceStats::default();
-    project_stats.project_path = project_root.clone();
+    Ok(remote_stats)
+}
 
+/// Target languages enabled in the Transifex projects of .tx/config.
+fn fetch_remote_languages(tx_config: &TxConfig) -> Result<BTreeSet<String>, CmdError> {
+    let mut projects = BTreeSet::new();
+    for resource_section in &tx_config.resource_sections {
+        let (organization_slug, project_slug, _) = resource_section.get_opr_slugs().map_err(CmdError::LoadTxConfig)?;
+        projects.insert((organization_slug, project_slug));
+    }
+    let api = TransifexRestApi::new_from_transifexrc().map_err(CmdError::LoadTransifexRc)?;
+    let mut languages = BTreeSet::new();
+    for (organization_slug, project_slug) in projects {
+        languages.extend(api.get_project_languages(&organization_slug, &project_slug)?.into_iter().map(|language| language.attributes.code));
+    }
+    Ok(languages)
+}
+
+/// Warnings about local translations of languages not enabled on Transifex, and enabled languages without local translations.
+///
+/// Languages filtered out by `accept_languages` or `ignore_languages` are not checked.
+pub fn language_mismatch_warnings(local_languages: &[String], remote_languages: &BTreeSet<String>, accept_languages: &[String], ignore_languages: &[String]) -> Vec<String> {
+    let checked = |lang: &String| (accept_languages.is_empty() || accept_languages.contains(lang)) && !ignore_languages.contains(lang);
+    let mut warnings = vec![];
+    for lang in local_languages.iter().filter(|lang| checked(lang) && !remote_languages.contains(*lang)) {
+        warnings.push(format!("Language {lang} has local translations but is not enabled on Transifex"));
+    }
+    for lang in remote_languages.iter().filter(|lang| checked(lang) && !local_languages.contains(*lang)) {
+        warnings.push(format!("Language {lang} is enabled on Transifex but has no local translation"));
+    }
+    warnings
+}
+
+/// Languages with local translation files of any resource in .tx/config, source languages excluded.
+pub fn local_languages(project_root: &Path, tx_config: &TxConfig) -> Vec<String> {
+    let mut languages: Vec<String> = tx_config.to_transifex_yaml().filters.iter()
+        .flat_map(|filter| filter.match_target_files(project_root).unwrap_or_default().into_iter()
+            .map(|(lang, _)| lang)
+            .filter(|lang| *lang != filter.source_lang))
+        .collect();
+    languages.sort();
+    languages.dedup();
+    languages
+}
+
+/// Fill remote completeness of each resource group from Transifex, resources are identified by the slugs in .tx/config.
+///
+/// Returns the target languages enabled on Transifex.
+fn fill_remote_stats(project_root: &Path, project_file: Option<&Path>, project_stats: &mut ProjectResourceStats) -> Result<BTreeSet<String>, CmdError> {
+    let tx_config = load_remote_tx_config(project_root, project_file)?;
+    let resource_ids: HashMap<PathBuf, &String> = tx_config.resource_sections.iter()
+        .map(|resource_section| (project_root.join(&resource_section.source_file), &resource_section.resource_full_slug))
+        .collect();
+    let mut remote_stats = fetch_remote_language_stats(&tx_config)?;
+
+    for resource_group in &mut project_stats.resource_groups {
+        let Some(resource_id) = resource_ids.get(&resource_group.source_path) else {
+            continue;
+        };
+        let remote_completeness: BTreeMap<String, f64> = remote_stats.remove(*resource_id).unwrap_or_default().iter()
+            .filter_map(|stats| stats.resource_and_language().map(|(_, lang)| (lang.to_string(), project_stats.metric.percentage(&remote_message_stats(stats, false), None))))
+            // The source language is not translated on Transifex.
+            .filter(|(lang, _)| *lang != resource_group.source_lang_code)
+            .collect();
+        resource_group.remote_completeness = remote_completeness;
+    }
+    fetch_remote_languages(&tx_config)
+}
+
+/// Convert stats of a resource language on Transifex into local message stats.
+///
+/// Transifex has no fuzzy or vanished strings, and the source strings are all counted as unfinished like in local source files.
+fn remote_message_stats(stats: &TransifexLanguageStats, is_source: bool) -> MessageStats {
+    let attributes = &stats.attributes;
+    let total_words = attributes.translated_words + attributes.untranslated_words;
+    match is_source {
+        true => MessageStats { unfinished: attributes.total_strings, untranslated_words: total_words, ..MessageStats::default() },
+        false => MessageStats {
+            finished: attributes.translated_strings,
+            unfinished: attributes.total_strings.saturating_sub(attributes.translated_strings),
+            translated_words: attributes.translated_words,
+            untranslated_words: attributes.untranslated_words,
+            ..MessageStats::default()
+        },
+    }
+}
+
+/// Build project stats from the stats on Transifex of the resources in .tx/config, as if the translation files were pulled.
+///
+/// Resources unknown to Transifex are skipped.
+fn project_stats_from_remote(project_root: &Path, tx_config: &TxConfig, remote_stats: &HashMap<String, Vec<TransifexLanguageStats>>,
+    accept_languages: &[String], ignore_languages: &[String], options: &StatsReportOptions) -> Result<ProjectResourceStats, CmdError> {
+    let mut project_stats = ProjectResourceStats {
+        project_path: project_root.to_path_buf(),
+        metric: options.metric,
+        ..ProjectResourceStats::default()
+    };
+    for resource_section in &tx_config.resource_sections {
+        let source_path = resource_section.source_file.strip_prefix("./").unwrap_or(&resource_section.source_file);
+        if !options.resources.is_match(source_path) {
+            continue;
+        }
+        let Some(language_stats) = remote_stats.get(&resource_section.resource_full_slug).filter(|stats| !stats.is_empty()) else {
+            eprintln!("Warning: Resource {} is not found on Transifex, skipped.", resource_section.resource_full_slug);
+            continue;
+        };
+        let mut resource_group = TsResourceGroupStats {
+            project: group_project(source_path, options.group_by_project)?,
+            source_path: project_root.join(&resource_section.source_file),
+            source_lang_code: resource_section.source_lang.clone(),
+            source_stats: remote_message_stats(&language_stats[0], true),
+            ..TsResourceGroupStats::default()
+        };
+        for stats in language_stats {
+            let Some((_, lang)) = stats.resource_and_language() else {
+                continue;
+            };
+            if lang == resource_section.source_lang
+                || (!accept_languages.is_empty() && !accept_languages.iter().any(|accepted| accepted == lang))
+                || ignore_languages.iter().any(|ignored| ignored == lang) {
+                continue;
+            }
+            resource_group.target_lang_codes.push(lang.to_string());
+            if !project_stats.target_lang_codes.iter().any(|known| known == lang) {
+                project_stats.target_lang_codes.push(lang.to_string());
+            }
+            resource_group.target_stats.insert(lang.to_string(), TsResourceStats {
+                resource_path: project_root.join(resource_section.file_filter.replace("<lang>", lang)),
+                stats: remote_message_stats(stats, false),
+            });
+        }
+        resource_group.target_lang_codes.sort();
+        project_stats.resource_groups.push(resource_group);
+    }
+    project_stats.target_lang_codes.sort();
+    Ok(project_stats)
+}
+
+/// Load stats of the given file. In `tolerant` mode, files that fail to load are reported and skipped.
+fn load_file_stats_tolerant(file_path: &Path, tolerant: bool) -> Result<Option<MessageStats>, CmdError> {
+    match load_file_stats(file_path) {
+        Ok(stats) => Ok(Some(stats)),
+        Err(e) if tolerant => {
+            eprintln!("Warning: {e}, skipped.");
+            Ok(None)
+        },
+        Err(e) => Err(e),
+    }
+}
+
+/// Collect and print statistics of the project, whose resources are listed in `project_file` or the transifex.yaml or .tx/config
+/// file under `project_root`. With `fail_under`, fails if any of `fail_under_languages` (or any target language if empty) is less
+/// complete than the given percentage.
+#[allow(clippy::too_many_arguments)]
+pub fn subcmd_statistics(project_root: &Path, project_file: Option<&Path>, accept_languages: Vec<String>, ignore_languages: Vec<String>, discover: bool,
+    options: &StatsReportOptions, fail_under: Option<f64>, fail_under_languages: &[String]) -> Result<(), CmdError> {
+    if options.with_remote && matches!(options.metric, CompletenessMetric::Chars) {
+        return Err(CmdError::RemoteCharsMetric);
+    }
+    // Progress messages would break machine-readable reports written to stdout.
+    let verbose = options.output.is_some() || (matches!(options.format, StatsFormat::PlainTable) && options.badge.is_none());
+    // Projects that are not onboarded to Transifex yet still get statistics of their translation files.
+    let discover = discover || (project_file.is_none() && !has_transifex_project_file(project_root));
+    let tx_yaml = if discover {
+        // Same as the default ignored paths of `gentxcfg`.
+        let ignore_paths: Vec<String> = crate::subcmd::gentxcfg::DEFAULT_IGNORE_PATHS.iter().map(|path| path.to_string()).collect();
+        let tx_yaml = crate::subcmd::gentxcfg::discover_transifex_yaml(&project_root.to_path_buf(), &ignore_paths)
+            .map_err(CmdError::DiscoverResources)?;
+        if verbose {
+            println!("Discovered {} resource(s) by scanning: {project_root:?}", tx_yaml.filters.len());
+        }
+        tx_yaml
+    } else {
+        let (transifex_yaml_file, tx_yaml) = load_transifex_project_file(project_root, project_file)?;
+        if verbose {
+            println!("Found Transifex project config file at: {transifex_yaml_file:?}");
+        }
+        tx_yaml
+    };
+    let mut project_stats = ProjectResourceStats {
+        project_path: project_root.to_path_buf(),
+        metric: options.metric,
+        ..ProjectResourceStats::default()
+    };
+
+    // Resource groups with their source file and matched target files, loaded all together in parallel later.
+    let mut pending_groups: Vec<(TsResourceGroupStats, Vec<(String, PathBuf)>)> = vec![];
     for filter in &tx_yaml.filters {
         if (filter.format != "QT" && filter.format != "PO") || filter.type_attr != "file" {
-            if matches!(format, StatsFormat::PlainTable) {
+            if verbose {
                 println!("Skipping resource {:?} with format {:?}...", filter.source, filter.format);
             }
             continue;
         }
-        let mut source_group_stats = TsResourceGroupStats::default();
+        let source_path = filter.source.strip_prefix("./").unwrap_or(&filter.source);
+        if !options.resources.is_match(source_path) {
+            continue;
+        }
         let source_file = project_root.join(&filter.source);
-        // check if project_root/filter.source_file exists, and print stats of the source file if exists.
+        // check if project_root/filter.source_file exists, and collect stats of the source file if exists.
         if source_file.is_file() {
-            if matches!(format, StatsFormat::PlainTable) {
+            if verbose {
                 println!("Hit source file at: {source_file:?}");
             }
-            let content_stats = load_file_stats(&source_file)?;
-            source_group_stats.source_path = source_file.clone();
-            source_group_stats.source_lang_code = filter.source_lang.clone();
-            source_group_stats.source_stats = content_stats;
         } else {
-            if matches!(format, StatsFormat::PlainTable) {
-                println!("Missing source resource: {source_file:?}");
-            }
+            eprintln!("Warning: Source file {source_file:?} does not exist, see `lint-config` for problems of the config.");
+            project_stats.missing_source_paths.push(source_file);
             continue;
         }
 
-        let matched_resources = filter.match_target_files(project_root).or_else(|e| { Err(CmdError::MatchResources(e)) })?;
-        for (lang, target_file) in matched_resources {
-            if !accept_languages.is_empty() && !accept_languages.contains(&lang) {
-                continue;
-            }
-            if ignore_languages.contains(&lang) {
+        let matched_resources = match filter.match_target_files(project_root) {
+            Ok(matched_resources) => matched_resources,
+            Err(e) if discover => {
+                eprintln!("Warning: Fail to match translations of {source_file:?} because: {e}, skipped.");
+                vec![]
+            },
+            Err(e) => return Err(CmdError::MatchResources(e)),
+        };
+        let matched_resources: Vec<(String, PathBuf)> = matched_resources.into_iter()
+            .filter(|(lang, _)| accept_languages.is_empty() || accept_languages.contains(lang))
+            .filter(|(lang, _)| !ignore_languages.contains(lang))
+            .collect();
+        let source_group_stats = TsResourceGroupStats {
+            project: group_project(source_path, options.group_by_project)?,
+            source_path: source_file,
+            source_lang_code: filter.source_lang.clone(),
+            ..TsResourceGroupStats::default()
+        };
+        pending_groups.push((source_group_stats, matched_resources));
+    }
+
+    // Each group contributes its source file followed by its target files.
+    let files: Vec<&Path> = pending_groups.iter()
+        .flat_map(|(group, targets)| std::iter::once(group.source_path.as_path()).chain(targets.iter().map(|(_, target_file)| target_file.as_path())))
+        .collect();
+    let mut file_stats = parallel::map(&files, |file| load_file_stats_tolerant(file, discover)).into_iter();
+    for (mut source_group_stats, matched_resources) in pending_groups {
+        let source_stats = file_stats.next().expect("stats of every source file should be collected")?;
+        let target_file_stats: Vec<_> = file_stats.by_ref().take(matched_resources.len()).collect();
+        let Some(source_stats) = source_stats else {
+            continue;
+        };
+        source_group_stats.source_stats = source_stats;
+        for ((lang, target_file), content_stats) in matched_resources.into_iter().zip(target_file_stats) {
+            let Some(content_stats) = content_stats? else {
                 continue;
-            }
-            let content_stats = load_file_stats(&target_file)?;
+            };
             let target_resource_stats = TsResourceStats {
-                resource_path: target_file.clone(),
+                resource_path: target_file,
                 stats: content_stats,
             };
             source_group_stats.target_lang_codes.push(lang.clone());
@@ -207,13 +953,366 @@ pub fn subcmd_statistics(project_root: &PathBuf, format: StatsFormat, sort_by: S
         project_stats.resource_groups.push(source_group_stats);
     }
     project_stats.target_lang_codes.sort();
+    if options.with_remote {
+        let remote_languages = fill_remote_stats(project_root, project_file, &mut project_stats)?;
+        for warning in language_mismatch_warnings(&project_stats.target_lang_codes, &remote_languages, &accept_languages, &ignore_languages) {
+            eprintln!("Warning: {warning}.");
+        }
+    }
 
     // finally, print the stats of the project
-    match format {
-        StatsFormat::PlainTable => project_stats.print_state_plain_table(standalone_percentage, sort_by),
-        StatsFormat::Yaml => project_stats.print_stats_yaml()?,
-        StatsFormat::Json => project_stats.print_stats_json()?,
+    write_report(&project_stats, options, verbose)?;
+    if let Some(history_file) = &options.record {
+        record_snapshot(history_file, project_stats.snapshot(options.standalone_percentage)).map_err(CmdError::RecordHistory)?;
+        if verbose {
+            println!("Snapshot recorded to: {history_file:?}");
+        }
+    }
+
+    if let Some(threshold) = fail_under {
+        let failed = project_stats.languages_below(threshold, fail_under_languages, options.standalone_percentage);
+        if !failed.is_empty() {
+            let failed = failed.into_iter().map(|(lang, completeness)| format!("{lang} ({completeness:.2}%)")).collect();
+            return Err(CmdError::BelowThreshold(threshold, failed));
+        }
     }
 
     Ok(())
 }
+
+/// Render the report in the requested format, and print it or write it to the output file.
+fn write_report(project_stats: &ProjectResourceStats, options: &StatsReportOptions, verbose: bool) -> Result<(), CmdError> {
+    let StatsReportOptions { format, sort_by, standalone_percentage, .. } = *options;
+    let report = match (&options.badge, format) {
+        (Some(language_code), _) => project_stats.render_badge(language_code, standalone_percentage)?,
+        (None, StatsFormat::PlainTable) => {
+            let mut report = project_stats.render_plain_table(standalone_percentage, sort_by);
+            if options.by_context {
+                report.push_str(&project_stats.render_context_breakdown()?);
+            }
+            if let Some(language_codes) = &options.list_untranslated {
+                report.push_str(&project_stats.render_untranslated(language_codes)?);
+            }
+            report
+        },
+        (None, StatsFormat::Yaml) => serde_yaml2::to_string(project_stats.to_report(standalone_percentage, sort_by))? + "\n",
+        (None, StatsFormat::Json) => serde_json::to_string_pretty(&project_stats.to_report(standalone_percentage, sort_by))? + "\n",
+        (None, StatsFormat::Csv) => project_stats.render_csv(standalone_percentage, sort_by),
+        (None, StatsFormat::Html) => project_stats.render_html(standalone_percentage, sort_by),
+        (None, StatsFormat::Svg) => project_stats.render_svg(standalone_percentage, sort_by, options.chart_thresholds),
+    };
+    match &options.output {
+        Some(output) => {
+            std::fs::write(output, report).map_err(|e| CmdError::WriteOutput(output.to_path_buf(), e))?;
+            if verbose {
+                println!("Report written to: {output:?}");
+            }
+        },
+        None => print!("{report}"),
+    }
+    Ok(())
+}
+
+/// Print statistics of the resources in .tx/config (or the given .tx/config file) as they are on Transifex, without any local
+/// translation file. Paths in the report are where `pull` would write the translations to.
+pub fn subcmd_remote_statistics(project_root: &Path, project_file: Option<&Path>, accept_languages: Vec<String>, ignore_languages: Vec<String>,
+    options: &StatsReportOptions) -> Result<(), CmdError> {
+    if matches!(options.metric, CompletenessMetric::Chars) {
+        return Err(CmdError::RemoteCharsMetric);
+    }
+    let verbose = options.output.is_some() || (matches!(options.format, StatsFormat::PlainTable) && options.badge.is_none());
+    let tx_config = load_remote_tx_config(project_root, project_file)?;
+    let remote_stats = fetch_remote_language_stats(&tx_config)?;
+    let project_stats = project_stats_from_remote(project_root, &tx_config, &remote_stats, &accept_languages, &ignore_languages, options)?;
+    write_report(&project_stats, options, verbose)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_group_by_project() {
+        assert_eq!(project_of_source("linuxdeepin/dde-network-core/translations/dcc-network.ts").as_deref(), Some("linuxdeepin/dde-network-core"));
+        assert_eq!(project_of_source("translations/demo.ts"), None);
+        assert_eq!(project_of_source("demo.ts"), None);
+        assert!(matches!(group_project("translations/demo.ts", true), Err(CmdError::NotMonoConfig(_))));
+        assert_eq!(group_project("translations/demo.ts", false).unwrap(), None);
+
+        let resource_group = |project: &str, lang: &str, finished: u64| {
+            let mut resource_group = TsResourceGroupStats {
+                project: Some(project.to_string()),
+                source_stats: MessageStats { unfinished: 2, ..MessageStats::default() },
+                target_lang_codes: vec![lang.to_string()],
+                ..TsResourceGroupStats::default()
+            };
+            resource_group.target_stats.insert(lang.to_string(), TsResourceStats {
+                stats: MessageStats { finished, unfinished: 2 - finished, ..MessageStats::default() },
+                ..TsResourceStats::default()
+            });
+            resource_group
+        };
+        let project_stats = ProjectResourceStats {
+            project_path: PathBuf::from("/mono"),
+            target_lang_codes: vec!["zh_CN".to_string(), "zh_TW".to_string()],
+            resource_groups: vec![resource_group("org/b", "zh_CN", 2), resource_group("org/a", "zh_CN", 1), resource_group("org/a", "zh_TW", 2)],
+            ..ProjectResourceStats::default()
+        };
+        let projects = project_stats.split_by_project();
+        assert_eq!(projects.keys().collect::<Vec<_>>(), ["org/a", "org/b"]);
+        assert_eq!(projects["org/a"].target_lang_codes, ["zh_CN", "zh_TW"]);
+
+        let table = project_stats.render_plain_table(false, StatsSortBy::LanguageCode);
+        assert!(table.contains("| org/a | Source |      100.00% |         2 |          4 |          0 |"));
+        assert!(table.contains("| org/a |  zh_CN |       25.00% |         1 |          1 |          1 |"));
+        assert!(table.contains("| org/b |  zh_CN |      100.00% |         1 |          2 |          0 |"));
+        assert!(!table.contains("marked with `?`"));
+
+        let project_stats = ProjectResourceStats {
+            project_path: PathBuf::from("/mono"),
+            target_lang_codes: vec!["zh_XX".to_string()],
+            resource_groups: vec![resource_group("org/a", "zh_XX", 2)],
+            ..ProjectResourceStats::default()
+        };
+        let table = project_stats.render_plain_table(false, StatsSortBy::LanguageCode);
+        assert!(table.contains("|   1 | zh_XX? |      100.00% |"));
+        assert!(table.contains("Language codes marked with `?` are not known ISO 639/3166 codes."));
+    }
+
+    #[test]
+    fn tst_language_mismatch_warnings() {
+        let local_languages = vec!["en".to_string(), "zh_CN".to_string(), "zh_HK".to_string()];
+        let remote_languages = BTreeSet::from(["zh_CN".to_string(), "zh_TW".to_string()]);
+        assert_eq!(language_mismatch_warnings(&local_languages, &remote_languages, &[], &["en".to_string()]), [
+            "Language zh_HK has local translations but is not enabled on Transifex",
+            "Language zh_TW is enabled on Transifex but has no local translation",
+        ]);
+        assert!(language_mismatch_warnings(&local_languages, &remote_languages, &["zh_CN".to_string()], &[]).is_empty());
+    }
+
+    #[test]
+    fn tst_local_languages() {
+        use std::str::FromStr;
+
+        let tx_config = TxConfig::from_str(r#"[main]
+host = https://www.transifex.com
+
+[o:linuxdeepin:p:deepin-home:r:deepin-home]
+file_filter = translations/deepin-home_<lang>.ts
+source_file = translations/deepin-home_en.ts
+source_lang = en
+type = QT
+
+[o:linuxdeepin:p:deepin-home:r:po]
+file_filter = po/<lang>.po
+source_file = po/deepin-home.pot
+source_lang = en
+type = PO
+"#).unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_root = temp_dir.path();
+        std::fs::create_dir_all(project_root.join("translations")).unwrap();
+        for file_name in ["deepin-home_en.ts", "deepin-home_zh_CN.ts", "deepin-home_de.ts"] {
+            std::fs::write(project_root.join("translations").join(file_name), "").unwrap();
+        }
+        // Resources without any translation yet are skipped.
+        assert_eq!(local_languages(project_root, &tx_config), ["de", "zh_CN"]);
+    }
+
+    #[test]
+    fn tst_project_stats_from_remote() {
+        use std::str::FromStr;
+        use crate::transifex::rest_api::TransifexLanguageStatsAttributes;
+
+        let tx_config = TxConfig::from_str(r#"[main]
+host = https://www.transifex.com
+
+[o:linuxdeepin:p:deepin-home:r:deepin-home]
+file_filter = translations/deepin-home_<lang>.ts
+source_file = translations/deepin-home.ts
+source_lang = en
+type = QT
+
+[o:linuxdeepin:p:deepin-home:r:not-pushed-yet]
+file_filter = misc/desktop_<lang>.ts
+source_file = misc/desktop.ts
+source_lang = en
+type = QT
+"#).unwrap();
+        let stats = |lang: &str, translated_strings: u64| TransifexLanguageStats {
+            id: format!("o:linuxdeepin:p:deepin-home:r:deepin-home:l:{lang}"),
+            attributes: TransifexLanguageStatsAttributes { total_strings: 4, translated_strings, translated_words: translated_strings * 2, untranslated_words: (4 - translated_strings) * 2, last_update: None },
+        };
+        let remote_stats = HashMap::from([
+            ("o:linuxdeepin:p:deepin-home:r:deepin-home".to_string(), vec![stats("en", 4), stats("zh_CN", 3), stats("fr", 1)]),
+        ]);
+        let project_root = Path::new("/nonexistent/deepin-home");
+        let project_stats = project_stats_from_remote(project_root, &tx_config, &remote_stats, &[], &["fr".to_string()], &StatsReportOptions::default()).unwrap();
+        assert_eq!(project_stats.target_lang_codes, ["zh_CN"]);
+        assert_eq!(project_stats.resource_groups.len(), 1);
+        let resource_group = &project_stats.resource_groups[0];
+        assert_eq!(resource_group.target_stats["zh_CN"].resource_path, project_root.join("translations/deepin-home_zh_CN.ts"));
+
+        let table = project_stats.render_plain_table(false, StatsSortBy::LanguageCode);
+        assert!(table.contains("|   0 | Source |      100.00% |         1 |          4 |          0 |     0 |        0 |                8 |                0 |"));
+        assert!(!table.contains("marked with `?`"));
+        assert!(table.contains("|   1 |  zh_CN |       75.00% |         1 |          3 |          1 |     0 |        0 |                6 |                2 |"));
+    }
+
+    #[test]
+    fn tst_render_untranslated() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_path = temp_dir.path().to_path_buf();
+        let target_file = project_path.join("demo_zh_CN.ts");
+        std::fs::write(&target_file, r#"<?xml version="1.0" encoding="utf-8"?>
+<!DOCTYPE TS>
+<TS version="2.1" language="zh_CN">
+<context>
+    <name>Demo</name>
+    <message>
+        <source>Hello</source>
+        <translation>你好</translation>
+    </message>
+    <message>
+        <source>Hello|World</source>
+        <translation type="unfinished"></translation>
+    </message>
+</context>
+</TS>
+"#).unwrap();
+        let mut resource_group = TsResourceGroupStats {
+            target_lang_codes: vec!["zh_CN".to_string()],
+            ..TsResourceGroupStats::default()
+        };
+        resource_group.target_stats.insert("zh_CN".to_string(), TsResourceStats { resource_path: target_file, ..TsResourceStats::default() });
+        let project_stats = ProjectResourceStats {
+            project_path: project_path.clone(),
+            target_lang_codes: vec!["zh_CN".to_string()],
+            resource_groups: vec![resource_group],
+            ..ProjectResourceStats::default()
+        };
+
+        let table = project_stats.render_untranslated(&[]).unwrap();
+        let breakdown = project_stats.render_context_breakdown().unwrap();
+        assert!(breakdown.contains("|  | Demo |  zh_CN |       50.00% |          1 |          1 |"));
+        assert!(table.contains("Untranslated strings of zh_CN (1):"));
+        assert!(table.contains("| demo_zh_CN.ts | Demo | Hello\\|World | unfinished |"));
+        assert!(project_stats.render_untranslated(&["zh_TW".to_string()]).unwrap().contains("Untranslated strings of zh_TW (0):"));
+    }
+
+    #[test]
+    fn tst_render_html() {
+        let project_path = PathBuf::from("/project/dde-demo");
+        let mut resource_group = TsResourceGroupStats {
+            source_path: project_path.join("translations/demo.ts"),
+            source_lang_code: "en".to_string(),
+            source_stats: MessageStats { unfinished: 4, ..MessageStats::default() },
+            ..TsResourceGroupStats::default()
+        };
+        resource_group.target_lang_codes.push("zh_CN".to_string());
+        resource_group.target_stats.insert("zh_CN".to_string(), TsResourceStats {
+            resource_path: project_path.join("translations/demo_zh_CN.ts"),
+            stats: MessageStats { finished: 3, fuzzy: 1, ..MessageStats::default() },
+        });
+        let project_stats = ProjectResourceStats {
+            project_path,
+            target_lang_codes: vec!["zh_CN".to_string()],
+            resource_groups: vec![resource_group],
+            ..ProjectResourceStats::default()
+        };
+
+        let html = project_stats.render_html(false, StatsSortBy::Completeness);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Translation statistics of dde-demo</title>"));
+        assert!(html.contains("<h2>translations/demo.ts</h2>"));
+        assert!(html.contains(r#"<tr><td>zh_CN</td><td><div class="bar"><div style="width: 75.00%"></div></div></td><td>75.00%</td>"#));
+        assert!(html.contains("<td>translations/demo_zh_CN.ts</td>"));
+        assert_eq!(escape_html("<a & \"b\">"), "&lt;a &amp; &quot;b&quot;&gt;");
+
+        let badge: serde_json::Value = serde_json::from_str(&project_stats.render_badge("zh_CN", false).unwrap()).unwrap();
+        assert_eq!(badge, serde_json::json!({ "schemaVersion": 1, "label": "translation zh_CN", "message": "75%", "color": "green" }));
+        let badge: serde_json::Value = serde_json::from_str(&project_stats.render_badge("zh_TW", false).unwrap()).unwrap();
+        assert_eq!(badge["message"], "0%");
+        assert_eq!(badge["color"], "red");
+
+        assert_eq!(project_stats.languages_below(80.0, &[], false), vec![("zh_CN".to_string(), 75.0)]);
+        assert!(project_stats.languages_below(75.0, &[], false).is_empty());
+        assert_eq!(project_stats.languages_below(50.0, &["zh_CN".to_string(), "zh_TW".to_string()], false), vec![("zh_TW".to_string(), 0.0)]);
+
+        let mut resource_group = TsResourceGroupStats {
+            source_stats: MessageStats { unfinished: 4, untranslated_words: 10, ..MessageStats::default() },
+            target_lang_codes: vec!["zh_CN".to_string(), "zh_HK".to_string()],
+            remote_completeness: BTreeMap::from([("zh_CN".to_string(), 100.0), ("zh_HK".to_string(), 50.0), ("zh_TW".to_string(), 25.0)]),
+            ..TsResourceGroupStats::default()
+        };
+        resource_group.target_stats.insert("zh_CN".to_string(), TsResourceStats {
+            stats: MessageStats { finished: 3, unfinished: 1, translated_words: 5, untranslated_words: 5, ..MessageStats::default() },
+            ..TsResourceStats::default()
+        });
+        resource_group.target_stats.insert("zh_HK".to_string(), TsResourceStats { stats: MessageStats { finished: 2, unfinished: 2, ..MessageStats::default() }, ..TsResourceStats::default() });
+        assert_eq!(resource_group.remote_comparison(CompletenessMetric::Strings, false), vec![
+            ("zh_CN".to_string(), Some(75.0), Some(100.0), "behind"),
+            ("zh_HK".to_string(), Some(50.0), Some(50.0), ""),
+            ("zh_TW".to_string(), None, Some(25.0), "missing locally"),
+        ]);
+        let words_comparison = resource_group.remote_comparison(CompletenessMetric::Words, false);
+        assert_eq!(words_comparison[0], ("zh_CN".to_string(), Some(50.0), Some(100.0), "behind"));
+
+        let report = serde_json::to_value(project_stats.to_report(false, StatsSortBy::Completeness)).unwrap();
+        assert_eq!(report["schema_version"], STATS_SCHEMA_VERSION);
+        assert_eq!(report["metric"], "strings");
+        assert_eq!(report["languages"][0], serde_json::json!({
+            "language": "zh_CN", "completeness": 75.0, "resources": 1, "translated": 3, "unfinished": 0, "fuzzy": 1, "vanished": 0, "translated_words": 0, "unfinished_words": 0, "translated_chars": 0, "unfinished_chars": 0,
+        }));
+        assert_eq!(report["resources"][0]["source_path"], "translations/demo.ts");
+        assert_eq!(report["resources"][0]["translations"][0]["path"], "translations/demo_zh_CN.ts");
+        assert!(report["resources"][0].get("project").is_none());
+
+        let csv = project_stats.render_csv(false, StatsSortBy::Completeness);
+        assert_eq!(csv.lines().nth(2), Some("zh_CN,75.00,1,3,0,1,0,0,0,0,0"));
+        let words_stats = ProjectResourceStats {
+            target_lang_codes: vec!["zh_CN".to_string()],
+            resource_groups: vec![TsResourceGroupStats {
+                source_stats: MessageStats { unfinished: 2, untranslated_words: 10, ..MessageStats::default() },
+                target_lang_codes: vec!["zh_CN".to_string()],
+                target_stats: HashMap::from([("zh_CN".to_string(), TsResourceStats {
+                    stats: MessageStats { finished: 1, unfinished: 1, translated_words: 2, untranslated_words: 8, ..MessageStats::default() },
+                    ..TsResourceStats::default()
+                })]),
+                ..TsResourceGroupStats::default()
+            }],
+            metric: CompletenessMetric::Words,
+            ..ProjectResourceStats::default()
+        };
+        assert_eq!(words_stats.languages_below(50.0, &[], false), vec![("zh_CN".to_string(), 20.0)]);
+        let chars_stats = ProjectResourceStats {
+            target_lang_codes: vec!["zh_CN".to_string()],
+            resource_groups: vec![TsResourceGroupStats {
+                source_stats: MessageStats { unfinished: 2, untranslated_chars: 40, ..MessageStats::default() },
+                target_lang_codes: vec!["zh_CN".to_string()],
+                target_stats: HashMap::from([("zh_CN".to_string(), TsResourceStats {
+                    stats: MessageStats { finished: 1, unfinished: 1, translated_chars: 30, untranslated_chars: 10, ..MessageStats::default() },
+                    ..TsResourceStats::default()
+                })]),
+                ..TsResourceGroupStats::default()
+            }],
+            metric: CompletenessMetric::Chars,
+            ..ProjectResourceStats::default()
+        };
+        assert_eq!(chars_stats.languages_below(80.0, &[], false), vec![("zh_CN".to_string(), 75.0)]);
+
+        let table = project_stats.render_plain_table(false, StatsSortBy::Completeness);
+        assert!(table.contains("|   1 |  zh_CN |       75.00% |         1 |          3 |          0 |     1 |        0 |"));
+        assert!(table.contains("|     |  Total |       75.00% |         1 |          3 |          0 |     1 |        0 |"));
+        assert!(table.contains("1 resource(s), 0 without any translated string, 0 missing source file(s)."));
+
+        let snapshot = project_stats.snapshot(false);
+        assert_eq!(snapshot.languages["zh_CN"], LanguageSnapshot { completeness: 75.0, translated: 3, unfinished: 1 });
+
+        let svg = project_stats.render_svg(false, StatsSortBy::Completeness, ChartThresholds::default());
+        assert!(svg.contains(r#"<text x="0" y="20">zh_CN</text>"#));
+        assert!(svg.contains(r##"width="225.0" height="14" rx="3" fill="#d4a72c""##));
+        let svg = project_stats.render_svg(false, StatsSortBy::Completeness, ChartThresholds { good: 70.0, fair: 50.0 });
+        assert!(svg.contains(r##"fill="#2da44e""##));
+    }
+}
@@ -0,0 +1,227 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+//! History of project statistics, recorded by `statistics --record` and shown by `statistics-trend`.
+//!
+//! Files ending with `.csv` store one `timestamp,language,completeness,translated,unfinished` row per
+//! language of each snapshot, other files store a JSON array of snapshots.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error as TeError;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const CSV_HEADER: &str = "timestamp,language,completeness,translated,unfinished";
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Fail to read history file {0:?} because: {1}")]
+    ReadHistory(PathBuf, #[source] std::io::Error),
+    #[error("Fail to write history file {0:?} because: {1}")]
+    WriteHistory(PathBuf, #[source] std::io::Error),
+    #[error("Fail to parse history file {0:?} because: {1}")]
+    ParseJson(PathBuf, #[source] serde_json::Error),
+    #[error("Fail to parse line {1} of history file {0:?}")]
+    ParseCsv(PathBuf, usize),
+    #[error("Fail to serialize history to JSON: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("No snapshot recorded in history file {0:?}")]
+    EmptyHistory(PathBuf),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct LanguageSnapshot {
+    pub completeness: f64,
+    pub translated: u64,
+    pub unfinished: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct StatsSnapshot {
+    /// UTC time in RFC 3339 format, e.g. `2025-01-01T00:00:00Z`
+    pub timestamp: String,
+    pub languages: BTreeMap<String, LanguageSnapshot>,
+}
+
+/// Format seconds since the Unix epoch as an RFC 3339 UTC timestamp.
+pub fn format_utc_timestamp(secs: u64) -> String {
+    let (days, secs_of_day) = (secs / 86400, secs % 86400);
+    // Civil date from days since 1970-01-01, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z", secs_of_day / 3600, secs_of_day / 60 % 60, secs_of_day % 60)
+}
+
+impl StatsSnapshot {
+    pub fn now(languages: BTreeMap<String, LanguageSnapshot>) -> Self {
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+        Self { timestamp: format_utc_timestamp(secs), languages }
+    }
+}
+
+fn is_csv(history_file: &Path) -> bool {
+    history_file.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+}
+
+fn parse_csv(content: &str, history_file: &Path) -> Result<Vec<StatsSnapshot>, CmdError> {
+    let mut snapshots: Vec<StatsSnapshot> = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        if line.trim().is_empty() || line == CSV_HEADER {
+            continue;
+        }
+        let parse_error = || CmdError::ParseCsv(history_file.to_path_buf(), idx + 1);
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let [timestamp, language, completeness, translated, unfinished] = fields[..] else {
+            return Err(parse_error());
+        };
+        let language_snapshot = LanguageSnapshot {
+            completeness: completeness.parse().map_err(|_| parse_error())?,
+            translated: translated.parse().map_err(|_| parse_error())?,
+            unfinished: unfinished.parse().map_err(|_| parse_error())?,
+        };
+        // Rows of the same snapshot are written together, each language once.
+        match snapshots.last_mut() {
+            Some(snapshot) if snapshot.timestamp == timestamp && !snapshot.languages.contains_key(language) => {},
+            _ => snapshots.push(StatsSnapshot { timestamp: timestamp.to_string(), ..StatsSnapshot::default() }),
+        }
+        snapshots.last_mut().unwrap().languages.insert(language.to_string(), language_snapshot);
+    }
+    Ok(snapshots)
+}
+
+fn to_csv_rows(snapshot: &StatsSnapshot) -> String {
+    snapshot.languages.iter().map(|(language, stats)| {
+        format!("{},{language},{:.2},{},{}\n", snapshot.timestamp, stats.completeness, stats.translated, stats.unfinished)
+    }).collect()
+}
+
+/// Load all snapshots of the history file, a missing file has no snapshot.
+pub fn load_history(history_file: &Path) -> Result<Vec<StatsSnapshot>, CmdError> {
+    if !history_file.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(history_file).map_err(|e| CmdError::ReadHistory(history_file.to_path_buf(), e))?;
+    if is_csv(history_file) {
+        parse_csv(&content, history_file)
+    } else if content.trim().is_empty() {
+        Ok(vec![])
+    } else {
+        serde_json::from_str(&content).map_err(|e| CmdError::ParseJson(history_file.to_path_buf(), e))
+    }
+}
+
+/// Append the snapshot to the history file, the file is created if it does not exist.
+pub fn record_snapshot(history_file: &Path, snapshot: StatsSnapshot) -> Result<(), CmdError> {
+    let write_error = |e| CmdError::WriteHistory(history_file.to_path_buf(), e);
+    if is_csv(history_file) {
+        let mut content = if history_file.exists() {
+            fs::read_to_string(history_file).map_err(|e| CmdError::ReadHistory(history_file.to_path_buf(), e))?
+        } else {
+            String::new()
+        };
+        if content.trim().is_empty() {
+            content = format!("{CSV_HEADER}\n");
+        } else if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(&to_csv_rows(&snapshot));
+        fs::write(history_file, content).map_err(write_error)
+    } else {
+        let mut snapshots = load_history(history_file)?;
+        snapshots.push(snapshot);
+        fs::write(history_file, serde_json::to_string_pretty(&snapshots)? + "\n").map_err(write_error)
+    }
+}
+
+/// Render completeness of each language in the last `last` snapshots, followed by the change between the first and last shown snapshot.
+fn render_trend(snapshots: &[StatsSnapshot], last: usize) -> String {
+    let shown = &snapshots[snapshots.len().saturating_sub(last.max(1))..];
+    let mut languages: Vec<&String> = shown.iter().flat_map(|snapshot| snapshot.languages.keys()).collect();
+    languages.sort();
+    languages.dedup();
+
+    let mut table = String::new();
+    table.push_str("| Lang   |");
+    for snapshot in shown {
+        table.push_str(&format!(" {} |", snapshot.timestamp));
+    }
+    table.push_str(" Change   |\n| ------ |");
+    for snapshot in shown {
+        table.push_str(&format!(" {} |", "-".repeat(snapshot.timestamp.len())));
+    }
+    table.push_str(" -------- |\n");
+
+    for language in languages {
+        table.push_str(&format!("| {language:>6} |"));
+        for snapshot in shown {
+            let cell = snapshot.languages.get(language).map_or("-".to_string(), |stats| format!("{:.2}%", stats.completeness));
+            table.push_str(&format!(" {cell:>width$} |", width = snapshot.timestamp.len()));
+        }
+        let first = shown.iter().find_map(|snapshot| snapshot.languages.get(language));
+        let latest = shown.last().and_then(|snapshot| snapshot.languages.get(language));
+        let change = match (first, latest) {
+            (Some(first), Some(latest)) => format!("{:+.2}%", latest.completeness - first.completeness),
+            _ => "-".to_string(),
+        };
+        table.push_str(&format!(" {change:>8} |\n"));
+    }
+    table
+}
+
+/// Print how the completeness of each language changed over the recorded snapshots.
+pub fn subcmd_statistics_trend(history_file: &Path, last: usize) -> Result<(), CmdError> {
+    let snapshots = load_history(history_file)?;
+    if snapshots.is_empty() {
+        return Err(CmdError::EmptyHistory(history_file.to_path_buf()));
+    }
+    print!("{}", render_trend(&snapshots, last));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_record_and_render_history() {
+        assert_eq!(format_utc_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_utc_timestamp(1_709_210_096), "2024-02-29T12:34:56Z");
+
+        let snapshot = |timestamp: &str, zh_cn: f64, zh_tw: Option<f64>| {
+            let mut languages = BTreeMap::from([("zh_CN".to_string(), LanguageSnapshot { completeness: zh_cn, translated: 1, unfinished: 1 })]);
+            if let Some(zh_tw) = zh_tw {
+                languages.insert("zh_TW".to_string(), LanguageSnapshot { completeness: zh_tw, translated: 1, unfinished: 1 });
+            }
+            StatsSnapshot { timestamp: timestamp.to_string(), languages }
+        };
+        let snapshots = vec![
+            snapshot("2025-01-01T00:00:00Z", 50.0, None),
+            snapshot("2025-02-01T00:00:00Z", 75.0, Some(25.0)),
+        ];
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        for history_file in [temp_dir.path().join("history.json"), temp_dir.path().join("history.csv")] {
+            for snapshot in &snapshots {
+                record_snapshot(&history_file, snapshot.clone()).unwrap();
+            }
+            assert_eq!(load_history(&history_file).unwrap(), snapshots);
+        }
+        assert!(fs::read_to_string(temp_dir.path().join("history.csv")).unwrap().starts_with(CSV_HEADER));
+
+        let trend = render_trend(&snapshots, 10);
+        assert!(trend.contains("| Lang   | 2025-01-01T00:00:00Z | 2025-02-01T00:00:00Z | Change   |"));
+        assert!(trend.contains("|  zh_CN |               50.00% |               75.00% |  +25.00% |"));
+        assert!(trend.contains("|  zh_TW |                    - |               25.00% |   +0.00% |"));
+        assert!(!render_trend(&snapshots, 1).contains("2025-01-01"));
+    }
+}
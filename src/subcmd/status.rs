@@ -0,0 +1,320 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use serde::{Deserialize, Serialize};
+use thiserror::Error as TeError;
+
+use crate::transifex::rest_api::{TransifexLanguageStats, TransifexRestApi, TransifexRestApiError};
+use crate::transifex::tx_config_file::{load_project_tx_config_file, write_file_atomically, LoadTxConfigError, TxConfig};
+
+use super::common::content_hash;
+use super::statistics::{language_mismatch_warnings, local_languages};
+use super::stats_history::format_utc_timestamp;
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Fail to load .tx/config file because: {0}")]
+    LoadTxConfig(#[source] LoadTxConfigError),
+    #[error("Fail to load ~/.transifexrc or TX_TOKEN because: {0}")]
+    LoadTransifexRc(#[source] LoadTxConfigError),
+    #[error("Fail to fetch statistics from Transifex because: {0}")]
+    FetchStats(#[source] TransifexRestApiError),
+    #[error("Fail to fetch languages from Transifex because: {0}")]
+    FetchLanguages(#[source] TransifexRestApiError),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum SyncAction {
+    UpToDate,
+    Push,
+    Pull,
+    /// The file was never pushed or pulled by this tool, so there is nothing to compare it with.
+    Unknown,
+}
+
+#[derive(Debug, PartialEq)]
+struct FileStatus {
+    resource_id: String,
+    /// `None` for the source file.
+    language_code: Option<String>,
+    file: PathBuf,
+    local_modified: Option<String>,
+    local_hash: Option<String>,
+    remote_updated: Option<String>,
+    completeness: Option<f64>,
+    action: SyncAction,
+}
+
+/// Name of the file in the `.tx` directory which records the state of the files pushed or pulled.
+pub const SYNC_STATE_FILE_NAME: &str = ".sync-state.json";
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SyncRecord {
+    /// Content hash of the local file right after it was pushed or pulled.
+    pub hash: String,
+    /// `last_update` of the translation on Transifex when it was pulled, `None` for pushed files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_updated: Option<String>,
+}
+
+/// State of the local files when they were last pushed or pulled, keyed by their path relative to the project root.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SyncState {
+    pub files: BTreeMap<String, SyncRecord>,
+}
+
+impl SyncState {
+    pub fn state_file(project_root: &Path) -> PathBuf {
+        project_root.join(".tx").join(SYNC_STATE_FILE_NAME)
+    }
+
+    /// Load the recorded state of the project, an empty state is returned if there is none or it can't be parsed.
+    pub fn load(project_root: &Path) -> Self {
+        fs::read_to_string(Self::state_file(project_root)).ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, project_root: &Path) -> std::io::Result<()> {
+        let content = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        write_file_atomically(&Self::state_file(project_root), &(content + "\n"))
+    }
+
+    fn key(project_root: &Path, file: &Path) -> String {
+        file.strip_prefix(project_root).unwrap_or(file).to_string_lossy().to_string()
+    }
+
+    pub fn get(&self, project_root: &Path, file: &Path) -> Option<&SyncRecord> {
+        self.files.get(&Self::key(project_root, file))
+    }
+
+    pub fn record(&mut self, project_root: &Path, file: &Path, content: &[u8], remote_updated: Option<String>) {
+        self.files.insert(Self::key(project_root, file), SyncRecord { hash: content_hash(content), remote_updated });
+    }
+}
+
+/// Modification time (as an RFC 3339 UTC timestamp) and content hash of a local file, `None` if it doesn't exist.
+fn local_file_info(file: &Path) -> Option<(String, String)> {
+    let modified = fs::metadata(file).ok()?.modified().ok()?;
+    let secs = modified.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+    let content = fs::read(file).ok()?;
+    Some((format_utc_timestamp(secs), content_hash(&content)))
+}
+
+/// Compare the local files of each resource with Transifex, using the state recorded by the last push or pull.
+///
+/// A source file needs a push if its content changed since it was last pushed or pulled, or the resource is not on
+/// Transifex at all. A translation needs a pull if it changed on Transifex since it was last pulled, or it's missing
+/// locally while reaching `minimum_perc`, and a push if only the local content changed. Files without a record are
+/// reported as unknown, since modification times change on every checkout and can't be trusted.
+fn collect_file_status(project_root: &Path, tx_config: &TxConfig, remote_stats: &[TransifexLanguageStats], sync_state: &SyncState) -> Vec<FileStatus> {
+    let mut statuses = vec![];
+    for resource_section in &tx_config.resource_sections {
+        let minimum_perc = resource_section.minimum_prec.or(tx_config.main_section.minimum_prec).unwrap_or(0);
+        let resource_stats: Vec<(&str, &TransifexLanguageStats)> = remote_stats.iter()
+            .filter_map(|stats| match stats.resource_and_language() {
+                Some((resource_id, language_code)) if resource_id == resource_section.resource_full_slug => Some((language_code, stats)),
+                _ => None,
+            })
+            .collect();
+
+        let source_file = project_root.join(&resource_section.source_file);
+        let local_info = local_file_info(&source_file);
+        let source_stats = resource_stats.iter().find(|(language_code, _)| *language_code == resource_section.source_lang);
+        let remote_updated = source_stats.and_then(|(_, stats)| stats.attributes.last_update.clone());
+        let action = match (&local_info, source_stats, sync_state.get(project_root, &source_file)) {
+            (None, _, _) => SyncAction::UpToDate,
+            (Some(_), None, _) => SyncAction::Push,
+            (Some(_), Some(_), None) => SyncAction::Unknown,
+            (Some((_, local_hash)), Some(_), Some(record)) if *local_hash != record.hash => SyncAction::Push,
+            _ => SyncAction::UpToDate,
+        };
+        statuses.push(FileStatus {
+            resource_id: resource_section.resource_full_slug.clone(),
+            language_code: None,
+            file: source_file,
+            local_modified: local_info.as_ref().map(|(modified, _)| modified.clone()),
+            local_hash: local_info.map(|(_, hash)| hash),
+            remote_updated,
+            completeness: None,
+            action,
+        });
+
+        let mut translation_statuses = vec![];
+        for (language_code, stats) in resource_stats.iter().filter(|(language_code, _)| *language_code != resource_section.source_lang) {
+            let file = project_root.join(resource_section.file_filter.replace("<lang>", language_code));
+            let local_info = local_file_info(&file);
+            let completeness = stats.completeness_percentage();
+            let remote_updated = stats.attributes.last_update.clone();
+            let action = match (&local_info, sync_state.get(project_root, &file)) {
+                (None, _) if stats.attributes.translated_strings == 0 || completeness < minimum_perc as f64 => continue,
+                (None, _) => SyncAction::Pull,
+                (Some(_), None) => SyncAction::Unknown,
+                (Some(_), Some(record)) if record.remote_updated.is_some() && record.remote_updated != remote_updated => SyncAction::Pull,
+                (Some((_, local_hash)), Some(record)) if *local_hash != record.hash => SyncAction::Push,
+                _ => SyncAction::UpToDate,
+            };
+            translation_statuses.push(FileStatus {
+                resource_id: resource_section.resource_full_slug.clone(),
+                language_code: Some(language_code.to_string()),
+                file,
+                local_modified: local_info.as_ref().map(|(modified, _)| modified.clone()),
+                local_hash: local_info.map(|(_, hash)| hash),
+                remote_updated,
+                completeness: Some(completeness),
+                action,
+            });
+        }
+        translation_statuses.sort_by(|a, b| a.file.cmp(&b.file));
+        statuses.extend(translation_statuses);
+    }
+    statuses
+}
+
+/// Show the local and remote state of the files of each resource in .tx/config, and whether a push or pull is needed.
+pub fn subcmd_status(project_root: &Path, tx_config_file: Option<&Path>) -> Result<(), CmdError> {
+    let (tx_config_file, tx_config) = load_project_tx_config_file(project_root, tx_config_file).map_err(CmdError::LoadTxConfig)?;
+    println!("Found Transifex project config file at: {tx_config_file:?}");
+    let api = TransifexRestApi::new_from_transifexrc().map_err(CmdError::LoadTransifexRc)?;
+
+    let mut projects = vec![];
+    for resource_section in &tx_config.resource_sections {
+        let (organization_slug, project_slug, _) = resource_section.get_opr_slugs().map_err(CmdError::LoadTxConfig)?;
+        if !projects.contains(&(organization_slug.clone(), project_slug.clone())) {
+            projects.push((organization_slug, project_slug));
+        }
+    }
+    let mut remote_stats = vec![];
+    let mut remote_languages = BTreeSet::new();
+    for (organization_slug, project_slug) in &projects {
+        remote_stats.extend(api.get_resource_language_stats(organization_slug, project_slug).map_err(CmdError::FetchStats)?);
+        remote_languages.extend(api.get_project_languages(organization_slug, project_slug).map_err(CmdError::FetchLanguages)?
+            .into_iter().map(|language| language.attributes.code));
+    }
+
+    let statuses = collect_file_status(project_root, &tx_config, &remote_stats, &SyncState::load(project_root));
+    println!("| Resource | Language | File | Local modified | Local hash | Remote updated | Completeness | Action |");
+    println!("| -------- | -------- | ---- | -------------- | ---------- | -------------- | ------------ | ------ |");
+    for status in &statuses {
+        let file = status.file.strip_prefix(project_root).unwrap_or(&status.file);
+        println!("| {} | {} | {} | {} | {} | {} | {} | {} |",
+            status.resource_id,
+            status.language_code.as_deref().unwrap_or("(source)"),
+            file.display(),
+            status.local_modified.as_deref().unwrap_or("missing"),
+            status.local_hash.as_deref().map_or("-", |hash| &hash[..8]),
+            status.remote_updated.as_deref().unwrap_or("-"),
+            status.completeness.map_or("-".to_string(), |completeness| format!("{completeness:.2}%")),
+            match status.action {
+                SyncAction::UpToDate => "-",
+                SyncAction::Push => "push",
+                SyncAction::Pull => "pull",
+                SyncAction::Unknown => "unknown",
+            });
+    }
+    let push_count = statuses.iter().filter(|status| status.action == SyncAction::Push).count();
+    let pull_count = statuses.iter().filter(|status| status.action == SyncAction::Pull).count();
+    let unknown_count = statuses.iter().filter(|status| status.action == SyncAction::Unknown).count();
+    match (push_count, pull_count) {
+        (0, 0) => println!("\nEverything is up to date."),
+        _ => println!("\n{push_count} file(s) need a push, {pull_count} translation file(s) need a pull."),
+    }
+    if unknown_count > 0 {
+        println!("{unknown_count} file(s) were never pushed or pulled by this tool, push or pull them once to track their state.");
+    }
+    for warning in language_mismatch_warnings(&local_languages(project_root, &tx_config), &remote_languages, &[], &[]) {
+        eprintln!("Warning: {warning}.");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use crate::transifex::rest_api::TransifexLanguageStatsAttributes;
+
+    #[test]
+    fn tst_collect_file_status() {
+        let tx_config = TxConfig::from_str(r#"[main]
+host = https://www.transifex.com
+minimum_perc = 50
+
+[o:linuxdeepin:p:deepin-home:r:deepin-home]
+file_filter = translations/deepin-home_<lang>.ts
+source_file = translations/deepin-home.ts
+source_lang = en
+type = QT
+"#).unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_root = temp_dir.path().to_path_buf();
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        for file_name in ["deepin-home.ts", "deepin-home_de.ts", "deepin-home_zh_CN.ts", "deepin-home_zh_TW.ts"] {
+            fs::write(project_root.join("translations").join(file_name), file_name).unwrap();
+        }
+
+        let stats = |lang: &str, translated_strings: u64, last_update: &str| TransifexLanguageStats {
+            id: format!("o:linuxdeepin:p:deepin-home:r:deepin-home:l:{lang}"),
+            attributes: TransifexLanguageStatsAttributes {
+                total_strings: 10,
+                translated_strings,
+                last_update: Some(last_update.to_string()),
+                ..Default::default()
+            },
+        };
+        let remote_stats = vec![
+            stats("en", 10, "2000-01-01T00:00:00Z"),
+            stats("zh_CN", 10, "2000-01-01T00:00:00Z"),
+            stats("zh_TW", 10, "2999-01-01T00:00:00.123Z"),
+            stats("de", 10, "2000-01-01T00:00:00Z"),
+            stats("ja", 8, "2000-01-01T00:00:00Z"),
+            stats("fr", 2, "2000-01-01T00:00:00Z"),
+        ];
+        let translations = project_root.join("translations");
+        let mut sync_state = SyncState::default();
+        sync_state.record(&project_root, &translations.join("deepin-home.ts"), b"older content", None);
+        for file_name in ["deepin-home_zh_CN.ts", "deepin-home_zh_TW.ts"] {
+            sync_state.record(&project_root, &translations.join(file_name), file_name.as_bytes(), Some("2000-01-01T00:00:00Z".to_string()));
+        }
+        // Touching a file doesn't matter, only its content does.
+        fs::write(translations.join("deepin-home_zh_CN.ts"), "deepin-home_zh_CN.ts").unwrap();
+        let statuses = collect_file_status(&project_root, &tx_config, &remote_stats, &sync_state);
+
+        let actions: Vec<(Option<&str>, SyncAction)> = statuses.iter().map(|status| (status.language_code.as_deref(), status.action)).collect();
+        assert_eq!(actions, [
+            (None, SyncAction::Push),
+            (Some("de"), SyncAction::Unknown),
+            (Some("ja"), SyncAction::Pull),
+            (Some("zh_CN"), SyncAction::UpToDate),
+            (Some("zh_TW"), SyncAction::Pull),
+        ]);
+        assert_eq!(statuses[3].local_hash.as_deref(), Some(content_hash(b"deepin-home_zh_CN.ts").as_str()));
+        assert_eq!(statuses[3].completeness, Some(100.0));
+
+        // Local changes of a pulled translation need a push.
+        fs::write(translations.join("deepin-home_zh_CN.ts"), "changed").unwrap();
+        let statuses = collect_file_status(&project_root, &tx_config, &remote_stats, &sync_state);
+        assert_eq!(statuses[3].action, SyncAction::Push);
+    }
+
+    #[test]
+    fn tst_sync_state_round_trip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_root = temp_dir.path().to_path_buf();
+        fs::create_dir_all(project_root.join(".tx")).unwrap();
+        assert_eq!(SyncState::load(&project_root), SyncState::default());
+
+        let mut sync_state = SyncState::default();
+        sync_state.record(&project_root, &project_root.join("translations/app.ts"), b"content", None);
+        sync_state.save(&project_root).unwrap();
+        let loaded = SyncState::load(&project_root);
+        assert_eq!(loaded, sync_state);
+        assert_eq!(loaded.files["translations/app.ts"].hash, content_hash(b"content"));
+    }
+}
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: MIT
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error as TeError;
 
 use crate::transifex::{yaml_file::*, tx_config_file::*};
@@ -14,18 +14,26 @@ pub enum CmdError {
     LoadTxConfig(#[from] LoadTxConfigError),
     #[error("Fail to save transifex.yaml file because: {0}")]
     SaveTransifexYaml(#[from] serde_yaml2::ser::Errors),
+    #[error("Fail to write {0:?} because: {1}")]
+    WriteFile(PathBuf, #[source] std::io::Error),
 }
 
-pub fn subcmd_txconfig2yaml(project_root: &PathBuf) -> Result<(), CmdError> {
+/// Convert .tx/config into transifex.yaml next to it. An existing transifex.yaml is only overwritten with `force`,
+/// and with `stdout` nothing but the YAML is printed.
+pub fn subcmd_txconfig2yaml(project_root: &Path, force: bool, stdout: bool) -> Result<(), CmdError> {
     let (tx_config_path, tx_config) = try_load_tx_config_file(project_root)?;
-    let tx_yaml = tx_config.to_transifex_yaml();
+    let tx_yaml = serde_yaml2::to_string::<TransifexYaml>(tx_config.to_transifex_yaml())?;
+    if stdout {
+        print!("{tx_yaml}");
+        return Ok(());
+    }
     let tx_yaml_path = tx_config_path.parent().unwrap().join("transifex.yaml");
-    if tx_yaml_path.exists() {
-        println!("Note: {tx_yaml_path:?} file already exists, not overwriting it.");
+    if tx_yaml_path.exists() && !force {
+        println!("Note: {tx_yaml_path:?} file already exists, not overwriting it. Use --force to overwrite it.");
         println!("You can use the following context to update the file manually:\n");
-        println!("{}", serde_yaml2::to_string::<TransifexYaml>(tx_yaml)?);
+        println!("{tx_yaml}");
     } else {
-        fs::write(&tx_yaml_path, serde_yaml2::to_string::<TransifexYaml>(tx_yaml)?).unwrap();
+        fs::write(&tx_yaml_path, tx_yaml).map_err(|e| CmdError::WriteFile(tx_yaml_path.clone(), e))?;
         println!("Wrote transifex.yaml file to: {}", tx_yaml_path.display());
     }
 
@@ -0,0 +1,38 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::path::{Path, PathBuf};
+use thiserror::Error as TeError;
+use crate::i18n_file::gettext::{Po, PoLoadError, PoSaveError, PoSaveOptions};
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Provided file {0:?} does not exist")]
+    FileNotFound(PathBuf),
+    #[error("Fail to load PO file {0:?} because: {1}")]
+    LoadPoFile(PathBuf, #[source] PoLoadError),
+    #[error("Fail to save PO file {0:?} because: {1}")]
+    SavePoFile(PathBuf, #[source] PoSaveError),
+}
+
+fn load_po_file(po_file: &Path) -> Result<Po, CmdError> {
+    if !po_file.is_file() {
+        return Err(CmdError::FileNotFound(po_file.to_path_buf()));
+    }
+    Po::load_from_file(po_file).map_err(|e| CmdError::LoadPoFile(po_file.to_path_buf(), e))
+}
+
+pub fn subcmd_updatepo(po_file: &Path, pot_file: &Path, no_fuzzy_matching: bool, output: Option<&Path>, save_options: &PoSaveOptions) -> Result<(), CmdError> {
+    let po = load_po_file(po_file)?;
+    let template = load_po_file(pot_file)?;
+
+    let (merged, stats) = po.merge_with_template(&template, !no_fuzzy_matching);
+
+    let output = output.unwrap_or(po_file);
+    merged.save_into_file(output, save_options).map_err(|e| CmdError::SavePoFile(output.to_path_buf(), e))?;
+    println!("Updated {output:?}: {} translated, {} fuzzy, {} untranslated, {} obsolete messages.",
+        stats.exact, stats.fuzzy, stats.untranslated, stats.obsolete);
+
+    Ok(())
+}
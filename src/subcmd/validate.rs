@@ -0,0 +1,1965 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error as TeError;
+use zhconv::{zhconv, Variant};
+
+use crate::i18n_file::common::{is_cjk_ideograph, I18nFileKind};
+use crate::i18n_file::gettext::{escape_po_string, unescape_po_string, Po};
+use crate::i18n_file::language_team::{lookup_language_team, parse_nplurals};
+use crate::i18n_file::linguist::Ts;
+use crate::parallel;
+use crate::glossary::{Glossary, GlossaryLoadError};
+pub use crate::glossary::Severity;
+use crate::subcmd::gentxcfg::find_language_codes_in_filename;
+use crate::conversion::{script_of, VariantMapping};
+use crate::transifex::tx_config_file::{try_load_tx_config_file, write_file_atomically, TxConfig};
+
+/// Extensions of files that are picked up when a directory is given.
+const TRANSLATION_FILE_EXTENSIONS: &[&str] = &["ts", "po", "pot"];
+
+/// Valid values of the `type` attribute of `<translation>`, finished translations have no `type`.
+const TRANSLATION_TYPES: &[&str] = &["unfinished", "vanished", "obsolete"];
+
+/// Marker of suppression comments, e.g. `dtu:disable placeholder-mismatch, double-space`.
+const SUPPRESSION_MARKER: &str = "dtu:disable";
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Provided path {0:?} does not exist")]
+    PathNotFound(PathBuf),
+    #[error("Fail to read file {0:?} because: {1}")]
+    ReadFile(PathBuf, #[source] std::io::Error),
+    #[error("Fail to write file {0:?} because: {1}")]
+    WriteFile(PathBuf, #[source] std::io::Error),
+    #[error("Fail to load glossary {0:?} because: {1}")]
+    LoadGlossary(PathBuf, #[source] GlossaryLoadError),
+    #[error("Fail to serialize findings because: {0}")]
+    Serialize(#[source] serde_json::Error),
+    #[error("{0} problem(s) found")]
+    Findings(usize),
+}
+
+#[derive(clap::ValueEnum, Clone, Default, Copy, Debug)]
+pub enum ValidateFormat {
+    /// One finding per line, as `<file>:<line>: <message> [<rule>]`
+    #[default]
+    Text,
+    Json,
+}
+
+/// Every rule with the default severity of its findings. Rules about style or based on heuristics only warn, so that
+/// they don't fail the check out of the box.
+pub const RULES: &[(&str, Severity)] = &[
+    ("malformed-xml", Severity::Error),
+    ("malformed-po", Severity::Error),
+    ("invalid-structure", Severity::Error),
+    ("invalid-encoding", Severity::Error),
+    ("charset-mismatch", Severity::Error),
+    ("mojibake", Severity::Warning),
+    ("missing-language", Severity::Error),
+    ("language-mismatch", Severity::Warning),
+    ("unsupported-version", Severity::Warning),
+    ("invalid-translation-type", Severity::Error),
+    ("broken-plural", Severity::Error),
+    ("empty-plural-form", Severity::Error),
+    ("missing-plural-forms", Severity::Error),
+    ("invalid-plural-forms", Severity::Error),
+    ("plural-forms-mismatch", Severity::Error),
+    ("plural-count-mismatch", Severity::Error),
+    ("duplicate-message", Severity::Error),
+    ("placeholder-mismatch", Severity::Error),
+    ("html-tag-mismatch", Severity::Error),
+    ("accelerator-mismatch", Severity::Error),
+    ("whitespace-mismatch", Severity::Error),
+    ("terminal-punctuation", Severity::Warning),
+    ("double-space", Severity::Warning),
+    ("chinese-script-mismatch", Severity::Error),
+    ("inconsistent-translation", Severity::Warning),
+    // The severity of each glossary term takes precedence.
+    ("glossary-term", Severity::Error),
+];
+
+/// Default severity of the findings of a rule, see [`RULES`].
+fn default_severity(rule: &str) -> Severity {
+    RULES.iter().find(|(name, _)| *name == rule).map_or(Severity::Error, |(_, severity)| *severity)
+}
+
+/// Check that the rule is one of [`RULES`].
+pub fn check_rule_name(rule: &str) -> Result<(), String> {
+    match RULES.iter().any(|(name, _)| *name == rule) {
+        true => Ok(()),
+        false => Err(format!("unknown rule {rule:?}, expected one of {}", RULES.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(", "))),
+    }
+}
+
+/// Parse a `<rule>=<severity>` argument, e.g. `double-space=warning`.
+pub fn parse_rule_severity(s: &str) -> Result<(String, Severity), String> {
+    let (rule, severity) = s.split_once('=').ok_or_else(|| format!("{s:?} is not in the form of <rule>=<severity>"))?;
+    let rule = rule.trim();
+    check_rule_name(rule)?;
+    Ok((rule.to_string(), severity.trim().parse()?))
+}
+
+/// Findings of which severity make the command exit with a non-zero status.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailOn {
+    /// Fail on errors only
+    #[default]
+    Error,
+    /// Fail on errors and warnings
+    Warning,
+    /// Fail on any finding
+    Info,
+    /// Never fail because of findings
+    Never,
+}
+
+impl FailOn {
+    fn fails_on(self, severity: Severity) -> bool {
+        match self {
+            FailOn::Error => severity >= Severity::Error,
+            FailOn::Warning => severity >= Severity::Warning,
+            FailOn::Info => true,
+            FailOn::Never => false,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ValidateOptions {
+    /// Glossary whose terms translations must use, see [`Glossary::load`].
+    pub glossary: Option<PathBuf>,
+    /// Severity of the findings of each rule, e.g. `double-space`, overriding the default of the rule.
+    pub severities: BTreeMap<String, Severity>,
+    pub fail_on: FailOn,
+    /// Fix safe problems in place before checking, see [`fix_file`].
+    pub fix: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ValidationFinding {
+    pub file: PathBuf,
+    /// 1-based line number, 0 if the problem is not about a specific line.
+    pub line: usize,
+    /// Name of the check, e.g. `malformed-xml`.
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl ValidationFinding {
+    fn new(line: usize, rule: &'static str, message: String) -> Self {
+        Self { file: PathBuf::new(), line, rule, severity: default_severity(rule), message }
+    }
+}
+
+/// 1-based line number of the given byte offset.
+fn line_at(content: &str, offset: u64) -> usize {
+    let offset = (offset as usize).min(content.len());
+    content.as_bytes()[..offset].iter().filter(|byte| **byte == b'\n').count() + 1
+}
+
+fn attribute_value(element: &BytesStart, name: &str) -> Option<String> {
+    element.try_get_attribute(name).ok()?.and_then(|attribute| attribute.unescape_value().ok().map(|value| value.into_owned()))
+}
+
+/// Byte range of the value of the given attribute of the start tag at `tag` in the content.
+fn attribute_value_span(content: &str, tag: Range<usize>, name: &str) -> Option<Range<usize>> {
+    let attribute_regex = Regex::new(&format!(r#"\s{name}\s*=\s*(?:"([^"]*)"|'([^']*)')"#)).unwrap();
+    let captures = attribute_regex.captures(content.get(tag.clone())?)?;
+    let value = captures.get(1).or(captures.get(2))?;
+    Some(tag.start + value.start()..tag.start + value.end())
+}
+
+/// Rules suppressed by the `dtu:disable` lines of a translator comment or extracted comment, e.g. `dtu:disable
+/// placeholder-mismatch, double-space` for a translation that intentionally leaves out a placeholder. Without rules,
+/// all rules are suppressed, which is recorded as `all`.
+fn suppressed_rules(comment: &str) -> Vec<String> {
+    let mut rules = vec![];
+    for line in comment.lines() {
+        let Some(position) = line.find(SUPPRESSION_MARKER) else {
+            continue;
+        };
+        let rest = &line[position + SUPPRESSION_MARKER.len()..];
+        if rest.starts_with(|c: char| !c.is_whitespace() && c != ':') {
+            continue;
+        }
+        let line_rules: Vec<String> = rest.trim_start_matches(':').split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|rule| !rule.is_empty())
+            .map(str::to_string)
+            .collect();
+        match line_rules.is_empty() {
+            true => rules.push("all".to_string()),
+            false => rules.extend(line_rules),
+        }
+    }
+    rules
+}
+
+/// Whether the finding is about a message whose comments suppress its rule.
+fn is_suppressed(suppressions: &BTreeMap<usize, Vec<String>>, finding: &ValidationFinding) -> bool {
+    suppressions.get(&finding.line).is_some_and(|rules| rules.iter().any(|rule| rule == finding.rule || rule == "all"))
+}
+
+/// A message as read by the structural checks, for the checks comparing translations with their source.
+#[derive(Debug, Default)]
+struct ScannedMessage {
+    /// Line of `<message>`, or of the first keyword of a PO entry.
+    line: usize,
+    /// `<name>` of the `<context>`, or `msgctxt` of PO files.
+    context: String,
+    source: String,
+    /// `<comment>` of Qt Linguist files.
+    disambiguation: Option<String>,
+    /// `msgid_plural` of PO files.
+    source_plural: Option<String>,
+    /// Whether it's a plural message, i.e. `numerus="yes"` or with `msgid_plural`.
+    plural: bool,
+    /// Translations that are not vanished or obsolete, one per plural form.
+    translations: Vec<String>,
+    /// Byte ranges of the raw (still escaped) text of each translation in the file, for `--fix`.
+    spans: Vec<Vec<Range<usize>>>,
+}
+
+/// What the message checks know about the file.
+#[derive(Debug, Default)]
+struct FileContext {
+    /// Language of the translations, empty if unknown.
+    language: String,
+}
+
+/// What the checks read from a file, besides the findings.
+#[derive(Debug, Default)]
+struct FileReport {
+    findings: Vec<ValidationFinding>,
+    context: FileContext,
+    messages: Vec<ScannedMessage>,
+    /// Rules suppressed by comments of the message at each line, see [`suppressed_rules`].
+    suppressions: BTreeMap<usize, Vec<String>>,
+    /// Attributes of `<TS>` for Qt Linguist files.
+    ts_attributes: Option<TsAttributes>,
+}
+
+/// Attributes of `<TS>` to check against the languages implied by the path of the file.
+#[derive(Debug, Default)]
+struct TsAttributes {
+    /// Line of `<TS>`.
+    line: usize,
+    language: Option<String>,
+    source_language: Option<String>,
+    /// Byte ranges of the values of the attributes in the file, for `--fix`.
+    language_span: Option<Range<usize>>,
+    source_language_span: Option<Range<usize>>,
+}
+
+/// Languages of a file implied by its path, see [`implied_languages`].
+#[derive(Debug, PartialEq)]
+struct ImpliedLanguages {
+    language: String,
+    /// Only known from .tx/config.
+    source_language: Option<String>,
+    /// Where the languages come from, e.g. `.tx/config`.
+    origin: String,
+}
+
+/// A check of a translation against the source of a message, returning the problem if any.
+type MessageCheck = fn(&ScannedMessage, &str, &FileContext) -> Option<String>;
+
+/// Checks of each non-empty translation of each message.
+const MESSAGE_CHECKS: &[(&str, MessageCheck)] = &[
+    ("placeholder-mismatch", check_placeholders),
+    ("html-tag-mismatch", check_html_tags),
+    ("accelerator-mismatch", check_accelerators),
+    ("whitespace-mismatch", check_surrounding_whitespace),
+    ("terminal-punctuation", check_terminal_punctuation),
+    ("double-space", check_double_spaces),
+    ("chinese-script-mismatch", check_chinese_script),
+];
+
+fn check_messages(messages: &[ScannedMessage], context: &FileContext) -> Vec<ValidationFinding> {
+    let mut findings = vec![];
+    for message in messages {
+        for (form, translation) in message.translations.iter().enumerate().filter(|(_, translation)| !translation.is_empty()) {
+            for (rule, check) in MESSAGE_CHECKS {
+                if let Some(problem) = check(message, translation, context) {
+                    let problem = match message.translations.len() {
+                        1 => problem,
+                        _ => format!("plural form {form}: {problem}"),
+                    };
+                    findings.push(ValidationFinding::new(message.line, rule, problem));
+                }
+            }
+        }
+    }
+    findings
+}
+
+/// Translations that don't use the target term of a glossary term their source uses, e.g. "Control Center" should be
+/// translated as "控制中心" in all deepin components. Each term is reported once per message.
+fn check_glossary(glossary: &Glossary, report: &FileReport) -> Vec<ValidationFinding> {
+    if report.context.language.is_empty() {
+        return vec![];
+    }
+    let terms = glossary.terms_for_language(&report.context.language);
+    let mut findings = vec![];
+    for message in &report.messages {
+        let sources = [Some(&message.source), message.source_plural.as_ref()];
+        for term in terms.iter().filter(|term| sources.iter().flatten().any(|source| term.is_used_by(source))) {
+            let mut forms = message.translations.iter().enumerate().filter(|(_, translation)| !translation.is_empty() && !term.is_followed_by(translation));
+            let Some((form, _)) = forms.next() else {
+                continue;
+            };
+            let targets = term.targets.iter().map(|target| format!("{target:?}")).collect::<Vec<_>>().join(" or ");
+            let problem = match message.translations.len() {
+                1 => format!("{:?} should be translated as {targets}", term.source),
+                _ => format!("plural form {form}: {:?} should be translated as {targets}", term.source),
+            };
+            findings.push(ValidationFinding { severity: term.severity, ..ValidationFinding::new(message.line, "glossary-term", problem) });
+        }
+    }
+    findings
+}
+
+/// Messages with the same context, source and disambiguation as an earlier message of the file, tools only keep one of them.
+fn check_duplicates(messages: &[ScannedMessage]) -> Vec<ValidationFinding> {
+    let mut seen = BTreeMap::new();
+    let mut findings = vec![];
+    for message in messages {
+        let key = (&message.context, &message.source, &message.disambiguation);
+        let Some(first) = seen.get(&key) else {
+            seen.insert(key, message);
+            continue;
+        };
+        let problem = match first.translations == message.translations {
+            true => format!("duplicate of the message at line {}", first.line),
+            false => format!("duplicate of the message at line {}, with a different translation", first.line),
+        };
+        findings.push(ValidationFinding::new(message.line, "duplicate-message", problem));
+    }
+    findings
+}
+
+/// Messages whose source is translated differently elsewhere in files of the same language, e.g. "Cancel" in two components
+/// of the project. Only messages with a single non-empty translation and without disambiguation are compared, and each one
+/// that differs from the most common translation is reported, with the index of its file.
+fn check_inconsistent_translations(reports: &[(&Path, FileReport)]) -> Vec<(usize, ValidationFinding)> {
+    // language -> source -> translation -> (file index, line) of each message.
+    let mut translations = BTreeMap::<String, BTreeMap<&str, BTreeMap<&str, Vec<(usize, usize)>>>>::new();
+    for (index, (_, report)) in reports.iter().enumerate().filter(|(_, (_, report))| !report.context.language.is_empty()) {
+        let language = report.context.language.replace('-', "_");
+        for message in &report.messages {
+            let [translation] = message.translations.as_slice() else {
+                continue;
+            };
+            if translation.is_empty() || message.disambiguation.is_some() {
+                continue;
+            }
+            translations.entry(language.clone()).or_default()
+                .entry(&message.source).or_default()
+                .entry(translation).or_default()
+                .push((index, message.line));
+        }
+    }
+
+    let mut findings = vec![];
+    for by_translation in translations.values().flat_map(BTreeMap::values).filter(|by_translation| by_translation.len() > 1) {
+        let (common, common_locations) = by_translation.iter().rev().max_by_key(|(_, locations)| locations.len()).unwrap();
+        let (example_index, example_line) = common_locations[0];
+        for (translation, locations) in by_translation.iter().filter(|(translation, _)| translation != &common) {
+            for (index, line) in locations {
+                findings.push((*index, ValidationFinding::new(*line, "inconsistent-translation", format!(
+                    "translated as {translation:?} here, but as {common:?} in {} other place(s), e.g. {}:{example_line}",
+                    common_locations.len(), reports[example_index].0.display()))));
+            }
+        }
+    }
+    findings
+}
+
+static PLACEHOLDER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"%%|%L?(\d+|n)|\{\d+\}|\$\{[A-Za-z_][A-Za-z0-9_.]*\}").unwrap()
+});
+
+/// Placeholders used by Qt (`%1`, `%L1`, `%n`, `%Ln`) and other formatting libraries (`{0}`, `${var}`), `%%` is an escaped `%`.
+/// The `L` (localized number) modifier is ignored since translations may add or drop it.
+fn placeholders(text: &str) -> BTreeSet<String> {
+    PLACEHOLDER.find_iter(text)
+        .map(|placeholder| placeholder.as_str())
+        .filter(|placeholder| *placeholder != "%%")
+        .map(|placeholder| placeholder.replacen("%L", "%", 1))
+        .collect()
+}
+
+/// Placeholders of the translation must be the same as the source. For plural messages, a form may omit placeholders like
+/// `%n` (e.g. "One file"), but it can't use a placeholder the source doesn't have.
+fn check_placeholders(message: &ScannedMessage, translation: &str, _context: &FileContext) -> Option<String> {
+    let mut expected = placeholders(&message.source);
+    if let Some(source_plural) = &message.source_plural {
+        expected.extend(placeholders(source_plural));
+    }
+    let actual = placeholders(translation);
+    let missing: Vec<&str> = match message.plural {
+        true => vec![],
+        false => expected.difference(&actual).map(String::as_str).collect(),
+    };
+    let extra: Vec<&str> = actual.difference(&expected).map(String::as_str).collect();
+    let problems: Vec<String> = [("missing", missing), ("unexpected", extra)].into_iter()
+        .filter(|(_, placeholders)| !placeholders.is_empty())
+        .map(|(kind, placeholders)| format!("{kind} {}", placeholders.join(", ")))
+        .collect();
+    match problems.is_empty() {
+        true => None,
+        false => Some(format!("placeholders of the translation do not match the source: {}", problems.join("; "))),
+    }
+}
+
+/// HTML elements that have no closing tag.
+const HTML_VOID_ELEMENTS: &[&str] = &["br", "hr", "img", "input", "meta", "link", "wbr"];
+
+static HTML_TAG: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"<(/?)([A-Za-z][A-Za-z0-9]*)(?:\s[^<>]*)?/?>").unwrap()
+});
+
+/// HTML-ish tags of rich text, as lowercase `b`, `/b` or `br`. Attributes are ignored, and `<br/>` is the same as `<br>`.
+fn html_tags(text: &str) -> Vec<String> {
+    HTML_TAG.captures_iter(text)
+        .map(|captures| format!("{}{}", &captures[1], captures[2].to_lowercase()))
+        .collect()
+}
+
+/// Whether each opening tag is closed in the right order, void elements and self-closing tags need no closing tag.
+fn is_balanced(tags: &[String]) -> bool {
+    let mut stack = vec![];
+    for tag in tags {
+        match tag.strip_prefix('/') {
+            Some(name) => {
+                if stack.pop() != Some(name) {
+                    return false;
+                }
+            },
+            None if HTML_VOID_ELEMENTS.contains(&tag.as_str()) => (),
+            None => stack.push(tag.as_str()),
+        }
+    }
+    stack.is_empty()
+}
+
+/// Tags of the translation must be the same as the source (in any order, since the word order may change), and balanced
+/// unless the source isn't.
+fn check_html_tags(message: &ScannedMessage, translation: &str, _context: &FileContext) -> Option<String> {
+    let count = |tags: Vec<String>| tags.into_iter().fold(BTreeMap::<String, usize>::new(), |mut counts, tag| {
+        *counts.entry(tag).or_default() += 1;
+        counts
+    });
+    let source_tags = html_tags(&message.source);
+    let translation_tags = html_tags(translation);
+    let expected = count(source_tags.clone());
+    let actual = count(translation_tags.clone());
+    let matches_plural_source = message.source_plural.as_ref().is_some_and(|source_plural| count(html_tags(source_plural)) == actual);
+    if expected != actual && !matches_plural_source {
+        let difference = |lhs: &BTreeMap<String, usize>, rhs: &BTreeMap<String, usize>| lhs.iter()
+            .filter(|(tag, count)| rhs.get(*tag).is_none_or(|rhs_count| rhs_count < count))
+            .map(|(tag, _)| format!("<{tag}>"))
+            .collect::<Vec<_>>();
+        let problems: Vec<String> = [("missing", difference(&expected, &actual)), ("unexpected", difference(&actual, &expected))].into_iter()
+            .filter(|(_, tags)| !tags.is_empty())
+            .map(|(kind, tags)| format!("{kind} {}", tags.join(", ")))
+            .collect();
+        return Some(format!("tags of the translation do not match the source: {}", problems.join("; ")));
+    }
+    if is_balanced(&source_tags) && !is_balanced(&translation_tags) {
+        return Some("tags of the translation are not balanced".to_string());
+    }
+    None
+}
+
+/// Languages whose translations put the accelerator into a `(&X)` suffix, since their scripts have no letters to underline.
+const SUFFIX_ACCELERATOR_LANGUAGES: &[&str] = &["zh", "ja", "ko"];
+
+static ACCELERATOR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"&&|&#?[A-Za-z0-9]+;|&([\p{L}\p{N}])").unwrap()
+});
+
+static SUFFIX_ACCELERATOR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\(&([\p{L}\p{N}])\)").unwrap()
+});
+
+/// Accelerator keys marked by `&`, e.g. `F` of `&File`. `&&` is a literal `&`, and HTML entities like `&nbsp;` are not accelerators.
+fn accelerators(text: &str) -> Vec<char> {
+    ACCELERATOR.captures_iter(text)
+        .filter_map(|captures| captures.get(1)?.as_str().chars().next())
+        .collect()
+}
+
+/// A translation must have exactly one accelerator if the source has one. Translations into [`SUFFIX_ACCELERATOR_LANGUAGES`]
+/// must use a `(&X)` suffix with the same key as the source instead.
+fn check_accelerators(message: &ScannedMessage, translation: &str, context: &FileContext) -> Option<String> {
+    let [source_key] = accelerators(&message.source)[..] else {
+        return None;
+    };
+    let translation_keys = accelerators(translation);
+    match translation_keys.len() {
+        0 => return Some(format!("the source has accelerator &{source_key} but the translation has none")),
+        1 => (),
+        _ => return Some(format!("the translation has {} accelerators instead of one", translation_keys.len())),
+    }
+    let language = context.language.split(['_', '-', '@']).next().unwrap_or_default();
+    if SUFFIX_ACCELERATOR_LANGUAGES.contains(&language) {
+        let suffix_key = SUFFIX_ACCELERATOR.captures(translation).and_then(|captures| captures[1].chars().next());
+        if suffix_key.is_none_or(|key| !key.eq_ignore_ascii_case(&source_key)) {
+            return Some(format!("the accelerator should be written as (&{}) in {} translations", source_key.to_ascii_uppercase(), context.language));
+        }
+    }
+    None
+}
+
+/// Whether the translation lacks or has extra leading and trailing whitespace compared to the source.
+fn surrounding_whitespace_mismatch(source: &str, translation: &str) -> (bool, bool) {
+    let has_leading = |text: &str| text.starts_with(char::is_whitespace);
+    let has_trailing = |text: &str| text.ends_with(char::is_whitespace);
+    (has_leading(source) != has_leading(translation), has_trailing(source) != has_trailing(translation))
+}
+
+/// Leading and trailing whitespace (e.g. a space before a following string, or a trailing newline) must be kept.
+fn check_surrounding_whitespace(message: &ScannedMessage, translation: &str, _context: &FileContext) -> Option<String> {
+    let (leading, trailing) = surrounding_whitespace_mismatch(&message.source, translation);
+    let problems: Vec<&str> = [(leading, "leading"), (trailing, "trailing")].into_iter()
+        .filter(|(mismatch, _)| *mismatch)
+        .map(|(_, position)| position)
+        .collect();
+    match problems.is_empty() {
+        true => None,
+        false => Some(format!("{} whitespace of the translation does not match the source", problems.join(" and "))),
+    }
+}
+
+/// Terminal punctuation of the source, and what translations may end with instead.
+type PunctuationRules = &'static [(&'static str, &'static [&'static str])];
+
+/// Terminal punctuation of languages using full-width punctuation.
+const FULL_WIDTH_PUNCTUATION_RULES: PunctuationRules = &[
+    ("...", &["…", "……", "..."]),
+    ("…", &["…", "……", "..."]),
+    (".", &["。"]),
+    ("!", &["！"]),
+    ("?", &["？"]),
+    (":", &["："]),
+];
+
+/// Terminal punctuation of languages without special rules, an ellipsis may be written either way.
+const DEFAULT_PUNCTUATION_RULES: PunctuationRules = &[
+    ("...", &["...", "…"]),
+    ("…", &["…", "..."]),
+    (".", &["."]),
+    ("!", &["!"]),
+    ("?", &["?"]),
+    (":", &[":"]),
+];
+
+/// Terminal punctuation of languages written in the Arabic script, which has its own question mark.
+const ARABIC_PUNCTUATION_RULES: PunctuationRules = &[
+    ("...", &["...", "…"]),
+    ("…", &["…", "..."]),
+    (".", &["."]),
+    ("!", &["!"]),
+    ("?", &["؟", "?"]),
+    (":", &[":"]),
+];
+
+/// Punctuation rules of languages that differ from [`DEFAULT_PUNCTUATION_RULES`].
+const LANGUAGE_PUNCTUATION_RULES: &[(&str, PunctuationRules)] = &[
+    ("zh", FULL_WIDTH_PUNCTUATION_RULES),
+    ("ja", FULL_WIDTH_PUNCTUATION_RULES),
+    ("ar", ARABIC_PUNCTUATION_RULES),
+    ("fa", ARABIC_PUNCTUATION_RULES),
+    ("ug", ARABIC_PUNCTUATION_RULES),
+    // Greek uses `;` as the question mark.
+    ("el", &[("...", &["...", "…"]), ("…", &["…", "..."]), (".", &["."]), ("!", &["!"]), ("?", &[";", "?"]), (":", &[":"])]),
+];
+
+static TRAILING_TAGS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:\s*<[^<>]+>)+\s*$").unwrap()
+});
+
+/// If the source ends with terminal punctuation, so must the translation, using the punctuation of its language
+/// (e.g. full-width `。` for Chinese). Trailing whitespace and rich text tags are ignored.
+fn check_terminal_punctuation(message: &ScannedMessage, translation: &str, context: &FileContext) -> Option<String> {
+    let strip_end = |text: &str| TRAILING_TAGS.replace(text.trim_end(), "").to_string();
+    let source = strip_end(&message.source);
+    let translation = strip_end(translation);
+    let language = context.language.split(['_', '-', '@']).next().unwrap_or_default();
+    let rules = LANGUAGE_PUNCTUATION_RULES.iter()
+        .find(|(rule_language, _)| *rule_language == language)
+        .map_or(DEFAULT_PUNCTUATION_RULES, |(_, rules)| *rules);
+    let (punctuation, expected) = rules.iter().find(|(punctuation, _)| source.ends_with(punctuation))?;
+    match expected.iter().any(|expected| translation.ends_with(expected)) {
+        true => None,
+        false => Some(format!("the source ends with {punctuation:?} but the translation does not end with {}",
+            expected.iter().map(|expected| format!("{expected:?}")).collect::<Vec<_>>().join(" or "))),
+    }
+}
+
+/// Double spaces inside the translation are usually typos, unless the source has them too.
+fn check_double_spaces(message: &ScannedMessage, translation: &str, _context: &FileContext) -> Option<String> {
+    match translation.trim().contains("  ") && !message.source.contains("  ") {
+        true => Some("the translation contains double spaces".to_string()),
+        false => None,
+    }
+}
+
+/// Chinese translations should be written in the script of their language, simplified characters in a `zh_TW` or `zh_HK`
+/// translation likely come from a missed conversion, and so do traditional characters in a `zh_CN` one. Characters are found
+/// by converting the whole translation, so that characters valid in both scripts (e.g. 后 in 皇后) are not reported, and only
+/// characters of the other script are kept, so that variants of the same script (e.g. 啟 and 啓) are not reported either.
+fn check_chinese_script(_message: &ScannedMessage, translation: &str, context: &FileContext) -> Option<String> {
+    if !translation.chars().any(is_cjk_ideograph) {
+        return None;
+    }
+    let (expected, unexpected, variant, other_variant) = match VariantMapping::default().variant(&context.language).ok().map(script_of)? {
+        Variant::ZhHant => ("traditional", "simplified", Variant::ZhHant, Variant::ZhHans),
+        Variant::ZhHans => ("simplified", "traditional", Variant::ZhHans, Variant::ZhHant),
+        _ => return None,
+    };
+    let converted = zhconv(translation, variant);
+    if converted == translation {
+        return None;
+    }
+    // Phrase conversions may change the length, then every character is a candidate.
+    let candidates: Vec<char> = match converted.chars().count() == translation.chars().count() {
+        true => translation.chars().zip(converted.chars()).filter(|(character, converted)| character != converted).map(|(character, _)| character).collect(),
+        false => translation.chars().filter(|character| is_cjk_ideograph(*character)).collect(),
+    };
+    let mut characters = String::new();
+    for character in candidates {
+        let character_text = character.to_string();
+        let is_other_script = zhconv(&character_text, variant) != character_text && zhconv(&character_text, other_variant) == character_text;
+        if is_other_script && !characters.contains(character) {
+            characters.push(character);
+        }
+    }
+    match characters.is_empty() {
+        true => None,
+        false => Some(format!("{unexpected} Chinese characters {characters:?} in a {expected} Chinese ({}) translation", context.language)),
+    }
+}
+
+/// State of the `<message>` being read.
+struct TsMessageState {
+    message: ScannedMessage,
+    numerus_forms: usize,
+    /// Whether the `<translation>` has text outside `<numerusform>`.
+    has_plain_text: bool,
+    /// Whether the translation is vanished or obsolete.
+    inactive: bool,
+    /// Whether the translation is `type="unfinished"`.
+    unfinished: bool,
+    translation: String,
+    translation_spans: Vec<Range<usize>>,
+    /// `<translatorcomment>` and `<extracomment>`, which may suppress rules.
+    comments: String,
+}
+
+impl TsMessageState {
+    fn finish(mut self, findings: &mut Vec<ValidationFinding>, messages: &mut Vec<ScannedMessage>, suppressions: &mut BTreeMap<usize, Vec<String>>) {
+        findings.extend(check_ts_message_plural(&self));
+        let rules = suppressed_rules(&self.comments);
+        if !rules.is_empty() {
+            suppressions.insert(self.message.line, rules);
+        }
+        if !self.inactive {
+            if self.numerus_forms == 0 {
+                self.message.translations.push(self.translation);
+                self.message.spans.push(self.translation_spans);
+            }
+            messages.push(self.message);
+        }
+    }
+}
+
+/// Check the structure of a Qt Linguist file: well-formed XML, the `language` attribute of `<TS>`, the `type` of
+/// `<translation>`, and that `<numerusform>` is used by (and only by) messages with `numerus="yes"`. Then check
+/// translations against their source, see [`MESSAGE_CHECKS`].
+fn validate_ts(content: &str) -> FileReport {
+    let mut findings = vec![];
+    let mut messages = vec![];
+    let mut reader = Reader::from_str(content);
+    let mut path: Vec<Vec<u8>> = vec![];
+    let mut message: Option<TsMessageState> = None;
+    let mut context = FileContext::default();
+    let mut ts_attributes = TsAttributes::default();
+    let mut context_name = String::new();
+    let mut suppressions = BTreeMap::new();
+    loop {
+        let start = reader.buffer_position() as usize;
+        let line = line_at(content, reader.buffer_position());
+        let event = match reader.read_event() {
+            Ok(event) => event,
+            Err(e) => {
+                findings.push(ValidationFinding::new(line_at(content, reader.error_position()), "malformed-xml", e.to_string()));
+                return FileReport { findings, ..Default::default() };
+            },
+        };
+        let span = start..reader.buffer_position() as usize;
+        match event {
+            Event::Start(ref element) | Event::Empty(ref element) => {
+                let name = element.name().as_ref().to_vec();
+                match name.as_slice() {
+                    b"TS" if path.is_empty() => {
+                        match attribute_value(element, "language") {
+                            Some(language) if !language.trim().is_empty() => context.language = language.trim().to_string(),
+                            _ => findings.push(ValidationFinding::new(line, "missing-language", "<TS> has no language attribute".to_string())),
+                        }
+                        match attribute_value(element, "version") {
+                            Some(version) if !Ts::SUPPORTED_VERSIONS.contains(&version.as_str()) => findings.push(ValidationFinding::new(line,
+                                "unsupported-version", format!("version {version:?} is not one of {}, saving the file may change its structure",
+                                    Ts::SUPPORTED_VERSIONS.join(", ")))),
+                            _ => (),
+                        }
+                        ts_attributes = TsAttributes {
+                            line,
+                            language: Some(context.language.clone()).filter(|language| !language.is_empty()),
+                            source_language: attribute_value(element, "sourcelanguage").map(|language| language.trim().to_string()),
+                            language_span: attribute_value_span(content, span.clone(), "language"),
+                            source_language_span: attribute_value_span(content, span.clone(), "sourcelanguage"),
+                        };
+                    },
+                    _ if path.is_empty() => {
+                        findings.push(ValidationFinding::new(line, "malformed-xml", format!("root element is <{}> instead of <TS>", String::from_utf8_lossy(&name))));
+                        return FileReport { findings, ..Default::default() };
+                    },
+                    b"context" => context_name.clear(),
+                    b"message" => message = Some(TsMessageState {
+                        message: ScannedMessage {
+                            line,
+                            context: context_name.clone(),
+                            plural: attribute_value(element, "numerus").as_deref() == Some("yes"),
+                            ..Default::default()
+                        },
+                        numerus_forms: 0,
+                        has_plain_text: false,
+                        inactive: false,
+                        unfinished: false,
+                        translation: String::new(),
+                        translation_spans: vec![],
+                        comments: String::new(),
+                    }),
+                    b"translation" => {
+                        let type_attr = attribute_value(element, "type");
+                        if let Some(message) = &mut message {
+                            message.inactive = matches!(type_attr.as_deref(), Some("vanished" | "obsolete"));
+                            message.unfinished = type_attr.as_deref() == Some("unfinished");
+                        }
+                        match type_attr {
+                            Some(type_attr) if !TRANSLATION_TYPES.contains(&type_attr.as_str()) => findings.push(ValidationFinding::new(line,
+                                "invalid-translation-type", format!("unknown translation type {type_attr:?}, expected one of {}", TRANSLATION_TYPES.join(", ")))),
+                            _ => (),
+                        }
+                    },
+                    b"numerusform" => {
+                        if let Some(message) = &mut message {
+                            message.numerus_forms += 1;
+                            message.message.translations.push(String::new());
+                            message.message.spans.push(vec![]);
+                        }
+                    },
+                    _ => (),
+                }
+                if matches!(event, Event::Start(_)) {
+                    path.push(name);
+                }
+                if matches!(event, Event::Empty(_)) && element.name().as_ref() == b"message" {
+                    if let Some(message) = message.take() {
+                        message.finish(&mut findings, &mut messages, &mut suppressions);
+                    }
+                }
+            },
+            Event::Text(ref text) => {
+                let text = text.unescape().map(|text| text.into_owned()).unwrap_or_else(|_| String::from_utf8_lossy(text).into_owned());
+                let Some(message) = &mut message else {
+                    if path.len() == 3 && path[1] == b"context" && path[2] == b"name" {
+                        context_name.push_str(&text);
+                    }
+                    continue;
+                };
+                match path.last().map(Vec::as_slice) {
+                    Some(b"source") => message.message.source.push_str(&text),
+                    Some(b"comment") => message.message.disambiguation.get_or_insert_default().push_str(&text),
+                    Some(b"translatorcomment" | b"extracomment") => {
+                        message.comments.push_str(&text);
+                        message.comments.push('\n');
+                    },
+                    Some(b"translation") => {
+                        message.has_plain_text |= !text.trim().is_empty();
+                        message.translation.push_str(&text);
+                        message.translation_spans.push(span);
+                    },
+                    Some(b"numerusform") => {
+                        if let (Some(form), Some(spans)) = (message.message.translations.last_mut(), message.message.spans.last_mut()) {
+                            form.push_str(&text);
+                            spans.push(span);
+                        }
+                    },
+                    _ => (),
+                }
+            },
+            Event::CData(ref text) => {
+                let Some(message) = &mut message else {
+                    continue;
+                };
+                let text = String::from_utf8_lossy(text);
+                // Without `<![CDATA[` and `]]>`.
+                let span = span.start + 9..span.end - 3;
+                match path.last().map(Vec::as_slice) {
+                    Some(b"source") => message.message.source.push_str(&text),
+                    Some(b"translation") => {
+                        message.has_plain_text = true;
+                        message.translation.push_str(&text);
+                        message.translation_spans.push(span);
+                    },
+                    Some(b"numerusform") => {
+                        if let (Some(form), Some(spans)) = (message.message.translations.last_mut(), message.message.spans.last_mut()) {
+                            form.push_str(&text);
+                            spans.push(span);
+                        }
+                    },
+                    _ => (),
+                }
+            },
+            Event::End(ref element) => {
+                path.pop();
+                if element.name().as_ref() == b"message" {
+                    if let Some(message) = message.take() {
+                        message.finish(&mut findings, &mut messages, &mut suppressions);
+                    }
+                }
+            },
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    if findings.is_empty() {
+        if let Err(e) = Ts::load_from_str(content) {
+            findings.push(ValidationFinding::new(0, "invalid-structure", e.to_string()));
+        }
+    }
+    findings.extend(check_messages(&messages, &context));
+    findings.extend(check_duplicates(&messages));
+    findings.sort_by_key(|finding| finding.line);
+    FileReport { findings, context, messages, suppressions, ts_attributes: Some(ts_attributes) }
+}
+
+/// Whether two language codes are the same language, `zh-CN` and `zh_CN` are, and so are `en` and `en_US` since many
+/// projects leave the region out in one of the places.
+fn is_same_language(lhs: &str, rhs: &str) -> bool {
+    let (lhs, rhs) = (lhs.replace('-', "_"), rhs.replace('-', "_"));
+    lhs == rhs || lhs.split_once('_').is_some_and(|(language, _)| language == rhs) || rhs.split_once('_').is_some_and(|(language, _)| language == lhs)
+}
+
+/// Check the `language` and `sourcelanguage` attributes of `<TS>` against the languages implied by the path of the file.
+fn check_ts_languages(attributes: &TsAttributes, implied: &ImpliedLanguages) -> Vec<ValidationFinding> {
+    let mut findings = vec![];
+    let checks = [
+        ("language", &attributes.language, Some(&implied.language)),
+        ("sourcelanguage", &attributes.source_language, implied.source_language.as_ref()),
+    ];
+    for (name, actual, expected) in checks {
+        if let (Some(actual), Some(expected)) = (actual, expected) {
+            if !is_same_language(actual, expected) {
+                findings.push(ValidationFinding::new(attributes.line, "language-mismatch",
+                    format!("{name} attribute is {actual:?} but {} implies {expected:?}", implied.origin)));
+            }
+        }
+    }
+    findings
+}
+
+fn check_ts_message_plural(message: &TsMessageState) -> Option<ValidationFinding> {
+    let problem = match (message.message.plural, message.numerus_forms, message.has_plain_text) {
+        (true, 0, _) => "message with numerus=\"yes\" has no <numerusform>",
+        (true, _, true) => "message with numerus=\"yes\" has translation text outside <numerusform>",
+        (false, 1.., _) => "message without numerus=\"yes\" has <numerusform>",
+        (true, _, false) if !message.unfinished && !message.inactive && message.message.translations.iter().any(|form| form.trim().is_empty()) => {
+            return Some(ValidationFinding::new(message.message.line, "empty-plural-form", "finished message with numerus=\"yes\" has an empty <numerusform>".to_string()));
+        },
+        _ => return None,
+    };
+    Some(ValidationFinding::new(message.message.line, "broken-plural", problem.to_string()))
+}
+
+/// Keywords of a PO entry seen so far, with values still escaped.
+#[derive(Default)]
+struct PoEntryState {
+    line: usize,
+    msgctxt: Option<String>,
+    msgid: Option<String>,
+    msgid_plural: Option<String>,
+    msgstr: Option<String>,
+    /// `msgstr[N]` as `(N, value)`.
+    msgstr_plural: Vec<(usize, String)>,
+    /// Byte ranges of the strings of `msgstr` or each `msgstr[N]` in the file.
+    msgstr_spans: Vec<Vec<Range<usize>>>,
+}
+
+impl PoEntryState {
+    fn is_header(&self) -> bool {
+        self.msgid.as_deref() == Some("")
+    }
+
+    /// The value of the given keyword, which continues with the following string lines.
+    fn value_mut(&mut self, keyword: &str) -> Option<&mut String> {
+        match keyword {
+            "msgctxt" => self.msgctxt.as_mut(),
+            "msgid" => self.msgid.as_mut(),
+            "msgid_plural" => self.msgid_plural.as_mut(),
+            "msgstr" => self.msgstr.as_mut(),
+            keyword if keyword.starts_with("msgstr[") => self.msgstr_plural.last_mut().map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    fn to_scanned_message(&self) -> ScannedMessage {
+        ScannedMessage {
+            line: self.line,
+            context: unescape_po_string(self.msgctxt.as_deref().unwrap_or_default()),
+            source: unescape_po_string(self.msgid.as_deref().unwrap_or_default()),
+            disambiguation: None,
+            source_plural: self.msgid_plural.as_deref().map(unescape_po_string),
+            plural: self.msgid_plural.is_some(),
+            translations: match &self.msgstr {
+                Some(msgstr) => vec![unescape_po_string(msgstr)],
+                None => self.msgstr_plural.iter().map(|(_, value)| unescape_po_string(value)).collect(),
+            },
+            spans: self.msgstr_spans.clone(),
+        }
+    }
+}
+
+/// Value of a field in the (still escaped) `msgstr` of the header entry, `None` if missing or empty.
+fn po_header_field(header: &str, name: &str) -> Option<String> {
+    unescape_po_string(header).lines()
+        .find_map(|field| field.strip_prefix(name)?.strip_prefix(':'))
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Check the structure of a GNU Gettext file: the syntax of each line, that each entry has a `msgid` and a `msgstr`,
+/// that plural entries use `msgstr[N]` (and only them), and the `Language` header of translations. Then check
+/// translations against their source, see [`MESSAGE_CHECKS`].
+fn validate_po(content: &str, is_template: bool) -> FileReport {
+    let keyword_regex = Regex::new(r#"^(msgctxt|msgid|msgid_plural|msgstr|msgstr\[(\d+)\])\s+"(.*)"$"#).unwrap();
+    let string_regex = Regex::new(r#"^"(.*)"$"#).unwrap();
+    let mut findings = vec![];
+    let mut entry = PoEntryState::default();
+    let mut entries = vec![];
+    let mut last_keyword: Option<String> = None;
+    // Translator comments and extracted comments of the next entry.
+    let mut comments = String::new();
+    let mut suppressions = BTreeMap::new();
+    let mut offset = 0;
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        let line_number = index + 1;
+        let line_start = offset + line.len() - line.trim_start().len();
+        offset += line.len();
+        let line = line.trim();
+        if line.starts_with("#~") {
+            comments.clear();
+        } else if let Some(comment) = line.strip_prefix("#.").or(line.strip_prefix('#').filter(|comment| comment.is_empty() || comment.starts_with(' '))) {
+            comments.push_str(comment);
+            comments.push('\n');
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let span = |captures: &regex::Captures, group: usize| captures.get(group).map_or(0..0, |value| line_start + value.start()..line_start + value.end());
+        if let Some(captures) = string_regex.captures(line) {
+            match &last_keyword {
+                Some(keyword) => {
+                    if let Some(value) = entry.value_mut(keyword) {
+                        value.push_str(&captures[1]);
+                    }
+                    if let Some(spans) = entry.msgstr_spans.last_mut().filter(|_| keyword.starts_with("msgstr")) {
+                        spans.push(span(&captures, 1));
+                    }
+                },
+                None => findings.push(ValidationFinding::new(line_number, "malformed-po", "string without a keyword".to_string())),
+            }
+            continue;
+        }
+        let Some(captures) = keyword_regex.captures(line) else {
+            findings.push(ValidationFinding::new(line_number, "malformed-po", format!("unexpected line {line:?}")));
+            continue;
+        };
+        let keyword = captures[1].to_string();
+        let value = captures[3].to_string();
+        let starts_entry = keyword == "msgctxt" || (keyword == "msgid" && last_keyword.as_deref() != Some("msgctxt"));
+        if starts_entry && last_keyword.is_some() {
+            entries.push(std::mem::take(&mut entry));
+        }
+        if starts_entry {
+            entry.line = line_number;
+            let rules = suppressed_rules(&std::mem::take(&mut comments));
+            if !rules.is_empty() {
+                suppressions.insert(line_number, rules);
+            }
+        }
+        if keyword.starts_with("msgstr") {
+            entry.msgstr_spans.push(vec![span(&captures, 3)]);
+        }
+        match keyword.as_str() {
+            "msgid" => entry.msgid = Some(value),
+            "msgid_plural" => entry.msgid_plural = Some(value),
+            "msgstr" => entry.msgstr = Some(value),
+            "msgctxt" => entry.msgctxt = Some(value),
+            _ => entry.msgstr_plural.push((captures[2].parse().unwrap_or(usize::MAX), value)),
+        }
+        last_keyword = Some(keyword);
+    }
+    if last_keyword.is_some() {
+        entries.push(entry);
+    }
+
+    for entry in &entries {
+        let problem = if entry.msgid.is_none() {
+            Some(("malformed-po", "entry has no msgid"))
+        } else if entry.msgstr.is_none() && entry.msgstr_plural.is_empty() {
+            Some(("malformed-po", "entry has no msgstr"))
+        } else if entry.msgid_plural.is_some() && entry.msgstr.is_some() {
+            Some(("broken-plural", "entry with msgid_plural uses msgstr instead of msgstr[N]"))
+        } else if entry.msgid_plural.is_none() && !entry.msgstr_plural.is_empty() {
+            Some(("broken-plural", "entry without msgid_plural uses msgstr[N]"))
+        } else if entry.msgstr_plural.iter().enumerate().any(|(index, (msgstr_index, _))| index != *msgstr_index) {
+            Some(("broken-plural", "msgstr[N] indexes are not 0, 1, 2..."))
+        } else {
+            None
+        };
+        if let Some((rule, message)) = problem {
+            findings.push(ValidationFinding::new(entry.line, rule, message.to_string()));
+        }
+    }
+    let header = entries.iter().find(|entry| entry.is_header());
+    let header_field = |name: &str| header.and_then(|header| po_header_field(header.msgstr.as_deref()?, name));
+    let context = FileContext { language: header_field("Language").unwrap_or_default() };
+    if !is_template && context.language.is_empty() {
+        findings.push(ValidationFinding::new(header.map_or(0, |header| header.line), "missing-language", "the header has no Language field".to_string()));
+    }
+    if !is_template {
+        findings.extend(check_po_plural_forms(&entries, header_field("Plural-Forms"), &context));
+    }
+    if findings.iter().all(|finding| finding.rule != "malformed-po") {
+        if let Err(e) = Po::load_from_str(content) {
+            findings.push(ValidationFinding::new(0, "malformed-po", e.to_string()));
+        }
+    }
+    let messages: Vec<ScannedMessage> = entries.iter()
+        .filter(|entry| entry.msgid.is_some() && !entry.is_header())
+        .map(PoEntryState::to_scanned_message)
+        .collect();
+    findings.extend(check_messages(&messages, &context));
+    findings.extend(check_duplicates(&messages));
+    findings.sort_by_key(|finding| finding.line);
+    FileReport { findings, context, messages, suppressions, ts_attributes: None }
+}
+
+/// Check that the `Plural-Forms` header has the number of plural forms of the language, and that each plural entry has
+/// that many `msgstr[N]`. Entries with other plural problems are left to the structural checks.
+fn check_po_plural_forms(entries: &[PoEntryState], plural_forms: Option<String>, context: &FileContext) -> Vec<ValidationFinding> {
+    let mut findings = vec![];
+    let header_line = entries.iter().find(|entry| entry.is_header()).map_or(0, |header| header.line);
+    let plural_entries: Vec<&PoEntryState> = entries.iter().filter(|entry| entry.msgid_plural.is_some() && entry.msgstr.is_none()).collect();
+    let expected = lookup_language_team(&context.language).map(|team| team.nplurals());
+    let nplurals = match plural_forms {
+        Some(plural_forms) => match parse_nplurals(&plural_forms).filter(|nplurals| *nplurals > 0) {
+            Some(nplurals) => {
+                if let Some(expected) = expected.filter(|expected| *expected != nplurals) {
+                    findings.push(ValidationFinding::new(header_line, "plural-forms-mismatch",
+                        format!("Plural-Forms has nplurals={nplurals} but {} has {expected} plural form(s)", context.language)));
+                }
+                Some(nplurals)
+            },
+            None => {
+                findings.push(ValidationFinding::new(header_line, "invalid-plural-forms", format!("Plural-Forms {plural_forms:?} has no valid nplurals")));
+                None
+            },
+        },
+        None if !plural_entries.is_empty() => {
+            findings.push(ValidationFinding::new(header_line, "missing-plural-forms", "the header has no Plural-Forms field but the file has plural entries".to_string()));
+            expected
+        },
+        None => None,
+    };
+    let Some(nplurals) = nplurals else {
+        return findings;
+    };
+    for entry in plural_entries.iter().filter(|entry| !entry.msgstr_plural.is_empty() && entry.msgstr_plural.len() != nplurals) {
+        findings.push(ValidationFinding::new(entry.line, "plural-count-mismatch",
+            format!("entry has {} msgstr[N] but nplurals is {nplurals}", entry.msgstr_plural.len())));
+    }
+    findings
+}
+
+/// Check that the charset in the `Content-Type` header of a PO file matches its content, and that the content is valid in that
+/// charset. Besides UTF-8 and ASCII, charsets can only be checked for content that is actually UTF-8. Valid UTF-8 is also
+/// checked for mojibake, e.g. UTF-8 text that was once decoded as Latin-1 (`Ã©` instead of `é`).
+fn validate_po_encoding(bytes: &[u8], is_template: bool) -> Vec<ValidationFinding> {
+    let mut findings = vec![];
+    let content = String::from_utf8_lossy(bytes);
+    let charset_regex = Regex::new(r"Content-Type:[^\n\x22]*charset=([A-Za-z0-9_.:-]+)").unwrap();
+    let charset = charset_regex.captures(&content).map(|captures| {
+        (captures[1].to_string(), line_at(&content, captures.get(0).unwrap().start() as u64))
+    });
+    let utf8 = std::str::from_utf8(bytes);
+    match (&charset, &utf8) {
+        (None, _) if !is_template => findings.push(ValidationFinding::new(0, "charset-mismatch", "the header has no Content-Type charset".to_string())),
+        (Some((charset, _)), _) if charset == "CHARSET" && is_template => (),
+        (Some((charset, line)), _) if charset == "CHARSET" => {
+            findings.push(ValidationFinding::new(*line, "charset-mismatch", "the Content-Type charset is still the CHARSET placeholder".to_string()));
+        },
+        (Some((charset, _)), Err(e)) if matches!(charset.to_ascii_lowercase().as_str(), "utf-8" | "utf8") => {
+            findings.push(ValidationFinding::new(line_at(&content, e.valid_up_to() as u64), "invalid-encoding",
+                format!("content is not valid {charset} as declared by the Content-Type header")));
+        },
+        (Some((charset, _)), _) if matches!(charset.to_ascii_lowercase().as_str(), "ascii" | "us-ascii") => {
+            if let Some(position) = bytes.iter().position(|byte| !byte.is_ascii()) {
+                findings.push(ValidationFinding::new(line_at(&content, position as u64), "invalid-encoding",
+                    format!("content is not valid {charset} as declared by the Content-Type header")));
+            }
+        },
+        (Some((charset, line)), Ok(text)) if !matches!(charset.to_ascii_lowercase().as_str(), "utf-8" | "utf8") && !text.is_ascii() => {
+            findings.push(ValidationFinding::new(*line, "charset-mismatch", format!("the Content-Type charset is {charset} but the content is UTF-8")));
+        },
+        _ => (),
+    }
+    if utf8.is_ok() {
+        let mojibake_regex = Regex::new(r"[ÃÂ][\u{80}-\u{BF}]|â€[\u{80}-\u{BF}™œž”“˜]|\u{FFFD}").unwrap();
+        for (index, line) in content.lines().enumerate().filter(|(_, line)| !line.starts_with('#')) {
+            if let Some(mojibake) = mojibake_regex.find(line) {
+                findings.push(ValidationFinding::new(index + 1, "mojibake", format!("{:?} looks like mojibake, the text may have been decoded with a wrong charset", mojibake.as_str())));
+            }
+        }
+    }
+    findings
+}
+
+/// .tx/config of the projects of the checked files by project root, `None` if it can't be loaded.
+type TxConfigs = BTreeMap<PathBuf, Option<ProjectTxConfig>>;
+
+/// A loaded .tx/config, with the file filter of each resource section compiled to a regex capturing the language.
+struct ProjectTxConfig {
+    tx_config: TxConfig,
+    file_filters: Vec<Option<Regex>>,
+}
+
+impl ProjectTxConfig {
+    fn new(tx_config: TxConfig) -> Self {
+        let file_filters = tx_config.resource_sections.iter()
+            .map(|section| regex::escape(section.file_filter.trim_start_matches("./")).replace("<lang>", "([^/]+)"))
+            .map(|file_filter| Regex::new(&format!("^{file_filter}$")).ok())
+            .collect();
+        Self { tx_config, file_filters }
+    }
+}
+
+/// Languages implied by the path of a file: by the nearest .tx/config, if a resource there has the file as its source file
+/// or matches it with its file filter, otherwise by a language code at the end of the file name, e.g. `dde-dock_zh_CN.ts`.
+fn implied_languages(file: &Path, tx_configs: &mut TxConfigs) -> Option<ImpliedLanguages> {
+    if let Some(project_root) = file.ancestors().skip(1).find(|dir| dir.join(".tx").join("config").is_file()) {
+        let tx_config = tx_configs.entry(project_root.to_path_buf()).or_insert_with(|| match try_load_tx_config_file(project_root) {
+            Ok((_, tx_config)) => Some(ProjectTxConfig::new(tx_config)),
+            Err(e) => {
+                eprintln!("Warning: Fail to load .tx/config of {project_root:?} because: {e}");
+                None
+            },
+        });
+        let relative_path = file.strip_prefix(project_root).unwrap_or(file).to_string_lossy();
+        let sections = tx_config.iter().flat_map(|project| project.tx_config.resource_sections.iter().zip(&project.file_filters));
+        for (section, file_filter) in sections {
+            let origin = format!("[{}] of .tx/config", section.resource_full_slug);
+            if relative_path == section.source_file.trim_start_matches("./") {
+                return Some(ImpliedLanguages { language: section.source_lang.clone(), source_language: Some(section.source_lang.clone()), origin });
+            }
+            let language = file_filter.as_ref()
+                .and_then(|file_filter| file_filter.captures(&relative_path).map(|captures| captures[1].to_string()));
+            if let Some(language) = language {
+                return Some(ImpliedLanguages { language, source_language: Some(section.source_lang.clone()), origin });
+            }
+        }
+    }
+    match find_language_codes_in_filename(file.file_name()?.to_str()?).as_slice() {
+        [language] => Some(ImpliedLanguages { language: language.clone(), source_language: None, origin: "the file name".to_string() }),
+        _ => None,
+    }
+}
+
+/// Check the given file, findings are sorted by line.
+fn validate_file(file: &Path) -> Result<FileReport, CmdError> {
+    let bytes = fs::read(file).map_err(|e| CmdError::ReadFile(file.to_path_buf(), e))?;
+    // Encoding problems are reported by the encoding checks, the other checks work on what can be decoded.
+    let content = String::from_utf8_lossy(&bytes);
+    let mut report = match I18nFileKind::from_ext_hint(file) {
+        Ok(I18nFileKind::Linguist) => {
+            let mut report = validate_ts(&content);
+            if let Err(e) = std::str::from_utf8(&bytes) {
+                report.findings.insert(0, ValidationFinding::new(line_at(&content, e.valid_up_to() as u64), "invalid-encoding", "content is not valid UTF-8".to_string()));
+            }
+            report
+        },
+        Ok(I18nFileKind::Gettext) => {
+            let is_template = file.extension().is_some_and(|ext| ext == "pot");
+            let mut report = validate_po(&content, is_template);
+            report.findings.extend(validate_po_encoding(&bytes, is_template));
+            report.findings.sort_by_key(|finding| finding.line);
+            report
+        },
+        Err(_) => FileReport::default(),
+    };
+    for finding in &mut report.findings {
+        finding.file = file.to_path_buf();
+    }
+    Ok(report)
+}
+
+/// Raw tokens of the text of a translation in the file with whether each one is whitespace, where escape sequences of PO
+/// files (e.g. `\n`) and entities of Qt Linguist files (e.g. `&amp;`) are kept whole.
+fn raw_tokens(content: &str, spans: &[Range<usize>], kind: &I18nFileKind) -> Vec<(Range<usize>, bool)> {
+    let mut tokens = vec![];
+    for span in spans {
+        let mut chars = content[span.clone()].char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            let end = match (kind, c) {
+                (I18nFileKind::Gettext, '\\') => chars.next().map_or(start + 1, |(index, c)| index + c.len_utf8()),
+                (I18nFileKind::Linguist, '&') => content[span.start + start..span.end].find(';').map_or(start + 1, |index| start + index + 1),
+                _ => start + c.len_utf8(),
+            };
+            while chars.next_if(|(index, _)| *index < end).is_some() {}
+            let token = &content[span.start + start..span.start + end];
+            let whitespace = match kind {
+                I18nFileKind::Gettext => matches!(token, " " | "\\n" | "\\t" | "\\r"),
+                _ => token.chars().all(char::is_whitespace),
+            };
+            tokens.push((span.start + start..span.start + end, whitespace));
+        }
+    }
+    tokens
+}
+
+/// Edits that make the leading and trailing whitespace of a translation the same as the source, for the side(s) that
+/// [`check_surrounding_whitespace`] reports. Translations of only whitespace are left alone.
+fn fix_surrounding_whitespace(content: &str, message: &ScannedMessage, form: usize, kind: &I18nFileKind) -> Vec<(Range<usize>, String)> {
+    let (Some(translation), Some(spans)) = (message.translations.get(form), message.spans.get(form)) else {
+        return vec![];
+    };
+    let (leading, trailing) = surrounding_whitespace_mismatch(&message.source, translation);
+    let tokens = raw_tokens(content, spans, kind);
+    let (Some(first), Some(last)) = (tokens.iter().position(|(_, ws)| !ws), tokens.iter().rposition(|(_, ws)| !ws)) else {
+        return vec![];
+    };
+    let escape = |text: &str| match kind {
+        I18nFileKind::Gettext => escape_po_string(text),
+        _ => text.to_string(),
+    };
+    let mut edits = vec![];
+    if leading {
+        let source_leading = &message.source[..message.source.len() - message.source.trim_start().len()];
+        edits.extend(tokens[..first].iter().map(|(range, _)| (range.clone(), String::new())));
+        edits.push((tokens[first].0.start..tokens[first].0.start, escape(source_leading)));
+    }
+    if trailing {
+        let source_trailing = &message.source[message.source.trim_end().len()..];
+        edits.push((tokens[last].0.end..tokens[last].0.end, escape(source_trailing)));
+        edits.extend(tokens[last + 1..].iter().map(|(range, _)| (range.clone(), String::new())));
+    }
+    edits
+}
+
+/// Fix problems that have only one sensible fix: leading and trailing whitespace of translations that does not match the
+/// source (`whitespace-mismatch`), and `language` and `sourcelanguage` attributes of Qt Linguist files that do not match
+/// the path of the file (`language-mismatch`). The file is edited in place so that everything else stays the same, files
+/// that are not valid UTF-8 or fail the structural checks are left alone. Returns the number of fixed problems.
+fn fix_file(file: &Path, tx_configs: &mut TxConfigs) -> Result<usize, CmdError> {
+    let Ok(kind) = I18nFileKind::from_ext_hint(file) else {
+        return Ok(0);
+    };
+    let Ok(mut content) = fs::read_to_string(file) else {
+        return Ok(0);
+    };
+    let report = match kind {
+        I18nFileKind::Linguist => validate_ts(&content),
+        I18nFileKind::Gettext => validate_po(&content, file.extension().is_some_and(|ext| ext == "pot")),
+    };
+    if report.findings.iter().any(|finding| matches!(finding.rule, "malformed-xml" | "malformed-po")) {
+        return Ok(0);
+    }
+    let mut fixed = 0;
+    let mut edits = vec![];
+    let suppressed = |line: usize| is_suppressed(&report.suppressions, &ValidationFinding::new(line, "whitespace-mismatch", String::new()));
+    for message in report.messages.iter().filter(|message| !suppressed(message.line)) {
+        for form in 0..message.translations.len() {
+            let form_edits = fix_surrounding_whitespace(&content, message, form, &kind);
+            fixed += usize::from(!form_edits.is_empty());
+            edits.extend(form_edits);
+        }
+    }
+    if let (Some(attributes), Some(implied)) = (&report.ts_attributes, implied_languages(file, tx_configs)) {
+        let attributes_to_fix = [
+            (&attributes.language, &attributes.language_span, Some(&implied.language)),
+            (&attributes.source_language, &attributes.source_language_span, implied.source_language.as_ref()),
+        ];
+        for (actual, span, expected) in attributes_to_fix {
+            if let (Some(actual), Some(span), Some(expected)) = (actual, span, expected) {
+                if !is_same_language(actual, expected) {
+                    edits.push((span.clone(), expected.clone()));
+                    fixed += 1;
+                }
+            }
+        }
+    }
+    if edits.is_empty() {
+        return Ok(0);
+    }
+    // Apply from the end, so that earlier ranges stay valid. Removals come before insertions at the same position.
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse((range.start, range.end)));
+    for (range, text) in edits {
+        content.replace_range(range, &text);
+    }
+    write_file_atomically(file, &content).map_err(|e| CmdError::WriteFile(file.to_path_buf(), e))?;
+    Ok(fixed)
+}
+
+/// Print findings as `<file>:<line>: <message> [<rule>]` lines, or as a JSON array.
+pub fn print_findings(findings: &[ValidationFinding], format: ValidateFormat) -> Result<(), serde_json::Error> {
+    match format {
+        ValidateFormat::Text => {
+            for finding in findings {
+                let severity = match finding.severity {
+                    Severity::Error => "",
+                    Severity::Warning => "warning: ",
+                    Severity::Info => "info: ",
+                };
+                println!("{}:{}: {severity}{} [{}]", finding.file.display(), finding.line, finding.message, finding.rule);
+            }
+        },
+        ValidateFormat::Json => println!("{}", serde_json::to_string_pretty(findings)?),
+    }
+    Ok(())
+}
+
+/// Number of findings that make the command fail.
+pub fn count_failures(findings: &[ValidationFinding], fail_on: FailOn) -> usize {
+    findings.iter().filter(|finding| fail_on.fails_on(finding.severity)).count()
+}
+
+/// Number of findings of each severity, e.g. `1 error(s), 2 warning(s)`, empty if there is no finding.
+pub fn severity_counts(findings: &[ValidationFinding]) -> String {
+    let count = |severity: Severity| findings.iter().filter(|finding| finding.severity == severity).count();
+    [(Severity::Error, "error(s)"), (Severity::Warning, "warning(s)"), (Severity::Info, "info(s)")].into_iter()
+        .map(|(severity, name)| (count(severity), name))
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, name)| format!("{count} {name}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Check Qt Linguist and GNU Gettext files for structural problems. Directories are scanned recursively, where .ts files
+/// that are not Qt Linguist files (e.g. TypeScript sources) are skipped.
+pub fn subcmd_validate(paths: &[PathBuf], format: ValidateFormat, options: &ValidateOptions) -> Result<(), CmdError> {
+    if let Some(path) = paths.iter().find(|path| !path.exists()) {
+        return Err(CmdError::PathNotFound(path.clone()));
+    }
+    let glossary = match &options.glossary {
+        Some(glossary) => Some(Glossary::load(glossary).map_err(|e| CmdError::LoadGlossary(glossary.to_path_buf(), e))?),
+        None => None,
+    };
+    let files: Vec<PathBuf> = crate::extract::collect_source_files(paths, TRANSLATION_FILE_EXTENSIONS).into_iter()
+        .filter(|file| paths.contains(file) || file.extension().is_none_or(|ext| ext != "ts") || I18nFileKind::sniff_linguist_file(file))
+        .collect();
+    let mut reports = vec![];
+    let mut tx_configs = TxConfigs::new();
+    if options.fix {
+        for file in &files {
+            let fixed = fix_file(file, &mut tx_configs)?;
+            if fixed > 0 && matches!(format, ValidateFormat::Text) {
+                println!("Fixed {fixed} problem(s) in {file:?}");
+            }
+        }
+    }
+    for (file, result) in files.iter().zip(parallel::map(&files, |file| validate_file(file))) {
+        let mut report = result?;
+        if let Some(ts_attributes) = &report.ts_attributes {
+            if let Some(implied) = implied_languages(file, &mut tx_configs) {
+                report.findings.extend(check_ts_languages(ts_attributes, &implied).into_iter().map(|finding| ValidationFinding { file: file.clone(), ..finding }));
+            }
+        }
+        if let Some(glossary) = &glossary {
+            let findings = check_glossary(glossary, &report);
+            report.findings.extend(findings.into_iter().map(|finding| ValidationFinding { file: file.clone(), ..finding }));
+        }
+        reports.push((file.as_path(), report));
+    }
+    for (index, mut finding) in check_inconsistent_translations(&reports) {
+        let (file, report) = &mut reports[index];
+        finding.file = file.to_path_buf();
+        report.findings.push(finding);
+    }
+    let mut findings = vec![];
+    let mut suppressed = 0;
+    for (_, mut report) in reports {
+        let count = report.findings.len();
+        report.findings.retain(|finding| !is_suppressed(&report.suppressions, finding));
+        suppressed += count - report.findings.len();
+        report.findings.sort_by_key(|finding| finding.line);
+        findings.extend(report.findings);
+    }
+    for finding in &mut findings {
+        if let Some(severity) = options.severities.get(finding.rule) {
+            finding.severity = *severity;
+        }
+    }
+
+    print_findings(&findings, format).map_err(CmdError::Serialize)?;
+    match count_failures(&findings, options.fail_on) {
+        0 => {
+            if matches!(format, ValidateFormat::Text) {
+                let counts = severity_counts(&findings);
+                match counts.is_empty() {
+                    true => println!("No problem found in {} file(s).", files.len()),
+                    false => println!("Found {counts} in {} file(s), none of them fails the check.", files.len()),
+                }
+                if suppressed > 0 {
+                    println!("{suppressed} finding(s) suppressed by `{SUPPRESSION_MARKER}` comments.");
+                }
+            }
+            Ok(())
+        },
+        count => Err(CmdError::Findings(count)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules_and_lines(findings: &[ValidationFinding]) -> Vec<(&str, usize)> {
+        findings.iter().map(|finding| (finding.rule, finding.line)).collect()
+    }
+
+    #[test]
+    fn tst_validate_ts() {
+        let content = r#"<?xml version="1.0" encoding="utf-8"?>
+<!DOCTYPE TS>
+<TS version="2.1">
+<context>
+    <name>Demo</name>
+    <message>
+        <source>Hello</source>
+        <translation type="finished">你好</translation>
+    </message>
+    <message numerus="yes">
+        <source>%n file(s)</source>
+        <translation>%n 个文件</translation>
+    </message>
+    <message numerus="yes">
+        <source>%n folder(s)</source>
+        <translation>
+            <numerusform>%n 个文件夹</numerusform>
+        </translation>
+    </message>
+    <message>
+        <source>Bye</source>
+        <translation><numerusform>再见</numerusform></translation>
+    </message>
+</context>
+</TS>
+"#;
+        assert_eq!(rules_and_lines(&validate_ts(content).findings), [
+            ("missing-language", 3),
+            ("invalid-translation-type", 8),
+            ("broken-plural", 10),
+            ("broken-plural", 20),
+        ]);
+
+        let malformed = "<TS version=\"2.1\" language=\"zh_CN\">\n<context>\n<name>Demo</name>\n</contxt>\n</TS>\n";
+        assert_eq!(rules_and_lines(&validate_ts(malformed).findings), [("malformed-xml", 4)]);
+
+        let numerus = |translation: &str| format!("<TS version=\"2.1\" language=\"fr\">\n<context>\n<name>Demo</name>\n\
+            <message numerus=\"yes\">\n<source>%n file(s)</source>\n{translation}\n</message>\n</context>\n</TS>\n");
+        let findings = validate_ts(&numerus("<translation><numerusform>%n fichier</numerusform><numerusform></numerusform></translation>")).findings;
+        assert_eq!(rules_and_lines(&findings), [("empty-plural-form", 4)]);
+        assert_eq!(findings[0].message, "finished message with numerus=\"yes\" has an empty <numerusform>");
+        assert!(validate_ts(&numerus("<translation type=\"unfinished\"><numerusform>%n fichier</numerusform><numerusform></numerusform></translation>")).findings.is_empty());
+    }
+
+    #[test]
+    fn tst_check_placeholders() {
+        let ts = r#"<TS version="2.1" language="zh_CN">
+<context>
+    <name>Demo</name>
+    <message>
+        <source>Copy %1 to %2</source>
+        <translation>将 %2 复制到 %L1</translation>
+    </message>
+    <message>
+        <source>Copy %1 to %2</source>
+        <comment>menu</comment>
+        <translation>复制 %1 到 {0}</translation>
+    </message>
+    <message numerus="yes">
+        <source>%n file(s) in ${dir}</source>
+        <translation>
+            <numerusform>${dir} 中有 %Ln 个文件</numerusform>
+            <numerusform>${folder} 中有 %n 个文件</numerusform>
+        </translation>
+    </message>
+    <message>
+        <source>100%% done, %1</source>
+        <translation type="obsolete">100%</translation>
+    </message>
+</context>
+</TS>
+"#;
+        let findings = validate_ts(ts).findings;
+        assert_eq!(rules_and_lines(&findings), [("placeholder-mismatch", 8), ("placeholder-mismatch", 13)]);
+        assert_eq!(findings[0].message, "placeholders of the translation do not match the source: missing %2; unexpected {0}");
+        assert_eq!(findings[1].message, "plural form 1: placeholders of the translation do not match the source: unexpected ${folder}");
+
+        let po = r#"msgid ""
+msgstr ""
+"Language: zh_CN\n"
+"Plural-Forms: nplurals=1; plural=0;\n"
+
+msgid "%1 of "
+"%2"
+msgstr "%2 中的第 %1 个"
+
+msgid "One file"
+msgid_plural "%n files"
+msgstr[0] "%n 个文件"
+
+msgid "Hello {0}"
+msgstr "你好"
+"#;
+        assert_eq!(rules_and_lines(&validate_po(po, false).findings), [("placeholder-mismatch", 14)]);
+    }
+
+    #[test]
+    fn tst_check_html_tags() {
+        let message = |source: &str| ScannedMessage { source: source.to_string(), ..Default::default() };
+        let check_html_tags = |message: &ScannedMessage, translation: &str| check_html_tags(message, translation, &FileContext::default());
+        assert_eq!(check_html_tags(&message("<b>Warning:</b> %1<br/>Continue?"), "%1<br>是否继续？<b>警告：</b>"), None);
+        assert_eq!(check_html_tags(&message("<a href=\"https://www.deepin.org\">deepin</a>"), "<a href='https://www.deepin.org'>深度</a>"), None);
+        assert_eq!(check_html_tags(&message("<b>Warning:</b> disk full"), "<b>警告：磁盘已满").as_deref(),
+            Some("tags of the translation do not match the source: missing </b>"));
+        assert_eq!(check_html_tags(&message("<b>Bold</b> and <i>italic</i>"), "<b>粗体</i>和<i>斜体</b>").as_deref(),
+            Some("tags of the translation are not balanced"));
+        assert_eq!(check_html_tags(&message("a < b > c"), "a < b > c"), None);
+        assert_eq!(check_html_tags(&message("Plain"), "<p>纯文本</p>").as_deref(),
+            Some("tags of the translation do not match the source: unexpected </p>, <p>"));
+    }
+
+    #[test]
+    fn tst_check_accelerators() {
+        let message = |source: &str| ScannedMessage { source: source.to_string(), ..Default::default() };
+        let check = |source: &str, translation: &str, language: &str| {
+            check_accelerators(&message(source), translation, &FileContext { language: language.to_string() })
+        };
+        assert_eq!(check("&File", "&Datei", "de"), None);
+        assert_eq!(check("&File", "文件(&F)", "zh_CN"), None);
+        assert_eq!(check("Save &As...", "另存为(&a)...", "zh_TW"), None);
+        assert_eq!(check("Tom && Jerry", "汤姆和杰瑞", "zh_CN"), None);
+        assert_eq!(check("A&nbsp;B", "A&nbsp;B", "fr"), None);
+        assert_eq!(check("&File", "Datei", "de").as_deref(), Some("the source has accelerator &F but the translation has none"));
+        assert_eq!(check("&File", "&Da&tei", "de").as_deref(), Some("the translation has 2 accelerators instead of one"));
+        assert_eq!(check("&File", "&文件", "zh_CN").as_deref(), Some("the accelerator should be written as (&F) in zh_CN translations"));
+        assert_eq!(check("&File", "文件(&W)", "ja").as_deref(), Some("the accelerator should be written as (&F) in ja translations"));
+    }
+
+    #[test]
+    fn tst_check_punctuation_and_whitespace() {
+        let message = |source: &str| ScannedMessage { source: source.to_string(), ..Default::default() };
+        let context = |language: &str| FileContext { language: language.to_string() };
+        assert_eq!(check_surrounding_whitespace(&message("Name: "), "名称：", &context("zh_CN")).as_deref(),
+            Some("trailing whitespace of the translation does not match the source"));
+        assert_eq!(check_surrounding_whitespace(&message(" %1\n"), " %1\n", &context("de")), None);
+        assert_eq!(check_surrounding_whitespace(&message("\tTab"), "Tab ", &context("de")).as_deref(),
+            Some("leading and trailing whitespace of the translation does not match the source"));
+
+        assert_eq!(check_terminal_punctuation(&message("Done."), "完成。", &context("zh_CN")), None);
+        assert_eq!(check_terminal_punctuation(&message("Loading..."), "加载中…", &context("zh_CN")), None);
+        assert_eq!(check_terminal_punctuation(&message("<b>Are you sure?</b>"), "<b>确定吗？</b>", &context("zh_TW")), None);
+        assert_eq!(check_terminal_punctuation(&message("Done."), "完成.", &context("zh_CN")).as_deref(),
+            Some("the source ends with \".\" but the translation does not end with \"。\""));
+        assert_eq!(check_terminal_punctuation(&message("Continue?"), "Continuer ?", &context("fr")), None);
+        assert_eq!(check_terminal_punctuation(&message("Continue?"), "Продолжить", &context("ru")).as_deref(),
+            Some("the source ends with \"?\" but the translation does not end with \"?\""));
+        assert_eq!(check_terminal_punctuation(&message("Continue?"), "متابعة؟", &context("ar")), None);
+        assert_eq!(check_terminal_punctuation(&message("Open"), "打开。", &context("zh_CN")), None);
+
+        assert_eq!(check_double_spaces(&message("Open file"), "Datei  öffnen", &context("de")).as_deref(), Some("the translation contains double spaces"));
+        assert_eq!(check_double_spaces(&message("Name:  %1"), "Name:  %1", &context("de")), None);
+    }
+
+    #[test]
+    fn tst_check_chinese_script() {
+        let message = ScannedMessage::default();
+        let context = |language: &str| FileContext { language: language.to_string() };
+        assert_eq!(check_chinese_script(&message, "開啟檔案", &context("zh_TW")), None);
+        assert_eq!(check_chinese_script(&message, "打开文件", &context("zh_CN")), None);
+        assert_eq!(check_chinese_script(&message, "皇后", &context("zh_HK")), None);
+        assert_eq!(check_chinese_script(&message, "Open file", &context("zh_TW")), None);
+        assert_eq!(check_chinese_script(&message, "打开文件", &context("ja")), None);
+        assert_eq!(check_chinese_script(&message, "開啟这个檔案", &context("zh_TW")).as_deref(),
+            Some("simplified Chinese characters \"这个\" in a traditional Chinese (zh_TW) translation"));
+        assert_eq!(check_chinese_script(&message, "打開文件", &context("zh-Hans")).as_deref(),
+            Some("traditional Chinese characters \"開\" in a simplified Chinese (zh-Hans) translation"));
+    }
+
+    #[test]
+    fn tst_check_duplicates() {
+        let ts = r#"<TS version="2.1" language="de">
+<context>
+    <name>Demo</name>
+    <message>
+        <source>Open</source>
+        <translation>Öffnen</translation>
+    </message>
+    <message>
+        <source>Open</source>
+        <comment>adjective</comment>
+        <translation>Offen</translation>
+    </message>
+    <message>
+        <source>Open</source>
+        <translation>Öffne</translation>
+    </message>
+</context>
+<context>
+    <name>Other</name>
+    <message>
+        <source>Open</source>
+        <translation>Öffnen</translation>
+    </message>
+</context>
+</TS>
+"#;
+        let findings = validate_ts(ts).findings;
+        assert_eq!(rules_and_lines(&findings), [("duplicate-message", 13)]);
+        assert_eq!(findings[0].message, "duplicate of the message at line 4, with a different translation");
+
+        let po = "msgid \"\"\nmsgstr \"\"\n\"Language: de\\n\"\n\nmsgctxt \"menu\"\nmsgid \"Open\"\nmsgstr \"Öffnen\"\n\n\
+            msgid \"Open\"\nmsgstr \"Öffnen\"\n\nmsgctxt \"menu\"\nmsgid \"Open\"\nmsgstr \"Öffnen\"\n";
+        let findings = validate_po(po, false).findings;
+        assert_eq!(rules_and_lines(&findings), [("duplicate-message", 12)]);
+        assert_eq!(findings[0].message, "duplicate of the message at line 5");
+    }
+
+    #[test]
+    fn tst_check_inconsistent_translations() {
+        let report = |language: &str, messages: &[(&str, &str)]| FileReport {
+            context: FileContext { language: language.to_string() },
+            messages: messages.iter().enumerate().map(|(index, (source, translation))| ScannedMessage {
+                line: index + 1,
+                source: source.to_string(),
+                translations: vec![translation.to_string()],
+                ..Default::default()
+            }).collect(),
+            ..Default::default()
+        };
+        let reports = vec![
+            (Path::new("a/zh_CN.ts"), report("zh_CN", &[("Cancel", "取消"), ("Open", "打开")])),
+            (Path::new("b/zh_CN.ts"), report("zh-CN", &[("Cancel", "取消"), ("Open", "")])),
+            (Path::new("c/zh_CN.ts"), report("zh_CN", &[("Open", "开启"), ("Cancel", "撤销")])),
+            (Path::new("c/zh_TW.ts"), report("zh_TW", &[("Open", "開啟"), ("Cancel", "取消")])),
+        ];
+        let findings = check_inconsistent_translations(&reports);
+        // "Open" is translated differently once each, one of them is reported.
+        assert_eq!(findings.iter().map(|(index, finding)| (*index, finding.line)).collect::<Vec<_>>(), [(2, 2), (0, 2)]);
+        assert_eq!(findings[0].1.message, "translated as \"撤销\" here, but as \"取消\" in 2 other place(s), e.g. a/zh_CN.ts:1");
+    }
+
+    #[test]
+    fn tst_check_glossary() {
+        let glossary = Glossary::from_csv("source,target,language,severity\n\
+            Control Center,控制中心,zh_CN,\n\
+            Dock,任务栏,zh_CN,warning\n\
+            Dock,dock,,\n").unwrap();
+        let messages = [
+            ("Open Control Center", None, vec!["打开控制中心"]),
+            ("Open &control center", None, vec!["打开控制面板(&C)"]),
+            ("Docker", None, vec!["容器"]),
+            ("%n window(s) in the Dock", Some("%n windows in the Dock"), vec!["任务栏中有 %n 个窗口"]),
+            ("Dock", None, vec!["停靠栏"]),
+            ("Dock position", None, vec![""]),
+        ];
+        let report = |language: &str| FileReport {
+            context: FileContext { language: language.to_string() },
+            messages: messages.iter().enumerate().map(|(index, (source, source_plural, translations))| ScannedMessage {
+                line: index + 1,
+                source: source.to_string(),
+                source_plural: source_plural.map(str::to_string),
+                translations: translations.iter().map(|translation| translation.to_string()).collect(),
+                ..Default::default()
+            }).collect(),
+            ..Default::default()
+        };
+        let findings = check_glossary(&glossary, &report("zh-CN"));
+        assert_eq!(findings.iter().map(|finding| (finding.line, finding.severity)).collect::<Vec<_>>(), [(2, Severity::Error), (5, Severity::Warning)]);
+        assert_eq!(findings[0].message, "\"Control Center\" should be translated as \"控制中心\"");
+        assert_eq!(rules_and_lines(&check_glossary(&glossary, &report("de"))), [("glossary-term", 4), ("glossary-term", 5)]);
+        assert!(check_glossary(&glossary, &report("")).is_empty());
+    }
+
+    #[test]
+    fn tst_check_ts_attributes() {
+        let report = validate_ts("<TS version=\"1.1\" language=\"zh_CN\" sourcelanguage=\"en\">\n</TS>\n");
+        assert_eq!(rules_and_lines(&report.findings), [("unsupported-version", 1)]);
+        assert_eq!(report.findings[0].severity, Severity::Warning);
+        let attributes = report.ts_attributes.unwrap();
+        assert_eq!((attributes.language.as_deref(), attributes.source_language.as_deref()), (Some("zh_CN"), Some("en")));
+
+        let implied = |language: &str, source_language: Option<&str>| ImpliedLanguages {
+            language: language.to_string(),
+            source_language: source_language.map(str::to_string),
+            origin: "the file name".to_string(),
+        };
+        assert!(check_ts_languages(&attributes, &implied("zh-CN", Some("en_US"))).is_empty());
+        let findings = check_ts_languages(&attributes, &implied("zh_TW", Some("de")));
+        assert_eq!(rules_and_lines(&findings), [("language-mismatch", 1), ("language-mismatch", 1)]);
+        assert_eq!(findings[0].message, "language attribute is \"zh_CN\" but the file name implies \"zh_TW\"");
+        assert!(findings.iter().all(|finding| finding.severity == Severity::Warning));
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_root = temp_dir.path().to_path_buf();
+        fs::create_dir_all(project_root.join(".tx")).unwrap();
+        fs::write(project_root.join(".tx").join("config"), r#"[main]
+host = https://www.transifex.com
+
+[o:linuxdeepin:p:deepin-home:r:deepin-home]
+file_filter = translations/deepin-home.<lang>.ts
+source_file = translations/deepin-home.ts
+source_lang = en
+type = QT
+"#).unwrap();
+        let mut tx_configs = TxConfigs::new();
+        let origin = "[o:linuxdeepin:p:deepin-home:r:deepin-home] of .tx/config";
+        let implied_languages = |path: &str, tx_configs: &mut TxConfigs| implied_languages(&project_root.join(path), tx_configs);
+        assert_eq!(implied_languages("translations/deepin-home.pt_BR.ts", &mut tx_configs),
+            Some(ImpliedLanguages { language: "pt_BR".to_string(), source_language: Some("en".to_string()), origin: origin.to_string() }));
+        assert_eq!(implied_languages("translations/deepin-home.ts", &mut tx_configs),
+            Some(ImpliedLanguages { language: "en".to_string(), source_language: Some("en".to_string()), origin: origin.to_string() }));
+        assert_eq!(implied_languages("misc/desktop_ja.ts", &mut tx_configs).map(|implied| implied.language), Some("ja".to_string()));
+        assert_eq!(implied_languages("misc/desktop.ts", &mut tx_configs), None);
+    }
+
+    #[test]
+    fn tst_fix_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().to_path_buf();
+        let ts_file = dir.join("demo_zh_TW.ts");
+        fs::write(&ts_file, r#"<?xml version="1.0" encoding="utf-8"?>
+<TS version="2.1" language="zh_CN">
+<context>
+    <name>Demo</name>
+    <message>
+        <source>Name: </source>
+        <translation>名稱：</translation>
+    </message>
+    <message>
+        <source>Open &amp; close</source>
+        <translation> 開啟 &amp; 關閉
+</translation>
+    </message>
+    <message numerus="yes">
+        <source>%n file(s)</source>
+        <translation>
+            <numerusform>%n 個檔案 </numerusform>
+        </translation>
+    </message>
+    <message>
+        <source> </source>
+        <translation>  </translation>
+    </message>
+</context>
+</TS>
+"#).unwrap();
+        let mut tx_configs = TxConfigs::new();
+        assert_eq!(fix_file(&ts_file, &mut tx_configs).unwrap(), 4);
+        let content = fs::read_to_string(&ts_file).unwrap();
+        assert!(content.contains("<TS version=\"2.1\" language=\"zh_TW\">"));
+        assert!(content.contains("<translation>名稱： </translation>"));
+        assert!(content.contains("<translation>開啟 &amp; 關閉</translation>"));
+        assert!(content.contains("<numerusform>%n 個檔案</numerusform>"));
+        assert!(content.contains("<translation>  </translation>"));
+        assert_eq!(fix_file(&ts_file, &mut tx_configs).unwrap(), 0);
+
+        let po_file = dir.join("zh_CN.po");
+        fs::write(&po_file, r#"msgid ""
+msgstr ""
+"Language: zh_CN\n"
+
+msgid "Line one"
+msgstr ""
+"第一行"
+"\n\n"
+
+msgid "\tIndented"
+msgstr "缩进 \\n"
+"#).unwrap();
+        assert_eq!(fix_file(&po_file, &mut tx_configs).unwrap(), 2);
+        assert_eq!(fs::read_to_string(&po_file).unwrap(), r#"msgid ""
+msgstr ""
+"Language: zh_CN\n"
+
+msgid "Line one"
+msgstr ""
+"第一行"
+""
+
+msgid "\tIndented"
+msgstr "\t缩进 \\n"
+"#);
+    }
+
+    #[test]
+    fn tst_suppression_comments() {
+        assert_eq!(suppressed_rules("dtu:disable placeholder-mismatch, double-space"), ["placeholder-mismatch", "double-space"]);
+        assert_eq!(suppressed_rules("Keep the English name.\ndtu:disable: glossary-term"), ["glossary-term"]);
+        assert_eq!(suppressed_rules("dtu:disable"), ["all"]);
+        assert!(suppressed_rules("dtu:disabled placeholder-mismatch").is_empty());
+
+        let ts = r#"<TS version="2.1" language="zh_CN">
+<context>
+    <name>Demo</name>
+    <message>
+        <source>Copy %1</source>
+        <extracomment>dtu:disable placeholder-mismatch</extracomment>
+        <translation>复制</translation>
+    </message>
+    <message>
+        <source>Move %1</source>
+        <translatorcomment>dtu:disable double-space</translatorcomment>
+        <translation>移动</translation>
+    </message>
+</context>
+</TS>
+"#;
+        let report = validate_ts(ts);
+        let unsuppressed: Vec<&ValidationFinding> = report.findings.iter().filter(|finding| !is_suppressed(&report.suppressions, finding)).collect();
+        assert_eq!(unsuppressed.iter().map(|finding| (finding.rule, finding.line)).collect::<Vec<_>>(), [("placeholder-mismatch", 9)]);
+
+        let po = r#"msgid ""
+msgstr ""
+"Language: de\n"
+
+# dtu:disable
+#: main.cpp:1
+msgid "Open file"
+msgstr "Datei  öffnen "
+
+#. dtu:disable terminal-punctuation
+#~ msgid "Old"
+#~ msgstr "Alt"
+
+msgid "Done."
+msgstr "Fertig"
+"#;
+        let report = validate_po(po, false);
+        assert_eq!(report.suppressions, BTreeMap::from([(7, vec!["all".to_string()])]));
+        let unsuppressed: Vec<&ValidationFinding> = report.findings.iter().filter(|finding| !is_suppressed(&report.suppressions, finding)).collect();
+        assert_eq!(unsuppressed.iter().map(|finding| (finding.rule, finding.line)).collect::<Vec<_>>(), [("terminal-punctuation", 14)]);
+    }
+
+    #[test]
+    fn tst_severity_options() {
+        assert_eq!(parse_rule_severity("double-space=warning"), Ok(("double-space".to_string(), Severity::Warning)));
+        assert_eq!(parse_rule_severity("terminal-punctuation = INFO"), Ok(("terminal-punctuation".to_string(), Severity::Info)));
+        assert!(parse_rule_severity("double-space").is_err());
+        assert!(parse_rule_severity("double-space=fatal").is_err());
+        assert!(parse_rule_severity("double-spaces=warning").is_err());
+        assert!(MESSAGE_CHECKS.iter().all(|(rule, _)| check_rule_name(rule).is_ok()));
+        assert_eq!(ValidationFinding::new(1, "double-space", String::new()).severity, Severity::Warning);
+        assert_eq!(ValidationFinding::new(1, "placeholder-mismatch", String::new()).severity, Severity::Error);
+        assert!(FailOn::Error.fails_on(Severity::Error) && !FailOn::Error.fails_on(Severity::Warning));
+        assert!(FailOn::Warning.fails_on(Severity::Warning) && !FailOn::Warning.fails_on(Severity::Info));
+        assert!(FailOn::Info.fails_on(Severity::Info) && !FailOn::Never.fails_on(Severity::Error));
+    }
+
+    #[test]
+    fn tst_validate_po_encoding() {
+        let po = |charset: &str, body: &[u8]| {
+            let mut content = format!("msgid \"\"\nmsgstr \"\"\n\"Language: de\\n\"\n\"Content-Type: text/plain; charset={charset}\\n\"\n\n").into_bytes();
+            content.extend_from_slice(body);
+            content
+        };
+        assert!(validate_po_encoding(&po("UTF-8", "msgid \"File\"\nmsgstr \"Datei über\"\n".as_bytes()), false).is_empty());
+        assert_eq!(rules_and_lines(&validate_po_encoding(&po("UTF-8", b"msgid \"File\"\nmsgstr \"Datei \xfcber\"\n"), false)),
+            [("invalid-encoding", 7)]);
+        assert_eq!(rules_and_lines(&validate_po_encoding(&po("ISO-8859-1", "msgid \"File\"\nmsgstr \"Datei über\"\n".as_bytes()), false)),
+            [("charset-mismatch", 4)]);
+        // Other charsets can't be checked.
+        assert!(validate_po_encoding(&po("ISO-8859-1", b"msgid \"File\"\nmsgstr \"Datei \xfcber\"\n"), false).is_empty());
+        assert_eq!(rules_and_lines(&validate_po_encoding(&po("ASCII", "msgid \"File\"\nmsgstr \"Datei über\"\n".as_bytes()), false)),
+            [("invalid-encoding", 7)]);
+        assert_eq!(rules_and_lines(&validate_po_encoding(&po("UTF-8", "msgid \"Café\"\nmsgstr \"CafÃ©\"\n".as_bytes()), false)),
+            [("mojibake", 7)]);
+        assert!(validate_po_encoding(&po("CHARSET", b"msgid \"File\"\nmsgstr \"\"\n"), true).is_empty());
+        assert_eq!(rules_and_lines(&validate_po_encoding(&po("CHARSET", b"msgid \"File\"\nmsgstr \"\"\n"), false)), [("charset-mismatch", 4)]);
+    }
+
+    #[test]
+    fn tst_validate_po() {
+        let content = r#"msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+
+msgid "Hello"
+msgstr "你好"
+
+msgid "%d file"
+msgid_plural "%d files"
+msgstr "%d 个文件"
+
+msgctxt "menu"
+msgid "Open"
+msgstr[0] "打开"
+
+msgid "Bye"
+msgstr "再见"
+oops
+"#;
+        assert_eq!(rules_and_lines(&validate_po(content, false).findings), [
+            ("missing-language", 1),
+            ("broken-plural", 8),
+            ("broken-plural", 12),
+            ("malformed-po", 18),
+        ]);
+        // Templates have no language.
+        let template = "msgid \"\"\nmsgstr \"\"\n\"Language: \\n\"\n\nmsgid \"Hello\"\nmsgstr \"\"\n";
+        assert!(validate_po(template, true).findings.is_empty());
+        assert_eq!(rules_and_lines(&validate_po(template, false).findings), [("missing-language", 1)]);
+
+        let plural = |plural_forms: &str| format!("msgid \"\"\nmsgstr \"\"\n\"Language: fr\\n\"\n{plural_forms}\n\
+            msgid \"%d file\"\nmsgid_plural \"%d files\"\nmsgstr[0] \"%d fichier\"\nmsgstr[1] \"%d fichiers\"\n\n\
+            msgid \"%d folder\"\nmsgid_plural \"%d folders\"\nmsgstr[0] \"%d dossier(s)\"\n");
+        assert_eq!(rules_and_lines(&validate_po(&plural("\"Plural-Forms: nplurals=2; plural=(n > 1);\\n\"\n"), false).findings), [
+            ("plural-count-mismatch", 11),
+        ]);
+        let findings = validate_po(&plural("\"Plural-Forms: nplurals=1; plural=0;\\n\"\n"), false).findings;
+        assert_eq!(rules_and_lines(&findings), [("plural-forms-mismatch", 1), ("plural-count-mismatch", 6)]);
+        assert_eq!(findings[0].message, "Plural-Forms has nplurals=1 but fr has 2 plural form(s)");
+        assert_eq!(rules_and_lines(&validate_po(&plural(""), false).findings), [("missing-plural-forms", 1), ("plural-count-mismatch", 10)]);
+        assert_eq!(rules_and_lines(&validate_po(&plural("\"Plural-Forms: nplurals=INTEGER; plural=EXPRESSION;\\n\"\n"), false).findings), [
+            ("invalid-plural-forms", 1),
+        ]);
+    }
+}
@@ -0,0 +1,126 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::path::{Path, PathBuf};
+use thiserror::Error as TeError;
+
+use crate::i18n_file::cache::{I18nFileCache, I18nFileLoadError};
+use crate::i18n_file::common::{FlatMessage, MessageState};
+use crate::i18n_file::diff::{diff_messages, ChangeKind, MessageChange};
+use crate::i18n_file::{gettext::Po, linguist::Ts};
+use crate::parallel;
+use crate::subcmd::diff::escape_cell;
+use crate::transifex::rest_api::{TransifexRestApi, TransifexRestApiError};
+use crate::transifex::tx_config_file::{load_project_tx_config_file, LoadTxConfigError};
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Fail to load .tx/config file because: {0}")]
+    LoadTxConfig(#[source] LoadTxConfigError),
+    #[error("Fail to load ~/.transifexrc or TX_TOKEN because: {0}")]
+    LoadTransifexRc(#[source] LoadTxConfigError),
+    #[error("Fail to load translation file {0:?} because: {1}")]
+    LoadI18nFile(PathBuf, #[source] I18nFileLoadError),
+    #[error("Fail to download source of {0} because: {1}")]
+    Download(String, #[source] TransifexRestApiError),
+    #[error("Fail to parse source of {0} from Transifex because: {1}")]
+    ParseRemote(String, #[source] I18nFileLoadError),
+    #[error("Sources of {0} resource(s) on Transifex differ from the local ones")]
+    StaleSources(usize),
+}
+
+/// Messages that are to be translated, vanished and obsolete ones are not pushed to Transifex.
+fn active_messages(messages: Vec<FlatMessage>) -> Vec<FlatMessage> {
+    messages.into_iter().filter(|message| !matches!(message.state, MessageState::Vanished | MessageState::Obsolete)).collect()
+}
+
+fn parse_remote_messages(resource_type: &str, content: &str) -> Result<Vec<FlatMessage>, I18nFileLoadError> {
+    Ok(match resource_type {
+        "PO" => Po::load_from_str(content)?.flat_messages(),
+        _ => Ts::load_from_str(content)?.flat_messages(),
+    })
+}
+
+/// Source strings only on one side, `Added` ones are local only, `Removed` ones are only on Transifex.
+fn compare_sources(remote: Vec<FlatMessage>, local: Vec<FlatMessage>) -> Vec<MessageChange> {
+    diff_messages(&active_messages(remote), &active_messages(local)).into_iter()
+        .filter(|change| matches!(change.kind, ChangeKind::Added | ChangeKind::Removed))
+        .collect()
+}
+
+/// Download the source strings of each Qt Linguist-based and PO-based resource in .tx/config, and compare them with the local
+/// source file. Fails if any resource on Transifex is stale, e.g. because the latest source was not pushed.
+pub fn subcmd_verify_sources(project_root: &Path, tx_config_file: Option<&Path>, details: bool) -> Result<(), CmdError> {
+    let (tx_config_file, tx_config) = load_project_tx_config_file(project_root, tx_config_file).map_err(CmdError::LoadTxConfig)?;
+    println!("Found Transifex project config file at: {tx_config_file:?}");
+    let api = TransifexRestApi::new_from_transifexrc().map_err(CmdError::LoadTransifexRc)?;
+
+    let resource_sections: Vec<_> = tx_config.resource_sections.iter()
+        .filter(|section| section.type_attr == "QT" || section.type_attr == "PO")
+        .collect();
+    let results = parallel::map(&resource_sections, |section| {
+        let source_file = project_root.join(&section.source_file);
+        let local = I18nFileCache::shared().load(&source_file)
+            .map_err(|e| CmdError::LoadI18nFile(source_file.clone(), e))?
+            .flat_messages();
+        println!("Downloading source of {}...", section.resource_full_slug);
+        let content = api.download_resource_strings(&section.resource_full_slug)
+            .map_err(|e| CmdError::Download(section.resource_full_slug.clone(), e))?;
+        let remote = parse_remote_messages(&section.type_attr, &content)
+            .map_err(|e| CmdError::ParseRemote(section.resource_full_slug.clone(), e))?;
+        Ok(compare_sources(remote, local))
+    });
+
+    println!("| Resource | Source file | Only local | Only on Transifex |");
+    println!("| -------- | ----------- | ---------- | ----------------- |");
+    let mut stale_resources = vec![];
+    for (section, result) in resource_sections.iter().zip(results) {
+        let changes = result?;
+        let local_only = changes.iter().filter(|change| change.kind == ChangeKind::Added).count();
+        println!("| {} | {} | {local_only} | {} |", section.resource_full_slug, section.source_file, changes.len() - local_only);
+        if !changes.is_empty() {
+            stale_resources.push((section, changes));
+        }
+    }
+    println!("\n{} of {} resource(s) have stale sources on Transifex.", stale_resources.len(), resource_sections.len());
+
+    if details {
+        for (section, changes) in &stale_resources {
+            println!("\n{}:", section.source_file);
+            for change in changes {
+                let side = if change.kind == ChangeKind::Added { "Only local" } else { "Only on Transifex" };
+                println!("- {side}: [{}] {}", escape_cell(&change.context), escape_cell(&change.source));
+            }
+        }
+    }
+    match stale_resources.len() {
+        0 => Ok(()),
+        count => Err(CmdError::StaleSources(count)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_compare_sources() {
+        let message = |source: &str, state: MessageState| FlatMessage {
+            context: "Demo".to_string(),
+            source: source.to_string(),
+            disambiguation: None,
+            translations: vec![],
+            state,
+        };
+        let remote = vec![message("Hello", MessageState::Unfinished), message("Removed", MessageState::Unfinished)];
+        let local = vec![
+            message("Hello", MessageState::Finished),
+            message("Added", MessageState::Unfinished),
+            message("Gone", MessageState::Vanished),
+        ];
+        let changes = compare_sources(remote, local);
+        assert_eq!(changes.iter().map(|change| (change.kind, change.source.as_str())).collect::<Vec<_>>(),
+            [(ChangeKind::Added, "Added"), (ChangeKind::Removed, "Removed")]);
+    }
+}
@@ -4,13 +4,16 @@
 
 use core::panic;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::io::stdin;
+use std::sync::OnceLock;
 use directories::ProjectDirs;
 use thiserror::Error as TeError;
 
+use crate::parallel;
 use crate::transifex::{
     rest_api::TransifexRestApi,
+    tx_config_file::{load_tx_config_file, write_file_atomically, LoadTxConfigError},
     yaml_file::*,
 };
 
@@ -18,13 +21,89 @@ use crate::transifex::{
 pub enum CmdError {
     #[error("Fail to load transifex.yaml file because: {0}")]
     LoadTxYaml(#[from] LoadTxYamlError),
+    #[error("Fail to load existing .tx/config file because: {0}")]
+    LoadTxConfig(#[from] LoadTxConfigError),
+    #[error("Fail to load ~/.transifexrc or TX_TOKEN because: {0}")]
+    LoadTransifexRc(#[source] LoadTxConfigError),
+    #[error("Fail to write {0:?} because: {1}")]
+    WriteFile(PathBuf, #[source] std::io::Error),
+    #[error("Fail to create directory {0:?} because: {1}")]
+    CreateDir(PathBuf, #[source] std::io::Error),
+}
+
+/// Extract `owner/repo` from a git remote URL like `git@github.com:owner/repo.git` or `https://github.com/owner/repo`.
+///
+/// Only GitHub remotes are accepted, since the Transifex GitHub integration links resources to GitHub repositories.
+fn parse_repository_from_remote_url(url: &str) -> Option<String> {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix(".git").unwrap_or(url);
+    // scp-like syntax `user@host:owner/repo` has no scheme, the path starts after the colon.
+    let (authority, path) = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?,
+        None => url.split_once(':')?,
+    };
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+    if !host.eq_ignore_ascii_case("github.com") {
+        return None;
+    }
+    let mut components = path.split('/').filter(|component| !component.is_empty());
+    let (Some(owner), Some(repo), None) = (components.next(), components.next(), components.next()) else {
+        return None;
+    };
+    Some(format!("{owner}/{repo}"))
+}
+
+/// Read the URL of the `origin` remote (or the first remote) of the git repository at the project root.
+///
+/// `git` is asked first so that worktrees and `insteadOf` rewrites are handled, `.git/config` is read if git is not available.
+fn detect_remote_url(project_root: &Path) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .arg("-C").arg(project_root)
+        .args(["remote", "get-url", "origin"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success());
+    if let Some(output) = output {
+        return Some(String::from_utf8_lossy(&output.stdout).trim().to_string()).filter(|url| !url.is_empty());
+    }
+    let content = fs::read_to_string(project_root.join(".git/config")).ok()?;
+    let mut config = configparser::ini::Ini::new_cs();
+    config.read(content).ok()?;
+    let remotes: Vec<String> = config.sections().into_iter().filter(|section| section.starts_with("remote ")).collect();
+    let remote = remotes.iter().find(|section| *section == "remote \"origin\"").or(remotes.first())?;
+    config.get(remote, "url")
+}
+
+/// Detect the current branch of the git repository at the project root, `None` if HEAD is detached.
+pub fn detect_git_branch(project_root: &Path) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .arg("-C").arg(project_root)
+        .args(["symbolic-ref", "--short", "-q", "HEAD"])
+        .output()
+        .ok();
+    if let Some(output) = output {
+        let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        return Some(branch).filter(|branch| output.status.success() && !branch.is_empty());
+    }
+    let head = fs::read_to_string(project_root.join(".git/HEAD")).ok()?;
+    head.trim().strip_prefix("ref: refs/heads/").map(ToOwned::to_owned)
+}
+
+/// Detect `owner/repo` of the project from its git remote.
+pub fn detect_github_repository(project_root: &Path) -> Option<String> {
+    parse_repository_from_remote_url(&detect_remote_url(project_root)?)
 }
 
 fn get_github_repository_from_user_input(project_root: &PathBuf, github_repository_hint: Option<String>) -> String {
     let project_root = fs::canonicalize(project_root).unwrap_or(project_root.to_path_buf());
+    let github_repository_hint = github_repository_hint.or_else(|| {
+        let detected = detect_github_repository(&project_root)?;
+        println!("Detected GitHub repository {detected:?} from git remote.");
+        Some(detected)
+    });
     let mut repo_name = match github_repository_hint {
         Some(github_repository_hint_name) => github_repository_hint_name,
-        None => project_root.file_name().and_then(|name| name.to_str().map(ToOwned::to_owned)).unwrap_or(String::new()),
+        None => project_root.file_name().and_then(|name| name.to_str().map(ToOwned::to_owned)).unwrap_or_default(),
     };
 
     loop {
@@ -52,95 +131,160 @@ fn get_github_repository_from_user_input(project_root: &PathBuf, github_reposito
     }
 }
 
-fn fetch_project_list(organization_slug: &str, force_online: bool) -> Vec<String> {
+/// The REST client shared by concurrent fetches, only created when anything is fetched online.
+fn shared_client(client: &OnceLock<TransifexRestApi>) -> Result<&TransifexRestApi, LoadTxConfigError> {
+    if let Some(client) = client.get() {
+        return Ok(client);
+    }
+    // Concurrent fetches may create a client each, only the first one is kept.
+    let new_client = TransifexRestApi::new_from_transifexrc()?;
+    Ok(client.get_or_init(|| new_client))
+}
+
+fn fetch_project_list(client: &OnceLock<TransifexRestApi>, organization_slug: &str, force_online: bool) -> Result<Vec<String>, LoadTxConfigError> {
     let xdg_proj_dirs = ProjectDirs::from("", "deepin", "deepin-translation-utils").expect("Not able to get project directories");
     let cache_file = xdg_proj_dirs.cache_dir().join(format!("{organization_slug}.yaml"));
     
     if cache_file.exists() && !force_online {
         let source_content = fs::read_to_string(&cache_file).expect("Failed to read cached project list");
-        let list = serde_yaml2::from_str::<Vec<String>>(source_content.as_str()).expect("Failed to parse cached project list");
-        return list;
+        Ok(serde_yaml2::from_str::<Vec<String>>(source_content.as_str()).expect("Failed to parse cached project list"))
     } else {
-        let client = TransifexRestApi::new_from_transifexrc().expect("Failed to create Transifex REST client");
-
-        println!("Fetching o:{organization_slug} project list from Transifex...");
-        let entries = client.get_all_projects(organization_slug).expect("Failed to fetch project resource list");
+        eprintln!("Fetching o:{organization_slug} project list from Transifex...");
+        let entries = shared_client(client)?.get_all_projects(organization_slug).expect("Failed to fetch project resource list");
         let entries = entries.into_iter().map(|entry| entry.id.to_string());
         let entries: Vec<String> = entries.collect();
         let cache_content = serde_yaml2::to_string(&entries).expect("Failed to serialize project list as cache");
         let parent_dir = cache_file.parent().expect("Failed to get cache file parent directory");
-        fs::create_dir_all(&parent_dir).expect("Failed to create cache directory");
+        fs::create_dir_all(parent_dir).expect("Failed to create cache directory");
         fs::write(&cache_file, cache_content).expect("Failed to write cache file");
-        return entries;
+        Ok(entries)
     }
 }
 
-fn fetch_linked_resource_list(organization_slug: &str, project_slug: &str, force_online: bool) -> Vec<TxResourceLookupEntry> {
+fn fetch_linked_resource_list(client: &OnceLock<TransifexRestApi>, organization_slug: &str, project_slug: &str, force_online: bool) -> Result<Vec<TxResourceLookupEntry>, LoadTxConfigError> {
     let xdg_proj_dirs = ProjectDirs::from("", "deepin", "deepin-translation-utils").expect("Not able to get project directories");
     let cache_file = xdg_proj_dirs.cache_dir().join(format!("{organization_slug}/{project_slug}.yaml"));
     
     if cache_file.exists() && !force_online {
-        println!("Reusing o:{organization_slug}:p:{project_slug} project resource list from local cache...");
+        eprintln!("Reusing o:{organization_slug}:p:{project_slug} project resource list from local cache...");
         let source_content = fs::read_to_string(&cache_file).expect("Failed to read cached project resource list");
-        let list = serde_yaml2::from_str::<Vec<TxResourceLookupEntry>>(source_content.as_str()).expect("Failed to parse cached project resource list");
-        return list;
+        Ok(serde_yaml2::from_str::<Vec<TxResourceLookupEntry>>(source_content.as_str()).expect("Failed to parse cached project resource list"))
     } else {
-        let client = TransifexRestApi::new_from_transifexrc().expect("Failed to create Transifex REST client");
-
-        println!("Fetching o:{organization_slug}:p:{project_slug} project resource list from Transifex...");
-        let entries = client.get_all_linked_resources(organization_slug, project_slug).expect("Failed to fetch project resource list");
+        eprintln!("Fetching o:{organization_slug}:p:{project_slug} project resource list from Transifex...");
+        let entries = shared_client(client)?.get_all_linked_resources(organization_slug, project_slug).expect("Failed to fetch project resource list");
         let entries = entries.into_iter().filter_map(|entry| entry.parse_linked_resource_category()).collect();
         let cache_content = serde_yaml2::to_string(&entries).expect("Failed to serialize project resource list as cache");
         let parent_dir = cache_file.parent().unwrap();
-        fs::create_dir_all(&parent_dir).expect("Failed to create cache directory");
-        fs::write(&cache_file, cache_content).expect(format!("Failed to write project cache file to {cache_file:?}").as_str());
-        return entries;
+        fs::create_dir_all(parent_dir).expect("Failed to create cache directory");
+        fs::write(&cache_file, cache_content).unwrap_or_else(|_| panic!("Failed to write project cache file to {cache_file:?}"));
+        Ok(entries)
     }
 }
 
-pub fn create_linked_resources_table(organization_slug: &str, project_slug: Option<String>, force_online: bool) -> Vec<TxResourceLookupEntry> {
-    let mut lookup_table = Vec::<TxResourceLookupEntry>::new();
-
+/// Linked resources of the project, or of all projects of the organization if `project_slug` is `None`.
+///
+/// Fails if anything has to be fetched online but the API token can't be loaded.
+pub fn create_linked_resources_table(organization_slug: &str, project_slug: Option<String>, force_online: bool) -> Result<Vec<TxResourceLookupEntry>, LoadTxConfigError> {
     if let Some(project_slug) = project_slug {
-        let resource_list = fetch_linked_resource_list(&organization_slug, &project_slug, force_online);
-        lookup_table.extend(resource_list);
-    } else {
-        let project_list = fetch_project_list(&organization_slug, force_online);
-        for project_full_slug in project_list {
-            // project_full_slug is in the format of o:linuxdeepin:p:deepin-home
-            // use regex to extract project_slug
-            let re = regex::Regex::new(r"^o:(?P<organization>[^:]+):p:(?P<project>[^:]+)$").unwrap();
-            let captures = re.captures(&project_full_slug).unwrap();
-            let project_slug = captures.name("project").unwrap().as_str();
-            let resource_list = fetch_linked_resource_list(&organization_slug, &project_slug, force_online);
-            lookup_table.extend(resource_list);
-        }
+        let client = OnceLock::new();
+        return fetch_linked_resource_list(&client, organization_slug, &project_slug, force_online);
     }
+    create_filtered_linked_resources_table(organization_slug, |_| true, force_online)
+}
 
-    lookup_table
+/// Like [`create_linked_resources_table`] for all projects of the organization, but only resources of the projects
+/// whose slug is accepted by `project_filter` are fetched.
+pub fn create_filtered_linked_resources_table(organization_slug: &str, project_filter: impl Fn(&str) -> bool, force_online: bool) -> Result<Vec<TxResourceLookupEntry>, LoadTxConfigError> {
+    let client = OnceLock::new();
+    let project_list = fetch_project_list(&client, organization_slug, force_online)?;
+    // project_full_slug is in the format of o:linuxdeepin:p:deepin-home
+    // use regex to extract project_slug
+    let re = regex::Regex::new(r"^o:(?P<organization>[^:]+):p:(?P<project>[^:]+)$").unwrap();
+    let project_slugs: Vec<&str> = project_list.iter()
+        .map(|project_full_slug| re.captures(project_full_slug).unwrap().name("project").unwrap().as_str())
+        .filter(|project_slug| project_filter(project_slug))
+        .collect();
+    // Projects are fetched concurrently (up to `--jobs` at a time), which matters for large organizations.
+    let resource_lists = parallel::map(&project_slugs, |project_slug| {
+        fetch_linked_resource_list(&client, organization_slug, project_slug, force_online)
+    });
+    let resource_lists = resource_lists.into_iter().collect::<Result<Vec<_>, _>>()?;
+    Ok(resource_lists.into_iter().flatten().collect())
 }
 
-pub fn subcmd_yaml2txconfig(project_root: &PathBuf, force_online: bool, github_repository: Option<String>, organization_slug: String, project_slug: Option<String>) -> Result<(), CmdError> {
+#[allow(clippy::too_many_arguments)]
+pub fn subcmd_yaml2txconfig(project_root: &PathBuf, force_online: bool, github_repository: Option<String>, branch: Option<String>, organization_slug: String, project_slug: Option<String>, minimum_perc: Option<i64>, merge: bool) -> Result<(), CmdError> {
     let (transifex_yaml_file, tx_yaml) = try_load_transifex_yaml_file(project_root)?;
     println!("Found Transifex project config file at: {transifex_yaml_file:?}");
 
     let github_repository = get_github_repository_from_user_input(project_root, github_repository);
     println!("GitHub repository name: {github_repository}");
     
-    let lookup_table = create_linked_resources_table(&organization_slug, project_slug, force_online);
-    let tx_config = tx_yaml.to_tx_config(github_repository, lookup_table);
+    let lookup_table = create_linked_resources_table(&organization_slug, project_slug, force_online).map_err(CmdError::LoadTransifexRc)?;
+    let branch = branch.or_else(|| detect_git_branch(project_root));
+    if let Some(branch) = &branch {
+        println!("Git branch: {branch}");
+    }
+    let mut tx_config = tx_yaml.to_tx_config(github_repository, branch.as_deref(), lookup_table);
+    tx_config.main_section.minimum_prec = minimum_perc;
 
     let tx_config_file = project_root.join(".tx/config");
-    if tx_config_file.exists() {
-        println!("Note: {tx_config_file:?} file already exists, not overwriting it.");
+    if tx_config_file.exists() && merge {
+        let mut existing_tx_config = load_tx_config_file(&tx_config_file)?;
+        let stats = existing_tx_config.merge(tx_config);
+        if minimum_perc.is_some() {
+            existing_tx_config.main_section.minimum_prec = minimum_perc;
+        }
+        write_file_atomically(&tx_config_file, &existing_tx_config.to_str()).map_err(|e| CmdError::WriteFile(tx_config_file.clone(), e))?;
+        println!("Merged into {tx_config_file:?}: {} section(s) added, {} placeholder slug(s) resolved, {} section(s) kept.",
+            stats.added, stats.resolved, stats.kept);
+        for source_file in &stats.removed {
+            println!("Note: {source_file} is in .tx/config but not in transifex.yaml, kept as is.");
+        }
+    } else if tx_config_file.exists() {
+        println!("Note: {tx_config_file:?} file already exists, not overwriting it. Use --merge to update it.");
         println!("You can use the following context to update the file manually:\n");
         println!("{}", tx_config.to_str());
     } else {
         let parent_dir = tx_config_file.parent().unwrap();
-        fs::create_dir_all(&parent_dir).expect("Failed to create .tx directory");
-        fs::write(&tx_config_file, tx_config.to_str()).expect("Failed to write .tx/config file");
+        fs::create_dir_all(parent_dir).map_err(|e| CmdError::CreateDir(parent_dir.to_path_buf(), e))?;
+        write_file_atomically(&tx_config_file, &tx_config.to_str()).map_err(|e| CmdError::WriteFile(tx_config_file.clone(), e))?;
         println!("Generated .tx/config file at: {tx_config_file:?}");
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_parse_repository_from_remote_url() {
+        for url in [
+            "git@github.com:linuxdeepin/deepin-home.git",
+            "https://github.com/linuxdeepin/deepin-home",
+            "https://github.com/linuxdeepin/deepin-home.git/",
+            "ssh://git@github.com/linuxdeepin/deepin-home.git",
+            "ssh://git@github.com:22/linuxdeepin/deepin-home.git",
+        ] {
+            assert_eq!(parse_repository_from_remote_url(url).as_deref(), Some("linuxdeepin/deepin-home"), "{url}");
+        }
+        assert_eq!(parse_repository_from_remote_url("https://github.com/deepin-home"), None);
+        assert_eq!(parse_repository_from_remote_url("/srv/git/deepin-home"), None);
+        assert_eq!(parse_repository_from_remote_url("git@gitlab.com:linuxdeepin/deepin-home.git"), None);
+        assert_eq!(parse_repository_from_remote_url("https://gitee.com/linuxdeepin/deepin-home"), None);
+        assert_eq!(parse_repository_from_remote_url("https://github.com/linuxdeepin/deepin-home/tree/master"), None);
+    }
+
+    #[test]
+    fn tst_detect_github_repository_from_git_config() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_root = temp_dir.path().to_path_buf();
+        fs::create_dir_all(project_root.join(".git")).unwrap();
+        fs::write(project_root.join(".git/config"), "[core]\n\tbare = false\n[remote \"upstream\"]\n\turl = https://github.com/someone/fork.git\n\
+            [remote \"origin\"]\n\turl = git@github.com:linuxdeepin/deepin-home.git\n\tfetch = +refs/heads/*:refs/remotes/origin/*\n").unwrap();
+        let detected = detect_github_repository(&project_root);
+        assert_eq!(detected.as_deref(), Some("linuxdeepin/deepin-home"));
+    }
+}
@@ -4,8 +4,12 @@
 
 use thiserror::Error as TeError;
 use std::path::{Path, PathBuf};
-use zhconv::zhconv;
-use crate::i18n_file::{self, linguist::Ts, gettext::Po};
+use crate::conversion::{translate_po_content, translate_ts_content, convert_text, ConversionError, VariantMapping, ZhConvOptions};
+use crate::i18n_file::{self, linguist::{Ts, TsSaveOptions}, gettext::{Po, PoHeaderOptions, PoSaveOptions}};
+use crate::i18n_file::common::FlatMessage;
+use crate::i18n_file::diff::{diff_messages, MessageChange};
+use crate::parallel;
+use crate::transifex::project_file::{load_transifex_project_file, TxProjectFileLoadError};
 
 #[derive(TeError, Debug)]
 pub enum CmdError {
@@ -17,16 +21,10 @@ pub enum CmdError {
     NoDirName,
     #[error("Input file {0:?} doesn't have the source language {1:?} in its file name.")]
     MismatchedLanguage(PathBuf, String),
-    #[error("Target file {0:?} has different number of contexts")]
-    DifferentContexts(String),
-    #[error("Target file for language {0:?} has different number of messages (Source {1:?} != Target {2:?})")]
-    DifferentMessages(String, usize, usize),
-    #[error("Target language ({0:?})'s source string doesn't match (Source: {1:?} != Target: {2:?}), did you forget to run `update_translations` beforehand?")]
-    DifferentMessage(String, String, String),
-    #[error("Fail to parse language code")]
-    ParseLanguageCode,
-    #[error("Missing language code in Linguist TS file")]
-    MissingLanguageCode,
+    #[error("{0}")]
+    Conversion(#[from] ConversionError),
+    #[error("Invalid context glob: {0}")]
+    InvalidContextGlob(#[source] globset::Error),
     #[error("Can not guess translation file kind from path {0:?} because: {1}")]
     GuessI18nFileType(PathBuf, #[source] i18n_file::common::UnknownI18nFileExtError),
     #[error("The translation file type of target file and reference file is mismatched.")]
@@ -43,81 +41,47 @@ pub enum CmdError {
     SaveTsFile(PathBuf, #[source] i18n_file::linguist::TsSaveError),
     #[error("Fail to save file {0:?} because: {1}")]
     SavePoFile(PathBuf, #[source] i18n_file::gettext::PoSaveError),
+    #[error("Fail to read input {0:?} because: {1}")]
+    ReadInput(PathBuf, #[source] std::io::Error),
+    #[error("Fail to load Transifex project file because: {0}")]
+    LoadProjectFile(#[from] TxProjectFileLoadError),
+    #[error("{0} of {1} target file(s) failed to convert")]
+    TargetsFailed(usize, usize),
 }
 
-// ===== Utils Functions =====
-
-fn correct_language_code(language_code: &str) -> String {
-    return language_code.replace("_", "-");
-}
-
-fn zhconv_wrapper(text: &str, target: &str) -> Result<String, CmdError> {
-    let target = correct_language_code(target);
-    let target = target.parse().map_err(|_| CmdError::ParseLanguageCode)?;
-    Ok(zhconv(text, target))
-}
-
-fn translate_ts_content(source_content: &Ts, target_content: &mut Ts) -> Result<(), CmdError> {
-    use i18n_file::linguist::TranslationType;
-
-    let language_code = target_content.get_language().ok_or(CmdError::MissingLanguageCode)?;
-    if target_content.contexts.len() != source_content.contexts.len() {
-        return Err(CmdError::DifferentContexts(language_code.clone()));
-    }
-    for (index, context) in target_content.contexts.iter_mut().enumerate() {
-        let source_context = &source_content.contexts[index];
-        if context.messages.len() != source_context.messages.len() {
-            return Err(CmdError::DifferentMessages(language_code.clone(), source_context.messages.len(), context.messages.len()));
-        }
-        // for loop with index so we could access the source context and message at the same index
-        for (index, message) in context.messages.iter_mut().enumerate() {
-            let source_message = &source_context.messages[index];
-            // Skip the message if it's finished
-            if !matches!(message.translation.type_attr, Some(TranslationType::Unfinished)) {
-                continue;
-            }
-            if matches!(source_message.translation.type_attr, Some(TranslationType::Unfinished)) {
-                continue;
-            }
-            if source_message.source != message.source {
-                return Err(CmdError::DifferentMessage(language_code.clone(), source_message.source.clone(), message.source.clone()));
-            }
-            if let Some(value) = &source_message.translation.value {
-                message.fill_translation(&zhconv_wrapper(&value, &language_code)?);
-            }
+/// Validate target languages and drop the source language from them, since it can't be converted into itself.
+fn prepare_target_languages(source_language: &str, target_languages: &[String], variant_mapping: &VariantMapping) -> Result<Vec<String>, CmdError> {
+    let mut prepared = vec![];
+    for target_language in target_languages {
+        variant_mapping.variant(target_language)?;
+        if target_language == source_language {
+            eprintln!("Warning: Target language {target_language} is the same as the source language, skipped.");
+            continue;
         }
+        prepared.push(target_language.clone());
     }
-    Ok(())
+    Ok(prepared)
 }
 
-fn translate_po_content(source_content: &Po, target_content: &mut Po) -> Result<(), CmdError> {
-    use polib::message::{MessageMutView, MessageView};
-
-    let language_code = target_content.get_language();
-    let source_catalog = &source_content.inner;
-    let target_catalog = &mut target_content.inner;
+/// Load the skip list from the given file, one source text per line. Empty lines are ignored.
+pub fn load_skip_list(skip_list_file: &Path) -> Result<Vec<String>, CmdError> {
+    let content = std::fs::read_to_string(skip_list_file).map_err(|e| CmdError::ReadInput(skip_list_file.to_path_buf(), e))?;
+    Ok(content.lines().filter(|line| !line.is_empty()).map(str::to_string).collect())
+}
 
-    let target_msg_count = target_catalog.count();
-    let source_msg_count = source_catalog.count();
-    if target_msg_count != source_msg_count {
-        return Err(CmdError::DifferentMessages(language_code, source_msg_count, target_msg_count));
-    };
-    for (mut message, reference_message) in target_catalog.messages_mut().zip(source_catalog.messages()) {
-        if message.is_translated() {
-            continue;
-        };
-        if reference_message.is_translated() && !message.is_translated() && !message.is_plural() {
-            // We have checked plural case, unwrap directly.
-            let msgstr = reference_message.msgstr().unwrap().to_string();
-            let translated_msg = zhconv_wrapper(&msgstr, &language_code)?;
-            message.set_msgstr(translated_msg).unwrap();
-        };
-    }
-    Ok(())
+/// Load conversion rules from the given file, one rule per line. Empty lines and lines starting with `#` are ignored.
+pub fn load_rules_file(rules_file: &Path) -> Result<Vec<String>, CmdError> {
+    let content = std::fs::read_to_string(rules_file).map_err(|e| CmdError::ReadInput(rules_file.to_path_buf(), e))?;
+    Ok(content.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
 }
 
 // ===== Uniform Translation File =====
 
+#[allow(clippy::large_enum_variant)]
 enum ZhConvFile {
     Linguist(Ts),
     Gettext(Po),
@@ -141,19 +105,26 @@ impl ZhConvFile {
         })
     }
 
-    fn load_or_create_target_file(&self, file_path: &Path, fallback_language_code: &str) -> Result<Self, CmdError> {
+    fn load_or_create_target_file(&self, file_path: &Path, fallback_language_code: &str, po_header_options: &PoHeaderOptions) -> Result<Self, CmdError> {
         Ok(match self {
             ZhConvFile::Linguist(ts) => Self::Linguist(
                 Ts::load_from_file_or_default(file_path, ts, fallback_language_code)
                     .map_err(|e| CmdError::LoadTsTargetFile(file_path.to_path_buf(), e))?,
             ),
             ZhConvFile::Gettext(po) => Self::Gettext(
-                Po::load_from_file_or_default(file_path, po, fallback_language_code)
+                Po::load_from_file_or_default(file_path, po, fallback_language_code, po_header_options)
                     .map_err(|e| CmdError::LoadPoTargetFile(file_path.to_path_buf(), e))?,
             ),
         })
     }
 
+    fn flat_messages(&self) -> Vec<FlatMessage> {
+        match self {
+            ZhConvFile::Linguist(ts) => ts.flat_messages(),
+            ZhConvFile::Gettext(po) => po.flat_messages(),
+        }
+    }
+
     fn get_language(&self) -> Option<String> {
         match self {
             ZhConvFile::Linguist(ts) => ts.get_language(),
@@ -168,114 +139,284 @@ impl ZhConvFile {
         }
     }
     
-    fn translate_content_based_on(&mut self, reference_content: &Self) -> Result<(), CmdError> {
+    fn translate_content_based_on(&mut self, reference_content: &Self, options: &ZhConvOptions) -> Result<(), CmdError> {
         match (self, reference_content) {
             (ZhConvFile::Linguist(lhs), ZhConvFile::Linguist(rhs)) => {
-                Ok(translate_ts_content(rhs, lhs)?)
+                Ok(translate_ts_content(rhs, lhs, options)?)
             },
             (ZhConvFile::Gettext(lhs), ZhConvFile::Gettext(rhs)) => {
-                Ok(translate_po_content(rhs, lhs)?)
+                Ok(translate_po_content(rhs, lhs, options)?)
             },
             _ => Err(CmdError::MismatchedI18nFileType)
         }
     }
 
-    fn save_file(&self, file_path: &Path) -> Result<(), CmdError> {
-        Ok(match self {
+    /// Add or remove the manual review marker of matched messages, returns the count of changed messages.
+    ///
+    /// Only finished messages can be marked. Empty `sources` matches all messages.
+    fn set_manually_reviewed(&mut self, context: Option<&str>, sources: &[String], reviewed: bool) -> usize {
+        let source_matches = |source: &str| sources.is_empty() || sources.iter().any(|s| s == source);
+        let mut changed = 0;
+        match self {
+            ZhConvFile::Linguist(ts) => {
+                for ts_context in &mut ts.contexts {
+                    if context.is_some_and(|name| name != ts_context.name) {
+                        continue;
+                    }
+                    for message in &mut ts_context.messages {
+                        let finished = message.translation.type_attr.is_none();
+                        if (reviewed && !finished) || !source_matches(&message.source) {
+                            continue;
+                        }
+                        if message.is_manually_reviewed() != reviewed {
+                            message.set_manually_reviewed(reviewed);
+                            changed += 1;
+                        }
+                    }
+                }
+            },
+            ZhConvFile::Gettext(po) => {
+                use polib::message::MessageView;
+                use i18n_file::gettext::{is_message_manually_reviewed, set_message_manually_reviewed};
+
+                for mut message in po.inner.messages_mut() {
+                    if context.is_some_and(|name| message.msgctxt() != Some(name)) {
+                        continue;
+                    }
+                    if (reviewed && !message.is_translated()) || !source_matches(message.msgid()) {
+                        continue;
+                    }
+                    if is_message_manually_reviewed(&message) != reviewed {
+                        set_message_manually_reviewed(&mut message, reviewed);
+                        changed += 1;
+                    }
+                }
+            },
+        }
+        changed
+    }
+
+    fn save_file(&self, file_path: &Path, ts_save_options: &TsSaveOptions, po_save_options: &PoSaveOptions) -> Result<(), CmdError> {
+        match self {
             ZhConvFile::Linguist(ts) => ts
-                .save_into_file(file_path)
-                .map_err(|e| CmdError::SaveTsFile(file_path.to_path_buf(), e))?,
+                .save_into_file(file_path, ts_save_options)
+                .map_err(|e| CmdError::SaveTsFile(file_path.to_path_buf(), e)),
             ZhConvFile::Gettext(po) => po
-                .save_into_file(file_path)
-                .map_err(|e| CmdError::SavePoFile(file_path.to_path_buf(), e))?,
-        })
+                .save_into_file(file_path, po_save_options)
+                .map_err(|e| CmdError::SavePoFile(file_path.to_path_buf(), e)),
+        }
     }
 }
 
 // ===== Sub Command =====
 
-pub fn subcmd_zhconv(source_language: &str, target_languages: &[String], linguist_ts_file: &Path) -> Result<(), CmdError> {
+#[allow(clippy::too_many_arguments)]
+pub fn subcmd_zhconv(source_language: &str, target_languages: &[String], linguist_ts_file: &Path, options: &ZhConvOptions, po_header_options: &PoHeaderOptions, ts_save_options: &TsSaveOptions, po_save_options: &PoSaveOptions, dry_run: bool) -> Result<(), CmdError> {
     if !linguist_ts_file.is_file() {
         return Err(CmdError::FileNotFound(linguist_ts_file.to_path_buf()));
     }
     let file_name = linguist_ts_file.file_name().ok_or(CmdError::NoFileName)?;
-    if !file_name.to_string_lossy().contains(&source_language) {
+    if !file_name.to_string_lossy().contains(source_language) {
         return Err(CmdError::MismatchedLanguage(linguist_ts_file.to_path_buf(), source_language.to_string()));
     }
 
+    let target_languages = prepare_target_languages(source_language, target_languages, &options.variant_mapping)?;
     let source_content = ZhConvFile::load_file(linguist_ts_file)?;
+    let parent_dir = linguist_ts_file.parent().ok_or(CmdError::NoDirName)?;
+    let targets: Vec<(String, PathBuf)> = target_languages.iter()
+        .map(|target_language| {
+            // replace the source language code with the target language code to get the target file name
+            let target_file_name = file_name.to_string_lossy().replace(source_language, target_language);
+            (target_language.clone(), parent_dir.join(target_file_name))
+        })
+        .collect();
 
-    let mut target_contents: Vec<(PathBuf, ZhConvFile)> = vec![];
-    for target_language in target_languages {
-        // replace the source language code with the target language code to get the target file name
-        let target_file_name = file_name.to_string_lossy().replace(source_language, &target_language);
-        let target_file_path = linguist_ts_file.parent().ok_or(CmdError::NoDirName)
-            .and_then(|p| { Ok(p.join(target_file_name)) })?;
-        let mut target_content = source_content.load_or_create_target_file(&target_file_path, &target_language)?;
-        // if the target file's language code is not match to target_language, set it to target_language
-        if !matches!(&target_content.get_language(), Some(lang) if lang == target_language.as_str()) {
-            eprintln!("Warning: Target file {target_file_path:?} has no or unmatched language code, will set it to {target_language}.");
-            target_content.set_language(&target_language);
+    convert_into_targets(&source_content, &targets, options, po_header_options, ts_save_options, po_save_options, dry_run)
+}
+
+/// Convert every Qt Linguist and GNU Gettext resource of the project that has a translation of the source language.
+///
+/// Resources are read from `project_file`, or the transifex.yaml or .tx/config file under `project_root` if not given,
+/// and target files are located by the translation file expression.
+#[allow(clippy::too_many_arguments)]
+pub fn subcmd_zhconv_project(project_root: &Path, project_file: Option<&Path>, source_language: &str, target_languages: &[String], options: &ZhConvOptions, po_header_options: &PoHeaderOptions, ts_save_options: &TsSaveOptions, po_save_options: &PoSaveOptions, dry_run: bool) -> Result<(), CmdError> {
+    let target_languages = prepare_target_languages(source_language, target_languages, &options.variant_mapping)?;
+    let (project_file, tx_yaml) = load_transifex_project_file(project_root, project_file)?;
+    println!("Found Transifex project config file at: {project_file:?}");
+
+    let mut resources: Vec<(PathBuf, Vec<(String, PathBuf)>)> = vec![];
+    for filter in &tx_yaml.filters {
+        if (filter.format != "QT" && filter.format != "PO") || filter.type_attr != "file" {
+            println!("Skipping resource {:?} with format {:?}...", filter.source, filter.format);
+            continue;
         }
-        target_contents.push((target_file_path, target_content));
+        let source_file = project_root.join(filter.target_pattern.replace("<lang>", source_language));
+        if !source_file.is_file() {
+            println!("Skipping resource {:?} without {source_language} translation...", filter.source);
+            continue;
+        }
+        let targets = target_languages.iter()
+            .map(|target_language| (target_language.clone(), project_root.join(filter.target_pattern.replace("<lang>", target_language))))
+            .collect();
+        resources.push((source_file, targets));
     }
 
-    for (target_path, target_content) in &mut target_contents {
-        target_content.translate_content_based_on(&source_content)?;
-        target_content.save_file(target_path)?;
+    // Flatten all (resource, target) pairs into a single job list, so large projects keep all workers busy
+    // instead of waiting for the slowest target of each resource.
+    let source_contents = parallel::map(&resources, |(source_file, _)| ZhConvFile::load_file(source_file))
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+    let jobs: Vec<(&ZhConvFile, &String, &PathBuf)> = source_contents.iter().zip(&resources)
+        .flat_map(|(source_content, (_, targets))| targets.iter().map(move |(target_language, target_file_path)| (source_content, target_language, target_file_path)))
+        .collect();
+    let results = parallel::map(&jobs, |(source_content, target_language, target_file_path)| {
+        convert_into_target(source_content, target_language, target_file_path, options, po_header_options, ts_save_options, po_save_options, dry_run)
+    });
+    let target_file_paths: Vec<&Path> = jobs.iter().map(|(_, _, target_file_path)| target_file_path.as_path()).collect();
+    report_target_results(&target_file_paths, results)?;
+
+    if !dry_run {
+        println!("Converted {} resource(s) into {}.", resources.len(), target_languages.join(", "));
     }
 
     Ok(())
 }
 
-pub fn subcmd_zhconv_plain(target_languages: &[String], content: &str) -> Result<(), CmdError> {
+/// Convert the source file into each of the `(language, path)` targets, target files are created if not exist.
+///
+/// With `dry_run`, nothing is written and changes of each target file are printed instead.
+fn convert_into_targets(source_content: &ZhConvFile, targets: &[(String, PathBuf)], options: &ZhConvOptions, po_header_options: &PoHeaderOptions, ts_save_options: &TsSaveOptions, po_save_options: &PoSaveOptions, dry_run: bool) -> Result<(), CmdError> {
+    // Each target language is converted and saved independently, so do them in parallel.
+    let results = parallel::map(targets, |(target_language, target_file_path)| {
+        convert_into_target(source_content, target_language, target_file_path, options, po_header_options, ts_save_options, po_save_options, dry_run)
+    });
+
+    let target_file_paths: Vec<&Path> = targets.iter().map(|(_, target_file_path)| target_file_path.as_path()).collect();
+    report_target_results(&target_file_paths, results)
+}
+
+/// Print the outcome of each target after all conversions are done, so that reports of different files are not interleaved.
+///
+/// A failed target doesn't keep the others from being written, so every failure is reported before giving up.
+fn report_target_results(target_file_paths: &[&Path], results: Vec<Result<Option<Vec<MessageChange>>, CmdError>>) -> Result<(), CmdError> {
+    let mut failed = 0;
+    for (target_file_path, result) in target_file_paths.iter().zip(results) {
+        match result {
+            Ok(Some(changes)) => print_dry_run_report(target_file_path, &changes),
+            Ok(None) => println!("Converted into {target_file_path:?}"),
+            Err(e) => {
+                eprintln!("Error: Fail to convert into {target_file_path:?} because: {e}");
+                failed += 1;
+            },
+        }
+    }
+    match failed {
+        0 => Ok(()),
+        failed => Err(CmdError::TargetsFailed(failed, target_file_paths.len())),
+    }
+}
+
+/// Convert the source file into a single target file, which is created if not exist.
+///
+/// With `dry_run`, nothing is written and changes of the target file are returned instead.
+#[allow(clippy::too_many_arguments)]
+fn convert_into_target(source_content: &ZhConvFile, target_language: &str, target_file_path: &Path, options: &ZhConvOptions, po_header_options: &PoHeaderOptions, ts_save_options: &TsSaveOptions, po_save_options: &PoSaveOptions, dry_run: bool) -> Result<Option<Vec<MessageChange>>, CmdError> {
+    let mut target_content = source_content.load_or_create_target_file(target_file_path, target_language, po_header_options)?;
+    let old_messages = if dry_run && target_file_path.exists() { target_content.flat_messages() } else { vec![] };
+    // if the target file's language code is not match to target_language, set it to target_language
+    if !matches!(&target_content.get_language(), Some(lang) if lang == target_language) {
+        eprintln!("Warning: Target file {target_file_path:?} has no or unmatched language code, will set it to {target_language}.");
+        target_content.set_language(target_language);
+    }
+    target_content.translate_content_based_on(source_content, options)?;
+    if dry_run {
+        return Ok(Some(diff_messages(&old_messages, &target_content.flat_messages())));
+    }
+    target_content.save_file(target_file_path, ts_save_options, po_save_options)?;
+    Ok(None)
+}
+
+fn print_dry_run_report(target_file_path: &Path, changes: &[MessageChange]) {
+    if !target_file_path.exists() {
+        println!("Dry run: {target_file_path:?} would be created:");
+    } else if changes.is_empty() {
+        println!("Dry run: {target_file_path:?} would not be changed.");
+        return;
+    } else {
+        println!("Dry run: {target_file_path:?} would be changed:");
+    }
+    crate::subcmd::diff::print_changes_table(changes);
+}
+
+/// Convert the given text, or the content of `file`, or the standard input if neither is given.
+pub fn subcmd_zhconv_plain(target_languages: &[String], content: Option<&str>, file: Option<&Path>, variant_mapping: &VariantMapping) -> Result<(), CmdError> {
+    let content = match (content, file) {
+        (Some(content), _) => content.to_string(),
+        (None, Some(file)) => std::fs::read_to_string(file).map_err(|e| CmdError::ReadInput(file.to_path_buf(), e))?,
+        (None, None) => std::io::read_to_string(std::io::stdin()).map_err(|e| CmdError::ReadInput(PathBuf::from("-"), e))?,
+    };
     for target_language in target_languages {
-        let converted = zhconv_wrapper(&content, &target_language)?;
-        println!("{}", converted);
+        let converted = convert_text(&content, target_language, variant_mapping)?;
+        // Keep the line structure of multi-line input as-is.
+        if converted.ends_with('\n') {
+            print!("{converted}");
+        } else {
+            println!("{converted}");
+        }
     }
 
     Ok(())
 }
 
+pub fn subcmd_mark_manual(translation_file: &Path, context: Option<&str>, sources: &[String], unmark: bool) -> Result<(), CmdError> {
+    if !translation_file.is_file() {
+        return Err(CmdError::FileNotFound(translation_file.to_path_buf()));
+    }
+
+    let mut content = ZhConvFile::load_file(translation_file)?;
+    let changed = content.set_manually_reviewed(context, sources, !unmark);
+    if changed > 0 {
+        content.save_file(translation_file, &TsSaveOptions::default(), &PoSaveOptions::default())?;
+    }
+    let action = if unmark { "Unmarked" } else { "Marked" };
+    println!("{action} {changed} message(s) in {translation_file:?}");
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn tst_translate_ts_content() {
-        use crate::i18n_file::linguist::Ts;
+    fn tst_set_manually_reviewed() {
         use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
 
-        let source_ts: Ts = Ts::load_from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
-        let mut target_ts: Ts = source_ts.clone();
-        target_ts.set_language("zh_TW");
-        target_ts.clear_finished_messages();
-        assert!(translate_ts_content(&source_ts, &mut target_ts).is_ok());
-        assert_eq!(target_ts.get_language(), Some("zh_TW".to_string()));
-        assert_eq!(target_ts.contexts.len(), 1);
-        assert_eq!(target_ts.contexts[0].messages.len(), 5);
-        assert_eq!(target_ts.contexts[0].messages[0].translation.value, Some(String::from("海內存知己")));
-        assert_eq!(target_ts.contexts[0].messages[1].translation.value, Some(String::from("軟體開發工程師在使用滑鼠操作螢幕上的游標")));
-        assert_eq!(target_ts.contexts[0].messages[2].translation.value, Some(String::from("电视频段"))); // marked as obsolete, should not be translated.
-        assert_eq!(target_ts.contexts[0].messages[3].translation.value, None); // source is also untranslated
+        let mut file = ZhConvFile::Linguist(Ts::load_from_str(TEST_ZH_CN_TS_CONTENT).unwrap());
+        // Unfinished and obsolete messages are never marked.
+        assert_eq!(file.set_manually_reviewed(None, &[], true), 3);
+        assert_eq!(file.set_manually_reviewed(None, &[], true), 0);
+        assert_eq!(file.set_manually_reviewed(Some("ts::OtherContext"), &[], false), 0);
+        assert_eq!(file.set_manually_reviewed(None, &["England".to_string(), "TV band".to_string()], false), 0);
+        assert_eq!(file.set_manually_reviewed(Some("ts::SampleContext"), &["A friend in need is a friend indeed".to_string()], false), 1);
     }
 
     #[test]
-    fn tst_translate_po_content() {
-        use crate::i18n_file::gettext::Po;
-        use crate::i18n_file::gettext::tests::TEST_ZH_CN_PO_CONTENT;
-
-        let source_po = Po::load_from_str(TEST_ZH_CN_PO_CONTENT).unwrap();
-        let mut target_po = source_po.clone();
-        target_po.set_language("zh_TW");
-        target_po.clear_finished_messages();
-        assert!(translate_po_content(&source_po, &mut target_po).is_ok());
-        assert_eq!(target_po.get_language(), "zh_TW".to_string());
-        assert_eq!(target_po.inner.count(), 4);
-        let mut msgs = target_po.inner.messages();
-        assert_eq!(msgs.next().unwrap().msgstr().unwrap(), "海內存知己");
-        assert_eq!(msgs.next().unwrap().msgstr().unwrap(), "軟體開發工程師在使用滑鼠操作螢幕上的游標");
-        assert_eq!(msgs.next().unwrap().msgstr().unwrap(), ""); // marked as obsolete. but polib will not read it.
-        assert_eq!(msgs.next().unwrap().msgstr().unwrap(), ""); // source is also untranslated
+    fn tst_convert_into_targets_with_failure() {
+        use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source_content = ZhConvFile::Linguist(Ts::load_from_str(TEST_ZH_CN_TS_CONTENT).unwrap());
+        let targets: Vec<(String, PathBuf)> = ["zh_HK", "zh_TW", "zh_SG"].iter()
+            .map(|language| (language.to_string(), temp_dir.path().join(format!("app_{language}.ts"))))
+            .collect();
+        // The existing zh_TW file can't be loaded, which must not keep the other targets from being converted.
+        std::fs::write(&targets[1].1, "<TS><broken").unwrap();
+        let result = convert_into_targets(&source_content, &targets, &ZhConvOptions::default(), &PoHeaderOptions::default(),
+            &TsSaveOptions::default(), &PoSaveOptions::default(), false);
+        assert!(matches!(result, Err(CmdError::TargetsFailed(1, 3))));
+        assert!(Ts::load_from_file(&targets[0].1).is_ok());
+        assert_eq!(std::fs::read_to_string(&targets[1].1).unwrap(), "<TS><broken");
+        assert!(Ts::load_from_file(&targets[2].1).is_ok());
     }
 }
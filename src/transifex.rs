@@ -5,4 +5,5 @@
 pub mod yaml_file;
 pub mod tx_config_file;
 pub mod project_file;
-pub mod rest_api;
\ No newline at end of file
+#[cfg(feature = "transifex-api")]
+pub mod rest_api;
@@ -4,7 +4,7 @@
 
 use thiserror::Error as TeError;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use crate::transifex::{yaml_file::*, tx_config_file::*};
 
 #[derive(TeError, Debug)]
@@ -15,11 +15,37 @@ pub enum TxProjectFileLoadError {
     ConvertError(#[from] LoadTxConfigError),
 }
 
+/// Whether any of the project files looked up by [`try_load_transifex_project_file`] exists.
+pub fn has_transifex_project_file(project_root: &Path) -> bool {
+    ["transifex.yaml", ".tx/transifex.yaml", ".tx/config"].iter()
+        .any(|file| project_root.join(file).is_file())
+}
+
+/// Whether the project file is a transifex.yaml file instead of a .tx/config file, judged by its extension.
+pub fn is_transifex_yaml_file(project_file: &Path) -> bool {
+    project_file.extension().is_some_and(|ext| ext == "yaml" || ext == "yml")
+}
+
+/// Load the given project file, either a transifex.yaml or a .tx/config file,
+/// or look it up under `project_root` with [`try_load_transifex_project_file`] if not given.
+pub fn load_transifex_project_file(project_root: &Path, project_file: Option<&Path>) -> Result<(PathBuf, TransifexYaml), TxProjectFileLoadError> {
+    let Some(project_file) = project_file else {
+        return try_load_transifex_project_file(project_root);
+    };
+    let project_file = project_file.to_path_buf();
+    let tx_yaml = if is_transifex_yaml_file(&project_file) {
+        load_tx_yaml_file(&project_file)?
+    } else {
+        load_tx_config_file(&project_file)?.to_transifex_yaml()
+    };
+    Ok((project_file, tx_yaml))
+}
+
 /// Try find transifex.yaml in `project_root/transifex.yaml`.
 /// And if not found, try `project_root/.tx/transifex.yaml`.
 /// And if not found, try `project_root/.tx/config`.
 /// If still not found, return error.
-pub fn try_load_transifex_project_file(project_root: &PathBuf) -> Result<(PathBuf, TransifexYaml), TxProjectFileLoadError> {
+pub fn try_load_transifex_project_file(project_root: &Path) -> Result<(PathBuf, TransifexYaml), TxProjectFileLoadError> {
     try_load_transifex_yaml_file(project_root).or_else(|e| {
         try_load_tx_config_file(project_root).map(|(tx_config_file, tx_config)| {
             let tx_yaml = tx_config.to_transifex_yaml();
@@ -6,10 +6,42 @@
 
 use directories::BaseDirs;
 use serde::Deserialize;
+use std::time::{Duration, Instant, SystemTime};
 use thiserror::Error as TeError;
+use ureq::{http::Response, Body};
 
 use super::{tx_config_file::{load_transifexrc_file, LoadTxConfigError}, yaml_file::TxResourceLookupEntry};
 
+const DEFAULT_REST_HOSTNAME: &str = "https://rest.api.transifex.com";
+/// Attempts of a request before giving up on rate limiting, server errors or network failures.
+const MAX_ATTEMPTS: u32 = 5;
+/// Upper bound of the exponential backoff between attempts, `Retry-After` given by the server is always respected.
+const MAX_BACKOFF_SECS: u64 = 30;
+/// How long to wait for an async upload or download job on Transifex before giving up.
+const ASYNC_JOB_TIMEOUT_SECS: u64 = 600;
+/// Upper bound of the growing interval between polls of an async job.
+const MAX_POLL_INTERVAL_SECS: u64 = 10;
+
+/// Delay before the next attempt, `Retry-After` (in seconds) if given, otherwise 1s, 2s, 4s and so on.
+fn retry_delay(attempt: u32, retry_after: Option<u64>) -> Duration {
+    Duration::from_secs(retry_after.unwrap_or_else(|| (1u64 << (attempt - 1).min(16)).min(MAX_BACKOFF_SECS)))
+}
+
+/// Interval before the next poll of an async job, 1s, 2s, 4s and so on.
+fn poll_interval(polls: u32) -> Duration {
+    Duration::from_secs((1u64 << polls.saturating_sub(1).min(16)).min(MAX_POLL_INTERVAL_SECS))
+}
+
+/// Seconds to wait according to a `Retry-After` header, which is either a number of seconds or an HTTP date.
+fn parse_retry_after(value: &str, now: SystemTime) -> Option<u64> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(secs);
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(now).map_or(0, |delay| delay.as_secs()))
+}
+
 pub struct TransifexRestApi {
     rest_hostname: String,
     token: String,
@@ -21,10 +53,19 @@ pub enum TransifexRestApiError {
     Ureq(#[from] ureq::Error),
     #[error("Error parsing response: {0}")]
     Serde(#[from] serde_json::Error),
+    #[error("{0} failed on Transifex: {1}")]
+    JobFailed(String, String),
+    #[error("Request to {0} still failed after {1} attempts: {2}")]
+    RetriesExhausted(String, u32, String),
+    #[error("{0} did not finish on Transifex within {1}s")]
+    JobTimedOut(String, u64),
+    #[error("{0} finished on Transifex without the location of the file")]
+    MissingLocation(String),
 }
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct TransifexDataAttributes {
+    pub name: Option<String>,
     pub categories: Option<Vec<String>>,
 }
 
@@ -57,6 +98,77 @@ impl TransifexData {
     }
 }
 
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct TransifexLanguageStatsAttributes {
+    pub total_strings: u64,
+    pub translated_strings: u64,
+    #[serde(default)]
+    pub translated_words: u64,
+    #[serde(default)]
+    pub untranslated_words: u64,
+    /// Time of the last change of the strings in this language, e.g. `2025-01-01T00:00:00Z`.
+    #[serde(default)]
+    pub last_update: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TransifexLanguageStats {
+    /// This field should follow format:
+    /// `o:organization_slug:p:project_slug:r:resource_slug:l:language_code`
+    pub id: String,
+    pub attributes: TransifexLanguageStatsAttributes,
+}
+
+impl TransifexLanguageStats {
+    /// Split the id into the resource id (`o:organization_slug:p:project_slug:r:resource_slug`) and the language code.
+    pub fn resource_and_language(&self) -> Option<(&str, &str)> {
+        self.id.rsplit_once(":l:")
+    }
+
+    pub fn completeness_percentage(&self) -> f64 {
+        match self.attributes.total_strings {
+            0 => 0.0,
+            total => self.attributes.translated_strings as f64 / total as f64 * 100.0,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TransifexLanguageAttributes {
+    pub code: String,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TransifexLanguage {
+    /// This field should follow format: `l:language_code`
+    pub id: String,
+    pub attributes: TransifexLanguageAttributes,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TransifexAsyncJobError {
+    pub detail: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TransifexAsyncJobAttributes {
+    /// One of `pending`, `processing`, `succeeded` or `failed`.
+    pub status: String,
+    #[serde(default)]
+    pub errors: Vec<TransifexAsyncJobError>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TransifexAsyncJob {
+    pub id: String,
+    pub attributes: TransifexAsyncJobAttributes,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TransifexDataResponse<T> {
+    pub data: T,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct TransifexPaginationResponse<T> {
     pub data: Vec<T>,
@@ -79,6 +191,12 @@ impl<T> Paginated for TransifexPaginationResponse<T> {
     }
 }
 
+/// A page of which only the links are parsed, to request the next page as soon as possible.
+#[derive(Deserialize, Debug)]
+struct TransifexPaginationLinksOnly {
+    links: TransifexPaginationLinks,
+}
+
 #[derive(Deserialize, Debug)]
 struct TransifexPaginationLinks {
     next: Option<String>,
@@ -96,27 +214,105 @@ impl TransifexRestApi {
         }
     }
 
+    /// Create the client from ~/.transifexrc, or from the `TX_TOKEN` and `TX_REST_HOSTNAME` environment variables
+    /// if the file does not exist, e.g. in CI jobs.
     pub fn new_from_transifexrc() -> Result<Self, LoadTxConfigError> {
         let xdg_dirs = BaseDirs::new().expect("Not able to get xdg base directories");
         let transifexrc_file = xdg_dirs.home_dir().join(".transifexrc");
-        let transifexrc = load_transifexrc_file(&transifexrc_file)?;
-        Ok(TransifexRestApi::new(&transifexrc.rest_hostname, &transifexrc.token))
+        match load_transifexrc_file(&transifexrc_file) {
+            Ok(transifexrc) => Ok(TransifexRestApi::new(&transifexrc.rest_hostname, &transifexrc.token)),
+            Err(LoadTxConfigError::FileNotFound) => {
+                Self::new_from_env_values(std::env::var("TX_TOKEN").ok(), std::env::var("TX_REST_HOSTNAME").ok())
+                    .ok_or(LoadTxConfigError::FileNotFound)
+            },
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Create the client from the values of `TX_TOKEN` and `TX_REST_HOSTNAME`, the hostname defaults to the one of transifex.com.
+    fn new_from_env_values(token: Option<String>, rest_hostname: Option<String>) -> Option<Self> {
+        let token = token.filter(|token| !token.is_empty())?;
+        let rest_hostname = rest_hostname.filter(|hostname| !hostname.is_empty()).unwrap_or_else(|| DEFAULT_REST_HOSTNAME.to_string());
+        Some(TransifexRestApi::new(&rest_hostname, &token))
     }
     
-    pub fn fetch_paginated<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<Vec<T>, TransifexRestApiError> {
-        let mut all_items = Vec::<T>::new();
-        let mut next_page_url = Some(self.rest_hostname.clone() + url);
-        while let Some(url) = next_page_url {
-            let mut resp = ureq::get(&url)
-                .header("Authorization", &format!("Bearer {}", self.token))
-                .call()?;
-            let resp_text = resp.body_mut().read_to_string()?;
-            let resp_json = serde_json::from_str::<TransifexPaginationResponse<T>>(&resp_text)?;
-            let next_url = resp_json.next_page_url().map(|s| s.to_string());
-            all_items.extend(resp_json.items());
-            next_page_url = next_url;
+    /// Send the request, and retry idempotent requests with backoff on rate limiting (429), server errors (5xx) and network
+    /// failures. Other requests, e.g. POSTs creating async jobs, are sent once, so that a job is never created twice.
+    ///
+    /// `send` must not treat HTTP status codes as errors, error status codes are returned as [`ureq::Error::StatusCode`].
+    fn send_with_retry(&self, url: &str, idempotent: bool, send: impl Fn() -> Result<Response<Body>, ureq::Error>) -> Result<Response<Body>, TransifexRestApiError> {
+        let mut attempt = 1;
+        loop {
+            let (failure, retry_after) = match send() {
+                Ok(resp) if idempotent && (resp.status().as_u16() == 429 || resp.status().is_server_error()) => {
+                    let retry_after = resp.headers().get("Retry-After")
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| parse_retry_after(value, SystemTime::now()));
+                    (format!("HTTP status {}", resp.status().as_u16()), retry_after)
+                },
+                Ok(resp) if resp.status().is_client_error() || resp.status().is_server_error() => {
+                    return Err(ureq::Error::StatusCode(resp.status().as_u16()).into());
+                },
+                Ok(resp) => return Ok(resp),
+                Err(e @ (ureq::Error::Io(_) | ureq::Error::Timeout(_) | ureq::Error::ConnectionFailed)) if idempotent => (e.to_string(), None),
+                Err(e) => return Err(e.into()),
+            };
+            if attempt >= MAX_ATTEMPTS {
+                return Err(TransifexRestApiError::RetriesExhausted(url.to_string(), attempt, failure));
+            }
+            let delay = retry_delay(attempt, retry_after);
+            eprintln!("Warning: Request to {url} failed ({failure}), retrying in {}s ({attempt}/{MAX_ATTEMPTS})...", delay.as_secs());
+            std::thread::sleep(delay);
+            attempt += 1;
         }
-        Ok(all_items)
+    }
+
+    fn get_text(&self, url: &str) -> Result<String, TransifexRestApiError> {
+        let mut resp = self.send_with_retry(url, true, || {
+            ureq::get(url)
+                .header("Authorization", &format!("Bearer {}", self.token))
+                .config().http_status_as_error(false).build()
+                .call()
+        })?;
+        Ok(resp.body_mut().read_to_string()?)
+    }
+
+    fn post_json_api(&self, url: &str, request_body: &serde_json::Value) -> Result<String, TransifexRestApiError> {
+        let request_body = request_body.to_string();
+        let mut resp = self.send_with_retry(url, false, || {
+            ureq::post(url)
+                .header("Authorization", &format!("Bearer {}", self.token))
+                .header("Content-Type", "application/vnd.api+json")
+                .config().http_status_as_error(false).build()
+                .send(&request_body)
+        })?;
+        Ok(resp.body_mut().read_to_string()?)
+    }
+
+    /// Fetch all pages of the list. Pages are linked by cursors so they are requested one by one, but the items of a page
+    /// are parsed while the next page is being fetched.
+    pub fn fetch_paginated<T: serde::de::DeserializeOwned + Send>(&self, url: &str) -> Result<Vec<T>, TransifexRestApiError> {
+        std::thread::scope(|scope| {
+            let mut pages = vec![];
+            let mut next_page_url = Some(self.rest_hostname.clone() + url);
+            while let Some(url) = next_page_url {
+                let resp_text = self.get_text(&url)?;
+                next_page_url = serde_json::from_str::<TransifexPaginationLinksOnly>(&resp_text)?.links.next;
+                pages.push(scope.spawn(move || {
+                    serde_json::from_str::<TransifexPaginationResponse<T>>(&resp_text).map(|resp_json| resp_json.items())
+                }));
+            }
+            let mut all_items = Vec::<T>::new();
+            for page in pages {
+                all_items.extend(page.join().unwrap_or_else(|e| std::panic::resume_unwind(e))?);
+            }
+            Ok(all_items)
+        })
+    }
+
+    /// Organizations the user of the token belongs to.
+    pub fn get_all_organizations(&self) -> Result<Vec<TransifexData>, TransifexRestApiError> {
+        self.fetch_paginated::<TransifexData>("/organizations")
     }
 
     pub fn get_all_projects(&self, organization_slug: &str) -> Result<Vec<TransifexData>, TransifexRestApiError> {
@@ -124,18 +320,181 @@ impl TransifexRestApi {
         self.fetch_paginated::<TransifexData>(&url)
     }
 
-    pub fn get_all_linked_resources(&self, organization_slug: &str, project_slug: &str) -> Result<Vec<TransifexData>, TransifexRestApiError> {
+    pub fn get_all_resources(&self, organization_slug: &str, project_slug: &str) -> Result<Vec<TransifexData>, TransifexRestApiError> {
         let url = format!("/resources?filter[project]=o:{}:p:{}", organization_slug, project_slug);
-        let resources = self.fetch_paginated::<TransifexData>(&url)?;
+        self.fetch_paginated::<TransifexData>(&url)
+    }
+
+    pub fn get_all_linked_resources(&self, organization_slug: &str, project_slug: &str) -> Result<Vec<TransifexData>, TransifexRestApiError> {
+        let resources = self.get_all_resources(organization_slug, project_slug)?;
         // linked resources are those with category attribute and match the following pattern:
         // github#repository:organization/repository#branch:branch#path:path/to/file
         let linked_resources = resources.into_iter().filter(|resource| {
-            resource.attributes.categories.as_ref().map_or(false, |categories| {
+            resource.attributes.categories.as_ref().is_some_and(|categories| {
                 categories.iter().any(|entry| entry.starts_with("github#repository:"))
             })
         }).collect();
         Ok(linked_resources)
     }
+
+    /// Delete the resource (`o:organization_slug:p:project_slug:r:resource_slug`) along with all its translations.
+    pub fn delete_resource(&self, resource_id: &str) -> Result<(), TransifexRestApiError> {
+        let url = format!("{}/resources/{resource_id}", self.rest_hostname);
+        self.send_with_retry(&url, true, || {
+            ureq::delete(&url)
+                .header("Authorization", &format!("Bearer {}", self.token))
+                .config().http_status_as_error(false).build()
+                .call()
+        })?;
+        Ok(())
+    }
+
+    /// Download the translation file of the resource (`o:organization_slug:p:project_slug:r:resource_slug`) in the given language.
+    ///
+    /// `mode` is the download mode like `reviewed` or `onlytranslated`, see the API doc of `resource_translations_async_downloads`.
+    pub fn download_resource_translation(&self, resource_id: &str, language_code: &str, mode: Option<&str>) -> Result<String, TransifexRestApiError> {
+        let request_body = serde_json::json!({
+            "data": {
+                "type": "resource_translations_async_downloads",
+                "attributes": {
+                    "content_encoding": "text",
+                    "file_type": "default",
+                    "mode": mode.unwrap_or("default"),
+                },
+                "relationships": {
+                    "language": { "data": { "type": "languages", "id": format!("l:{language_code}") } },
+                    "resource": { "data": { "type": "resources", "id": resource_id } },
+                },
+            },
+        });
+        self.run_async_download("resource_translations_async_downloads", request_body, format!("Download of {resource_id} ({language_code})"))
+    }
+
+    /// Download the source file of the resource (`o:organization_slug:p:project_slug:r:resource_slug`) as it is on Transifex.
+    pub fn download_resource_strings(&self, resource_id: &str) -> Result<String, TransifexRestApiError> {
+        let request_body = serde_json::json!({
+            "data": {
+                "type": "resource_strings_async_downloads",
+                "attributes": { "content_encoding": "text", "file_type": "default" },
+                "relationships": {
+                    "resource": { "data": { "type": "resources", "id": resource_id } },
+                },
+            },
+        });
+        self.run_async_download("resource_strings_async_downloads", request_body, format!("Download of {resource_id}"))
+    }
+
+    /// Poll an async job with growing intervals until `poll` returns its result, giving up after [`ASYNC_JOB_TIMEOUT_SECS`].
+    fn wait_for_job<T>(job_name: &str, mut poll: impl FnMut() -> Result<Option<T>, TransifexRestApiError>) -> Result<T, TransifexRestApiError> {
+        let deadline = Instant::now() + Duration::from_secs(ASYNC_JOB_TIMEOUT_SECS);
+        let mut polls = 0;
+        loop {
+            if let Some(result) = poll()? {
+                return Ok(result);
+            }
+            polls += 1;
+            let interval = poll_interval(polls);
+            if Instant::now() + interval > deadline {
+                return Err(TransifexRestApiError::JobTimedOut(job_name.to_string(), ASYNC_JOB_TIMEOUT_SECS));
+            }
+            std::thread::sleep(interval);
+        }
+    }
+
+    /// Create an async download job of the given type, wait until it's done and return the file content.
+    fn run_async_download(&self, job_type: &str, request_body: serde_json::Value, job_name: String) -> Result<String, TransifexRestApiError> {
+        let resp_text = self.post_json_api(&format!("{}/{job_type}", self.rest_hostname), &request_body)?;
+        let download_id = serde_json::from_str::<TransifexDataResponse<TransifexAsyncJob>>(&resp_text)?.data.id;
+
+        // The job redirects to the file once it's done, which must be fetched without our token.
+        let job_url = format!("{}/{job_type}/{download_id}", self.rest_hostname);
+        let location = Self::wait_for_job(&job_name, || {
+            let mut resp = self.send_with_retry(&job_url, true, || {
+                ureq::get(&job_url)
+                    .header("Authorization", &format!("Bearer {}", self.token))
+                    .config().max_redirects(0).http_status_as_error(false).build()
+                    .call()
+            })?;
+            if resp.status().is_redirection() {
+                let location = resp.headers().get("Location").and_then(|value| value.to_str().ok()).map(str::trim).unwrap_or_default();
+                return match location.is_empty() {
+                    true => Err(TransifexRestApiError::MissingLocation(job_name.clone())),
+                    false => Ok(Some(location.to_string())),
+                };
+            }
+            let resp_text = resp.body_mut().read_to_string()?;
+            let attributes = serde_json::from_str::<TransifexDataResponse<TransifexAsyncJob>>(&resp_text)?.data.attributes;
+            if attributes.status == "failed" {
+                let details = attributes.errors.into_iter().map(|error| error.detail).collect::<Vec<_>>().join(", ");
+                return Err(TransifexRestApiError::JobFailed(job_name.clone(), details));
+            }
+            Ok(None)
+        })?;
+        let mut resp = self.send_with_retry(&location, true, || {
+            ureq::get(&location).config().http_status_as_error(false).build().call()
+        })?;
+        Ok(resp.body_mut().read_to_string()?)
+    }
+
+    /// Create an async job of the given type, and wait until it's done.
+    fn run_async_job(&self, job_type: &str, request_body: serde_json::Value, job_name: String) -> Result<(), TransifexRestApiError> {
+        let resp_text = self.post_json_api(&format!("{}/{job_type}", self.rest_hostname), &request_body)?;
+        let job_id = serde_json::from_str::<TransifexDataResponse<TransifexAsyncJob>>(&resp_text)?.data.id;
+
+        let job_url = format!("{}/{job_type}/{job_id}", self.rest_hostname);
+        Self::wait_for_job(&job_name, || {
+            let resp_text = self.get_text(&job_url)?;
+            let attributes = serde_json::from_str::<TransifexDataResponse<TransifexAsyncJob>>(&resp_text)?.data.attributes;
+            match attributes.status.as_str() {
+                "succeeded" => Ok(Some(())),
+                "failed" => {
+                    let details = attributes.errors.into_iter().map(|error| error.detail).collect::<Vec<_>>().join(", ");
+                    Err(TransifexRestApiError::JobFailed(job_name.clone(), details))
+                },
+                _ => Ok(None),
+            }
+        })
+    }
+
+    /// Upload the source file content of the resource (`o:organization_slug:p:project_slug:r:resource_slug`).
+    pub fn upload_resource_strings(&self, resource_id: &str, content: &str) -> Result<(), TransifexRestApiError> {
+        let request_body = serde_json::json!({
+            "data": {
+                "type": "resource_strings_async_uploads",
+                "attributes": { "content": content, "content_encoding": "text" },
+                "relationships": {
+                    "resource": { "data": { "type": "resources", "id": resource_id } },
+                },
+            },
+        });
+        self.run_async_job("resource_strings_async_uploads", request_body, format!("Upload of {resource_id}"))
+    }
+
+    /// Upload the translation file content of the resource (`o:organization_slug:p:project_slug:r:resource_slug`) in the given language.
+    pub fn upload_resource_translation(&self, resource_id: &str, language_code: &str, content: &str) -> Result<(), TransifexRestApiError> {
+        let request_body = serde_json::json!({
+            "data": {
+                "type": "resource_translations_async_uploads",
+                "attributes": { "content": content, "content_encoding": "text", "file_type": "default" },
+                "relationships": {
+                    "language": { "data": { "type": "languages", "id": format!("l:{language_code}") } },
+                    "resource": { "data": { "type": "resources", "id": resource_id } },
+                },
+            },
+        });
+        self.run_async_job("resource_translations_async_uploads", request_body, format!("Upload of {resource_id} ({language_code})"))
+    }
+
+    /// Target languages enabled in the project, the source language is not included.
+    pub fn get_project_languages(&self, organization_slug: &str, project_slug: &str) -> Result<Vec<TransifexLanguage>, TransifexRestApiError> {
+        let url = format!("/projects/o:{}:p:{}/languages", organization_slug, project_slug);
+        self.fetch_paginated::<TransifexLanguage>(&url)
+    }
+
+    pub fn get_resource_language_stats(&self, organization_slug: &str, project_slug: &str) -> Result<Vec<TransifexLanguageStats>, TransifexRestApiError> {
+        let url = format!("/resource_language_stats?filter[project]=o:{}:p:{}", organization_slug, project_slug);
+        self.fetch_paginated::<TransifexLanguageStats>(&url)
+    }
 }
 
 #[cfg(test)]
@@ -180,4 +539,122 @@ pub mod tests {
         let resp_json: TransifexPaginationResponse<TransifexData> = serde_json::from_str(resp_text).unwrap();
         println!("{:?}", resp_json);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn tst_parse_async_download_response() {
+        let resp_text = r#"{
+    "data": {
+        "id": "4abfc726-6a27-4c33-9d99-e5254c8df748",
+        "type": "resource_translations_async_downloads",
+        "attributes": {
+            "status": "failed",
+            "errors": [
+                { "code": "not_found", "detail": "Resource not found" }
+            ],
+            "date_created": "2025-01-01T00:00:00Z",
+            "date_modified": "2025-01-01T00:00:00Z"
+        },
+        "links": {
+            "self": "https://rest.api.transifex.com/resource_translations_async_downloads/4abfc726-6a27-4c33-9d99-e5254c8df748"
+        }
+    }
+}"#;
+        let resp_json: TransifexDataResponse<TransifexAsyncJob> = serde_json::from_str(resp_text).unwrap();
+        assert_eq!(resp_json.data.id, "4abfc726-6a27-4c33-9d99-e5254c8df748");
+        assert_eq!(resp_json.data.attributes.status, "failed");
+        assert_eq!(resp_json.data.attributes.errors[0].detail, "Resource not found");
+    }
+
+    #[test]
+    fn tst_parse_resource_language_stats_response() {
+        let resp_text = r#"{
+    "data": [
+        {
+            "id": "o:linuxdeepin:p:deepin-home:r:bad354a0c370deff052c13b687289331:l:zh_TW",
+            "type": "resource_language_stats",
+            "attributes": {
+                "untranslated_words": 12,
+                "translated_words": 36,
+                "reviewed_strings": 0,
+                "total_strings": 8,
+                "translated_strings": 6,
+                "untranslated_strings": 2,
+                "last_update": "2025-01-01T00:00:00Z"
+            }
+        }
+    ],
+    "links": {
+        "self": "https://rest.api.transifex.com/resource_language_stats?filter[project]=o:linuxdeepin:p:deepin-home",
+        "next": null,
+        "previous": null
+    }
+}"#;
+        let resp_json: TransifexPaginationResponse<TransifexLanguageStats> = serde_json::from_str(resp_text).unwrap();
+        let stats = &resp_json.data[0];
+        assert_eq!(stats.resource_and_language(), Some(("o:linuxdeepin:p:deepin-home:r:bad354a0c370deff052c13b687289331", "zh_TW")));
+        assert_eq!(stats.completeness_percentage(), 75.0);
+        assert_eq!(stats.attributes.untranslated_words, 12);
+        assert_eq!(stats.attributes.last_update.as_deref(), Some("2025-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn tst_parse_project_languages_response() {
+        let resp_text = r#"{
+    "data": [
+        {
+            "id": "l:zh_CN",
+            "type": "languages",
+            "attributes": {
+                "code": "zh_CN",
+                "name": "Chinese (China)",
+                "rtl": false,
+                "plural_equation": "0",
+                "plural_rules": { "other": "everything" }
+            },
+            "links": { "self": "https://rest.api.transifex.com/languages/l:zh_CN" }
+        }
+    ],
+    "links": {
+        "self": "https://rest.api.transifex.com/projects/o:linuxdeepin:p:deepin-home/languages"
+    }
+}"#;
+        let resp_json: TransifexPaginationResponse<TransifexLanguage> = serde_json::from_str(resp_text).unwrap();
+        assert_eq!(resp_json.data[0].attributes.code, "zh_CN");
+        assert!(resp_json.next_page_url().is_none());
+    }
+
+    #[test]
+    fn tst_new_from_env_values() {
+        assert!(TransifexRestApi::new_from_env_values(None, Some("https://tx.example.com".to_string())).is_none());
+        assert!(TransifexRestApi::new_from_env_values(Some(String::new()), None).is_none());
+        let api = TransifexRestApi::new_from_env_values(Some("secret".to_string()), None).unwrap();
+        assert_eq!((api.rest_hostname.as_str(), api.token.as_str()), (DEFAULT_REST_HOSTNAME, "secret"));
+        let api = TransifexRestApi::new_from_env_values(Some("secret".to_string()), Some("https://tx.example.com".to_string())).unwrap();
+        assert_eq!(api.rest_hostname, "https://tx.example.com");
+    }
+
+    #[test]
+    fn tst_retry_delay() {
+        assert_eq!(retry_delay(1, None), Duration::from_secs(1));
+        assert_eq!(retry_delay(3, None), Duration::from_secs(4));
+        assert_eq!(retry_delay(10, None), Duration::from_secs(MAX_BACKOFF_SECS));
+        assert_eq!(retry_delay(1, Some(120)), Duration::from_secs(120));
+    }
+
+    #[test]
+    fn tst_poll_interval() {
+        assert_eq!(poll_interval(1), Duration::from_secs(1));
+        assert_eq!(poll_interval(3), Duration::from_secs(4));
+        assert_eq!(poll_interval(100), Duration::from_secs(MAX_POLL_INTERVAL_SECS));
+    }
+
+    #[test]
+    fn tst_parse_retry_after() {
+        let now = httpdate::parse_http_date("Wed, 21 Oct 2015 07:28:00 GMT").unwrap();
+        assert_eq!(parse_retry_after(" 120 ", now), Some(120));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:30:00 GMT", now), Some(120));
+        // Dates in the past mean retrying right away.
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now), Some(0));
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+}
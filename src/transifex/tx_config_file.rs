@@ -5,7 +5,7 @@
 // .transifexrc content: https://github.com/transifex/cli/blob/devel/examples/exampleconf/.transifexrc
 // .tx/config file spec: https://developers.transifex.com/docs/using-the-client
 
-use std::{fs, path::PathBuf};
+use std::{fs, path::{Path, PathBuf}, str::FromStr};
 use configparser::ini::{Ini, WriteOptions};
 use thiserror::Error as TeError;
 use super::yaml_file::{self, TransifexYaml};
@@ -27,7 +27,7 @@ pub struct TransifexRcSection {
     pub token: String,
 }
 
-pub fn try_load_tx_config_file(project_root: &PathBuf) -> Result<(PathBuf, TxConfig), LoadTxConfigError> {
+pub fn try_load_tx_config_file(project_root: &Path) -> Result<(PathBuf, TxConfig), LoadTxConfigError> {
     let tx_config_file = project_root.join(".tx").join("config");
     if tx_config_file.is_file() {
         let tx_config = load_tx_config_file(&tx_config_file)?;
@@ -36,16 +36,28 @@ pub fn try_load_tx_config_file(project_root: &PathBuf) -> Result<(PathBuf, TxCon
     Err(LoadTxConfigError::FileNotFound)
 }
 
+/// Load the given .tx/config file, or look it up under `project_root` with [`try_load_tx_config_file`] if not given.
+pub fn load_project_tx_config_file(project_root: &Path, tx_config_file: Option<&Path>) -> Result<(PathBuf, TxConfig), LoadTxConfigError> {
+    let Some(tx_config_file) = tx_config_file else {
+        return try_load_tx_config_file(project_root);
+    };
+    let tx_config_file = tx_config_file.to_path_buf();
+    let tx_config = load_tx_config_file(&tx_config_file)?;
+    Ok((tx_config_file, tx_config))
+}
+
 pub fn load_transifexrc_file(transifexrc_file: &PathBuf) -> Result<TransifexRcSection, LoadTxConfigError> {
     if !transifexrc_file.is_file() {
         return Err(LoadTxConfigError::FileNotFound);
     }
-    let source_content = fs::read_to_string(&transifexrc_file)?;
+    let source_content = fs::read_to_string(transifexrc_file)?;
     TransifexRcSection::from_str(&source_content)
 }
 
-impl TransifexRcSection {
-    pub fn from_str(content: &str) -> Result<Self, LoadTxConfigError> {
+impl FromStr for TransifexRcSection {
+    type Err = LoadTxConfigError;
+
+    fn from_str(content: &str) -> Result<Self, LoadTxConfigError> {
         let mut config = Ini::new_cs();
         config.read(content.to_string())
           .map_err(|err| LoadTxConfigError::ParseFile(err.to_string()))?;
@@ -53,12 +65,10 @@ impl TransifexRcSection {
         let mut tx_section = TransifexRcSection::default();
 
         let sections = config.sections();
-        for section in sections {
+        if let Some(section) = sections.first() {
             tx_section.host_section = section.to_string();
-            tx_section.rest_hostname = config.get(&section, "rest_hostname").ok_or(LoadTxConfigError::ParseFile("missing rest_hostname key".to_string()))?;
-            tx_section.token = config.get(&section, "token").ok_or(LoadTxConfigError::ParseFile("missing token key".to_string()))?;
-
-            break;
+            tx_section.rest_hostname = config.get(section, "rest_hostname").ok_or(LoadTxConfigError::ParseFile("missing rest_hostname key".to_string()))?;
+            tx_section.token = config.get(section, "token").ok_or(LoadTxConfigError::ParseFile("missing token key".to_string()))?;
         };
         Ok(tx_section)
     }
@@ -74,19 +84,22 @@ pub fn load_tx_config_file(tx_config_file: &PathBuf) -> Result<TxConfig, LoadTxC
     if !tx_config_file.is_file() {
         return Err(LoadTxConfigError::FileNotFound);
     }
-    let source_content = fs::read_to_string(&tx_config_file)?;
+    let source_content = fs::read_to_string(tx_config_file)?;
     TxConfig::from_str(&source_content)
 }
 
-impl TxConfig {
-    pub fn from_str(content: &str) -> Result<Self, LoadTxConfigError> {
+impl FromStr for TxConfig {
+    type Err = LoadTxConfigError;
+
+    fn from_str(content: &str) -> Result<Self, LoadTxConfigError> {
         let mut config = Ini::new_cs();
         config.read(content.to_string())
             .map_err(|err| LoadTxConfigError::ParseFile(err.to_string()))?;
-        let mut main_section = TxConfigSectionMain::default();
-        main_section.host = config.get("main", "host").unwrap_or("https://www.transifex.com".to_string());
-        main_section.minimum_prec = config.getint("main", "minimum_perc").unwrap_or(None);
-        main_section.mode = config.get("main", "mode");
+        let main_section = TxConfigSectionMain {
+            host: config.get("main", "host").unwrap_or("https://www.transifex.com".to_string()),
+            minimum_prec: config.getint("main", "minimum_perc").unwrap_or(None),
+            mode: config.get("main", "mode"),
+        };
 
         let mut tx_config = TxConfig {
             main_section,
@@ -110,7 +123,9 @@ impl TxConfig {
         };
         Ok(tx_config)
     }
+}
 
+impl TxConfig {
     pub fn to_str(&self) -> String {
         let mut config = Ini::new_cs();
         config.setstr("main", "host", Some(&self.main_section.host));
@@ -118,7 +133,7 @@ impl TxConfig {
             config.setstr("main", "minimum_perc", Some(&minimum_prec.to_string()));
         };
         if let Some(mode) = &self.main_section.mode {
-            config.setstr("main", "mode", Some(&mode));
+            config.setstr("main", "mode", Some(mode));
         };
 
         for resource_section in &self.resource_sections {
@@ -137,6 +152,49 @@ impl TxConfig {
         config.pretty_writes(&write_options)
     }
 
+    /// Merge a freshly generated config into this one, matching resource sections by their source file.
+    ///
+    /// Sections for new source files are appended, and sections still using a placeholder slug (`o:unknown-org:...`) take
+    /// the resolved slug of the generated one. Everything else, including the main section, is left as it is, since it may
+    /// have been edited manually.
+    pub fn merge(&mut self, generated: TxConfig) -> TxConfigMergeStats {
+        let mut stats = TxConfigMergeStats {
+            removed: self.resource_sections.iter()
+                .filter(|section| !generated.resource_sections.iter()
+                    .any(|generated_section| normalize_source_file(&generated_section.source_file) == normalize_source_file(&section.source_file)))
+                .map(|section| section.source_file.clone())
+                .collect(),
+            ..TxConfigMergeStats::default()
+        };
+        for generated_section in generated.resource_sections {
+            let existing_section = self.resource_sections.iter_mut()
+                .find(|section| normalize_source_file(&section.source_file) == normalize_source_file(&generated_section.source_file));
+            match existing_section {
+                Some(section) if section.is_placeholder() && !generated_section.is_placeholder() => {
+                    section.resource_full_slug = generated_section.resource_full_slug;
+                    stats.resolved += 1;
+                },
+                Some(_) => stats.kept += 1,
+                None => {
+                    let mut section = generated_section;
+                    let slug_taken = self.resource_sections.iter().any(|existing| existing.resource_full_slug == section.resource_full_slug);
+                    if section.is_placeholder() && slug_taken {
+                        section.resource_full_slug = self.next_placeholder_slug();
+                    }
+                    self.resource_sections.push(section);
+                    stats.added += 1;
+                },
+            }
+        }
+        stats
+    }
+
+    fn next_placeholder_slug(&self) -> String {
+        (1..).map(|count| format!("{PLACEHOLDER_SLUG_PREFIX}p:unknown-proj:r:unknown-res-{count}"))
+            .find(|slug| !self.resource_sections.iter().any(|section| &section.resource_full_slug == slug))
+            .unwrap()
+    }
+
     pub fn to_transifex_yaml(&self) -> TransifexYaml {
         let mut filters = Vec::<yaml_file::Filter>::new();
         for resource_section in &self.resource_sections {
@@ -158,6 +216,34 @@ impl TxConfig {
     }
 }
 
+/// What [`TxConfig::merge`] (or [`TransifexYaml::merge`]) did to the resources.
+#[derive(Debug, Default, PartialEq)]
+pub struct TxConfigMergeStats {
+    pub added: usize,
+    pub resolved: usize,
+    pub kept: usize,
+    /// Source files of existing resources that are not in the generated config, they are kept but may have been removed.
+    pub removed: Vec<String>,
+}
+
+/// Prefix of the slugs written for resources that could not be found on Transifex.
+pub const PLACEHOLDER_SLUG_PREFIX: &str = "o:unknown-org:";
+
+pub(crate) fn normalize_source_file(source_file: &str) -> &str {
+    source_file.strip_prefix("./").unwrap_or(source_file)
+}
+
+/// Write `content` to a temporary file next to `path` first, then rename it over `path`, so an interrupted write never
+/// leaves a truncated file behind.
+pub fn write_file_atomically(path: &Path, content: &str) -> std::io::Result<()> {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp_path = path.with_file_name(format!(".{file_name}.tmp{}", std::process::id()));
+    fs::write(&temp_path, content)?;
+    fs::rename(&temp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })
+}
+
 #[derive(Default)]
 pub struct TxConfigSectionMain {
     pub host: String,
@@ -176,7 +262,10 @@ pub struct TxConfigSectionResource {
 }
 
 impl TxConfigSectionResource {
-    #[cfg(test)]
+    pub fn is_placeholder(&self) -> bool {
+        self.resource_full_slug.starts_with(PLACEHOLDER_SLUG_PREFIX)
+    }
+
     pub fn get_opr_slugs(&self) -> Result<(String, String, String), LoadTxConfigError> {
         // regex match section name, and extract organization_slug, project_slug, resource_slug.
         // section name format: o:organization_slug:p:project_slug:r:resource_slug
@@ -258,4 +347,68 @@ type = QT
         let content = tx_config.to_str();
         assert_eq!(normalize_eol(&content), TEST_TX_CONFIG_CONTENT);
     }
+
+    #[test]
+    fn tst_merge_tx_config() {
+        let mut tx_config = TxConfig::from_str(r#"[main]
+host = https://www.transifex.com
+
+[o:linuxdeepin:p:deepin-home:r:deepin-home]
+file_filter = translations/deepin-home_<lang>.ts
+minimum_perc = 50
+source_file = translations/deepin-home.ts
+source_lang = en
+type = QT
+
+[o:unknown-org:p:unknown-proj:r:unknown-res-1]
+file_filter = misc/desktop_<lang>.ts
+source_file = misc/desktop.ts
+source_lang = en
+type = QT
+"#).unwrap();
+        let generated = TxConfig::from_str(r#"[main]
+host = https://www.transifex.com
+
+[o:linuxdeepin:p:deepin-home:r:renamed]
+file_filter = translations/deepin-home_<lang>.ts
+source_file = ./translations/deepin-home.ts
+source_lang = en
+type = QT
+
+[o:linuxdeepin:p:deepin-home:r:desktop]
+file_filter = misc/desktop_<lang>.ts
+source_file = misc/desktop.ts
+source_lang = en
+type = QT
+
+[o:unknown-org:p:unknown-proj:r:unknown-res-1]
+file_filter = po/<lang>.po
+source_file = po/deepin-home.pot
+source_lang = en
+type = PO
+"#).unwrap();
+        let stats = tx_config.merge(generated);
+        assert_eq!(stats, TxConfigMergeStats { added: 1, resolved: 1, kept: 1, removed: vec![] });
+        let slugs: Vec<&str> = tx_config.resource_sections.iter().map(|section| section.resource_full_slug.as_str()).collect();
+        assert_eq!(slugs, [
+            "o:linuxdeepin:p:deepin-home:r:deepin-home",
+            "o:linuxdeepin:p:deepin-home:r:desktop",
+            "o:unknown-org:p:unknown-proj:r:unknown-res-1",
+        ]);
+        assert_eq!(tx_config.resource_sections[0].minimum_prec, Some(50));
+        assert_eq!(tx_config.resource_sections[2].source_file, "po/deepin-home.pot");
+    }
+
+    #[test]
+    fn tst_load_project_tx_config_file() {
+        let project_root = tempfile::tempdir().unwrap();
+        let custom_file = project_root.path().join("custom.ini");
+        assert!(matches!(load_project_tx_config_file(project_root.path(), None), Err(LoadTxConfigError::FileNotFound)));
+        assert!(matches!(load_project_tx_config_file(project_root.path(), Some(&custom_file)), Err(LoadTxConfigError::FileNotFound)));
+
+        fs::write(&custom_file, TEST_TX_CONFIG_CONTENT).unwrap();
+        let (tx_config_file, tx_config) = load_project_tx_config_file(project_root.path(), Some(&custom_file)).unwrap();
+        assert_eq!(tx_config_file, custom_file);
+        assert_eq!(tx_config.resource_sections.len(), 2);
+    }
 }
@@ -4,7 +4,7 @@
 
 // transifex.yaml file spec: https://help.transifex.com/en/articles/6265125-github-installation-and-configuration#h_94380d9cd8
 
-use std::{fs, path::PathBuf};
+use std::{fs, path::{Path, PathBuf}};
 
 use regex::Regex;
 use serde::{Serialize, Deserialize};
@@ -29,20 +29,40 @@ pub struct TxResourceLookupEntry {
 }
 
 impl TransifexYaml {
-    pub fn to_tx_config(&self, github_repository: String, lookup_table: Vec<TxResourceLookupEntry>) -> TxConfig {
+    /// Append filters of a freshly generated config whose source file is not covered yet, existing filters are left
+    /// intact. Existing filters whose source file is not in the generated config are reported as removed.
+    pub fn merge(&mut self, generated: TransifexYaml) -> TxConfigMergeStats {
+        let generated_sources: Vec<&str> = generated.filters.iter().map(|filter| normalize_source_file(&filter.source)).collect();
+        let removed: Vec<String> = self.filters.iter()
+            .filter(|filter| !generated_sources.contains(&normalize_source_file(&filter.source)))
+            .map(|filter| filter.source.clone())
+            .collect();
+        let known_sources: Vec<String> = self.filters.iter().map(|filter| normalize_source_file(&filter.source).to_string()).collect();
+        let (kept, new_filters): (Vec<Filter>, Vec<Filter>) = generated.filters.into_iter()
+            .partition(|filter| known_sources.iter().any(|source| source == normalize_source_file(&filter.source)));
+        let added = new_filters.len();
+        self.filters.extend(new_filters);
+        TxConfigMergeStats { added, kept: kept.len(), removed, ..TxConfigMergeStats::default() }
+    }
+
+    /// Convert into .tx/config, resolving resource slugs from the linked resources in `lookup_table`.
+    ///
+    /// Repositories with per-branch Transifex resources have an entry for each branch, the one of `branch` is preferred,
+    /// then the one of the default branch (`master` or `main`), then any of them.
+    pub fn to_tx_config(&self, github_repository: String, branch: Option<&str>, lookup_table: Vec<TxResourceLookupEntry>) -> TxConfig {
         let mut resource_sections = Vec::<TxConfigSectionResource>::new();
         let mut unknown_count = 0; // avoid duplicate resource name when attempting to convert to .tx/config file
         for filter in &self.filters {
-            let mut resource_section = TxConfigSectionResource::default();
-            resource_section.source_file = filter.source.clone();
-            resource_section.source_lang = filter.source_lang.clone();
-            resource_section.type_attr = filter.format.clone();
-            resource_section.file_filter = filter.target_pattern.clone();
+            let mut resource_section = TxConfigSectionResource {
+                source_file: filter.source.clone(),
+                source_lang: filter.source_lang.clone(),
+                type_attr: filter.format.clone(),
+                file_filter: filter.target_pattern.clone(),
+                ..TxConfigSectionResource::default()
+            };
 
             // from lookup table, find if we have resource have the same repository and resource name
-            if let Some(lookup_entry) = lookup_table.iter().find(|entry| {
-                entry.repository == github_repository && entry.resource == filter.source
-            }) {
+            if let Some(lookup_entry) = find_lookup_entry(&lookup_table, &github_repository, branch, &filter.source) {
                 resource_section.resource_full_slug = lookup_entry.transifex_resource_id.clone();
             } else {
                 unknown_count += 1;
@@ -61,6 +81,16 @@ impl TransifexYaml {
     }
 }
 
+fn find_lookup_entry<'a>(lookup_table: &'a [TxResourceLookupEntry], github_repository: &str, branch: Option<&str>, source: &str) -> Option<&'a TxResourceLookupEntry> {
+    let candidates: Vec<&TxResourceLookupEntry> = lookup_table.iter()
+        .filter(|entry| entry.repository == github_repository && entry.resource == source)
+        .collect();
+    candidates.iter().find(|entry| Some(entry.branch.as_str()) == branch)
+        .or_else(|| candidates.iter().find(|entry| entry.branch == "master" || entry.branch == "main"))
+        .or(candidates.first())
+        .copied()
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Filter {
     #[serde(rename = "filter_type")]
@@ -76,20 +106,20 @@ pub struct Filter {
 }
 
 impl Filter {
-    pub fn match_target_files(&self, project_root: &PathBuf) -> Result<Vec<(String, PathBuf)>, std::io::Error> {
+    pub fn match_target_files(&self, project_root: &Path) -> Result<Vec<(String, PathBuf)>, std::io::Error> {
         let target_pattern_path = project_root.join(&self.target_pattern);
         let Some(target_filename_pattern) = target_pattern_path.file_name() else {
-            return Err(std::io::Error::new(std::io::ErrorKind::Other, "File name not found"));
+            return Err(std::io::Error::other("File name not found"));
         };
         let Some(target_filename_pattern) = target_filename_pattern.to_str() else {
-            return Err(std::io::Error::new(std::io::ErrorKind::Other, "File name not valid"));
+            return Err(std::io::Error::other("File name not valid"));
         };
         if target_filename_pattern.contains("<lang>") {
             let Some(target_filter_pattern) = create_filter_pattern(target_filename_pattern) else {
-                return Err(std::io::Error::new(std::io::ErrorKind::Other, "Filter pattern not valid"));
+                return Err(std::io::Error::other("Filter pattern not valid"));
             };
             let Some(target_parent) = target_pattern_path.parent() else {
-                return Err(std::io::Error::new(std::io::ErrorKind::Other, "Parent dir not found"));
+                return Err(std::io::Error::other("Parent dir not found"));
             };
             let target_files = target_parent.read_dir()?;
             let mut matched_files = Vec::<(String, PathBuf)>::new();
@@ -130,29 +160,27 @@ impl Filter {
                 }
             };
             if remain_path.is_none() {
-                return Err(std::io::Error::new(std::io::ErrorKind::Other, "Missing <lang> inside the pattern."));
+                return Err(std::io::Error::other("Missing <lang> inside the pattern."));
             }
             let remain_path = remain_path.unwrap();
             let language_folders = parent_dir.read_dir()?;
             let mut matched_files = Vec::<(String, PathBuf)>::new();
             let language_code_regex = regex::Regex::new(r"[a-z_A-Z]{2,6}").unwrap();
-            for language_folder in language_folders {
+            for language_folder in language_folders.flatten() {
                 // check if language_folder is a valid language code ([a-z_A-Z]{{2,6}}) in regex
-                if let Ok(language_folder) = language_folder {
-                    let language_folder_dir = language_folder.path();
-                    let language_folder = language_folder.file_name();
-                    let Some(language_folder) = language_folder.to_str() else {
-                        continue;
-                    };
-                    if !language_code_regex.is_match(language_folder) {
-                        continue;
-                    }
-                    let matched_file = language_folder_dir.join(&remain_path);
-                    if !matched_file.is_file() {
-                        continue;
-                    }
-                    matched_files.push((language_folder.to_string(), matched_file));
+                let language_folder_dir = language_folder.path();
+                let language_folder = language_folder.file_name();
+                let Some(language_folder) = language_folder.to_str() else {
+                    continue;
+                };
+                if !language_code_regex.is_match(language_folder) {
+                    continue;
+                }
+                let matched_file = language_folder_dir.join(&remain_path);
+                if !matched_file.is_file() {
+                    continue;
                 }
+                matched_files.push((language_folder.to_string(), matched_file));
             }
             Ok(matched_files)
         }
@@ -177,27 +205,23 @@ pub enum LoadTxYamlError {
     ConvertFile(#[from] LoadTxConfigError),
 }
 
-pub fn try_load_transifex_yaml_file(project_root: &PathBuf) -> Result<(PathBuf, TransifexYaml), LoadTxYamlError> {
-    // try find transifex.yaml in project_root/transifex.yaml and if not found, try project_root/.tx/transifex.yaml. If still not found, return error.
-    let transifex_yaml_file = project_root.join("transifex.yaml");
-    if transifex_yaml_file.is_file() {
-        let tx_yaml = load_tx_yaml_file(&transifex_yaml_file)?;
-        return Ok((transifex_yaml_file, tx_yaml));
-    }
-    let transifex_yaml_file = project_root.join(".tx").join("transifex.yaml");
-    if transifex_yaml_file.is_file() {
-        let tx_yaml = load_tx_yaml_file(&transifex_yaml_file)?;
-        return Ok((transifex_yaml_file, tx_yaml));
-    }
+/// Find transifex.yaml in project_root/transifex.yaml, and if not found, in project_root/.tx/transifex.yaml.
+pub fn find_transifex_yaml_file(project_root: &Path) -> Option<PathBuf> {
+    [project_root.join("transifex.yaml"), project_root.join(".tx").join("transifex.yaml")].into_iter()
+        .find(|transifex_yaml_file| transifex_yaml_file.is_file())
+}
 
-    Err(LoadTxYamlError::FileNotFound)
+pub fn try_load_transifex_yaml_file(project_root: &Path) -> Result<(PathBuf, TransifexYaml), LoadTxYamlError> {
+    let transifex_yaml_file = find_transifex_yaml_file(project_root).ok_or(LoadTxYamlError::FileNotFound)?;
+    let tx_yaml = load_tx_yaml_file(&transifex_yaml_file)?;
+    Ok((transifex_yaml_file, tx_yaml))
 }
 
 pub fn load_tx_yaml_file(transifex_yaml_file: &PathBuf) -> Result<TransifexYaml, LoadTxYamlError> {
     if !transifex_yaml_file.is_file() {
         return Err(LoadTxYamlError::FileNotFound);
     }
-    let source_content = fs::read_to_string(&transifex_yaml_file)?;
+    let source_content = fs::read_to_string(transifex_yaml_file)?;
     Ok(serde_yaml2::from_str::<TransifexYaml>(source_content.as_str())?)
 }
 
@@ -250,12 +274,49 @@ settings:
     #[test]
     fn tst_convert_to_tx_config() {
         let tx_yaml: TransifexYaml = serde_yaml2::from_str::<TransifexYaml>(TEST_TX_YAML_CONTENT).unwrap();
-        let tx_config = tx_yaml.to_tx_config("user/repo".to_string(), vec![]);
+        let tx_config = tx_yaml.to_tx_config("user/repo".to_string(), None, vec![]);
         assert_eq!(tx_config.resource_sections[0].resource_full_slug, "o:unknown-org:p:unknown-proj:r:unknown-res-1");
         assert_eq!(tx_config.resource_sections[0].file_filter, "shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet_<lang>.ts");
         assert_eq!(tx_config.resource_sections[1].resource_full_slug, "o:unknown-org:p:unknown-proj:r:unknown-res-2");
     }
 
+    #[test]
+    fn tst_convert_to_tx_config_by_branch() {
+        let tx_yaml: TransifexYaml = serde_yaml2::from_str::<TransifexYaml>(TEST_TX_YAML_CONTENT).unwrap();
+        let source = tx_yaml.filters[0].source.clone();
+        let entry = |branch: &str| TxResourceLookupEntry {
+            repository: "user/repo".to_string(),
+            branch: branch.to_string(),
+            resource: source.clone(),
+            transifex_resource_id: format!("o:user:p:repo:r:launcherapplet-{branch}"),
+        };
+        let lookup_table = || vec![entry("release-1.0"), entry("master"), entry("develop")];
+        let slug_of = |branch: Option<&str>| tx_yaml.to_tx_config("user/repo".to_string(), branch, lookup_table()).resource_sections[0].resource_full_slug.clone();
+        assert_eq!(slug_of(Some("develop")), "o:user:p:repo:r:launcherapplet-develop");
+        // Fall back to the default branch.
+        assert_eq!(slug_of(Some("feature")), "o:user:p:repo:r:launcherapplet-master");
+        assert_eq!(slug_of(None), "o:user:p:repo:r:launcherapplet-master");
+    }
+
+    #[test]
+    fn tst_merge_tx_yaml() {
+        let mut tx_yaml: TransifexYaml = serde_yaml2::from_str::<TransifexYaml>(TEST_TX_YAML_CONTENT).unwrap();
+        let mut generated: TransifexYaml = serde_yaml2::from_str::<TransifexYaml>(TEST_TX_YAML_CONTENT).unwrap();
+        let removed = generated.filters.remove(1);
+        generated.filters[0].source = format!("./{}", generated.filters[0].source);
+        generated.filters.push(Filter {
+            type_attr: "file".to_string(),
+            source: "translations/new.ts".to_string(),
+            format: "QT".to_string(),
+            source_lang: "en_US".to_string(),
+            target_pattern: "translations/new_<lang>.ts".to_string(),
+        });
+        let stats = tx_yaml.merge(generated);
+        assert_eq!(stats, TxConfigMergeStats { added: 1, kept: 1, removed: vec![removed.source], ..TxConfigMergeStats::default() });
+        assert_eq!(tx_yaml.filters.len(), 3);
+        assert_eq!(tx_yaml.filters[2].source, "translations/new.ts");
+    }
+
     #[test]
     fn test_pathbuf() {
         let path = PathBuf::from("/example/sample_<lang>.ts");